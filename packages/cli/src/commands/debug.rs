@@ -0,0 +1,67 @@
+use bpm_core::packages::utils::signatures::verify_package;
+use bpm_core::services::blockchains::BlockchainsService;
+use std::sync::Arc;
+
+use clap::{Parser, Subcommand};
+use log::debug;
+
+use super::errors::command_error::CommandError;
+use super::outcome::CommandOutcome;
+
+/** Debugging helpers */
+#[derive(Debug, Parser)]
+pub struct DebugCommand {
+    #[clap(subcommand)]
+    pub action: DebugAction,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum DebugAction {
+    /**
+     * Fetch single message using its topic sequence number, even if it does not verify
+     */
+    #[clap(name = "get-message")]
+    GetMessage { sequence_number: u64 },
+}
+
+/**
+ * Handles debugging requests from CLI
+ */
+impl DebugCommand {
+    /**
+     * Debug command
+     */
+    pub async fn run(
+        &self,
+        blockchains_service: &Arc<BlockchainsService>,
+    ) -> Result<CommandOutcome, CommandError> {
+        debug!("Subcommand debug is being run...");
+
+        let outcome = match &self.action {
+            DebugAction::GetMessage { sequence_number } => {
+                let client = blockchains_service.get_selected_client().await;
+
+                let package = client
+                    .read_package_at(*sequence_number)
+                    .await
+                    .map_err(|e| CommandError::Other(e.to_string()))?;
+
+                CommandOutcome::MessageFetched {
+                    sequence_number: *sequence_number,
+                    package: package.as_ref().map(|package| package.to_string()),
+                    maintainer: package
+                        .as_ref()
+                        .map(|package| hex::encode_upper(package.maintainer.to_bytes())),
+                    verified: package
+                        .as_ref()
+                        .map(|package| package.sig.is_some() && verify_package(package).is_some())
+                        .unwrap_or(false),
+                }
+            }
+        };
+
+        debug!("Subcommand debug successfully ran !");
+
+        Ok(outcome)
+    }
+}
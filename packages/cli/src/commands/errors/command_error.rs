@@ -0,0 +1,51 @@
+use bpm_core::{
+    blockchains::errors::blockchain_error::BlockchainError,
+    package_managers::errors::package_manager_error::PackageManagerError,
+    services::errors::install_error::InstallError,
+};
+use thiserror::Error;
+
+/**
+ * CLI commands errors
+ */
+#[derive(Error, Debug)]
+pub enum CommandError {
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+
+    #[error("Package not found: {0}")]
+    PackageNotFound(String),
+
+    #[error("Package cannot be installed given its state: {0}")]
+    ForbiddenStatus(String),
+
+    #[error("Package already published: {0}, use --force to submit it again")]
+    AlreadyPublished(String),
+
+    #[error("Maintainer key could not be loaded: {0}")]
+    MaintainerKey(String),
+
+    #[error("Maintainer key rotation incomplete: {0}")]
+    KeyRotation(String),
+
+    #[error("Prompt failed: {0}")]
+    Prompt(#[from] dialoguer::Error),
+
+    #[error(transparent)]
+    PackageManager(#[from] PackageManagerError),
+
+    #[error(transparent)]
+    Blockchain(#[from] BlockchainError),
+
+    #[error(transparent)]
+    Install(#[from] InstallError),
+
+    #[error("{0}")]
+    Other(String),
+}
+
+impl From<Box<dyn std::error::Error>> for CommandError {
+    fn from(value: Box<dyn std::error::Error>) -> Self {
+        CommandError::Other(value.to_string())
+    }
+}
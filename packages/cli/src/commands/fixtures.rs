@@ -0,0 +1,218 @@
+#[cfg(test)]
+pub mod tests {
+    use std::{
+        path::PathBuf,
+        sync::{Arc, Mutex},
+    };
+
+    use bpm_core::{
+        blockchains::{blockchain::BlockchainClient, file::blockchain_client::FileBlockchain},
+        config::{init_config, manager::ConfigManager},
+        db::client::DbClient,
+        package_managers::{
+            errors::package_manager_error::PackageManagerError,
+            installed_package::InstalledPackage, traits::package_manager::PackageManager,
+        },
+        packages::{
+            package::Package,
+            package_builder::PackageBuilder,
+            package_integrity::PackageIntegrity,
+            package_status::PackageStatus,
+            utils::{signatures::sign_package_now, signer::Signer},
+        },
+        services::{
+            blockchains::BlockchainsService,
+            db::{
+                blockchains_repository::BlockchainsRepository,
+                packages_repository::PackagesRepository,
+            },
+            package_managers::PackageManagersService,
+            packages::PackagesService,
+        },
+    };
+    use tempfile::TempDir;
+    use url::Url;
+
+    const LOG_FILENAME: &str = "blockchain.log";
+
+    /**
+     * Package manager listing given installed packages and recording removed ones, nothing can be installed with it
+     */
+    pub struct RecordingPackageManager {
+        installed: Arc<Mutex<Vec<InstalledPackage>>>,
+        removed: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl PackageManager for RecordingPackageManager {
+        fn get_name(&self) -> String {
+            String::from("recording")
+        }
+
+        async fn install_verified(
+            &self,
+            package_url: &Url,
+            _integrity: &PackageIntegrity,
+        ) -> Result<PathBuf, PackageManagerError> {
+            Err(PackageManagerError::InstallationError(
+                package_url.to_string(),
+            ))
+        }
+
+        fn cancel_download(&self) {}
+
+        fn reset_download(&self) {}
+
+        async fn remove(&self, package_name: &String) -> Result<(), PackageManagerError> {
+            self.removed.lock().unwrap().push(package_name.clone());
+
+            Ok(())
+        }
+
+        async fn list_installed(&self) -> Result<Vec<InstalledPackage>, PackageManagerError> {
+            Ok(self.installed.lock().unwrap().clone())
+        }
+
+        async fn last_transaction(&self) -> Result<Vec<String>, PackageManagerError> {
+            Ok(vec![])
+        }
+    }
+
+    /**
+     * Services wired on top of a throwaway DB and a local log, so commands run without network
+     */
+    pub struct CommandEnv {
+        pub dir: TempDir,
+        pub config_manager: ConfigManager,
+        pub packages_service: Arc<PackagesService>,
+        pub blockchains_service: Arc<BlockchainsService>,
+        pub package_managers_service: PackageManagersService,
+        installed: Arc<Mutex<Vec<InstalledPackage>>>,
+        removed: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl CommandEnv {
+        /**
+         * Create env reading and writing packages to a log in its own directory
+         */
+        pub async fn new() -> Self {
+            let dir = TempDir::new().unwrap();
+
+            let config_manager = init_config(&dir.path().to_path_buf());
+
+            let db_client = Arc::new(DbClient::in_memory());
+
+            let blockchains_repository = Arc::new(BlockchainsRepository::from(&db_client));
+            let packages_repository = Arc::new(PackagesRepository::from(&db_client));
+
+            let packages_service = Arc::new(PackagesService::from(&packages_repository));
+
+            let blockchain_client: Box<dyn BlockchainClient> =
+                Box::new(FileBlockchain::from(&dir.path().join(LOG_FILENAME)));
+
+            let blockchains_service = Arc::new(
+                BlockchainsService::new(
+                    &vec![Arc::new(blockchain_client)],
+                    &blockchains_repository,
+                    &packages_service,
+                )
+                .await,
+            );
+
+            blockchains_service.set_client(0).await;
+
+            let installed = Arc::new(Mutex::new(vec![]));
+            let removed = Arc::new(Mutex::new(vec![]));
+
+            let package_manager: Box<dyn PackageManager> = Box::new(RecordingPackageManager {
+                installed: Arc::clone(&installed),
+                removed: Arc::clone(&removed),
+            });
+
+            let package_managers_service =
+                PackageManagersService::new(&vec![Arc::new(package_manager)]);
+
+            Self {
+                dir,
+                config_manager,
+                packages_service,
+                blockchains_service,
+                package_managers_service,
+                installed,
+                removed,
+            }
+        }
+
+        /**
+         * Set packages package manager reports as installed, given as name and version
+         */
+        pub fn set_installed(&self, packages: &[(&str, &str)]) {
+            *self.installed.lock().unwrap() = packages
+                .iter()
+                .map(|(name, version)| InstalledPackage {
+                    name: name.to_string(),
+                    version: version.to_string(),
+                })
+                .collect();
+        }
+
+        /**
+         * Cache given packages as if they were read from log
+         */
+        pub async fn cache(&self, packages: &[Package]) {
+            let blockchain_client = self.blockchains_service.get_selected_client().await;
+
+            for package in packages {
+                self.packages_service.add(package, &blockchain_client).await;
+            }
+        }
+
+        /**
+         * Check whether anything was written to log
+         */
+        pub fn has_written(&self) -> bool {
+            self.dir.path().join(LOG_FILENAME).exists()
+        }
+
+        /**
+         * Get packages removed through package manager, in order
+         */
+        pub fn get_removed(&self) -> Vec<String> {
+            self.removed.lock().unwrap().clone()
+        }
+
+        /**
+         * Generate maintainer key, not persisted
+         */
+        pub fn create_signer(&self) -> Box<dyn Signer> {
+            Box::new(self.config_manager.new_signing_key())
+        }
+    }
+
+    /**
+     * Create package release published by given maintainer with given status
+     */
+    pub fn create_release(
+        name: &str,
+        version: &str,
+        signer: &dyn Signer,
+        status: PackageStatus,
+    ) -> Package {
+        let archive_url = Url::parse(&format!(
+            "https://archive.archlinux.org/packages/{}-{}.pkg.tar.zst",
+            name, version
+        ))
+        .unwrap();
+
+        let package = PackageBuilder::default()
+            .set_name(&name.to_string())
+            .set_version(&version.to_string())
+            .set_status(&status)
+            .set_maintainer(&signer.public_key())
+            .set_archive_url(&archive_url)
+            .set_integrity(&String::from("SHA256"), &[0; 32])
+            .build();
+
+        sign_package_now(&package, signer)
+    }
+}
@@ -0,0 +1,149 @@
+use bpm_core::packages::package::Package;
+use bpm_core::packages::utils::signatures::verify_package;
+use bpm_core::services::blockchains::BlockchainsService;
+use bpm_core::services::packages::PackagesService;
+use std::fmt::Write;
+use std::sync::Arc;
+
+use clap::Parser;
+use colored::Colorize;
+use log::debug;
+
+use super::errors::command_error::CommandError;
+use super::outcome::CommandOutcome;
+
+/** Print every detail of a package found in local cache */
+#[derive(Debug, Parser)]
+pub struct InfoCommand {
+    pub package_name: String,
+
+    /**
+     * Package version, last known one when not given
+     */
+    #[clap(required = false)]
+    pub package_version: Option<String>,
+}
+
+/**
+ * Handles package information request from CLI
+ */
+impl InfoCommand {
+    /**
+     * Write package details, its integrity and whether its signature is valid
+     */
+    fn describe(
+        &self,
+        package: &Package,
+        last_mutation_time: Option<u64>,
+        buf: &mut String,
+    ) -> std::fmt::Result {
+        write!(buf, "{}\n", package)?;
+        write!(
+            buf,
+            "Maintainer fingerprint => {}\n",
+            package.get_maintainer_fingerprint().blue()
+        )?;
+        write!(buf, "Archive URL => {}\n", package.archive_url)?;
+        write!(buf, "Package integrity :\n")?;
+        write!(buf, "\tAlgorithm => {}\n", package.integrity.algorithm)?;
+        write!(
+            buf,
+            "\tArchive hash => {}\n",
+            hex::encode(&package.integrity.archive_hash)
+        )?;
+
+        if !package.integrity.source_code_hash.is_empty() {
+            write!(
+                buf,
+                "\tSource code hash => {}\n",
+                hex::encode(&package.integrity.source_code_hash)
+            )?;
+        }
+
+        for (dependency_name, dependency_version) in &package.dependencies {
+            write!(
+                buf,
+                "Dependency => {}:{}\n",
+                dependency_name, dependency_version
+            )?;
+        }
+
+        if let Some(signed_at) = package.signed_at {
+            write!(buf, "Signed at => {}\n", signed_at)?;
+        }
+
+        if let Some(last_mutation_time) = last_mutation_time {
+            write!(buf, "Last mutated at => {}\n", last_mutation_time)?;
+        }
+
+        let signature_state = match package.sig {
+            None => "missing".red(),
+            Some(_) if verify_package(package).is_some() => "valid".green(),
+            Some(_) => "invalid".red(),
+        };
+
+        write!(buf, "Signature => {}", signature_state)?;
+
+        Ok(())
+    }
+
+    /**
+     * Info command
+     */
+    pub async fn run(
+        &self,
+        blockchains_service: &Arc<BlockchainsService>,
+        packages_service: &PackagesService,
+    ) -> Result<CommandOutcome, CommandError> {
+        debug!("Subcommand info is being run...");
+
+        let package_version = match &self.package_version {
+            Some(package_version) => package_version.clone(),
+            // Versions are ordered using compare_versions, so last one is newest
+            None => blockchains_service
+                .get_versions(&self.package_name)
+                .await
+                .pop()
+                .ok_or_else(|| CommandError::PackageNotFound(self.package_name.clone()))?,
+        };
+
+        let packages = blockchains_service
+            .find_package(&self.package_name, &package_version, &None)
+            .await;
+
+        if packages.is_empty() {
+            return Err(CommandError::PackageNotFound(format!(
+                "{}:{}",
+                self.package_name, package_version
+            )));
+        }
+
+        let blockchain_client = blockchains_service.get_selected_client().await;
+
+        let mut details = vec![];
+
+        for package in packages.iter() {
+            let last_mutation_time = packages_service
+                .last_mutation_time(
+                    &package.name,
+                    &package.version,
+                    &package.maintainer,
+                    &blockchain_client,
+                )
+                .await;
+
+            let mut buf = String::new();
+
+            self.describe(package, last_mutation_time, &mut buf)
+                .map_err(|e| CommandError::Other(e.to_string()))?;
+
+            details.push(buf);
+        }
+
+        let last_sync = blockchains_service.get_last_sync().await;
+
+        debug!("Subcommand info successfully ran !");
+
+        Ok(CommandOutcome::Described { details, last_sync })
+    }
+}
@@ -1,19 +1,31 @@
 use bpm_core::blockchains::errors::blockchain_error::BlockchainError;
+use bpm_core::package_managers::download::download_verified;
+use bpm_core::package_managers::errors::package_manager_error::PackageManagerError;
+use bpm_core::packages::package::Package;
 use bpm_core::packages::package_status::PackageStatus;
-use bpm_core::services::blockchains::BlockchainsService;
+use bpm_core::packages::utils::maintainers::{has_maintainers_conflict, matches_maintainer};
+use bpm_core::services::blockchains::{BlockchainsService, UpdateTally};
+use bpm_core::utils::retry::backoff_delay;
 use bpm_core::{
-    config::manager::ConfigManager, services::package_managers::PackageManagersService,
+    config::manager::ConfigManager,
+    services::package_managers::{InstallProgress, PackageManagersService},
 };
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use std::time::Duration;
 
 use colored::Colorize;
 
 use clap::Parser;
-use dialoguer::{theme::ColorfulTheme, Select};
-use indicatif::{ProgressBar, ProgressStyle};
-use log::{debug, error, info};
-use tokio::sync::mpsc;
+use log::{debug, error, info, warn};
+
+use super::errors::command_error::CommandError;
+use super::outcome::CommandOutcome;
+use super::progress::run_update;
+use super::prompter::Prompter;
 
 /** Install package using its name */
 #[derive(Debug, Parser)]
@@ -23,162 +35,720 @@ pub struct InstallCommand {
 
     #[clap(required = false)]
     pub package_version: Option<String>,
+
+    /**
+     * Refuse to install when several maintainers published the same package release
+     */
+    #[clap(long)]
+    pub strict: bool,
+
+    /**
+     * Only consider packages published by this maintainer ( hex key or fingerprint )
+     */
+    #[clap(long)]
+    pub maintainer: Option<String>,
+
+    /**
+     * Only consider packages with at least this status ( eg: Fine )
+     */
+    #[clap(long)]
+    pub min_status: Option<String>,
+
+    /**
+     * Read packages from this topic instead of configured one ( eg: 0.0.1234 )
+     */
+    #[clap(long)]
+    pub topic: Option<String>,
+
+    /**
+     * Skip blockchain synchronization, only install from local cache
+     */
+    #[clap(long)]
+    pub offline: bool,
+
+    /**
+     * Skip blockchain synchronization when last one is more recent than this many seconds
+     */
+    #[clap(long, conflicts_with = "offline")]
+    pub max_age: Option<u64>,
+
+    /**
+     * How many times blockchain synchronization is retried on connectivity failures
+     */
+    #[clap(long, default_value_t = 3)]
+    pub retries: u32,
+
+    /**
+     * Only download package then check its signature and integrity, without installing it
+     */
+    #[clap(long)]
+    pub verify_only: bool,
+
+    /**
+     * Keep verified archive in this directory instead of discarding it
+     */
+    #[clap(long, requires = "verify_only")]
+    pub output: Option<String>,
 }
 
+// Delay before first synchronization retry, doubled on each following one up to max delay
+const RETRY_BASE_DELAY_MS: u64 = 500;
+const RETRY_MAX_DELAY_MS: u64 = 30_000;
+
 /**
  * Handles package installation request from CLI
  */
 impl InstallCommand {
     /**
-     * Build progress bar
-     */
-    fn build_progress_bar(&self) -> ProgressBar {
-        let pb = ProgressBar::new_spinner();
-        pb.enable_steady_tick(Duration::from_millis(60));
-        pb.set_style(
-            ProgressStyle::with_template("{spinner:.blue} {msg}")
-                .unwrap()
-                .tick_strings(&[
-                    "[    ]", "[=   ]", "[==  ]", "[=== ]", "[====]", "[ ===]", "[  ==]", "[   =]",
-                    "[    ]", "[   =]", "[  ==]", "[ ===]", "[====]", "[=== ]", "[==  ]", "[====]",
-                ]),
-        );
+     * Update available packages mutations from blockchain
+     */
+    async fn update(
+        &self,
+        blockchains_service: &Arc<BlockchainsService>,
+    ) -> Result<UpdateTally, BlockchainError> {
+        let (update_result, mutations) = run_update(
+            blockchains_service,
+            |blockchains_service, tx_packages_update| async move {
+                blockchains_service.update(&tx_packages_update).await
+            },
+        )
+        .await
+        // Task which panicked or was cancelled would most likely fail again, do not report it as connectivity issue
+        .unwrap_or_else(|e| (Err(BlockchainError::UpdateAborted(e.to_string())), vec![]));
+
+        if let Ok(tally) = &update_result {
+            info!(
+                "Done fetching packages from blockchain ! ({} packages mutations found : {} new, {} updated)",
+                mutations.len(),
+                tally.added,
+                tally.updated
+            );
+        }
 
-        pb
+        update_result
     }
 
     /**
-     * Update available packages mutations from blockchain
+     * Update available packages mutations, retrying connectivity failures with exponential backoff
+     *
+     * Only this read-only phase is retried, installing has side effects
      */
-    async fn update(&self, blockchains_service: &Arc<BlockchainsService>) {
-        let (tx_packages_update, mut rx_packages_update) = mpsc::channel(1);
-
-        let task_blockchains_service_ref = Arc::clone(&blockchains_service);
-        tokio::spawn(async move {
-            let task_res = task_blockchains_service_ref.update(&tx_packages_update);
+    async fn update_with_retries(&self, blockchains_service: &Arc<BlockchainsService>) {
+        let mut attempt = 0;
 
-            match task_res.await {
+        loop {
+            let e = match self.update(blockchains_service).await {
                 Ok(_) => return,
-                Err(e) => match e {
-                    BlockchainError::NoPackagesData => {
-                        info!("No new packages mutations found")
-                    }
-                    _ => error!("Unhandled error : {}", e),
-                },
+                Err(e) => e,
+            };
+
+            if e.is_transient() && attempt < self.retries {
+                let delay = backoff_delay(
+                    Duration::from_millis(RETRY_BASE_DELAY_MS),
+                    attempt,
+                    Duration::from_millis(RETRY_MAX_DELAY_MS),
+                );
+
+                attempt += 1;
+
+                warn!(
+                    "Synchronization failed ( {} ), retrying in {} ms ( {}/{} )...",
+                    e,
+                    delay.as_millis(),
+                    attempt,
+                    self.retries
+                );
+
+                tokio::time::sleep(delay).await;
+                continue;
+            }
+
+            match e {
+                BlockchainError::NoPackagesData => {
+                    info!("No new packages mutations found")
+                }
+                BlockchainError::SubscriptionTimeout => {
+                    error!("Blockchain subscription timed out, check your connection")
+                }
+                _ => error!("Unhandled error : {}", e),
             }
-        });
-
-        let mut packages_count: u128 = 0;
-
-        let pb = self.build_progress_bar();
-        pb.set_message("Updating blockchain DB...");
-
-        while let Some(package) = rx_packages_update.recv().await {
-            //pb.println(format!(
-            //    "New package mutation found => {}:{} ( Maintainer : {}, Status : {}  )",
-            //    package.name,
-            //    package.version,
-            //    hex::encode_upper(package.maintainer),
-            //    package.status,
-            //));
-            packages_count += 1;
-
-            pb.set_message(format!(
-                "Found {} new packages mutations...",
-                packages_count
+
+            return;
+        }
+    }
+
+    /**
+     * Print package dependencies found in local cache, recursively
+     */
+    async fn print_dependency_tree(
+        &self,
+        package: &Package,
+        blockchains_service: &Arc<BlockchainsService>,
+    ) {
+        if package.dependencies.is_empty() {
+            return;
+        }
+
+        let mut tree = format!("{}:{}", package.name, package.version);
+
+        // Dependencies shared by several packages are only expanded once, which also stops cycles
+        let mut visited: HashSet<(String, String)> = HashSet::new();
+
+        let mut pending: Vec<(usize, (String, String))> = package
+            .dependencies
+            .iter()
+            .rev()
+            .map(|dependency| (1, dependency.clone()))
+            .collect();
+
+        while let Some((depth, (dependency_name, dependency_constraint))) = pending.pop() {
+            let indent = "  ".repeat(depth - 1);
+
+            let dependency_packages = blockchains_service
+                .find_satisfying(&dependency_name, &dependency_constraint)
+                .await;
+
+            // Release satisfying constraint is only shown when constraint is not an exact version
+            let state = match dependency_packages.first() {
+                Some(dependency_package) if dependency_package.version != dependency_constraint => {
+                    format!(
+                        "{}, {}",
+                        dependency_package.version, dependency_package.status
+                    )
+                    .normal()
+                }
+                Some(dependency_package) => dependency_package.status.to_string().normal(),
+                None => "not found".red(),
+            };
+
+            tree.push_str(&format!(
+                "\n{}└─ {}:{} ( {} )",
+                indent, dependency_name, dependency_constraint, state
             ));
+
+            let is_first_visit =
+                visited.insert((dependency_name.clone(), dependency_constraint.clone()));
+
+            if let (true, Some(dependency_package)) = (is_first_visit, dependency_packages.first())
+            {
+                pending.extend(
+                    dependency_package
+                        .dependencies
+                        .iter()
+                        .rev()
+                        .map(|dependency| (depth + 1, dependency.clone())),
+                );
+            }
         }
 
-        pb.finish_with_message(format!(
-            "Done fetching packages from Hedera blockchain ! ({} packages mutations found)",
-            packages_count
-        ));
+        info!("{}\n{}", "Dependencies :".yellow().bold(), tree);
     }
 
     /**
-     * Install package using package_name argument
+     * Offer to remove packages added before a failure, ones already on system are kept
      */
-    pub async fn run(
+    async fn offer_rollback(
         &self,
-        config_manager: &ConfigManager,
-        blockchains_service: &Arc<BlockchainsService>,
+        installed_packages: &Vec<String>,
         package_managers_service: &PackageManagersService,
+        prompter: &dyn Prompter,
     ) {
-        debug!("Subcommand install is being run...");
+        if installed_packages.is_empty() {
+            return;
+        }
 
-        // First update available packages list
+        warn!(
+            "Following packages were installed before failure : {}",
+            installed_packages.join(", ")
+        );
 
-        self.update(blockchains_service).await;
+        let rollback_confirmed = prompter
+            .confirm("Do you want to roll them back?")
+            .unwrap_or(false);
 
-        // Ask which matching package to install
+        if !rollback_confirmed {
+            return;
+        }
+
+        match package_managers_service.rollback(installed_packages).await {
+            Ok(_) => info!("Installed packages have been {} !", "rolled back".red()),
+            Err(e) => error!("Rollback did not complete, reason : {}", e),
+        }
+    }
+
+    /**
+     * Install packages, aborting download in progress on Ctrl-C
+     */
+    async fn install_cancellable(
+        &self,
+        packages: &[Package],
+        progress: &mut InstallProgress,
+        package_managers_service: &PackageManagersService,
+    ) -> Result<(), PackageManagerError> {
+        let install_task = package_managers_service.install_all(packages, progress);
+
+        tokio::pin!(install_task);
+
+        tokio::select! {
+            install_result = &mut install_task => install_result,
+            _ = tokio::signal::ctrl_c() => {
+                warn!("Cancelling download in progress...");
 
-        let package_name = self.package_name.clone().unwrap();
-        let package_version = match self.package_version.clone() {
-            Some(version) => version,
-            _ => {
-                error!("Please specify a package version as CLI does not support latest tags for now, currently WIP");
-                return;
+                package_managers_service.cancel_download().await?;
+
+                install_task.await
             }
+        }
+    }
+
+    /**
+     * Download selected package and check it, without installing it
+     */
+    async fn run_verify_only(
+        &self,
+        package: &Package,
+        package_managers_service: &PackageManagersService,
+    ) -> Result<CommandOutcome, CommandError> {
+        let full_package_name = format!("{}:{}", package.name, package.version);
+
+        info!("Verifying {}...", full_package_name.blue());
+
+        // Archive is discarded along with temporary directory unless output is given
+        let temp_dir = tempfile::tempdir().map_err(|e| CommandError::Other(e.to_string()))?;
+
+        let target_dir = match &self.output {
+            Some(output) => PathBuf::from(output),
+            None => temp_dir.path().to_path_buf(),
+        };
+
+        let archive_url = package_managers_service.resolve_archive_url(&package.archive_url)?;
+
+        let archive_path = download_verified(
+            package_managers_service.get_http_client(),
+            package,
+            &archive_url,
+            &target_dir,
+            &AtomicBool::new(false),
+        )
+        .await?;
+
+        debug!("Subcommand install successfully ran !");
+
+        Ok(CommandOutcome::Verified {
+            package: full_package_name,
+            maintainer_fingerprint: package.get_maintainer_fingerprint(),
+            archive: self
+                .output
+                .as_ref()
+                .map(|_| archive_path.display().to_string()),
+        })
+    }
+
+    /**
+     * Ask which package matching release arguments to install, once maintainer, status and strict filters are applied
+     */
+    async fn select_package(
+        &self,
+        blockchains_service: &Arc<BlockchainsService>,
+        offline: bool,
+        prompter: &dyn Prompter,
+    ) -> Result<Package, CommandError> {
+        let package_name = self
+            .package_name
+            .clone()
+            .ok_or(CommandError::InvalidArgument(String::from(
+                "Package name is required",
+            )))?;
+        let package_version = self.package_version.clone().ok_or(CommandError::InvalidArgument(
+            String::from("Please specify a package version as CLI does not support latest tags for now, currently WIP"),
+        ))?;
+
+        let min_status = match &self.min_status {
+            Some(raw_status) => Some(
+                PackageStatus::from_str(raw_status)
+                    .map_err(|e| CommandError::InvalidArgument(e.to_string()))?,
+            ),
+            None => None,
         };
 
-        let matching_packages = blockchains_service
-            .find_package(&package_name, &package_version)
+        let mut matching_packages = blockchains_service
+            .find_package(&package_name, &package_version, &min_status)
             .await;
 
-        let selection = match Select::with_theme(&ColorfulTheme::default())
-            .with_prompt("BPM found these matches :")
-            .default(0)
-            .items(&matching_packages[..])
-            .interact()
-        {
-            Ok(selection) => selection,
-            Err(e) => {
-                error!("Could not find matching packages, reason : {}", e);
-                return;
+        if offline && matching_packages.is_empty() {
+            return Err(CommandError::PackageNotFound(format!(
+                "{}:{} is not cached, run install without offline mode to synchronize",
+                package_name, package_version
+            )));
+        }
+
+        // Restrict to trusted maintainer if any
+        if let Some(maintainer) = &self.maintainer {
+            matching_packages.retain(|package| matches_maintainer(&package.maintainer, maintainer));
+
+            if matching_packages.is_empty() {
+                return Err(CommandError::PackageNotFound(format!(
+                    "{}:{} published by maintainer {}",
+                    package_name, package_version, maintainer
+                )));
             }
+        }
+
+        // Several maintainers may publish same release
+        if has_maintainers_conflict(&matching_packages) {
+            warn!(
+                "Several maintainers published {}:{}, make sure you trust the selected maintainer",
+                package_name, package_version
+            );
+
+            if self.strict {
+                return Err(CommandError::InvalidArgument(String::from(
+                    "Strict mode enabled, refusing to select a package claimed by several maintainers",
+                )));
+            }
+        }
+
+        let matching_packages_labels: Vec<String> = matching_packages
+            .iter()
+            .map(|package| package.to_compact_string())
+            .collect();
+
+        if matching_packages.is_empty() {
+            return Err(CommandError::PackageNotFound(format!(
+                "{}:{}",
+                package_name, package_version
+            )));
+        }
+
+        // No need to ask when maintainer filter leaves a single package
+        let selection = if self.maintainer.is_some() && matching_packages.len() == 1 {
+            info!("Selected {}", matching_packages_labels[0]);
+            0
+        } else {
+            prompter.select("BPM found these matches :", &matching_packages_labels, 0)?
+        };
+
+        matching_packages
+            .into_iter()
+            .nth(selection)
+            .ok_or(CommandError::InvalidArgument(String::from(
+                "Selected package does not exist",
+            )))
+    }
+
+    /**
+     * Install package using package_name argument
+     */
+    pub async fn run(
+        &self,
+        config_manager: &ConfigManager,
+        blockchains_service: &Arc<BlockchainsService>,
+        package_managers_service: &PackageManagersService,
+        prompter: &dyn Prompter,
+    ) -> Result<CommandOutcome, CommandError> {
+        debug!("Subcommand install is being run...");
+
+        // Fail early rather than after fetching packages when nothing can install them
+        if !self.verify_only {
+            package_managers_service
+                .get_selected_package_manager()
+                .await?;
+        }
+
+        // First update available packages list, unless offline or cache is fresh enough
+
+        let config = config_manager.read_config().ok();
+
+        let offline = self.offline
+            || config
+                .as_ref()
+                .and_then(|config| config.offline)
+                .unwrap_or(false);
+
+        let max_age = self
+            .max_age
+            .or(config.as_ref().and_then(|config| config.cache_max_age))
+            .map(Duration::from_secs);
+
+        let is_cache_fresh = match &max_age {
+            Some(max_age) => blockchains_service.is_cache_fresh(max_age).await,
+            None => false,
         };
 
-        let selected_package = matching_packages.get(selection).unwrap();
-        let package_manager = package_managers_service
-            .get_selected_package_manager()
+        if offline {
+            info!("Offline mode enabled, using local cache only");
+        } else if is_cache_fresh {
+            info!("Local cache is fresh enough, skipping synchronization");
+        } else {
+            self.update_with_retries(blockchains_service).await;
+        }
+
+        // Ask which matching package to install
+
+        let selected_package = self
+            .select_package(blockchains_service, offline, prompter)
+            .await?;
+
+        self.print_dependency_tree(&selected_package, blockchains_service)
             .await;
 
+        // Vetting a package is allowed whatever its status
+        if self.verify_only {
+            return self
+                .run_verify_only(&selected_package, package_managers_service)
+                .await;
+        }
+
         // Check package status
 
         if selected_package.status < PackageStatus::Outdated {
-            error!(
-                "This package cannot be installed given its state : {}",
-                selected_package.status
-            );
-            return;
+            return Err(CommandError::ForbiddenStatus(
+                selected_package.status.to_string(),
+            ));
         }
 
         let full_package_name = format!("{}:{}", selected_package.name, selected_package.version);
 
-        match package_manager
-            .install_from_url(&selected_package.archive_url)
+        // Dependencies come first, packages are installed in order, track them so a failure can be rolled back
+        let packages_to_install = blockchains_service
+            .resolve_dependencies(&selected_package, self.strict)
+            .await?;
+        let mut progress = InstallProgress::default();
+
+        if let Err(e) = self
+            .install_cancellable(
+                &packages_to_install,
+                &mut progress,
+                package_managers_service,
+            )
             .await
         {
-            Ok(_) => {
-                info!(
-                    "Package {} has been {} !",
-                    full_package_name.blue(),
-                    "installed".green()
-                );
-            }
-            Err(_) => {
-                error!(
-                    "Package {} could not be installed",
-                    full_package_name.blue()
-                )
-            }
+            error!(
+                "Package {} could not be installed, reason : {}",
+                full_package_name.blue(),
+                e
+            );
+
+            self.offer_rollback(&progress.added, package_managers_service, prompter)
+                .await;
+
+            return Err(CommandError::PackageManager(e));
         }
 
         debug!("Subcommand install successfully ran !");
+
+        Ok(CommandOutcome::Installed {
+            package: full_package_name,
+            maintainer_fingerprint: selected_package.get_maintainer_fingerprint(),
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::commands::{
+        fixtures::tests::{create_release, CommandEnv},
+        prompter::tests::ScriptedPrompter,
+    };
+
     use super::*;
+
+    /**
+     * It should only offer packages having at least given status
+     */
+    #[tokio::test]
+    async fn test_select_package_min_status() {
+        let env = CommandEnv::new().await;
+
+        let outdated_signer = env.create_signer();
+        let fine_signer = env.create_signer();
+
+        env.cache(&[
+            create_release("foo", "1.0.0", &*outdated_signer, PackageStatus::Outdated),
+            create_release("foo", "1.0.0", &*fine_signer, PackageStatus::Fine),
+        ])
+        .await;
+
+        let command =
+            InstallCommand::parse_from(["install", "foo", "1.0.0", "--min-status", "Fine"]);
+
+        let prompter = ScriptedPrompter::new(&[0], &[]);
+
+        let selected_package = command
+            .select_package(&env.blockchains_service, true, &prompter)
+            .await
+            .unwrap();
+
+        assert_eq!(selected_package.maintainer, fine_signer.public_key());
+        assert_eq!(prompter.get_offered()[0].len(), 1);
+    }
+
+    /**
+     * It should pick package of given maintainer without asking, refusing unknown maintainer
+     */
+    #[tokio::test]
+    async fn test_select_package_maintainer() {
+        let env = CommandEnv::new().await;
+
+        let trusted_signer = env.create_signer();
+        let other_signer = env.create_signer();
+
+        env.cache(&[
+            create_release("foo", "1.0.0", &*trusted_signer, PackageStatus::Outdated),
+            create_release("foo", "1.0.0", &*other_signer, PackageStatus::Recommended),
+        ])
+        .await;
+
+        let trusted_key = hex::encode(trusted_signer.public_key().to_bytes());
+
+        let command =
+            InstallCommand::parse_from(["install", "foo", "1.0.0", "--maintainer", &trusted_key]);
+
+        // Nothing scripted, asking would panic
+        let prompter = ScriptedPrompter::new(&[], &[]);
+
+        let selected_package = command
+            .select_package(&env.blockchains_service, true, &prompter)
+            .await
+            .unwrap();
+
+        assert_eq!(selected_package.maintainer, trusted_signer.public_key());
+
+        let unknown_maintainer_command =
+            InstallCommand::parse_from(["install", "foo", "1.0.0", "--maintainer", "ABCD"]);
+
+        assert_eq!(
+            matches!(
+                unknown_maintainer_command
+                    .select_package(&env.blockchains_service, true, &prompter)
+                    .await,
+                Err(CommandError::PackageNotFound(_))
+            ),
+            true
+        );
+    }
+
+    /**
+     * It should let user pick among several maintainers, unless strict mode is enabled
+     */
+    #[tokio::test]
+    async fn test_select_package_strict() {
+        let env = CommandEnv::new().await;
+
+        let first_signer = env.create_signer();
+        let second_signer = env.create_signer();
+
+        env.cache(&[
+            create_release("foo", "1.0.0", &*first_signer, PackageStatus::Fine),
+            create_release("foo", "1.0.0", &*second_signer, PackageStatus::Fine),
+        ])
+        .await;
+
+        let command = InstallCommand::parse_from(["install", "foo", "1.0.0"]);
+
+        let prompter = ScriptedPrompter::new(&[1], &[]);
+
+        let selected_package = command
+            .select_package(&env.blockchains_service, true, &prompter)
+            .await
+            .unwrap();
+
+        assert_eq!(prompter.get_offered()[0].len(), 2);
+        assert_eq!(
+            selected_package.to_compact_string(),
+            prompter.get_offered()[0][1]
+        );
+
+        let strict_command = InstallCommand::parse_from(["install", "foo", "1.0.0", "--strict"]);
+
+        assert_eq!(
+            matches!(
+                strict_command
+                    .select_package(&env.blockchains_service, true, &prompter)
+                    .await,
+                Err(CommandError::InvalidArgument(_))
+            ),
+            true
+        );
+    }
+
+    /**
+     * It should refuse installing selected package given its status, without installing anything
+     */
+    #[tokio::test]
+    async fn test_run_forbidden_status() {
+        let env = CommandEnv::new().await;
+
+        let signer = env.create_signer();
+
+        env.cache(&[create_release(
+            "foo",
+            "1.0.0",
+            &*signer,
+            PackageStatus::Prohibited,
+        )])
+        .await;
+
+        let command = InstallCommand::parse_from(["install", "foo", "1.0.0", "--offline"]);
+
+        let prompter = ScriptedPrompter::new(&[0], &[]);
+
+        let install_result = command
+            .run(
+                &env.config_manager,
+                &env.blockchains_service,
+                &env.package_managers_service,
+                &prompter,
+            )
+            .await;
+
+        assert_eq!(
+            matches!(install_result, Err(CommandError::ForbiddenStatus(_))),
+            true
+        );
+    }
+
+    /**
+     * It should keep installed packages when rollback is declined
+     */
+    #[tokio::test]
+    async fn test_offer_rollback_declined() {
+        let env = CommandEnv::new().await;
+
+        let command = InstallCommand::parse_from(["install", "foo", "1.0.0"]);
+
+        let prompter = ScriptedPrompter::new(&[], &[false]);
+
+        command
+            .offer_rollback(
+                &vec![String::from("bar"), String::from("foo")],
+                &env.package_managers_service,
+                &prompter,
+            )
+            .await;
+
+        assert_eq!(env.get_removed().is_empty(), true);
+    }
+
+    /**
+     * It should remove installed packages in reverse order when rollback is confirmed
+     */
+    #[tokio::test]
+    async fn test_offer_rollback_confirmed() {
+        let env = CommandEnv::new().await;
+
+        let command = InstallCommand::parse_from(["install", "foo", "1.0.0"]);
+
+        let prompter = ScriptedPrompter::new(&[], &[true]);
+
+        command
+            .offer_rollback(
+                &vec![String::from("bar"), String::from("foo")],
+                &env.package_managers_service,
+                &prompter,
+            )
+            .await;
+
+        assert_eq!(env.get_removed(), vec!["foo", "bar"]);
+    }
 }
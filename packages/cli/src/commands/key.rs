@@ -0,0 +1,294 @@
+use bpm_core::config::manager::ConfigManager;
+use bpm_core::packages::package_builder::PackageBuilder;
+use bpm_core::packages::utils::signatures::sign_package_now;
+use bpm_core::services::blockchains::BlockchainsService;
+use bpm_core::services::packages::PackagesService;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use clap::{Parser, Subcommand};
+use colored::Colorize;
+use log::{debug, info, warn};
+
+use super::errors::command_error::CommandError;
+use super::outcome::CommandOutcome;
+use super::prompter::Prompter;
+
+/** Manage maintainer key */
+#[derive(Debug, Parser)]
+pub struct KeyCommand {
+    #[clap(subcommand)]
+    pub action: KeyAction,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum KeyAction {
+    /**
+     * Rotate maintainer key then re-publish packages signed with the new key
+     *
+     * Packages are identified by their name, version and maintainer key, so re-signed packages
+     * are published as new packages owned by the new key. Packages published under the previous
+     * key are left untouched on the blockchain, the previous key is kept next to the new one
+     * ( key.pem.<timestamp>.old ) so they can still be mutated ( eg: marked as prohibited ).
+     *
+     * Previous key stays in use until every package has been re-published, otherwise new key is
+     * saved aside ( key.pem.<timestamp>.pending ) so rotation can be resumed using --import.
+     * Packages already re-signed with the imported key and revoked packages are skipped.
+     */
+    #[clap(name = "rotate")]
+    Rotate {
+        /**
+         * Import new key from PKCS#8 PEM file instead of generating one
+         */
+        #[clap(long)]
+        import: Option<String>,
+
+        /**
+         * Do not ask for confirmation
+         */
+        #[clap(long, short)]
+        yes: bool,
+    },
+}
+
+/**
+ * Handles maintainer key management requests from CLI
+ */
+impl KeyCommand {
+    /**
+     * Rotate maintainer key then re-sign published packages
+     */
+    async fn rotate(
+        &self,
+        import: &Option<String>,
+        yes: bool,
+        config_manager: &ConfigManager,
+        blockchains_service: &Arc<BlockchainsService>,
+        packages_service: &PackagesService,
+        prompter: &dyn Prompter,
+    ) -> Result<CommandOutcome, CommandError> {
+        let previous_verifying_key = config_manager
+            .get_verifying_key()
+            .map_err(|e| CommandError::MaintainerKey(e.to_string()))?;
+
+        let blockchain_client = blockchains_service.get_selected_client().await;
+
+        // Revoked packages are withdrawn, there is nothing to carry over to the new key
+        let published_packages: Vec<_> = packages_service
+            .get_by_maintainer(&previous_verifying_key, &blockchain_client)
+            .await
+            .into_iter()
+            .filter(|package| !package.revoked)
+            .collect();
+
+        let new_signing_key = match import {
+            Some(key_path) => ConfigManager::read_signing_key(&PathBuf::from(key_path))?,
+            None => config_manager.new_signing_key(),
+        };
+
+        let new_verifying_key = new_signing_key.verifying_key();
+
+        // Packages re-signed by an interrupted rotation are already owned by the new key
+        let rotated_packages = packages_service
+            .get_by_maintainer(&new_verifying_key, &blockchain_client)
+            .await;
+
+        info!(
+            "Maintainer key {} will be replaced by {}, {} published packages will be re-signed",
+            hex::encode_upper(previous_verifying_key.to_bytes()),
+            hex::encode_upper(new_verifying_key.to_bytes()),
+            published_packages.len()
+        );
+
+        if !yes && !prompter.confirm("Do you want to continue?")? {
+            return Ok(CommandOutcome::Cancelled);
+        }
+
+        let mut republished_packages = 0;
+
+        for package in published_packages.iter() {
+            let rotated_package = PackageBuilder::from_package(&package)
+                .set_maintainer(&new_verifying_key)
+                .build();
+
+            if rotated_packages
+                .iter()
+                .any(|rotated| rotated.content_eq(&rotated_package))
+            {
+                republished_packages += 1;
+
+                info!(
+                    "Package {}:{} has already been re-signed, skipping",
+                    package.name.blue(),
+                    package.version.blue()
+                );
+
+                continue;
+            }
+
+            let signed_rotated_package = sign_package_now(&rotated_package, &new_signing_key);
+
+            match blockchains_service
+                .submit_signed(&signed_rotated_package)
+                .await
+            {
+                Ok(_) => {
+                    republished_packages += 1;
+
+                    info!(
+                        "Package {}:{} has been re-signed !",
+                        package.name.blue(),
+                        package.version.blue()
+                    );
+                }
+                Err(e) => warn!(
+                    "Package {}:{} could not be re-signed : {}",
+                    package.name.blue(),
+                    package.version.blue(),
+                    e
+                ),
+            }
+        }
+
+        // Keep previous key in use as long as some packages are only published under it
+        if republished_packages < published_packages.len() {
+            let pending_key_path = config_manager.save_pending_signing_key(&new_signing_key)?;
+
+            return Err(CommandError::KeyRotation(format!(
+                "{} of {} packages re-signed, previous key is still in use, run again with --import {} to resume",
+                republished_packages,
+                published_packages.len(),
+                pending_key_path.display()
+            )));
+        }
+
+        let backup_key_path = config_manager.rotate_signing_key(&new_signing_key)?;
+
+        info!(
+            "Previous key has been saved at {}",
+            backup_key_path.display()
+        );
+
+        Ok(CommandOutcome::KeyRotated {
+            maintainer: hex::encode_upper(new_verifying_key.to_bytes()),
+            republished_packages,
+        })
+    }
+
+    /**
+     * Key command
+     */
+    pub async fn run(
+        &self,
+        config_manager: &ConfigManager,
+        blockchains_service: &Arc<BlockchainsService>,
+        packages_service: &PackagesService,
+        prompter: &dyn Prompter,
+    ) -> Result<CommandOutcome, CommandError> {
+        debug!("Subcommand key is being run...");
+
+        let outcome = match &self.action {
+            KeyAction::Rotate { import, yes } => {
+                self.rotate(
+                    import,
+                    *yes,
+                    config_manager,
+                    blockchains_service,
+                    packages_service,
+                    prompter,
+                )
+                .await?
+            }
+        };
+
+        debug!("Subcommand key successfully ran !");
+
+        Ok(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bpm_core::packages::{
+        package_status::PackageStatus, utils::signatures::sign_revocation_now,
+    };
+
+    use crate::commands::{
+        fixtures::tests::{create_release, CommandEnv},
+        prompter::tests::ScriptedPrompter,
+    };
+
+    use super::*;
+
+    /**
+     * It should only re-sign packages neither revoked nor re-signed by an interrupted rotation
+     */
+    #[tokio::test]
+    async fn test_rotate_resume() {
+        let env = CommandEnv::new().await;
+
+        let previous_signer = env.config_manager.get_signer().unwrap();
+
+        let new_signing_key = env.config_manager.new_signing_key();
+
+        let pending_key_path = env
+            .config_manager
+            .save_pending_signing_key(&new_signing_key)
+            .unwrap();
+
+        let rotated_package =
+            create_release("foo", "1.0.0", &*previous_signer, PackageStatus::Fine);
+        let revoked_package =
+            create_release("bar", "1.0.0", &*previous_signer, PackageStatus::Fine);
+
+        let already_rotated_package = sign_package_now(
+            &PackageBuilder::from_package(&rotated_package)
+                .set_maintainer(&new_signing_key.verifying_key())
+                .build(),
+            &new_signing_key,
+        );
+
+        env.cache(&[
+            rotated_package,
+            sign_revocation_now(&revoked_package, &*previous_signer),
+            already_rotated_package,
+        ])
+        .await;
+
+        let command = KeyCommand::parse_from([
+            "key",
+            "rotate",
+            "--yes",
+            "--import",
+            pending_key_path.to_str().unwrap(),
+        ]);
+
+        // Nothing scripted, asking would panic
+        let prompter = ScriptedPrompter::new(&[], &[]);
+
+        let outcome = command
+            .run(
+                &env.config_manager,
+                &env.blockchains_service,
+                &env.packages_service,
+                &prompter,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            outcome,
+            CommandOutcome::KeyRotated {
+                maintainer: hex::encode_upper(new_signing_key.verifying_key().to_bytes()),
+                republished_packages: 1,
+            }
+        );
+
+        assert_eq!(env.has_written(), false);
+
+        assert_eq!(
+            env.config_manager.get_verifying_key().unwrap(),
+            new_signing_key.verifying_key()
+        );
+    }
+}
@@ -0,0 +1,120 @@
+use bpm_core::packages::package::Package;
+use bpm_core::packages::package_status::PackageStatus;
+use bpm_core::packages::utils::decoding::decode_maintainer;
+use bpm_core::packages::utils::versions::compare_versions;
+use bpm_core::services::{blockchains::BlockchainsService, packages::PackagesService};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use clap::Parser;
+use log::debug;
+
+use super::errors::command_error::CommandError;
+use super::outcome::{CommandOutcome, PackageSummary};
+
+/** List packages found in local cache */
+#[derive(Debug, Parser)]
+pub struct ListCommand {
+    /**
+     * Only list packages published by this maintainer public key ( hex )
+     */
+    #[clap(long)]
+    pub maintainer: Option<String>,
+
+    /**
+     * Only list packages having this status ( eg: Fine )
+     */
+    #[clap(long)]
+    pub status: Option<String>,
+
+    /**
+     * Print packages as JSON instead of a table
+     */
+    #[clap(long)]
+    pub json: bool,
+}
+
+/**
+ * Handles list request from CLI
+ */
+impl ListCommand {
+    /**
+     * Read packages matching given filters from local cache
+     */
+    async fn read_packages(
+        &self,
+        blockchains_service: &Arc<BlockchainsService>,
+        packages_service: &Arc<PackagesService>,
+    ) -> Result<Vec<Package>, CommandError> {
+        let status = match &self.status {
+            Some(raw_status) => Some(
+                PackageStatus::from_str(raw_status)
+                    .map_err(|e| CommandError::InvalidArgument(e.to_string()))?,
+            ),
+            None => None,
+        };
+
+        let blockchain_client = blockchains_service.get_selected_client().await;
+
+        let packages = match (&self.maintainer, &status) {
+            (Some(raw_maintainer), _) => {
+                let maintainer_bytes = hex::decode(raw_maintainer)
+                    .map_err(|e| CommandError::InvalidArgument(e.to_string()))?;
+
+                let maintainer = decode_maintainer(&maintainer_bytes)
+                    .map_err(|e| CommandError::InvalidArgument(e.to_string()))?;
+
+                let mut packages = packages_service
+                    .get_by_maintainer(&maintainer, &blockchain_client)
+                    .await;
+
+                if let Some(status) = &status {
+                    packages.retain(|package| &package.status == status);
+                }
+
+                packages
+            }
+            (None, Some(status)) => {
+                packages_service
+                    .get_by_status(status, &blockchain_client)
+                    .await
+            }
+            (None, None) => packages_service.get_all().await,
+        };
+
+        Ok(packages)
+    }
+
+    /**
+     * List command
+     */
+    pub async fn run(
+        &self,
+        blockchains_service: &Arc<BlockchainsService>,
+        packages_service: &Arc<PackagesService>,
+    ) -> Result<CommandOutcome, CommandError> {
+        debug!("Subcommand list is being run...");
+
+        let mut packages: Vec<PackageSummary> = self
+            .read_packages(blockchains_service, packages_service)
+            .await?
+            .iter()
+            .map(|package| PackageSummary {
+                name: package.name.clone(),
+                version: package.version.clone(),
+                status: package.status.to_string(),
+                maintainer_fingerprint: package.get_maintainer_fingerprint(),
+            })
+            .collect();
+
+        packages.sort_by(|a, b| {
+            a.name
+                .cmp(&b.name)
+                .then_with(|| compare_versions(&a.version, &b.version))
+        });
+
+        debug!("Subcommand list successfully ran !");
+
+        Ok(CommandOutcome::Listed { packages })
+    }
+}
@@ -0,0 +1,170 @@
+use bpm_core::packages::utils::validation::validate_dependency;
+use std::path::PathBuf;
+
+use super::errors::command_error::CommandError;
+
+/**
+ * Package release listed in submit manifest
+ */
+#[derive(serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ManifestEntry {
+    pub name: String,
+    pub version: String,
+    pub archive: String,         // Local archive path, hashed before signing
+    pub archive_url: String,     // Url archive is published at
+    pub sources: Option<String>, // Local sources directory, hashed before signing
+    #[serde(default)]
+    pub dependencies: Vec<(String, String)>, // Name and version constraint of required packages ( exact or minimum, eg: >=2.40 )
+}
+
+/**
+ * Batch of packages to submit at once
+ *
+ * eg: { "packages": [ { "name": "neofetch", "version": "7.1.0-2", "archive": "...", "archive_url": "..." } ] }
+ */
+#[derive(serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SubmitManifest {
+    pub packages: Vec<ManifestEntry>,
+}
+
+impl SubmitManifest {
+    /**
+     * Read manifest from JSON file
+     */
+    pub fn from_file(manifest_path: &PathBuf) -> Result<Self, CommandError> {
+        let raw_manifest = std::fs::read_to_string(manifest_path).map_err(|e| {
+            CommandError::InvalidArgument(format!(
+                "Manifest {} could not be read: {}",
+                manifest_path.display(),
+                e
+            ))
+        })?;
+
+        Self::from_json(&raw_manifest)
+    }
+
+    /**
+     * Parse manifest from JSON, refusing empty ones
+     */
+    pub fn from_json(raw_manifest: &str) -> Result<Self, CommandError> {
+        let manifest: SubmitManifest = serde_json::from_str(raw_manifest)
+            .map_err(|e| CommandError::InvalidArgument(format!("Invalid manifest: {}", e)))?;
+
+        if manifest.packages.is_empty() {
+            return Err(CommandError::InvalidArgument(String::from(
+                "Manifest does not list any package",
+            )));
+        }
+
+        for entry in &manifest.packages {
+            for (dependency_name, dependency_version) in &entry.dependencies {
+                validate_dependency(dependency_name, dependency_version)
+                    .map_err(|e| CommandError::InvalidArgument(e.to_string()))?;
+            }
+        }
+
+        Ok(manifest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /**
+     * It should parse manifest entries in order
+     */
+    #[test]
+    fn test_manifest_from_json() {
+        let raw_manifest = r#"{
+            "packages": [
+                {
+                    "name": "neofetch",
+                    "version": "7.1.0-2",
+                    "archive": "/tmp/neofetch-7.1.0-2-any.pkg.tar.zst",
+                    "archive_url": "https://archive.archlinux.org/packages/n/neofetch/neofetch-7.1.0-2-any.pkg.tar.zst"
+                },
+                {
+                    "name": "htop",
+                    "version": "3.3.0-1",
+                    "archive": "/tmp/htop-3.3.0-1-x86_64.pkg.tar.zst",
+                    "archive_url": "https://archive.archlinux.org/packages/h/htop/htop-3.3.0-1-x86_64.pkg.tar.zst"
+                }
+            ]
+        }"#;
+
+        let manifest = SubmitManifest::from_json(raw_manifest).unwrap();
+
+        let names: Vec<&str> = manifest
+            .packages
+            .iter()
+            .map(|entry| entry.name.as_str())
+            .collect();
+
+        assert_eq!(names, vec!["neofetch", "htop"]);
+    }
+
+    /**
+     * It should refuse empty or malformed manifest
+     */
+    #[test]
+    fn test_manifest_from_invalid_json() {
+        assert_eq!(
+            matches!(
+                SubmitManifest::from_json(r#"{ "packages": [] }"#),
+                Err(CommandError::InvalidArgument(_))
+            ),
+            true
+        );
+        assert_eq!(
+            matches!(
+                SubmitManifest::from_json(r#"{ "packages": [ { "name": "foo" } ] }"#),
+                Err(CommandError::InvalidArgument(_))
+            ),
+            true
+        );
+    }
+
+    /**
+     * It should accept minimum version dependencies and refuse other constraints
+     */
+    #[test]
+    fn test_manifest_with_dependency_constraint() {
+        let min_version_manifest = r#"{
+            "packages": [
+                {
+                    "name": "neofetch",
+                    "version": "7.1.0-2",
+                    "archive": "/tmp/neofetch-7.1.0-2-any.pkg.tar.zst",
+                    "archive_url": "https://archive.archlinux.org/packages/n/neofetch/neofetch-7.1.0-2-any.pkg.tar.zst",
+                    "dependencies": [ [ "bash", ">=5.0" ] ]
+                }
+            ]
+        }"#;
+
+        assert_eq!(
+            SubmitManifest::from_json(min_version_manifest).is_ok(),
+            true
+        );
+
+        let raw_manifest = r#"{
+            "packages": [
+                {
+                    "name": "neofetch",
+                    "version": "7.1.0-2",
+                    "archive": "/tmp/neofetch-7.1.0-2-any.pkg.tar.zst",
+                    "archive_url": "https://archive.archlinux.org/packages/n/neofetch/neofetch-7.1.0-2-any.pkg.tar.zst",
+                    "dependencies": [ [ "bash", "^5.0" ] ]
+                }
+            ]
+        }"#;
+
+        assert_eq!(
+            matches!(
+                SubmitManifest::from_json(raw_manifest),
+                Err(CommandError::InvalidArgument(_))
+            ),
+            true
+        );
+    }
+}
@@ -1,25 +1,68 @@
+mod debug;
+mod errors;
+mod fixtures;
+mod info;
 mod install;
+mod key;
+mod list;
+mod manifest;
 mod mutate;
+mod outcome;
+mod progress;
+mod prompter;
 mod remove;
+mod status;
 mod submit;
+mod sync;
+mod update;
+mod upgrade;
+mod watch;
+mod whoami;
 
 use bpm_core::{
+    blockchains::get_topic_client,
+    bpm::Bpm,
     config::manager::ConfigManager,
     services::{
         blockchains::BlockchainsService, package_managers::PackageManagersService,
         packages::PackagesService,
     },
 };
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use debug::DebugCommand;
+use errors::command_error::CommandError;
+use key::KeyCommand;
+use list::ListCommand;
 use mutate::MutateCommand;
 use remove::RemoveCommand;
 
-use dialoguer::{theme::ColorfulTheme, Select};
+use info::InfoCommand;
 use install::InstallCommand;
+use log::{info, warn};
+use outcome::CommandOutcome;
+use prompter::{DialoguerPrompter, Prompter};
+use status::StatusCommand;
 use std::sync::Arc;
 use submit::SubmitCommand;
+use sync::SyncCommand;
+use update::UpdateCommand;
+use upgrade::UpgradeCommand;
+use watch::WatchCommand;
+use whoami::WhoamiCommand;
 
 #[derive(Debug, Parser)]
+struct BpmCli {
+    /**
+     * Prompt which blockchain to use even if one was saved, then save new selection
+     */
+    #[clap(long, global = true)]
+    select_chain: bool,
+
+    #[clap(subcommand)]
+    command: BbpmCLIOptions,
+}
+
+#[derive(Debug, Subcommand)]
 enum BbpmCLIOptions {
     #[clap(name = "install")]
     Install(InstallCommand),
@@ -32,28 +75,114 @@ enum BbpmCLIOptions {
 
     #[clap(name = "submit")]
     Submit(SubmitCommand),
+
+    #[clap(name = "key")]
+    Key(KeyCommand),
+
+    #[clap(name = "sync")]
+    Sync(SyncCommand),
+
+    #[clap(name = "update")]
+    Update(UpdateCommand),
+
+    #[clap(name = "upgrade")]
+    Upgrade(UpgradeCommand),
+
+    #[clap(name = "watch")]
+    Watch(WatchCommand),
+
+    #[clap(name = "status")]
+    Status(StatusCommand),
+
+    #[clap(name = "list")]
+    List(ListCommand),
+
+    #[clap(name = "info")]
+    Info(InfoCommand),
+
+    #[clap(name = "whoami")]
+    Whoami(WhoamiCommand),
+
+    #[clap(name = "debug", hide = true)]
+    Debug(DebugCommand),
 }
 
 impl BbpmCLIOptions {
     /**
-     * Prompt which blockchain to use
+     * Prompt which blockchain to use then save selection
      */
     async fn blockchain_prompt(
         &self,
         config_manager: &mut ConfigManager,
         blockchains_service: &Arc<BlockchainsService>,
-    ) {
-        // TODO: save selection
+        prompter: &dyn Prompter,
+    ) -> Result<(), CommandError> {
         let clients = blockchains_service.get_clients();
-        let selections = clients.lock().await;
-        let selection = Select::with_theme(&ColorfulTheme::default())
-            .with_prompt("Which blockchain would you like to use ?")
-            .default(0)
-            .items(&selections[..])
-            .interact()
-            .unwrap();
+        let (selections, labels): (Vec<String>, Vec<String>) = clients
+            .lock()
+            .await
+            .iter()
+            .map(|client| (client.to_string(), client.get_label()))
+            .unzip();
+
+        let selection =
+            prompter.select("Which blockchain would you like to use ?", &selections, 0)?;
 
         blockchains_service.set_client(selection).await;
+
+        config_manager.set_selected_blockchain(&labels[selection])?;
+
+        Ok(())
+    }
+
+    /**
+     * Use saved blockchain, only prompting when none was saved, it is not available anymore or a new selection is asked
+     */
+    async fn select_blockchain(
+        &self,
+        select_chain: bool,
+        config_manager: &mut ConfigManager,
+        blockchains_service: &Arc<BlockchainsService>,
+        prompter: &dyn Prompter,
+    ) -> Result<(), CommandError> {
+        if !select_chain {
+            if let Some(saved_label) = config_manager.get_selected_blockchain()? {
+                if blockchains_service.set_client_by_label(&saved_label).await {
+                    return Ok(());
+                }
+
+                warn!(
+                    "Saved blockchain {} is not available anymore, please select another one",
+                    saved_label
+                );
+            }
+        }
+
+        self.blockchain_prompt(config_manager, blockchains_service, prompter)
+            .await
+    }
+
+    /**
+     * Check if command works on selected blockchain, others use every blockchain
+     */
+    fn uses_selected_blockchain(&self) -> bool {
+        match self {
+            Self::Submit(submit) => !submit.all_chains,
+            Self::Status(_) | Self::Whoami(_) => false,
+            _ => true,
+        }
+    }
+
+    /**
+     * Get topic overriding configured one, if any
+     */
+    fn get_topic_override(&self) -> Option<&String> {
+        match self {
+            Self::Install(install) => install.topic.as_ref(),
+            Self::Submit(submit) => submit.topic.as_ref(),
+            Self::Sync(sync) => sync.topic.as_ref(),
+            _ => None,
+        }
     }
 
     /**
@@ -61,59 +190,147 @@ impl BbpmCLIOptions {
      */
     pub async fn run(
         &self,
+        select_chain: bool,
         config_manager: &mut ConfigManager,
         blockchains_service: &Arc<BlockchainsService>,
         packages_service: &Arc<PackagesService>,
         package_managers_service: &Arc<PackageManagersService>,
+        prompter: &dyn Prompter,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        self.blockchain_prompt(config_manager, &blockchains_service)
-            .await;
-        match self {
+        match self.get_topic_override() {
+            Some(topic) => {
+                let client_idx = blockchains_service
+                    .add_client(&get_topic_client(topic)?)
+                    .await;
+
+                blockchains_service.set_client(client_idx).await;
+            }
+            None if !self.uses_selected_blockchain() => {}
+            None => {
+                self.select_blockchain(select_chain, config_manager, &blockchains_service, prompter)
+                    .await?
+            }
+        }
+
+        let outcome = match self {
             Self::Install(install) => {
                 install
                     .run(
                         &config_manager,
                         &blockchains_service,
                         package_managers_service,
+                        prompter,
                     )
-                    .await
-            }
-            Self::Remove(remove) => {
-                remove.run(package_managers_service).await;
+                    .await?
             }
+            Self::Remove(remove) => remove.run(package_managers_service).await?,
             Self::Mutate(mutate) => {
+                let signer = config_manager
+                    .get_signer()
+                    .map_err(|e| CommandError::MaintainerKey(e.to_string()))?;
+
                 mutate
-                    .run(&config_manager, &blockchains_service, &packages_service)
-                    .await;
+                    .run(
+                        signer.as_ref(),
+                        &blockchains_service,
+                        &packages_service,
+                        prompter,
+                    )
+                    .await?
             }
-            Self::Submit(submit) => submit.run(&config_manager, blockchains_service).await?,
-        }
+            Self::Submit(submit) => {
+                let signer = config_manager
+                    .get_signer()
+                    .map_err(|e| CommandError::MaintainerKey(e.to_string()))?;
+
+                submit
+                    .run(signer.as_ref(), blockchains_service, prompter)
+                    .await?
+            }
+            Self::Key(key) => {
+                key.run(
+                    &config_manager,
+                    &blockchains_service,
+                    &packages_service,
+                    prompter,
+                )
+                .await?
+            }
+            Self::Sync(sync) => sync.run(&blockchains_service, &packages_service).await?,
+            Self::Update(update) => update.run(&blockchains_service).await?,
+            Self::Upgrade(upgrade) => {
+                upgrade
+                    .run(
+                        &blockchains_service,
+                        &packages_service,
+                        package_managers_service,
+                        prompter,
+                    )
+                    .await?
+            }
+            Self::Watch(watch) => watch.run(&blockchains_service).await?,
+            Self::Status(status) => status.run(&blockchains_service).await?,
+            Self::List(list) => list.run(&blockchains_service, &packages_service).await?,
+            Self::Info(info) => info.run(&blockchains_service, &packages_service).await?,
+            Self::Whoami(whoami) => whoami.run(&config_manager).await?,
+            Self::Debug(debug) => debug.run(&blockchains_service).await?,
+        };
+
+        self.render(&outcome);
 
         Ok(())
     }
+
+    /**
+     * Check if command output must be machine-readable
+     */
+    fn wants_json(&self) -> bool {
+        match self {
+            Self::List(list) => list.json,
+            _ => false,
+        }
+    }
+
+    /**
+     * Render command outcome
+     */
+    fn render(&self, outcome: &CommandOutcome) {
+        if self.wants_json() {
+            match serde_json::to_string_pretty(outcome) {
+                Ok(json) => println!("{}", json),
+                Err(e) => warn!("Outcome could not be rendered as JSON : {}", e),
+            }
+
+            return;
+        }
+
+        info!("{}", outcome);
+    }
 }
 
 /**
  * Parse CLI args then run chain of commands
  */
 #[cfg(not(tarpaulin_include))]
-pub async fn bootstrap(
-    config_manager: &mut ConfigManager,
-    blockchains_service: &Arc<BlockchainsService>,
-    packages_service: &Arc<PackagesService>,
-    package_managers_service: &Arc<PackageManagersService>,
-) -> Result<(), Box<dyn std::error::Error>> {
-    use bpm_core::services::packages::PackagesService;
-
-    let args = BbpmCLIOptions::parse();
-
-    args.run(
-        config_manager,
-        blockchains_service,
-        packages_service,
-        package_managers_service,
-    )
-    .await?;
+pub async fn bootstrap(bpm: &mut Bpm) -> Result<(), Box<dyn std::error::Error>> {
+    let args = BpmCli::parse();
+
+    let blockchains_service = bpm.get_blockchains_service();
+    let packages_service = bpm.get_packages_service();
+    let package_managers_service = bpm.get_package_managers_service();
+
+    let prompter = DialoguerPrompter::default();
+
+    args.command
+        .run(
+            args.select_chain,
+            bpm.get_config_manager_mut(),
+            &blockchains_service,
+            &packages_service,
+            &package_managers_service,
+            &prompter,
+        )
+        .await?;
 
     Ok(())
 }
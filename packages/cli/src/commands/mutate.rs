@@ -1,127 +1,373 @@
-use bpm_core::config::manager::ConfigManager;
+use bpm_core::packages::package::Package;
 use bpm_core::packages::package_builder::PackageBuilder;
 use bpm_core::packages::package_status::PackageStatus;
-use bpm_core::packages::utils::signatures::sign_package;
+use bpm_core::packages::utils::integrity::compute_package_file_hash;
+use bpm_core::packages::utils::signatures::sign_package_now;
+use bpm_core::packages::utils::signer::Signer;
 use bpm_core::services::blockchains::BlockchainsService;
 use bpm_core::services::packages::PackagesService;
+use std::path::PathBuf;
 use std::sync::Arc;
-use std::time::Duration;
+use url::Url;
 
 use clap::Parser;
-use dialoguer::theme::ColorfulTheme;
-use dialoguer::{FuzzySelect, Select};
-use indicatif::{ProgressBar, ProgressStyle};
+use colored::Colorize;
 use log::{debug, info};
 use std::str::FromStr;
 use strum::IntoEnumIterator;
 
+use super::errors::command_error::CommandError;
+use super::outcome::CommandOutcome;
+use super::prompter::Prompter;
+
 /** Mutate package */
 #[derive(Debug, Parser)]
-pub struct MutateCommand {}
+pub struct MutateCommand {
+    /**
+     * New package status ( eg: Outdated ), asked interactively when no field is given
+     */
+    #[clap(long)]
+    pub status: Option<String>,
+
+    /**
+     * New package archive url, requires --archive so integrity can be recomputed
+     */
+    #[clap(long)]
+    pub archive_url: Option<String>,
+
+    /**
+     * Package archive matching new archive url ( eg: /home/user/neofetch-7.1.0-2-any.pkg.tar.zst )
+     */
+    #[clap(long)]
+    pub archive: Option<String>,
+
+    /**
+     * Do not ask for confirmation
+     */
+    #[clap(long, short)]
+    pub yes: bool,
+}
 
 /**
  * Handles package mutation request from CLI
  */
 impl MutateCommand {
     /**
-     * Build progress bar
+     * Prompt new package status
      */
-    fn build_progress_bar(&self) -> ProgressBar {
-        let pb = ProgressBar::new_spinner();
-        pb.enable_steady_tick(Duration::from_millis(60));
-        pb.set_style(
-            ProgressStyle::with_template("{spinner:.blue} {msg}")
-                .unwrap()
-                .tick_strings(&[
-                    "[    ]", "[=   ]", "[==  ]", "[=== ]", "[====]", "[ ===]", "[  ==]", "[   =]",
-                    "[    ]", "[   =]", "[  ==]", "[ ===]", "[====]", "[=== ]", "[==  ]", "[====]",
-                ]),
-        );
+    fn status_prompt(&self, prompter: &dyn Prompter) -> Result<PackageStatus, CommandError> {
+        let package_status_choices: Vec<String> = PackageStatus::iter()
+            .map(|status| status.to_string())
+            .collect();
+
+        let package_status_selection =
+            prompter.select("Package status", &package_status_choices, 0)?;
+
+        let raw_selected_status = package_status_choices.get(package_status_selection).ok_or(
+            CommandError::InvalidArgument(String::from("Selected status does not exist")),
+        )?;
 
-        pb
+        PackageStatus::from_str(&raw_selected_status)
+            .map_err(|e| CommandError::InvalidArgument(e.to_string()))
     }
 
     /**
-     * Install package using package_name argument
+     * Mutate package selected among published ones
      */
     pub async fn run(
         &self,
-        config_manager: &ConfigManager,
+        signer: &dyn Signer,
         blockchains_service: &Arc<BlockchainsService>,
         packages_service: &PackagesService,
-    ) {
+        prompter: &dyn Prompter,
+    ) -> Result<CommandOutcome, CommandError> {
         debug!("Subcommand mutate is being run...");
 
-        let maintainer_verifying_key = config_manager
-            .get_verifying_key()
-            .expect("Could not find maintainer key to mutate package");
+        // Archive url cannot change without integrity matching new archive
+
+        if self.archive_url.is_some() && self.archive.is_none() {
+            return Err(CommandError::InvalidArgument(String::from(
+                "Changing archive url requires --archive so integrity can be recomputed",
+            )));
+        }
+
+        let new_archive_url = match &self.archive_url {
+            Some(archive_url) => Some(
+                Url::parse(archive_url)
+                    .map_err(|e| CommandError::InvalidArgument(e.to_string()))?,
+            ),
+            None => None,
+        };
+
+        let maintainer_verifying_key = signer.public_key();
 
         let blockchain_client = blockchains_service.get_selected_client().await;
 
-        let published_packages = packages_service
+        // Revoked releases are final, re-signing their tombstone would only publish another one
+        let published_packages: Vec<Package> = packages_service
             .get_by_maintainer(&maintainer_verifying_key, &blockchain_client)
-            .await;
-
-        let package_selection = FuzzySelect::with_theme(&ColorfulTheme::default())
-            .with_prompt("Published packages")
-            .default(0)
-            .items(&published_packages[..])
-            .interact()
-            .unwrap();
+            .await
+            .into_iter()
+            .filter(|package| !package.revoked)
+            .collect();
 
-        let selected_package = published_packages
-            .get(package_selection)
-            .expect("Selected package does not exist");
+        if published_packages.is_empty() {
+            return Err(CommandError::PackageNotFound(String::from(
+                "No mutable package published with your key",
+            )));
+        }
 
-        let package_status_choices: Vec<String> = PackageStatus::iter()
-            .map(|status| status.to_string())
+        let published_packages_labels: Vec<String> = published_packages
+            .iter()
+            .map(|package| package.to_string())
             .collect();
 
-        let package_status_selection = Select::with_theme(&ColorfulTheme::default())
-            .with_prompt("Package status")
-            .default(0)
-            .items(&package_status_choices[..])
-            .interact()
-            .unwrap();
+        let package_selection =
+            prompter.fuzzy_select("Published packages", &published_packages_labels, 0)?;
 
-        let raw_selected_status = package_status_choices
-            .get(package_status_selection)
-            .unwrap();
-        let selected_status: PackageStatus = PackageStatus::from_str(&raw_selected_status)
-            .expect("Could not parse package status from string");
+        let selected_package =
+            published_packages
+                .get(package_selection)
+                .ok_or(CommandError::InvalidArgument(String::from(
+                    "Selected package does not exist",
+                )))?;
 
-        let updated_package = PackageBuilder::from_package(&selected_package)
-            .set_status(&selected_status)
-            .build();
+        // Only ask for status when no field to mutate was given
 
-        // Sign package
+        let selected_status: PackageStatus = match (&self.status, &new_archive_url) {
+            (Some(raw_status), _) => PackageStatus::from_str(raw_status)
+                .map_err(|e| CommandError::InvalidArgument(e.to_string()))?,
+            (None, Some(_)) => selected_package.status.clone(),
+            (None, None) => self.status_prompt(prompter)?,
+        };
 
-        info!("Signing package mutations...");
-        let mut signing_key = config_manager
-            .get_signing_key()
-            .expect("Could not load your signing key");
+        let mut builder = PackageBuilder::from_package(&selected_package);
+
+        builder.set_status(&selected_status);
+
+        if let (Some(archive_url), Some(archive)) = (&new_archive_url, &self.archive) {
+            // Keep algorithm release was published with
+            let algorithm = selected_package
+                .integrity
+                .get_algorithm()
+                .map_err(|e| CommandError::InvalidArgument(e.to_string()))?;
+
+            let (archive_hash, integrity_algorithm) =
+                compute_package_file_hash(&PathBuf::from(archive), &algorithm).await?;
+
+            // Archive is rebuilt from same release, so it still commits to same sources
+            builder
+                .set_archive_url(archive_url)
+                .set_integrity(&integrity_algorithm, &archive_hash)
+                .set_source_code_hash(&selected_package.integrity.source_code_hash);
+        }
+
+        let updated_package = builder.build();
 
-        let package_sig = sign_package(&updated_package, &mut signing_key);
+        // Mutations cannot be reverted once published, make sure it is wanted
 
-        let signed_updated_package = PackageBuilder::from_package(&updated_package)
-            .set_signature(&package_sig)
-            .build();
+        info!(
+            "{} \n{}:{} ( Maintainer : {} )\nStatus => {} -> {}\nArchive URL => {} -> {}",
+            "Following mutation will be published to the blockchain :"
+                .yellow()
+                .bold(),
+            selected_package.name.blue(),
+            selected_package.version.blue(),
+            hex::encode_upper(selected_package.maintainer.to_bytes()),
+            selected_package.status,
+            updated_package.status,
+            selected_package.archive_url,
+            updated_package.archive_url
+        );
+
+        if !self.yes && !prompter.confirm("Do you want to continue?")? {
+            return Ok(CommandOutcome::Cancelled);
+        }
+
+        // Sign package
+
+        info!("Signing package mutations...");
+        let signed_updated_package = sign_package_now(&updated_package, signer);
 
         info!("Done signing package mutations !");
 
         info!("Mutating package remotely...");
 
         blockchains_service
-            .submit_package(&signed_updated_package)
-            .await;
+            .submit_signed(&signed_updated_package)
+            .await?;
 
         info!("Done mutating package remotely !");
 
         debug!("Subcommand mutate successfully ran !");
+
+        Ok(CommandOutcome::Mutated {
+            package: format!(
+                "{}:{}",
+                signed_updated_package.name, signed_updated_package.version
+            ),
+            status: selected_status.to_string(),
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use bpm_core::packages::utils::signatures::sign_revocation_now;
+
+    use crate::commands::{
+        fixtures::tests::{create_release, CommandEnv},
+        prompter::tests::ScriptedPrompter,
+    };
+
     use super::*;
+
+    /**
+     * It should map selected choice to matching status
+     */
+    #[test]
+    fn test_status_prompt() {
+        let command = MutateCommand::parse_from(["mutate"]);
+
+        let selected_index = PackageStatus::iter()
+            .position(|status| status == PackageStatus::Outdated)
+            .unwrap();
+
+        let prompter = ScriptedPrompter::new(&[selected_index], &[]);
+
+        assert_eq!(
+            command.status_prompt(&prompter).unwrap(),
+            PackageStatus::Outdated
+        );
+    }
+
+    /**
+     * It should publish mutation of selected package once confirmed
+     */
+    #[tokio::test]
+    async fn test_run_confirmed() {
+        let env = CommandEnv::new().await;
+
+        let signer = env.create_signer();
+
+        env.cache(&[create_release(
+            "foo",
+            "1.0.0",
+            &*signer,
+            PackageStatus::Fine,
+        )])
+        .await;
+
+        let command = MutateCommand::parse_from(["mutate", "--status", "Outdated"]);
+
+        let prompter = ScriptedPrompter::new(&[0], &[true]);
+
+        let outcome = command
+            .run(
+                &*signer,
+                &env.blockchains_service,
+                &env.packages_service,
+                &prompter,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            outcome,
+            CommandOutcome::Mutated {
+                package: String::from("foo:1.0.0"),
+                status: PackageStatus::Outdated.to_string(),
+            }
+        );
+        assert_eq!(env.has_written(), true);
+    }
+
+    /**
+     * It should not publish anything when mutation is declined
+     */
+    #[tokio::test]
+    async fn test_run_declined() {
+        let env = CommandEnv::new().await;
+
+        let signer = env.create_signer();
+
+        env.cache(&[create_release(
+            "foo",
+            "1.0.0",
+            &*signer,
+            PackageStatus::Fine,
+        )])
+        .await;
+
+        let command = MutateCommand::parse_from(["mutate", "--status", "Outdated"]);
+
+        let prompter = ScriptedPrompter::new(&[0], &[false]);
+
+        let outcome = command
+            .run(
+                &*signer,
+                &env.blockchains_service,
+                &env.packages_service,
+                &prompter,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(outcome, CommandOutcome::Cancelled);
+        assert_eq!(env.has_written(), false);
+    }
+
+    /**
+     * It should leave revoked packages out of mutable ones
+     */
+    #[tokio::test]
+    async fn test_run_skips_revoked() {
+        let env = CommandEnv::new().await;
+
+        let signer = env.create_signer();
+
+        let revoked_package = create_release("foo", "1.0.0", &*signer, PackageStatus::Fine);
+        let package = create_release("bar", "1.0.0", &*signer, PackageStatus::Fine);
+
+        env.cache(&[sign_revocation_now(&revoked_package, &*signer), package])
+            .await;
+
+        let command = MutateCommand::parse_from(["mutate", "--status", "Outdated"]);
+
+        let prompter = ScriptedPrompter::new(&[0], &[false]);
+
+        command
+            .run(
+                &*signer,
+                &env.blockchains_service,
+                &env.packages_service,
+                &prompter,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(prompter.get_offered()[0].len(), 1);
+        assert_eq!(prompter.get_offered()[0][0].starts_with("bar"), true);
+
+        let revoked_only_env = CommandEnv::new().await;
+
+        revoked_only_env
+            .cache(&[sign_revocation_now(&revoked_package, &*signer)])
+            .await;
+
+        assert_eq!(
+            matches!(
+                command
+                    .run(
+                        &*signer,
+                        &revoked_only_env.blockchains_service,
+                        &revoked_only_env.packages_service,
+                        &prompter,
+                    )
+                    .await,
+                Err(CommandError::PackageNotFound(_))
+            ),
+            true
+        );
+    }
 }
@@ -0,0 +1,441 @@
+use std::fmt;
+
+use colored::Colorize;
+
+/**
+ * Synchronization state of a blockchain
+ */
+#[derive(serde::Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct BlockchainStatus {
+    pub label: String,
+    pub last_synchronization: u64,
+    pub last_attempt_age: Option<u64>, // Seconds since last attempt, None if never attempted
+    pub last_error: Option<String>,
+}
+
+impl fmt::Display for BlockchainStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.last_attempt_age, &self.last_error) {
+            (None, _) => write!(f, "{}: never synchronized", self.label.blue()),
+            (Some(age), Some(last_error)) => write!(
+                f,
+                "{}: last sync {} {} ago: {}",
+                self.label.blue(),
+                "failed".red(),
+                format_age(*age),
+                last_error
+            ),
+            (Some(age), None) => write!(
+                f,
+                "{}: {} {} ago",
+                self.label.blue(),
+                "synchronized".green(),
+                format_age(*age)
+            ),
+        }
+    }
+}
+
+/**
+ * Package as listed from local cache
+ */
+#[derive(serde::Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct PackageSummary {
+    pub name: String,
+    pub version: String,
+    pub status: String,
+    pub maintainer_fingerprint: String,
+}
+
+/**
+ * Format seconds using largest fitting unit ( eg: 2h )
+ */
+fn format_age(seconds: u64) -> String {
+    match seconds {
+        0..=59 => format!("{}s", seconds),
+        60..=3599 => format!("{}m", seconds / 60),
+        3600..=86399 => format!("{}h", seconds / 3600),
+        _ => format!("{}d", seconds / 86400),
+    }
+}
+
+/**
+ * Describe what a CLI command did
+ */
+#[derive(serde::Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum CommandOutcome {
+    Installed {
+        package: String,
+        maintainer_fingerprint: String,
+    },
+    Removed {
+        package: String,
+    },
+    Verified {
+        package: String,
+        maintainer_fingerprint: String,
+        archive: Option<String>, // None when archive was discarded
+    },
+    Mutated {
+        package: String,
+        status: String,
+    },
+    Submitted {
+        package: String,
+        confirmed: Option<bool>, // None when confirmation was not requested
+    },
+    SubmittedToAll {
+        package: String,
+        submitted_chains: Vec<String>,
+        failed_chains: Vec<String>,
+        skipped_chains: Vec<String>, // Already published to
+    },
+    BatchSubmitted {
+        submitted: Vec<String>, // Packages, along with blockchain when submitting to every one
+        failed: Vec<String>,
+    },
+    Previewed {
+        packages: Vec<String>, // Packages that would have been submitted
+    },
+    KeyRotated {
+        maintainer: String,
+        republished_packages: usize,
+    },
+    Synced {
+        added: usize,
+        updated: usize,
+        unchanged: usize,
+        rejected: usize, // Mutations not signed after stored ones
+        revoked: usize,
+    },
+    Updated {
+        added: usize,
+        updated: usize,
+        mutations: Vec<String>, // Packages mutations found on blockchain
+        last_sync: u64,
+    },
+    Upgraded {
+        upgraded: Vec<String>,
+        skipped: Vec<String>, // Installed packages not published on blockchain
+        conflicting: Vec<String>, // Installed packages published by several maintainers, needing --maintainer
+    },
+    SyncReported {
+        added: Vec<String>,
+        removed: Vec<String>,
+        status_changed: Vec<String>, // Package along with its previous and new status
+    },
+    MessageFetched {
+        sequence_number: u64,
+        package: Option<String>, // None when message could not be found
+        maintainer: Option<String>,
+        verified: bool,
+    },
+    Status {
+        blockchains: Vec<BlockchainStatus>,
+    },
+    Listed {
+        packages: Vec<PackageSummary>,
+    },
+    Described {
+        details: Vec<String>, // One entry per maintainer having published the release
+        last_sync: u64,
+    },
+    Identity {
+        maintainer: String,
+        maintainer_fingerprint: String,
+    },
+    Cancelled,
+}
+
+impl fmt::Display for CommandOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Installed { package, .. } => {
+                write!(
+                    f,
+                    "Package {} has been {} !",
+                    package.blue(),
+                    "installed".green()
+                )
+            }
+            Self::Verified {
+                package,
+                maintainer_fingerprint,
+                archive,
+            } => {
+                write!(
+                    f,
+                    "Package {} has been {} ( Maintainer : {} )",
+                    package.blue(),
+                    "verified".green(),
+                    maintainer_fingerprint
+                )?;
+
+                match archive {
+                    Some(archive) => write!(f, ", archive kept at {}", archive),
+                    None => Ok(()),
+                }
+            }
+            Self::Removed { package } => {
+                write!(
+                    f,
+                    "Package {} has been {} !",
+                    package.blue(),
+                    "removed".red()
+                )
+            }
+            Self::Mutated { package, status } => write!(
+                f,
+                "Package {} has been {} ( Status : {} ) !",
+                package.blue(),
+                "mutated".green(),
+                status
+            ),
+            Self::Submitted {
+                package,
+                confirmed: Some(false),
+            } => write!(
+                f,
+                "Package {} has been submitted but could not be read back from blockchain",
+                package.blue()
+            ),
+            Self::Submitted { package, confirmed } => write!(
+                f,
+                "Package {} has been {} !",
+                package.blue(),
+                match confirmed {
+                    Some(true) => "confirmed".green(),
+                    _ => "submitted".green(),
+                }
+            ),
+            Self::SubmittedToAll {
+                package,
+                submitted_chains,
+                failed_chains,
+                ..
+            } if failed_chains.is_empty() => write!(
+                f,
+                "Package {} has been {} to {} blockchains !",
+                package.blue(),
+                "submitted".green(),
+                submitted_chains.len()
+            ),
+            Self::SubmittedToAll {
+                package,
+                submitted_chains,
+                failed_chains,
+                ..
+            } => write!(
+                f,
+                "Package {} has been submitted to {} blockchains but {} for {}",
+                package.blue(),
+                submitted_chains.len(),
+                "failed".red(),
+                failed_chains.join(", ")
+            ),
+            Self::BatchSubmitted { submitted, failed } if failed.is_empty() => write!(
+                f,
+                "{} packages have been {} !",
+                submitted.len(),
+                "submitted".green()
+            ),
+            Self::BatchSubmitted { submitted, failed } => write!(
+                f,
+                "{} packages have been submitted but {} for {}",
+                submitted.len(),
+                "failed".red(),
+                failed.join(", ")
+            ),
+            Self::Previewed { packages } => write!(
+                f,
+                "Dry run, {} packages would have been submitted : {}",
+                packages.len(),
+                packages.join(", ")
+            ),
+            Self::KeyRotated {
+                maintainer,
+                republished_packages,
+            } => write!(
+                f,
+                "Maintainer key has been rotated to {} ( {} packages re-signed ) !",
+                maintainer, republished_packages
+            ),
+            Self::Synced {
+                added,
+                updated,
+                unchanged,
+                rejected,
+                revoked,
+            } => write!(
+                f,
+                "Packages have been {} ! ( {} new, {} updated, {} unchanged, {} rejected, {} revoked )",
+                "synchronized".green(),
+                added,
+                updated,
+                unchanged,
+                rejected,
+                revoked
+            ),
+            Self::Updated {
+                mutations,
+                last_sync,
+                ..
+            } if mutations.is_empty() => {
+                write!(
+                    f,
+                    "Packages are already {} ! ( last synced at {} )",
+                    "up to date".green(),
+                    last_sync
+                )
+            }
+            Self::Updated {
+                added,
+                updated,
+                mutations,
+                last_sync,
+            } => {
+                write!(
+                    f,
+                    "Packages have been {} ! ( {} mutations found : {} new, {} updated )",
+                    "updated".green(),
+                    mutations.len(),
+                    added,
+                    updated
+                )?;
+
+                for mutation in mutations {
+                    write!(f, "\n  {} {}", "*".blue(), mutation)?;
+                }
+
+                write!(f, "\nLast synced at {}", last_sync)
+            }
+            Self::Upgraded {
+                upgraded,
+                skipped,
+                conflicting,
+            } if upgraded.is_empty() => write!(
+                f,
+                "Installed packages are already {} ! ( {} not published on blockchain, {} published by several maintainers )",
+                "up to date".green(),
+                skipped.len(),
+                conflicting.len()
+            ),
+            Self::Upgraded {
+                upgraded,
+                skipped,
+                conflicting,
+            } => {
+                write!(
+                    f,
+                    "{} packages have been {} ! ( {} not published on blockchain, {} published by several maintainers )",
+                    upgraded.len(),
+                    "upgraded".green(),
+                    skipped.len(),
+                    conflicting.len()
+                )?;
+
+                for package in upgraded {
+                    write!(f, "\n  {} {}", "*".blue(), package)?;
+                }
+
+                Ok(())
+            }
+            Self::SyncReported {
+                added,
+                removed,
+                status_changed,
+            } => {
+                write!(
+                    f,
+                    "Packages have been {} ! ( {} added, {} removed, {} status changed )",
+                    "synchronized".green(),
+                    added.len(),
+                    removed.len(),
+                    status_changed.len()
+                )?;
+
+                for package in added {
+                    write!(f, "\n  {} {}", "+".green(), package)?;
+                }
+
+                for package in removed {
+                    write!(f, "\n  {} {}", "-".red(), package)?;
+                }
+
+                for package in status_changed {
+                    write!(f, "\n  {} {}", "~".yellow(), package)?;
+                }
+
+                Ok(())
+            }
+            Self::MessageFetched {
+                sequence_number,
+                package: Some(package),
+                maintainer,
+                verified,
+            } => write!(
+                f,
+                "Message {} => {} ( Maintainer : {}, Signature : {} )",
+                sequence_number,
+                package.blue(),
+                maintainer.as_deref().unwrap_or_default(),
+                if *verified {
+                    "valid".green()
+                } else {
+                    "invalid".red()
+                }
+            ),
+            Self::MessageFetched {
+                sequence_number, ..
+            } => write!(f, "Message {} could not be found", sequence_number),
+            Self::Status { blockchains } if blockchains.is_empty() => {
+                write!(f, "No blockchain registered yet")
+            }
+            Self::Status { blockchains } => {
+                let lines: Vec<String> = blockchains.iter().map(|b| b.to_string()).collect();
+
+                write!(f, "{}", lines.join("\n"))
+            }
+            Self::Listed { packages } if packages.is_empty() => {
+                write!(f, "No package found in local cache")
+            }
+            Self::Listed { packages } => {
+                let name_width = packages.iter().map(|p| p.name.len()).max().unwrap_or(0);
+                let version_width = packages.iter().map(|p| p.version.len()).max().unwrap_or(0);
+                let status_width = packages.iter().map(|p| p.status.len()).max().unwrap_or(0);
+
+                let lines: Vec<String> = packages
+                    .iter()
+                    .map(|package| {
+                        format!(
+                            "{}  {:<version_width$}  {:<status_width$}  {}",
+                            format!("{:<name_width$}", package.name).blue(),
+                            package.version,
+                            package.status,
+                            package.maintainer_fingerprint
+                        )
+                    })
+                    .collect();
+
+                write!(f, "{}", lines.join("\n"))
+            }
+            Self::Described { details, last_sync } => write!(
+                f,
+                "{}\n\nLast synced at {}",
+                details.join("\n\n"),
+                last_sync
+            ),
+            Self::Identity {
+                maintainer,
+                maintainer_fingerprint,
+            } => write!(
+                f,
+                "Maintainer => {} ( Fingerprint : {} )",
+                maintainer,
+                maintainer_fingerprint.blue()
+            ),
+            Self::Cancelled => write!(f, "nevermind then :("),
+        }
+    }
+}
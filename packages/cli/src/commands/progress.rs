@@ -0,0 +1,73 @@
+use bpm_core::blockchains::errors::blockchain_error::BlockchainError;
+use bpm_core::packages::package::Package;
+use bpm_core::services::blockchains::BlockchainsService;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use indicatif::{ProgressBar, ProgressStyle};
+use log::debug;
+use tokio::sync::mpsc;
+
+use super::errors::command_error::CommandError;
+
+/**
+ * Build spinner shared by every long running command
+ */
+pub fn build_progress_bar() -> ProgressBar {
+    let pb = ProgressBar::new_spinner();
+    pb.enable_steady_tick(Duration::from_millis(60));
+    pb.set_style(
+        ProgressStyle::with_template("{spinner:.blue} {msg}")
+            .unwrap()
+            .tick_strings(&[
+                "[    ]", "[=   ]", "[==  ]", "[=== ]", "[====]", "[ ===]", "[  ==]", "[   =]",
+                "[    ]", "[   =]", "[  ==]", "[ ===]", "[====]", "[=== ]", "[==  ]", "[====]",
+            ]),
+    );
+
+    pb
+}
+
+/**
+ * Run given update of blockchain DB in background while spinner counts found mutations
+ *
+ * Returns update result along with every mutation found, failing only when update task died
+ */
+pub async fn run_update<T, F, Fut>(
+    blockchains_service: &Arc<BlockchainsService>,
+    update: F,
+) -> Result<(Result<T, BlockchainError>, Vec<Package>), CommandError>
+where
+    F: FnOnce(Arc<BlockchainsService>, mpsc::Sender<Package>) -> Fut,
+    Fut: Future<Output = Result<T, BlockchainError>> + Send + 'static,
+    T: Send + 'static,
+{
+    let (tx_packages_update, mut rx_packages_update) = mpsc::channel(1);
+
+    let update_task = tokio::spawn(update(Arc::clone(blockchains_service), tx_packages_update));
+
+    let mut mutations: Vec<Package> = vec![];
+
+    let pb = build_progress_bar();
+    pb.set_message("Updating blockchain DB...");
+
+    while let Some(package) = rx_packages_update.recv().await {
+        debug!("New package mutation found => {}", package);
+
+        mutations.push(package);
+
+        pb.set_message(format!(
+            "Found {} new packages mutations...",
+            mutations.len()
+        ));
+    }
+
+    let update_result = update_task.await;
+
+    pb.finish_and_clear();
+
+    let update_result = update_result.map_err(|e| CommandError::Other(e.to_string()))?;
+
+    Ok((update_result, mutations))
+}
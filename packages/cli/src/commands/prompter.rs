@@ -0,0 +1,170 @@
+use dialoguer::{theme::ColorfulTheme, Confirm, FuzzySelect, Select};
+
+/**
+ * Interactive prompts used by commands, abstracted so flows can be scripted in tests
+ */
+pub trait Prompter: Send + Sync {
+    /**
+     * Ask user to pick one of items, returns selected index
+     */
+    fn select(
+        &self,
+        prompt: &str,
+        items: &[String],
+        default: usize,
+    ) -> Result<usize, dialoguer::Error>;
+
+    /**
+     * Ask user to confirm
+     */
+    fn confirm(&self, prompt: &str) -> Result<bool, dialoguer::Error>;
+
+    /**
+     * Ask user to pick one of items with fuzzy search, returns selected index
+     */
+    fn fuzzy_select(
+        &self,
+        prompt: &str,
+        items: &[String],
+        default: usize,
+    ) -> Result<usize, dialoguer::Error>;
+}
+
+/**
+ * Prompter asking user on terminal
+ */
+#[derive(Default)]
+pub struct DialoguerPrompter {
+    theme: ColorfulTheme,
+}
+
+impl Prompter for DialoguerPrompter {
+    fn select(
+        &self,
+        prompt: &str,
+        items: &[String],
+        default: usize,
+    ) -> Result<usize, dialoguer::Error> {
+        Select::with_theme(&self.theme)
+            .with_prompt(prompt)
+            .default(default)
+            .items(items)
+            .interact()
+    }
+
+    fn confirm(&self, prompt: &str) -> Result<bool, dialoguer::Error> {
+        Confirm::with_theme(&self.theme)
+            .with_prompt(prompt)
+            .interact()
+    }
+
+    fn fuzzy_select(
+        &self,
+        prompt: &str,
+        items: &[String],
+        default: usize,
+    ) -> Result<usize, dialoguer::Error> {
+        FuzzySelect::with_theme(&self.theme)
+            .with_prompt(prompt)
+            .default(default)
+            .items(items)
+            .interact()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use std::{collections::VecDeque, sync::Mutex};
+
+    use super::*;
+
+    /**
+     * Prompter replaying scripted answers, selections are shared by select and fuzzy_select
+     */
+    #[derive(Default)]
+    pub struct ScriptedPrompter {
+        selections: Mutex<VecDeque<usize>>,
+        confirmations: Mutex<VecDeque<bool>>,
+        offered: Mutex<Vec<Vec<String>>>,
+    }
+
+    impl ScriptedPrompter {
+        /**
+         * Create prompter answering with given selections and confirmations, in order
+         */
+        pub fn new(selections: &[usize], confirmations: &[bool]) -> Self {
+            Self {
+                selections: Mutex::new(selections.iter().copied().collect()),
+                confirmations: Mutex::new(confirmations.iter().copied().collect()),
+                offered: Mutex::new(vec![]),
+            }
+        }
+
+        /**
+         * Get items offered by each selection prompt so far, in order
+         */
+        pub fn get_offered(&self) -> Vec<Vec<String>> {
+            self.offered.lock().unwrap().clone()
+        }
+
+        fn next_selection(&self, items: &[String]) -> Result<usize, dialoguer::Error> {
+            self.offered.lock().unwrap().push(items.to_vec());
+
+            let selection = self
+                .selections
+                .lock()
+                .unwrap()
+                .pop_front()
+                .expect("No scripted selection left");
+
+            assert!(selection < items.len(), "Scripted selection out of range");
+
+            Ok(selection)
+        }
+    }
+
+    impl Prompter for ScriptedPrompter {
+        fn select(
+            &self,
+            _prompt: &str,
+            items: &[String],
+            _default: usize,
+        ) -> Result<usize, dialoguer::Error> {
+            self.next_selection(items)
+        }
+
+        fn confirm(&self, _prompt: &str) -> Result<bool, dialoguer::Error> {
+            Ok(self
+                .confirmations
+                .lock()
+                .unwrap()
+                .pop_front()
+                .expect("No scripted confirmation left"))
+        }
+
+        fn fuzzy_select(
+            &self,
+            _prompt: &str,
+            items: &[String],
+            _default: usize,
+        ) -> Result<usize, dialoguer::Error> {
+            self.next_selection(items)
+        }
+    }
+
+    /**
+     * It should replay scripted answers in order
+     */
+    #[test]
+    fn test_scripted_prompter() {
+        let prompter = ScriptedPrompter::new(&[1, 0], &[true, false]);
+
+        let items = vec![String::from("foo"), String::from("bar")];
+
+        assert_eq!(prompter.select("", &items, 0).unwrap(), 1);
+        assert_eq!(prompter.fuzzy_select("", &items, 0).unwrap(), 0);
+        assert_eq!(prompter.confirm("").unwrap(), true);
+        assert_eq!(prompter.confirm("").unwrap(), false);
+        assert_eq!(prompter.get_offered(), vec![items.clone(), items]);
+    }
+}
@@ -1,9 +1,10 @@
 use bpm_core::services::package_managers::PackageManagersService;
 
-use colored::Colorize;
-
 use clap::Parser;
-use log::{debug, error, info};
+use log::debug;
+
+use super::errors::command_error::CommandError;
+use super::outcome::CommandOutcome;
 
 /** Remove package using its name */
 #[derive(Debug, Parser)]
@@ -16,29 +17,30 @@ impl RemoveCommand {
     /**
      * Remove package using package_name argument
      */
-    pub async fn run(&self, package_managers_service: &PackageManagersService) {
+    pub async fn run(
+        &self,
+        package_managers_service: &PackageManagersService,
+    ) -> Result<CommandOutcome, CommandError> {
         debug!("Subcommand remove is being run...");
 
-        let package_name = self.package_name.as_ref().unwrap();
+        let package_name = self
+            .package_name
+            .as_ref()
+            .ok_or(CommandError::InvalidArgument(String::from(
+                "Package name is required",
+            )))?;
 
         let package_manager = package_managers_service
             .get_selected_package_manager()
-            .await;
+            .await?;
 
         // TODO : when fetching by installed implemented use this instead of raw package_name
-        match package_manager.remove(package_name).await {
-            Ok(_) => {
-                info!(
-                    "Package {} has been {} !",
-                    package_name.blue(),
-                    "removed".red()
-                );
-            }
-            Err(_) => {
-                error!("Package {} could not be removed", package_name.blue())
-            }
-        }
+        package_manager.remove(package_name).await?;
 
         debug!("Subcommand remove successfully ran !");
+
+        Ok(CommandOutcome::Removed {
+            package: package_name.clone(),
+        })
     }
 }
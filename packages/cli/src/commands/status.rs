@@ -0,0 +1,53 @@
+use bpm_core::services::blockchains::BlockchainsService;
+use std::{
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use clap::Parser;
+use log::debug;
+
+use super::errors::command_error::CommandError;
+use super::outcome::{BlockchainStatus, CommandOutcome};
+
+/** Show synchronization state of every blockchain */
+#[derive(Debug, Parser)]
+pub struct StatusCommand {}
+
+/**
+ * Handles status request from CLI
+ */
+impl StatusCommand {
+    /**
+     * Status command
+     */
+    pub async fn run(
+        &self,
+        blockchains_service: &Arc<BlockchainsService>,
+    ) -> Result<CommandOutcome, CommandError> {
+        debug!("Subcommand status is being run...");
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|e| CommandError::Other(e.to_string()))?
+            .as_secs() as i64;
+
+        let blockchains = blockchains_service
+            .get_blockchains_status()
+            .await
+            .into_iter()
+            .map(|doc| BlockchainStatus {
+                label: doc.label,
+                last_synchronization: doc.last_synchronization,
+                // Blockchains never synchronized since attempts are tracked have no age
+                last_attempt_age: (doc.last_attempt > 0)
+                    .then(|| u64::try_from(now - doc.last_attempt).unwrap_or_default()),
+                last_error: doc.last_error,
+            })
+            .collect();
+
+        debug!("Subcommand status successfully ran !");
+
+        Ok(CommandOutcome::Status { blockchains })
+    }
+}
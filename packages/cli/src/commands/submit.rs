@@ -1,52 +1,116 @@
-use clap::Parser;
-use colored::*;
 use bpm_core::{
-    config::manager::ConfigManager,
     packages::{
+        integrity_algorithm::IntegrityAlgorithm,
         package::{Package, DEFAULT_PACKAGE_STATUS},
         package_builder::PackageBuilder,
-        utils::{integrity::compute_package_file_hash, signatures::sign_package},
+        utils::{
+            integrity::{compute_package_directory_hash, compute_package_file_hash},
+            signatures::sign_package_now,
+            signer::Signer,
+            validation::{validate_dependency, validate_package_name, validate_package_version},
+        },
     },
     services::blockchains::BlockchainsService,
 };
-use dialoguer::{theme::ColorfulTheme, Confirm};
-use log::{debug, info};
+use clap::Parser;
+use colored::*;
+use log::{debug, error, info, warn};
 use std::fmt::Write;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use std::{path::PathBuf, sync::Arc};
 use url::Url;
 
+use super::errors::command_error::CommandError;
+use super::manifest::SubmitManifest;
+use super::outcome::CommandOutcome;
+use super::prompter::Prompter;
+
 /** Submit package using sources  */
 #[derive(Debug, Parser)]
 pub struct SubmitCommand {
     /**
      * Package name ( eg: neofetch )
      */
-    #[clap(required = true)]
+    #[clap(required_unless_present = "manifest")]
     pub package_name: Option<String>,
 
     /**
      * Package version ( eg: 7.1.0-2  )
      */
-    #[clap(required = true)]
+    #[clap(required_unless_present = "manifest")]
     pub package_version: Option<String>,
 
-    ///**
-    // * Package sources directory ( eg: git repo... )
-    // */
-    //#[clap(required = true)]
-    //pub package_sources_directory: Option<String>,
-
     /**
      * Package archive directory ( eg: /home/user/neofetch-7.1.0-2-any.pkg.tar.zst... )
      */
-    #[clap(required = true)]
+    #[clap(required_unless_present = "manifest")]
     pub package_archive_directory: Option<String>,
 
     /**
      * Package archive url ( eg: https://archive.archlinux.org/packages/n/neofetch/neofetch-7.1.0-2-any.pkg.tar.zst )
      */
-    #[clap(required = true)]
+    #[clap(required_unless_present = "manifest")]
     pub package_archive_url: Option<String>,
+
+    /**
+     * Package sources directory, hashed so package commits to its sources ( eg: git repo... )
+     */
+    #[clap(long = "sources", conflicts_with = "manifest")]
+    pub package_sources_directory: Option<String>,
+
+    /**
+     * Algorithm archive and sources are hashed with ( SHA256, SHA512 or BLAKE3 )
+     */
+    #[clap(long, default_value_t = IntegrityAlgorithm::Sha256)]
+    pub integrity_algorithm: IntegrityAlgorithm,
+
+    /**
+     * Wait for submitted package to be readable from blockchain
+     */
+    #[clap(long)]
+    pub verify: bool,
+
+    /**
+     * Maximum time to wait for submitted package, in seconds
+     */
+    #[clap(long, default_value_t = 60)]
+    pub verify_timeout: u64,
+
+    /**
+     * Publish package to this topic instead of configured one ( eg: 0.0.1234 )
+     */
+    #[clap(long)]
+    pub topic: Option<String>,
+
+    /**
+     * Publish package to every configured blockchain instead of selected one
+     */
+    #[clap(long, conflicts_with_all = ["topic", "verify"])]
+    pub all_chains: bool,
+
+    /**
+     * Submit package even if same content is already published
+     */
+    #[clap(long)]
+    pub force: bool,
+
+    /**
+     * Submit every package listed in this JSON manifest instead of a single one
+     */
+    #[clap(long, conflicts_with_all = ["package_name", "verify"])]
+    pub manifest: Option<String>,
+
+    /**
+     * Only show what would be published, without submitting anything
+     */
+    #[clap(long)]
+    pub dry_run: bool,
+
+    /**
+     * Package this one depends on at an exact or minimum version, can be repeated ( eg: --dependency glibc:>=2.40 )
+     */
+    #[clap(long = "dependency", conflicts_with = "manifest")]
+    pub dependencies: Vec<String>,
 }
 
 /**
@@ -78,7 +142,11 @@ impl SubmitCommand {
             "\tArchive hash => {} \n",
             hex::encode(&package.integrity.archive_hash)
         )?;
-        write!(buf, "\tSource code hash =>\n\n")?;
+        write!(
+            buf,
+            "\tSource code hash => {}\n\n",
+            hex::encode(&package.integrity.source_code_hash)
+        )?;
 
         write!(
             buf,
@@ -90,41 +158,74 @@ impl SubmitCommand {
     }
 
     /**
-     * Submit command
+     * Parse dependencies given as name:version, version being exact as constraints are not supported
      */
-    pub async fn run(
-        &self,
-        config_manager: &ConfigManager,
-        blockchains_service: &Arc<BlockchainsService>,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        debug!("Subcommand submit is being run...");
-
-        let package_name = self.package_name.as_ref().unwrap();
-        let package_version = self.package_version.as_ref().unwrap();
-
-        //let sources_directory = self.package_sources_directory.as_ref().unwrap();
-
-        let package_archive_directory =
-            PathBuf::from(self.package_archive_directory.as_ref().unwrap());
+    fn parse_dependencies(&self) -> Result<Vec<(String, String)>, CommandError> {
+        self.dependencies
+            .iter()
+            .map(|dependency| {
+                let (name, version) = dependency.split_once(':').ok_or_else(|| {
+                    CommandError::InvalidArgument(format!(
+                        "Dependency {} must be formatted as name:version or name:>=version",
+                        dependency
+                    ))
+                })?;
+
+                validate_dependency(name, version)
+                    .map_err(|e| CommandError::InvalidArgument(e.to_string()))?;
+
+                Ok((name.to_string(), version.to_string()))
+            })
+            .collect()
+    }
 
-        let package_archive_url = self.package_archive_url.as_ref().unwrap();
+    /**
+     * Hash archive then build and sign package from given release information
+     */
+    async fn build_signed_package(
+        &self,
+        signer: &dyn Signer,
+        package_name: &String,
+        package_version: &String,
+        package_archive_directory: &PathBuf,
+        package_sources_directory: Option<&PathBuf>,
+        package_archive_url: &String,
+        dependencies: &[(String, String)],
+    ) -> Result<Package, CommandError> {
+        // Reject releases which would corrupt composite keys before anything is signed
+
+        validate_package_name(package_name)
+            .map_err(|e| CommandError::InvalidArgument(e.to_string()))?;
+        validate_package_version(package_version)
+            .map_err(|e| CommandError::InvalidArgument(e.to_string()))?;
 
         // Parse archive url
-        let archive_url = Url::parse(&package_archive_url.as_str())?;
+        let archive_url = Url::parse(&package_archive_url.as_str())
+            .map_err(|e| CommandError::InvalidArgument(e.to_string()))?;
 
-        // Get maintainer signing key
+        // Get maintainer key
 
-        let verifying_key = config_manager.get_verifying_key()?;
+        let verifying_key = signer.public_key();
 
         // Compute hashes
 
         let (package_archive_hash, integrity_algorithm) =
-            compute_package_file_hash(&package_archive_directory).await?;
+            compute_package_file_hash(package_archive_directory, &self.integrity_algorithm).await?;
 
-        //let package_source_code_hash =
-        //    compute_package_file_hash(&package_archive_directory).await?;
         let mut builder = PackageBuilder::default();
 
+        builder.set_integrity(&integrity_algorithm, &package_archive_hash);
+
+        if let Some(package_sources_directory) = package_sources_directory {
+            let (package_source_code_hash, _) = compute_package_directory_hash(
+                package_sources_directory,
+                &self.integrity_algorithm,
+            )
+            .await?;
+
+            builder.set_source_code_hash(&package_source_code_hash);
+        }
+
         // Build base package
         let package = builder
             .set_name(&package_name.to_string())
@@ -132,23 +233,256 @@ impl SubmitCommand {
             .set_status(&DEFAULT_PACKAGE_STATUS)
             .set_maintainer(&verifying_key)
             .set_archive_url(&archive_url)
-            .set_integrity(&integrity_algorithm, &package_archive_hash)
-            .build();
+            .set_dependencies(dependencies)
+            .try_build()
+            .map_err(|e| CommandError::InvalidArgument(e.to_string()))?;
 
         // Sign package
 
-        let mut signing_key = config_manager.get_signing_key()?;
+        Ok(sign_package_now(&package, signer))
+    }
 
-        let package_sig = sign_package(&package, &mut signing_key);
+    /**
+     * Avoid paying for a duplicate mutation of an already published release, unless forced
+     *
+     * When submitting to every blockchain, only refused once every one has it, returns ones to skip otherwise
+     */
+    async fn ensure_not_published(
+        &self,
+        signed_package: &Package,
+        blockchains_service: &Arc<BlockchainsService>,
+    ) -> Result<Vec<String>, CommandError> {
+        if self.force {
+            return Ok(vec![]);
+        }
 
-        let signed_package = PackageBuilder::from_package(&package)
-            .set_signature(&package_sig)
-            .build();
+        let published_chains = if self.all_chains {
+            blockchains_service
+                .get_published_chains(signed_package)
+                .await
+        } else if blockchains_service.is_published(signed_package).await {
+            vec![blockchains_service.get_selected_client().await.get_label()]
+        } else {
+            vec![]
+        };
+
+        if published_chains.is_empty() {
+            return Ok(vec![]);
+        }
+
+        warn!(
+            "Package {}:{} is already published to {}",
+            signed_package.name.blue(),
+            signed_package.version.blue(),
+            published_chains.join(", ")
+        );
+
+        let chains_count = if self.all_chains {
+            blockchains_service.get_clients().lock().await.len()
+        } else {
+            1
+        };
+
+        if published_chains.len() >= chains_count {
+            return Err(CommandError::AlreadyPublished(format!(
+                "{}:{}",
+                signed_package.name, signed_package.version
+            )));
+        }
+
+        info!("Skipping {}", published_chains.join(", "));
+
+        Ok(published_chains)
+    }
+
+    /**
+     * Build, sign then submit every package listed in manifest, after a single confirmation
+     *
+     * Every entry is built before anything is published, so a broken entry does not leave a half published batch
+     */
+    async fn run_manifest(
+        &self,
+        manifest_path: &PathBuf,
+        signer: &dyn Signer,
+        blockchains_service: &Arc<BlockchainsService>,
+        prompter: &dyn Prompter,
+    ) -> Result<CommandOutcome, CommandError> {
+        let manifest = SubmitManifest::from_file(manifest_path)?;
+
+        let mut signed_packages = Vec::with_capacity(manifest.packages.len());
+
+        for entry in &manifest.packages {
+            let signed_package = self
+                .build_signed_package(
+                    signer,
+                    &entry.name,
+                    &entry.version,
+                    &PathBuf::from(&entry.archive),
+                    entry.sources.as_ref().map(PathBuf::from).as_ref(),
+                    &entry.archive_url,
+                    &entry.dependencies,
+                )
+                .await?;
+
+            signed_packages.push(signed_package);
+        }
+
+        let batch: Vec<String> = signed_packages
+            .iter()
+            .map(|package| format!("{}:{}", package.name, package.version))
+            .collect();
+
+        info!(
+            "{} \n{}",
+            "Following packages will be published to the blockchain :"
+                .yellow()
+                .bold(),
+            signed_packages
+                .iter()
+                .map(|package| format!("\t{}", package.to_compact_string()))
+                .collect::<Vec<String>>()
+                .join("\n")
+        );
+
+        if self.dry_run {
+            return Ok(CommandOutcome::Previewed { packages: batch });
+        }
+
+        if !prompter.confirm("Do you want to continue?")? {
+            return Ok(CommandOutcome::Cancelled);
+        }
+
+        let mut submitted = vec![];
+        let mut failed = vec![];
+
+        for (signed_package, full_package_name) in signed_packages.iter().zip(batch) {
+            // Each blockchain is reported on its own when submitting to every one
+            let submission_results: Vec<(String, Result<(), CommandError>)> = match self
+                .ensure_not_published(signed_package, blockchains_service)
+                .await
+            {
+                Ok(skipped_chains) if self.all_chains => blockchains_service
+                    .submit_package_to_all(signed_package, &skipped_chains)
+                    .await
+                    .into_iter()
+                    .map(|submission| {
+                        let submission_result = if submission.submitted {
+                            Ok(())
+                        } else {
+                            Err(CommandError::Other(String::from(
+                                "write to blockchain failed",
+                            )))
+                        };
+
+                        (
+                            format!("{} ( {} )", full_package_name, submission.blockchain_label),
+                            submission_result,
+                        )
+                    })
+                    .collect(),
+                Ok(_) => vec![(
+                    full_package_name,
+                    blockchains_service
+                        .submit_signed(signed_package)
+                        .await
+                        .map_err(CommandError::from),
+                )],
+                Err(e) => vec![(full_package_name, Err(e))],
+            };
+
+            for (submission_name, submission_result) in submission_results {
+                match submission_result {
+                    Ok(_) => {
+                        info!("Submitted {}", submission_name.blue());
+                        submitted.push(submission_name);
+                    }
+                    Err(e) => {
+                        error!(
+                            "Could not submit {}, reason : {}",
+                            submission_name.blue(),
+                            e
+                        );
+                        failed.push(submission_name);
+                    }
+                }
+            }
+        }
+
+        debug!("Subcommand submit successfully ran !");
+
+        Ok(CommandOutcome::BatchSubmitted { submitted, failed })
+    }
+
+    /**
+     * Submit command
+     */
+    pub async fn run(
+        &self,
+        signer: &dyn Signer,
+        blockchains_service: &Arc<BlockchainsService>,
+        prompter: &dyn Prompter,
+    ) -> Result<CommandOutcome, CommandError> {
+        debug!("Subcommand submit is being run...");
+
+        if let Some(manifest_path) = &self.manifest {
+            return self
+                .run_manifest(
+                    &PathBuf::from(manifest_path),
+                    signer,
+                    blockchains_service,
+                    prompter,
+                )
+                .await;
+        }
+
+        let missing_argument =
+            |name: &str| CommandError::InvalidArgument(format!("{} is required", name));
+
+        let package_name = self
+            .package_name
+            .as_ref()
+            .ok_or(missing_argument("Package name"))?;
+        let package_version = self
+            .package_version
+            .as_ref()
+            .ok_or(missing_argument("Package version"))?;
+
+        let package_sources_directory = self.package_sources_directory.as_ref().map(PathBuf::from);
+
+        let package_archive_directory = PathBuf::from(
+            self.package_archive_directory
+                .as_ref()
+                .ok_or(missing_argument("Package archive directory"))?,
+        );
+
+        let package_archive_url = self
+            .package_archive_url
+            .as_ref()
+            .ok_or(missing_argument("Package archive url"))?;
+
+        let signed_package = self
+            .build_signed_package(
+                signer,
+                package_name,
+                package_version,
+                &package_archive_directory,
+                package_sources_directory.as_ref(),
+                package_archive_url,
+                &self.parse_dependencies()?,
+            )
+            .await?;
+
+        let package = &signed_package;
+
+        let skipped_chains = self
+            .ensure_not_published(&signed_package, blockchains_service)
+            .await?;
 
         // Pretty print
 
         let mut pretty_print_buf = String::new();
-        self.pretty_print(&signed_package, &mut pretty_print_buf)?;
+        self.pretty_print(&signed_package, &mut pretty_print_buf)
+            .map_err(|e| CommandError::Other(e.to_string()))?;
 
         info!(
             "{} \n{}",
@@ -158,45 +492,241 @@ impl SubmitCommand {
             pretty_print_buf
         );
 
-        if Confirm::with_theme(&ColorfulTheme::default())
-            .with_prompt("Do you want to continue?")
-            .interact()
-            .unwrap()
-        {
-            info!("Submitting package to blockchain...");
+        if self.dry_run {
+            return Ok(CommandOutcome::Previewed {
+                packages: vec![format!("{}:{}", package.name, package.version)],
+            });
+        }
 
-            blockchains_service.submit_package(&signed_package).await;
+        if !prompter.confirm("Do you want to continue?")? {
+            return Ok(CommandOutcome::Cancelled);
+        }
 
-            info!(
-                "Done submitting package {}:{} to blockchain !",
-                package.name.blue(),
-                package.version.blue()
-            );
+        if self.all_chains {
+            return self
+                .submit_to_all(&signed_package, &skipped_chains, blockchains_service)
+                .await;
+        }
+
+        info!("Submitting package to blockchain...");
+
+        let submission_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|e| CommandError::Other(e.to_string()))?
+            .as_secs();
+
+        blockchains_service.submit_signed(&signed_package).await?;
+
+        info!(
+            "Done submitting package {}:{} to blockchain !",
+            package.name.blue(),
+            package.version.blue()
+        );
+
+        let confirmed = if self.verify {
+            info!("Waiting for package to be readable from blockchain...");
+
+            let confirmed = blockchains_service
+                .confirm_package(
+                    &signed_package,
+                    submission_time,
+                    Duration::from_secs(self.verify_timeout),
+                )
+                .await;
+
+            if !confirmed {
+                error!(
+                    "Package {}:{} could not be read back from blockchain within {} seconds",
+                    package.name.blue(),
+                    package.version.blue(),
+                    self.verify_timeout
+                );
+            }
+
+            Some(confirmed)
         } else {
-            println!("nevermind then :(");
+            None
+        };
+
+        debug!("Subcommand submit successfully ran !");
+
+        Ok(CommandOutcome::Submitted {
+            package: format!("{}:{}", package.name, package.version),
+            confirmed,
+        })
+    }
+
+    /**
+     * Submit signed package to every configured blockchain but skipped ones, reporting each one
+     */
+    async fn submit_to_all(
+        &self,
+        signed_package: &Package,
+        skipped_chains: &[String],
+        blockchains_service: &Arc<BlockchainsService>,
+    ) -> Result<CommandOutcome, CommandError> {
+        info!("Submitting package to every blockchain...");
+
+        let submissions = blockchains_service
+            .submit_package_to_all(signed_package, skipped_chains)
+            .await;
+
+        let mut submitted_chains = vec![];
+        let mut failed_chains = vec![];
+
+        for submission in submissions {
+            if submission.submitted {
+                info!("Submitted to {}", submission.blockchain_label.green());
+                submitted_chains.push(submission.blockchain_label);
+            } else {
+                error!("Could not submit to {}", submission.blockchain_label.red());
+                failed_chains.push(submission.blockchain_label);
+            }
         }
 
         debug!("Subcommand submit successfully ran !");
 
-        Ok(())
+        Ok(CommandOutcome::SubmittedToAll {
+            package: format!("{}:{}", signed_package.name, signed_package.version),
+            submitted_chains,
+            failed_chains,
+            skipped_chains: skipped_chains.to_vec(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::{fixtures::tests::CommandEnv, prompter::tests::ScriptedPrompter};
+
+    use super::*;
+
+    /**
+     * Build submit command for a foo release whose archive is written to env directory
+     */
+    fn create_command(env: &CommandEnv, flags: &[&str]) -> SubmitCommand {
+        let archive_path = env.dir.path().join("foo-1.0.0.pkg.tar.zst");
+
+        std::fs::write(&archive_path, "foo").unwrap();
+
+        let archive_path = archive_path.display().to_string();
+
+        let args = [
+            "submit",
+            "foo",
+            "1.0.0",
+            &archive_path,
+            "https://archive.archlinux.org/packages/f/foo/foo-1.0.0.pkg.tar.zst",
+        ];
+
+        SubmitCommand::parse_from(args.iter().chain(flags))
+    }
+
+    /**
+     * It should only preview package in dry run mode, without asking nor submitting
+     */
+    #[tokio::test]
+    async fn test_run_dry_run() {
+        let env = CommandEnv::new().await;
+
+        let signer = env.create_signer();
+
+        let command = create_command(&env, &["--dry-run"]);
+
+        // Nothing scripted, asking would panic
+        let prompter = ScriptedPrompter::new(&[], &[]);
+
+        let outcome = command
+            .run(&*signer, &env.blockchains_service, &prompter)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            outcome,
+            CommandOutcome::Previewed {
+                packages: vec![String::from("foo:1.0.0")]
+            }
+        );
+        assert_eq!(env.has_written(), false);
+    }
+
+    /**
+     * It should not submit anything when submission is declined
+     */
+    #[tokio::test]
+    async fn test_run_declined() {
+        let env = CommandEnv::new().await;
+
+        let signer = env.create_signer();
+
+        let command = create_command(&env, &[]);
+
+        let prompter = ScriptedPrompter::new(&[], &[false]);
+
+        let outcome = command
+            .run(&*signer, &env.blockchains_service, &prompter)
+            .await
+            .unwrap();
+
+        assert_eq!(outcome, CommandOutcome::Cancelled);
+        assert_eq!(env.has_written(), false);
+    }
+
+    /**
+     * It should submit package once confirmed
+     */
+    #[tokio::test]
+    async fn test_run_confirmed() {
+        let env = CommandEnv::new().await;
+
+        let signer = env.create_signer();
+
+        let command = create_command(&env, &[]);
+
+        let prompter = ScriptedPrompter::new(&[], &[true]);
+
+        let outcome = command
+            .run(&*signer, &env.blockchains_service, &prompter)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            outcome,
+            CommandOutcome::Submitted {
+                package: String::from("foo:1.0.0"),
+                confirmed: None,
+            }
+        );
+        assert_eq!(env.has_written(), true);
+    }
+
+    /**
+     * It should report each blockchain package is submitted to
+     */
+    #[tokio::test]
+    async fn test_run_all_chains() {
+        let env = CommandEnv::new().await;
+
+        let signer = env.create_signer();
+
+        let command = create_command(&env, &["--all-chains"]);
+
+        let prompter = ScriptedPrompter::new(&[], &[true]);
+
+        let outcome = command
+            .run(&*signer, &env.blockchains_service, &prompter)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            outcome,
+            CommandOutcome::SubmittedToAll {
+                package: String::from("foo:1.0.0"),
+                submitted_chains: vec![String::from("file")],
+                failed_chains: vec![],
+                skipped_chains: vec![],
+            }
+        );
+        assert_eq!(env.has_written(), true);
     }
 }
-//
-//#[cfg(test)]
-//mod tests {
-//    use super::*;
-//
-//    /**
-//     * It should install package
-//     */
-//    #[test]
-//    fn test_package_installation() {
-//        let package_name_mock = String::from("foo");
-//        let command = InstallCommand {
-//            package_name: Some(package_name_mock),
-//            version: None,
-//        };
-//
-//        command.run();
-//    }
-//}
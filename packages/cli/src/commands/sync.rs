@@ -0,0 +1,132 @@
+use bpm_core::services::{blockchains::BlockchainsService, packages::PackagesService};
+use std::sync::Arc;
+
+use clap::Parser;
+use log::{debug, info};
+
+use super::errors::command_error::CommandError;
+use super::outcome::CommandOutcome;
+use super::progress::run_update;
+
+/** Synchronize local packages with blockchain */
+#[derive(Debug, Parser)]
+pub struct SyncCommand {
+    /**
+     * Only read mutations published before this epoch timestamp, reconstructing packages as they were without touching local cache
+     */
+    #[clap(long)]
+    pub as_of: Option<u64>,
+
+    /**
+     * Read packages from this topic instead of configured one ( eg: 0.0.1234 )
+     */
+    #[clap(long)]
+    pub topic: Option<String>,
+
+    /**
+     * Report which packages were added, removed or changed status instead of counts only
+     */
+    #[clap(long)]
+    pub report: bool,
+}
+
+/**
+ * Handles synchronization request from CLI
+ */
+impl SyncCommand {
+    /**
+     * Sync command
+     */
+    pub async fn run(
+        &self,
+        blockchains_service: &Arc<BlockchainsService>,
+        packages_service: &Arc<PackagesService>,
+    ) -> Result<CommandOutcome, CommandError> {
+        debug!("Subcommand sync is being run...");
+
+        // Cache is only snapshotted when a report is requested
+        let packages_before = if self.report {
+            packages_service.get_all().await
+        } else {
+            vec![]
+        };
+
+        let as_of = self.as_of;
+
+        // Past state is reconstructed in a throwaway view, so next synchronization is not rewound
+        let (update_result, _) = run_update(
+            blockchains_service,
+            move |blockchains_service, tx_packages_update| async move {
+                match as_of {
+                    Some(as_of) => blockchains_service
+                        .view_as_of(&tx_packages_update, as_of)
+                        .await
+                        .map(|(view_packages_service, tally)| (Some(view_packages_service), tally)),
+                    None => blockchains_service
+                        .update(&tx_packages_update)
+                        .await
+                        .map(|tally| (None, tally)),
+                }
+            },
+        )
+        .await?;
+
+        let (view_packages_service, tally) = update_result?;
+
+        if let Some(as_of) = as_of {
+            info!(
+                "Packages were reconstructed as of {}, local cache was left untouched",
+                as_of
+            );
+        }
+
+        if self.report {
+            let packages_after = view_packages_service
+                .as_ref()
+                .unwrap_or(packages_service)
+                .get_all()
+                .await;
+
+            let diff = PackagesService::diff(&packages_before, &packages_after);
+
+            debug!("Subcommand sync successfully ran !");
+
+            return Ok(CommandOutcome::SyncReported {
+                added: diff
+                    .added
+                    .iter()
+                    .map(|package| package.to_compact_string())
+                    .collect(),
+                removed: diff
+                    .removed
+                    .iter()
+                    .map(|package| package.to_compact_string())
+                    .collect(),
+                status_changed: diff
+                    .status_changed
+                    .iter()
+                    .map(|(before, after)| {
+                        format!(
+                            "{}:{} ( {} -> {}, Maintainer : {} )",
+                            after.name,
+                            after.version,
+                            before.status,
+                            after.status,
+                            after.get_maintainer_fingerprint()
+                        )
+                    })
+                    .collect(),
+            });
+        }
+
+        debug!("Subcommand sync successfully ran !");
+
+        Ok(CommandOutcome::Synced {
+            added: tally.added,
+            updated: tally.updated,
+            unchanged: tally.unchanged,
+            rejected: tally.rejected,
+            revoked: tally.revoked,
+        })
+    }
+}
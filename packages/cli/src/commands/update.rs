@@ -0,0 +1,68 @@
+use bpm_core::blockchains::errors::blockchain_error::BlockchainError;
+use bpm_core::services::blockchains::BlockchainsService;
+use std::sync::Arc;
+
+use clap::Parser;
+use log::debug;
+
+use super::errors::command_error::CommandError;
+use super::outcome::CommandOutcome;
+use super::progress::run_update;
+
+/** Update local packages cache from blockchain without installing anything */
+#[derive(Debug, Parser)]
+pub struct UpdateCommand {
+    /**
+     * Re-read whole blockchain instead of only mutations since last sync, eg: after local DB corruption
+     */
+    #[clap(long)]
+    pub full_resync: bool,
+}
+
+/**
+ * Handles update request from CLI
+ */
+impl UpdateCommand {
+    /**
+     * Update command
+     */
+    pub async fn run(
+        &self,
+        blockchains_service: &Arc<BlockchainsService>,
+    ) -> Result<CommandOutcome, CommandError> {
+        debug!("Subcommand update is being run...");
+
+        if self.full_resync {
+            blockchains_service.reset_last_sync().await;
+        }
+
+        let (update_result, mutations) = run_update(
+            blockchains_service,
+            |blockchains_service, tx_packages_update| async move {
+                blockchains_service.update(&tx_packages_update).await
+            },
+        )
+        .await?;
+
+        let (added, updated) = match update_result {
+            Ok(tally) => (tally.added, tally.updated),
+            // Nothing new on blockchain is not a failure
+            Err(BlockchainError::NoPackagesData) => (0, 0),
+            Err(e) => return Err(e.into()),
+        };
+
+        let last_sync = blockchains_service.get_last_sync().await;
+
+        debug!("Subcommand update successfully ran !");
+
+        Ok(CommandOutcome::Updated {
+            added,
+            updated,
+            mutations: mutations
+                .iter()
+                .map(|package| package.to_compact_string())
+                .collect(),
+            last_sync,
+        })
+    }
+}
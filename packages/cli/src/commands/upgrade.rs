@@ -0,0 +1,295 @@
+use bpm_core::packages::package::Package;
+use bpm_core::packages::utils::maintainers::has_maintainers_conflict;
+use bpm_core::services::{
+    blockchains::BlockchainsService,
+    package_managers::{InstallProgress, PackageManagersService},
+    packages::PackagesService,
+};
+use std::sync::Arc;
+
+use colored::Colorize;
+
+use clap::Parser;
+use log::{debug, error, info, warn};
+
+use super::errors::command_error::CommandError;
+use super::outcome::CommandOutcome;
+use super::prompter::Prompter;
+
+/** Upgrade installed packages to better releases found on blockchain */
+#[derive(Debug, Parser)]
+pub struct UpgradeCommand {
+    /**
+     * Only upgrade to releases of this maintainer ( hex key or fingerprint ), otherwise maintainer of installed release is kept
+     */
+    #[clap(long)]
+    pub maintainer: Option<String>,
+
+    /**
+     * Refuse to pick a dependency claimed by several maintainers
+     */
+    #[clap(long)]
+    pub strict: bool,
+}
+
+/**
+ * Handles upgrade request from CLI
+ */
+impl UpgradeCommand {
+    /**
+     * Format what would change, one installed package per line
+     */
+    fn format_summary(&self, upgrades: &[(String, Package)]) -> String {
+        let name_width = upgrades
+            .iter()
+            .map(|(_, package)| package.name.len())
+            .max()
+            .unwrap_or(0);
+        let version_width = upgrades
+            .iter()
+            .map(|(installed_version, _)| installed_version.len())
+            .max()
+            .unwrap_or(0);
+
+        let lines: Vec<String> = upgrades
+            .iter()
+            .map(|(installed_version, package)| {
+                format!(
+                    "{}  {:<version_width$}  =>  {} ( Status : {}, Maintainer : {} )",
+                    format!("{:<name_width$}", package.name).blue(),
+                    installed_version,
+                    package.version.green(),
+                    package.status,
+                    package.get_maintainer_fingerprint()
+                )
+            })
+            .collect();
+
+        lines.join("\n")
+    }
+
+    /**
+     * Upgrade command
+     */
+    pub async fn run(
+        &self,
+        blockchains_service: &Arc<BlockchainsService>,
+        packages_service: &Arc<PackagesService>,
+        package_managers_service: &PackageManagersService,
+        prompter: &dyn Prompter,
+    ) -> Result<CommandOutcome, CommandError> {
+        debug!("Subcommand upgrade is being run...");
+
+        let installed_packages = package_managers_service.list_installed().await?;
+
+        let blockchain_client = blockchains_service.get_selected_client().await;
+
+        // Installed version along with release to upgrade to
+        let mut upgrades: Vec<(String, Package)> = vec![];
+        let mut skipped: Vec<String> = vec![];
+        let mut conflicting: Vec<String> = vec![];
+
+        for installed_package in &installed_packages {
+            let known_versions = packages_service
+                .get_versions(&installed_package.name, &blockchain_client)
+                .await;
+
+            if known_versions.is_empty() {
+                debug!(
+                    "Package {} is not published on blockchain, skipping...",
+                    installed_package.name
+                );
+
+                skipped.push(installed_package.name.clone());
+                continue;
+            }
+
+            // Upgrade must come from maintainer of installed release, unless another one is explicitly trusted
+            let maintainer = match &self.maintainer {
+                Some(maintainer) => maintainer.clone(),
+                None => {
+                    let installed_releases = blockchains_service
+                        .find_package(&installed_package.name, &installed_package.version, &None)
+                        .await;
+
+                    if has_maintainers_conflict(&installed_releases) {
+                        warn!(
+                            "Several maintainers published {}:{}, use --maintainer to pick one, skipping...",
+                            installed_package.name, installed_package.version
+                        );
+
+                        conflicting.push(installed_package.name.clone());
+                        continue;
+                    }
+
+                    match installed_releases.first() {
+                        Some(installed_release) => {
+                            hex::encode(installed_release.maintainer.to_bytes())
+                        }
+                        None => {
+                            debug!(
+                                "Installed release {}:{} is not published on blockchain, use --maintainer to upgrade it, skipping...",
+                                installed_package.name, installed_package.version
+                            );
+
+                            skipped.push(installed_package.name.clone());
+                            continue;
+                        }
+                    }
+                }
+            };
+
+            let upgrade = packages_service
+                .find_upgrade(
+                    &installed_package.name,
+                    &installed_package.version,
+                    &maintainer,
+                    &blockchain_client,
+                )
+                .await;
+
+            if let Some(package) = upgrade {
+                upgrades.push((installed_package.version.clone(), package));
+            }
+        }
+
+        if !skipped.is_empty() {
+            info!(
+                "{} installed packages are not published on blockchain and were skipped",
+                skipped.len()
+            );
+        }
+
+        if !conflicting.is_empty() {
+            info!(
+                "{} installed packages were published by several maintainers and were skipped : {}",
+                conflicting.len(),
+                conflicting.join(", ")
+            );
+        }
+
+        if upgrades.is_empty() {
+            return Ok(CommandOutcome::Upgraded {
+                upgraded: vec![],
+                skipped,
+                conflicting,
+            });
+        }
+
+        info!(
+            "{}\n{}",
+            "Available upgrades :".yellow().bold(),
+            self.format_summary(&upgrades)
+        );
+
+        // Dependencies come first, shared ones being installed once
+        let mut packages_to_install: Vec<Package> = vec![];
+
+        for (_, package) in &upgrades {
+            for resolved_package in blockchains_service
+                .resolve_dependencies(package, self.strict)
+                .await?
+            {
+                let is_planned = packages_to_install.iter().any(|planned_package| {
+                    planned_package.name == resolved_package.name
+                        && planned_package.version == resolved_package.version
+                });
+
+                if !is_planned {
+                    packages_to_install.push(resolved_package);
+                }
+            }
+        }
+
+        if packages_to_install.len() > upgrades.len() {
+            info!(
+                "{} dependencies will be installed along with upgrades",
+                packages_to_install.len() - upgrades.len()
+            );
+        }
+
+        if !prompter.confirm("Do you want to upgrade these packages?")? {
+            return Ok(CommandOutcome::Cancelled);
+        }
+
+        // Upgraded packages must not be rolled back, that would remove them from system
+        let mut progress = InstallProgress::default();
+
+        if let Err(e) = package_managers_service
+            .install_all(&packages_to_install, &mut progress)
+            .await
+        {
+            error!(
+                "Upgrade stopped after {} packages, reason : {}",
+                progress.installed.len(),
+                e
+            );
+
+            return Err(CommandError::PackageManager(e));
+        }
+
+        debug!("Subcommand upgrade successfully ran !");
+
+        Ok(CommandOutcome::Upgraded {
+            upgraded: progress.installed,
+            skipped,
+            conflicting,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bpm_core::packages::package_status::PackageStatus;
+
+    use crate::commands::{
+        fixtures::tests::{create_release, CommandEnv},
+        prompter::tests::ScriptedPrompter,
+    };
+
+    use super::*;
+
+    /**
+     * It should tell packages published by several maintainers apart from unpublished ones
+     */
+    #[tokio::test]
+    async fn test_run_skipped() {
+        let env = CommandEnv::new().await;
+
+        let first_signer = env.create_signer();
+        let second_signer = env.create_signer();
+
+        env.cache(&[
+            create_release("foo", "1.0.0", &*first_signer, PackageStatus::Fine),
+            create_release("foo", "1.0.0", &*second_signer, PackageStatus::Fine),
+            create_release("foo", "2.0.0", &*first_signer, PackageStatus::Fine),
+        ])
+        .await;
+
+        env.set_installed(&[("foo", "1.0.0"), ("bar", "1.0.0")]);
+
+        let command = UpgradeCommand::parse_from(["upgrade"]);
+
+        // Nothing scripted, asking would panic
+        let prompter = ScriptedPrompter::new(&[], &[]);
+
+        let outcome = command
+            .run(
+                &env.blockchains_service,
+                &env.packages_service,
+                &env.package_managers_service,
+                &prompter,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            outcome,
+            CommandOutcome::Upgraded {
+                upgraded: vec![],
+                skipped: vec![String::from("bar")],
+                conflicting: vec![String::from("foo")],
+            }
+        );
+    }
+}
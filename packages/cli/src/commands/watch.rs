@@ -0,0 +1,75 @@
+use bpm_core::services::blockchains::BlockchainsService;
+use std::{sync::Arc, time::Duration};
+
+use clap::Parser;
+use log::{debug, info};
+use tokio::sync::mpsc;
+
+use super::errors::command_error::CommandError;
+use super::outcome::CommandOutcome;
+
+/** Keep local packages synchronized with blockchain until interrupted */
+#[derive(Debug, Parser)]
+pub struct WatchCommand {
+    /**
+     * Time to wait between two synchronizations, in seconds
+     */
+    #[clap(long, default_value_t = 30)]
+    pub interval: u64,
+}
+
+/**
+ * Handles watch request from CLI
+ */
+impl WatchCommand {
+    /**
+     * Watch command
+     */
+    pub async fn run(
+        &self,
+        blockchains_service: &Arc<BlockchainsService>,
+    ) -> Result<CommandOutcome, CommandError> {
+        debug!("Subcommand watch is being run...");
+
+        let (tx_packages_update, mut rx_packages_update) = mpsc::channel(1);
+
+        let task_blockchains_service_ref = Arc::clone(&blockchains_service);
+        let interval = Duration::from_secs(self.interval);
+
+        let watch_task = tokio::spawn(async move {
+            task_blockchains_service_ref
+                .watch(interval, &tx_packages_update)
+                .await
+        });
+
+        info!("Watching blockchain for new packages mutations, press Ctrl+C to stop...");
+
+        tokio::select! {
+            _ = async {
+                while let Some(package) = rx_packages_update.recv().await {
+                    info!("New package mutation found => {}", package);
+                }
+            } => (),
+            _ = tokio::signal::ctrl_c() => {
+                info!("Stopping watch...");
+            }
+        }
+
+        // Dropping receiver lets current round complete then stops watching
+        drop(rx_packages_update);
+
+        let tally = watch_task
+            .await
+            .map_err(|e| CommandError::Other(e.to_string()))??;
+
+        debug!("Subcommand watch successfully ran !");
+
+        Ok(CommandOutcome::Synced {
+            added: tally.added,
+            updated: tally.updated,
+            unchanged: tally.unchanged,
+            rejected: tally.rejected,
+            revoked: tally.revoked,
+        })
+    }
+}
@@ -0,0 +1,39 @@
+use bpm_core::{
+    config::manager::ConfigManager, packages::utils::maintainers::compute_maintainer_fingerprint,
+};
+
+use clap::Parser;
+use log::debug;
+
+use super::errors::command_error::CommandError;
+use super::outcome::CommandOutcome;
+
+/** Print maintainer public key so it can be shared with users */
+#[derive(Debug, Parser)]
+pub struct WhoamiCommand {}
+
+/**
+ * Handles whoami request from CLI
+ */
+impl WhoamiCommand {
+    /**
+     * Whoami command
+     */
+    pub async fn run(
+        &self,
+        config_manager: &ConfigManager,
+    ) -> Result<CommandOutcome, CommandError> {
+        debug!("Subcommand whoami is being run...");
+
+        let verifying_key = config_manager
+            .get_verifying_key()
+            .map_err(|e| CommandError::MaintainerKey(e.to_string()))?;
+
+        debug!("Subcommand whoami successfully ran !");
+
+        Ok(CommandOutcome::Identity {
+            maintainer: hex::encode_upper(verifying_key.to_bytes()),
+            maintainer_fingerprint: compute_maintainer_fingerprint(&verifying_key),
+        })
+    }
+}
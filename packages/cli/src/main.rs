@@ -1,22 +1,14 @@
 mod commands;
 
+use bpm_core::bpm::Bpm;
 use bpm_core::config::init_config;
 use bpm_core::logging::init_logger;
 use home::home_dir;
-use log::info;
+use log::{error, info};
+use std::time::Duration;
 
-use std::sync::Arc;
-
-use bpm_core::{
-    blockchains::get_available_clients,
-    db::client::DbClient,
-    package_managers::init_package_managers,
-    services::{
-        blockchains::BlockchainsService, db::blockchains_repository::BlockchainsRepository,
-        db::packages_repository::PackagesRepository, package_managers::PackageManagersService,
-        packages::PackagesService,
-    },
-};
+// Time to wait for another BPM process to be done before giving up, in seconds
+const PROCESS_LOCK_TIMEOUT: u64 = 10;
 
 /**
  * Main CLI entry point
@@ -32,44 +24,33 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let config_path = home_dir().unwrap();
 
-    let mut config_manager = init_config(&config_path);
-
-    let db_client = Arc::new(DbClient::from(&config_manager.get_db_path()));
-
-    // Blockchains clients
-    let available_blockchains = get_available_clients();
-
-    // Package managers
-    let available_package_managers = init_package_managers().await;
-
-    // Repositories
-    let blockchains_repository = Arc::new(BlockchainsRepository::from(&db_client));
-    let packages_repository = Arc::new(PackagesRepository::from(&db_client));
-
-    // Services
-    let package_managers_service =
-        Arc::new(PackageManagersService::new(&available_package_managers));
-
-    let packages_service = Arc::new(PackagesService::from(&packages_repository));
-
-    let blockchains_service = Arc::new(
-        BlockchainsService::new(
-            &available_blockchains,
-            &blockchains_repository,
-            &packages_service,
-        )
-        .await,
-    );
-
-    blockchains_service.init_blockchains().await;
-
-    commands::bootstrap(
-        &mut config_manager,
-        &blockchains_service,
-        &packages_service,
-        &package_managers_service,
-    )
-    .await?;
+    // Taken before DB is opened, as only one process can open it, held until process exits
+    let _process_lock = match init_config(&config_path)
+        .lock(Duration::from_secs(PROCESS_LOCK_TIMEOUT))
+        .await
+    {
+        Ok(process_lock) => process_lock,
+        Err(e) => {
+            error!("{}", e);
+
+            std::process::exit(1);
+        }
+    };
+
+    let mut bpm = match Bpm::new(&config_path).await {
+        Ok(bpm) => bpm,
+        Err(e) => {
+            error!("Could not start BPM : {}", e);
+
+            std::process::exit(1);
+        }
+    };
+
+    if let Err(e) = commands::bootstrap(&mut bpm).await {
+        error!("{}", e);
+
+        std::process::exit(1);
+    }
 
     Ok(())
 }
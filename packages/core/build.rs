@@ -1,7 +1,11 @@
-use std::path::{Path, PathBuf};
+/**
+ * Compile Hedera mirror protos
+ */
+#[cfg(feature = "hedera")]
+fn compile_hedera_protos() {
+    use std::path::{Path, PathBuf};
 
-fn main() {
-    // TODO : Move to hedera dir then import here if feature enabled
+    // TODO : Move to hedera dir then import here
     let hedera_package_path = PathBuf::from(Path::new("src").join("blockchains").join("hedera"));
     let hedera_protos_path = hedera_package_path.join("hedera-protobufs");
     let hedera_protos_services_path = hedera_protos_path.join("services");
@@ -20,3 +24,8 @@ fn main() {
         )
         .unwrap();
 }
+
+fn main() {
+    #[cfg(feature = "hedera")]
+    compile_hedera_protos();
+}
@@ -1,4 +1,4 @@
-use log::debug;
+use log::{debug, error};
 use rlp::DecoderError;
 use std::{
     sync::Arc,
@@ -6,9 +6,17 @@ use std::{
 };
 use tokio::sync::mpsc::{self, Sender};
 
-use super::errors::blockchain_error::BlockchainError;
-use crate::packages::{
-    package::Package, package_builder::PackageBuilder, utils::signatures::verify_package,
+use super::{
+    errors::blockchain_error::BlockchainError,
+    message::BlockchainMessage,
+    payload::{decode_package, decode_payload, encode_payload},
+};
+use crate::{
+    metrics::{self, Counter},
+    packages::{
+        package::Package, package_builder::PackageBuilder, package_status::PackageStatus,
+        utils::signatures::verify_package,
+    },
 };
 use std::fmt::Debug;
 
@@ -18,8 +26,14 @@ use mockall::automock;
 #[async_trait::async_trait]
 #[cfg_attr(test, automock)]
 pub trait BlockchainIO: Sync + Send + Debug {
-    async fn write(&self, data: &[u8]);
-    async fn read(&self, tx_data: &Sender<Result<Vec<u8>, BlockchainError>>, last_sync: &u64);
+    async fn write(&self, data: &[u8]) -> Result<(), BlockchainError>;
+    async fn read(
+        &self,
+        tx_data: &Sender<Result<BlockchainMessage, BlockchainError>>,
+        last_sync: &u64,
+        end_timestamp: &Option<u64>, // Only read messages published before, if any
+    );
+    async fn read_at(&self, sequence_number: u64) -> Result<Option<Vec<u8>>, BlockchainError>;
 }
 
 #[async_trait::async_trait]
@@ -28,71 +42,219 @@ pub trait BlockchainClient: Sync + Send + Debug {
     /**
      * Write package
      */
-    async fn write_package(&self, package: &Package) {
+    async fn write_package(&self, package: &Package) -> Result<(), BlockchainError> {
         let io = self.create_io().await;
         debug!("Writing package {} to blockchain...", package.name);
 
-        let encoded_package = rlp::encode(package);
-        io.write(&encoded_package).await;
+        let encoded_package = package
+            .to_rlp()
+            .map_err(|_| BlockchainError::UnsignedPackage)?;
+        io.write(&encode_payload(&encoded_package)).await?;
 
         debug!("Done writing package {} to blockchain !", package.name);
+
+        Ok(())
     }
 
     /**
-     * Read packages from blockchain
+     * Write packages one after another, reusing same IO
+     *
+     * A failing write does not stop following ones, result of each package is given in same order
+     */
+    async fn write_packages(&self, packages: &[Package]) -> Vec<Result<(), BlockchainError>> {
+        let io = self.create_io().await;
+        debug!("Writing {} packages to blockchain...", packages.len());
+
+        let mut results = Vec::with_capacity(packages.len());
+
+        for package in packages {
+            let write_result = match package.to_rlp() {
+                Ok(encoded_package) => io.write(&encode_payload(&encoded_package)).await,
+                Err(_) => Err(BlockchainError::UnsignedPackage),
+            };
+
+            if let Err(e) = &write_result {
+                error!("Could not write package {} : {}", package.name, e);
+            }
+
+            results.push(write_result);
+        }
+
+        debug!("Done writing {} packages to blockchain !", packages.len());
+
+        results
+    }
+
+    /**
+     * Submit signed tombstone of package, only accepted by readers when signed by package maintainer
+     */
+    async fn revoke_package(&self, package: &Package) -> Result<(), BlockchainError> {
+        debug!("Revoking package {} from blockchain...", package.name);
+
+        // Tombstone must be stamped, otherwise it could never supersede stored package, and prohibit release
+        if !package.revoked
+            || package.signed_at.is_none()
+            || package.status != PackageStatus::Prohibited
+        {
+            return Err(BlockchainError::InvalidRevocation);
+        }
+
+        if package.sig.is_none() {
+            return Err(BlockchainError::UnsignedPackage);
+        }
+
+        if verify_package(package).is_none() {
+            return Err(BlockchainError::InvalidSignature);
+        }
+
+        self.write_package(package).await?;
+
+        debug!("Done revoking package {} from blockchain !", package.name);
+
+        Ok(())
+    }
+
+    /**
+     * Read packages published since last sync from blockchain, then store current time as last sync
      */
     async fn read_packages(
         &self,
         tx_packages: &Sender<Result<Package, BlockchainError>>,
+    ) -> Result<(), BlockchainError> {
+        let last_sync = self.get_last_sync().await;
+
+        self.read_packages_between(tx_packages, last_sync, None)
+            .await?;
+
+        self.set_last_sync(self.now()).await;
+
+        Ok(())
+    }
+
+    /**
+     * Read every package published before given timestamp from blockchain, last sync is left untouched
+     */
+    async fn read_packages_as_of(
+        &self,
+        tx_packages: &Sender<Result<Package, BlockchainError>>,
+        as_of: u64,
+    ) -> Result<(), BlockchainError> {
+        self.read_packages_between(tx_packages, 0, Some(as_of))
+            .await
+    }
+
+    /**
+     * Read packages published after start timestamp from blockchain, and before end timestamp if any
+     */
+    async fn read_packages_between(
+        &self,
+        tx_packages: &Sender<Result<Package, BlockchainError>>,
+        start_timestamp: u64,
+        end_timestamp: Option<u64>,
     ) -> Result<(), BlockchainError> {
         let io = self.create_io().await;
 
-        let (tx_raw_bytes, mut rx_raw_bytes) = mpsc::channel(1);
+        let (tx_messages, mut rx_messages) = mpsc::channel(1);
 
-        let last_sync = self.get_last_sync().await;
         tokio::spawn(async move {
-            io.read(&tx_raw_bytes, &last_sync).await;
+            io.read(&tx_messages, &start_timestamp, &end_timestamp)
+                .await;
         });
 
-        while let Some(raw_bytes_res) = rx_raw_bytes.recv().await {
-            let raw_bytes = raw_bytes_res?;
+        while let Some(message_res) = rx_messages.recv().await {
+            let message = message_res?;
+
+            let encoded_package = match decode_payload(&message.payload) {
+                Ok(encoded_package) => encoded_package,
+                Err(_) => {
+                    debug!("Package payload could not be decompressed, skipping");
+                    metrics::increment(Counter::PackagesSkipped);
+                    continue;
+                }
+            };
+
             let package_parsing_result: Result<PackageBuilder, DecoderError> =
-                PackageBuilder::from_rlp(raw_bytes.as_slice());
+                PackageBuilder::from_rlp(encoded_package.as_slice());
 
             let mut builder = match package_parsing_result {
                 Ok(builder) => builder,
                 Err(_) => {
                     debug!("Package could not be parsed, skipping",);
+                    metrics::increment(Counter::PackagesSkipped);
                     continue;
                 }
             };
 
             let untrusted_package = builder.build();
 
+            // Signature covers re-encoded fields, so only canonical encoding is accepted to keep received bytes unique
+            if rlp::encode(&untrusted_package).as_ref() != encoded_package.as_slice() {
+                debug!("Package encoding is not canonical, skipping");
+                metrics::increment(Counter::PackagesSkipped);
+                continue;
+            }
+
             let signature_verification = verify_package(&untrusted_package);
 
             let trusted_package = match signature_verification {
                 Some(trusted_package) => trusted_package,
                 None => {
                     debug!("Package signature is wrong, skipping");
+                    metrics::increment(Counter::PackagesSkipped);
                     continue;
                 }
             };
 
-            tx_packages.send(Ok(trusted_package.clone())).await.unwrap();
-        }
+            // Archive could never be verified, rather than hashing it with a wrong algorithm
+            if let Err(e) = trusted_package.integrity.ensure_supported_algorithm() {
+                debug!("{}, skipping", e);
+                metrics::increment(Counter::PackagesSkipped);
+                continue;
+            }
 
-        let current_time = SystemTime::now();
-        let epoch_timestamp = current_time
-            .duration_since(UNIX_EPOCH)
-            .expect("Time went backwards")
-            .as_secs();
+            metrics::increment(Counter::PackagesFetched);
+
+            // Consensus time is not signed, it comes from blockchain itself
+            let mut builder = PackageBuilder::from_package(trusted_package);
+
+            builder.set_raw(&encoded_package);
+
+            if let Some(consensus_time) = message.consensus_time {
+                builder.set_consensus_time(&consensus_time);
+            }
 
-        self.set_last_sync(epoch_timestamp).await;
+            tx_packages.send(Ok(builder.build())).await.unwrap();
+        }
 
         Ok(())
     }
 
+    /**
+     * Read single package using its message sequence number, signature is not verified
+     */
+    async fn read_package_at(
+        &self,
+        sequence_number: u64,
+    ) -> Result<Option<Package>, BlockchainError> {
+        let io = self.create_io().await;
+
+        debug!("Reading package at sequence number {}...", sequence_number);
+
+        let raw_bytes = match io.read_at(sequence_number).await? {
+            Some(raw_bytes) => raw_bytes,
+            None => return Ok(None),
+        };
+
+        let package = decode_package(&raw_bytes)?;
+
+        debug!(
+            "Done reading package at sequence number {} !",
+            sequence_number
+        );
+
+        Ok(Some(package))
+    }
+
     /**
      * Get label
      */
@@ -112,6 +274,16 @@ pub trait BlockchainClient: Sync + Send + Debug {
      * Get last sync
      */
     async fn get_last_sync(&self) -> u64;
+
+    /**
+     * Get current epoch timestamp, stored as last sync once packages are read
+     */
+    fn now(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_secs()
+    }
 }
 
 impl std::fmt::Display for dyn BlockchainClient {
@@ -126,6 +298,8 @@ mod tests {
 
     use std::sync::Arc;
 
+    use ed25519::signature::rand_core::OsRng;
+    use ed25519_dalek::{SigningKey, PUBLIC_KEY_LENGTH};
     use tokio::sync::{
         mpsc::{Receiver, Sender},
         Mutex,
@@ -135,10 +309,18 @@ mod tests {
         blockchains::{
             blockchain::{BlockchainClient, BlockchainIO, MockBlockchainIO},
             errors::blockchain_error::BlockchainError,
-            hedera::blockchain_client::HederaBlockchain,
         },
-        packages::{package::Package, package_builder::PackageBuilder},
-        test_utils::package::tests::create_package_with_sig,
+        packages::{
+            package::Package,
+            package_builder::PackageBuilder,
+            utils::signatures::{sign_package_now, sign_revocation_now},
+        },
+        test_utils::{
+            blockchain::tests::TestBlockchainClient,
+            package::tests::{
+                create_package_with_sig, create_package_without_sig, replace_encoded_package_item,
+            },
+        },
     };
 
     /**
@@ -146,16 +328,16 @@ mod tests {
      */
     #[tokio::test]
     async fn test_display_blockchain_name() {
-        let mut hedera_io_mock = MockBlockchainIO::default();
+        let mut io_mock = MockBlockchainIO::default();
 
-        hedera_io_mock
+        io_mock
             .expect_read()
-            .returning(move |_, _| Box::pin(async move {}));
+            .returning(move |_, _, _| Box::pin(async move {}));
 
-        let hedera_io: Box<dyn BlockchainIO> = Box::new(hedera_io_mock);
+        let io: Arc<Box<dyn BlockchainIO>> = Arc::new(Box::new(io_mock));
 
         let blockchain_client: Box<dyn BlockchainClient> =
-            Box::new(HederaBlockchain::new(hedera_io));
+            Box::new(TestBlockchainClient::new(io, 0));
 
         assert_eq!(
             format!("{}", blockchain_client),
@@ -170,26 +352,24 @@ mod tests {
     async fn test_should_get_packages() {
         let expected_package = create_package_with_sig().unwrap();
 
-        let mut hedera_io_mock = MockBlockchainIO::default();
+        let mut io_mock = MockBlockchainIO::default();
 
         let shared_pkg = expected_package.clone();
 
-        hedera_io_mock
-            .expect_read()
-            .returning(move |tx_packages, _| {
-                let pkg = shared_pkg.clone();
-                let tx = tx_packages.clone();
-                Box::pin(async move {
-                    let encoded_pkg = rlp::encode(&pkg).to_vec();
+        io_mock.expect_read().returning(move |tx_packages, _, _| {
+            let pkg = shared_pkg.clone();
+            let tx = tx_packages.clone();
+            Box::pin(async move {
+                let encoded_pkg = rlp::encode(&pkg).to_vec();
 
-                    tx.send(Ok(encoded_pkg)).await.unwrap();
-                })
-            });
+                tx.send(Ok(encoded_pkg.into())).await.unwrap();
+            })
+        });
 
-        let hedera_io: Box<dyn BlockchainIO> = Box::new(hedera_io_mock);
+        let io: Arc<Box<dyn BlockchainIO>> = Arc::new(Box::new(io_mock));
 
         let blockchain_client: Box<dyn BlockchainClient> =
-            Box::new(HederaBlockchain::new(hedera_io));
+            Box::new(TestBlockchainClient::new(io, 0));
 
         let (tx_packages, mut rx_packages): (
             Sender<Result<Package, BlockchainError>>,
@@ -201,6 +381,9 @@ mod tests {
         let package = rx_packages.recv().await.unwrap().unwrap();
 
         assert_eq!(package, expected_package);
+
+        // Raw bytes are kept as read, but left out of equality
+        assert_eq!(package.raw, Some(rlp::encode(&expected_package).to_vec()));
     }
 
     /**
@@ -210,28 +393,26 @@ mod tests {
     async fn test_should_skip_not_parseable_packages() {
         let expected_package = create_package_with_sig().unwrap();
 
-        let mut hedera_io_mock = MockBlockchainIO::default();
+        let mut io_mock = MockBlockchainIO::default();
 
         let shared_pkg = expected_package.clone();
 
-        hedera_io_mock
-            .expect_read()
-            .returning(move |tx_packages, _| {
-                let pkg = shared_pkg.clone();
-                let tx = tx_packages.clone();
+        io_mock.expect_read().returning(move |tx_packages, _, _| {
+            let pkg = shared_pkg.clone();
+            let tx = tx_packages.clone();
 
-                Box::pin(async move {
-                    let encoded_pkg = rlp::encode(&pkg).to_vec();
+            Box::pin(async move {
+                let encoded_pkg = rlp::encode(&pkg).to_vec();
 
-                    tx.send(Ok(Vec::from("foobar"))).await.unwrap();
-                    tx.send(Ok(encoded_pkg)).await.unwrap();
-                })
-            });
+                tx.send(Ok(Vec::from("foobar").into())).await.unwrap();
+                tx.send(Ok(encoded_pkg.into())).await.unwrap();
+            })
+        });
 
-        let hedera_io: Box<dyn BlockchainIO> = Box::new(hedera_io_mock);
+        let io: Arc<Box<dyn BlockchainIO>> = Arc::new(Box::new(io_mock));
 
         let blockchain_client: Box<dyn BlockchainClient> =
-            Box::new(HederaBlockchain::new(hedera_io));
+            Box::new(TestBlockchainClient::new(io, 0));
 
         let (tx_packages, mut rx_packages): (
             Sender<Result<Package, BlockchainError>>,
@@ -257,30 +438,28 @@ mod tests {
 
         let expected_package = create_package_with_sig().unwrap();
 
-        let mut hedera_io_mock = MockBlockchainIO::default();
+        let mut io_mock = MockBlockchainIO::default();
 
         let shared_pkg = expected_package.clone();
 
-        hedera_io_mock
-            .expect_read()
-            .returning(move |tx_packages, _| {
-                let pkg = shared_pkg.clone();
-                let forged_pkg = forged_package.clone();
-                let tx = tx_packages.clone();
+        io_mock.expect_read().returning(move |tx_packages, _, _| {
+            let pkg = shared_pkg.clone();
+            let forged_pkg = forged_package.clone();
+            let tx = tx_packages.clone();
 
-                Box::pin(async move {
-                    let encoded_forged_pkg = rlp::encode(&forged_pkg).to_vec();
-                    let encoded_pkg = rlp::encode(&pkg).to_vec();
+            Box::pin(async move {
+                let encoded_forged_pkg = rlp::encode(&forged_pkg).to_vec();
+                let encoded_pkg = rlp::encode(&pkg).to_vec();
 
-                    tx.send(Ok(encoded_forged_pkg)).await.unwrap();
-                    tx.send(Ok(encoded_pkg)).await.unwrap();
-                })
-            });
+                tx.send(Ok(encoded_forged_pkg.into())).await.unwrap();
+                tx.send(Ok(encoded_pkg.into())).await.unwrap();
+            })
+        });
 
-        let hedera_io: Box<dyn BlockchainIO> = Box::new(hedera_io_mock);
+        let io: Arc<Box<dyn BlockchainIO>> = Arc::new(Box::new(io_mock));
 
         let blockchain_client: Box<dyn BlockchainClient> =
-            Box::new(HederaBlockchain::new(hedera_io));
+            Box::new(TestBlockchainClient::new(io, 0));
 
         let (tx_packages, mut rx_packages): (
             Sender<Result<Package, BlockchainError>>,
@@ -294,6 +473,304 @@ mod tests {
         assert_eq!(package, expected_package);
     }
 
+    /**
+     * It should skip package whose encoding is not canonical
+     */
+    #[tokio::test]
+    async fn test_should_skip_non_canonical_packages() {
+        let expected_package = create_package_with_sig().unwrap();
+
+        let mut io_mock = MockBlockchainIO::default();
+
+        let shared_pkg = expected_package.clone();
+
+        io_mock.expect_read().returning(move |tx_packages, _, _| {
+            let pkg = shared_pkg.clone();
+            let tx = tx_packages.clone();
+
+            Box::pin(async move {
+                let encoded_pkg = rlp::encode(&pkg).to_vec();
+
+                // Trailing bytes decode to same fields but make encoding malleable
+                let mut non_canonical_pkg = encoded_pkg.clone();
+                non_canonical_pkg.push(0x00);
+
+                tx.send(Ok(non_canonical_pkg.into())).await.unwrap();
+                tx.send(Ok(encoded_pkg.into())).await.unwrap();
+            })
+        });
+
+        let io: Arc<Box<dyn BlockchainIO>> = Arc::new(Box::new(io_mock));
+
+        let blockchain_client: Box<dyn BlockchainClient> =
+            Box::new(TestBlockchainClient::new(io, 0));
+
+        let (tx_packages, mut rx_packages): (
+            Sender<Result<Package, BlockchainError>>,
+            Receiver<Result<Package, BlockchainError>>,
+        ) = tokio::sync::mpsc::channel(1);
+
+        blockchain_client.read_packages(&tx_packages).await.unwrap();
+
+        let package = rx_packages.recv().await.unwrap().unwrap();
+
+        assert_eq!(package.raw, Some(rlp::encode(&expected_package).to_vec()));
+    }
+
+    /**
+     * It should skip packages with maintainer key of wrong size rather than aborting
+     */
+    #[tokio::test]
+    async fn test_should_skip_wrong_size_maintainer() {
+        let expected_package = create_package_with_sig().unwrap();
+
+        let encoded_package = expected_package.to_rlp().unwrap();
+
+        let malformed_packages: Vec<Vec<u8>> = [PUBLIC_KEY_LENGTH - 1, PUBLIC_KEY_LENGTH + 1]
+            .iter()
+            .map(|maintainer_length| {
+                replace_encoded_package_item(&encoded_package, 3, &vec![1u8; *maintainer_length])
+                    .unwrap()
+            })
+            .collect();
+
+        let mut io_mock = MockBlockchainIO::default();
+
+        io_mock.expect_read().returning(move |tx_packages, _, _| {
+            let mut payloads = malformed_packages.clone();
+            payloads.push(encoded_package.clone());
+
+            let tx = tx_packages.clone();
+
+            Box::pin(async move {
+                for payload in payloads {
+                    tx.send(Ok(payload.into())).await.unwrap();
+                }
+            })
+        });
+
+        let io: Arc<Box<dyn BlockchainIO>> = Arc::new(Box::new(io_mock));
+
+        let blockchain_client: Box<dyn BlockchainClient> =
+            Box::new(TestBlockchainClient::new(io, 0));
+
+        let (tx_packages, mut rx_packages): (
+            Sender<Result<Package, BlockchainError>>,
+            Receiver<Result<Package, BlockchainError>>,
+        ) = tokio::sync::mpsc::channel(1);
+
+        blockchain_client.read_packages(&tx_packages).await.unwrap();
+
+        let package = rx_packages.recv().await.unwrap().unwrap();
+
+        assert_eq!(package, expected_package);
+    }
+
+    /**
+     * It should skip packages whose integrity algorithm is unknown
+     */
+    #[tokio::test]
+    async fn test_should_skip_unsupported_integrity_algorithm() {
+        let expected_package = create_package_with_sig().unwrap();
+
+        let mut csprng = OsRng;
+        let key = SigningKey::generate(&mut csprng);
+
+        let unsupported_package = sign_package_now(
+            &PackageBuilder::from_package(&expected_package)
+                .set_maintainer(&key.verifying_key())
+                .set_integrity(
+                    &String::from("MD5"),
+                    &expected_package.integrity.archive_hash,
+                )
+                .build(),
+            &key,
+        );
+
+        let mut io_mock = MockBlockchainIO::default();
+
+        io_mock.expect_read().returning(move |tx_packages, _, _| {
+            let packages = [unsupported_package.clone(), expected_package.clone()];
+            let tx = tx_packages.clone();
+
+            Box::pin(async move {
+                for pkg in packages {
+                    tx.send(Ok(rlp::encode(&pkg).to_vec().into()))
+                        .await
+                        .unwrap();
+                }
+            })
+        });
+
+        let io: Arc<Box<dyn BlockchainIO>> = Arc::new(Box::new(io_mock));
+
+        let blockchain_client: Box<dyn BlockchainClient> =
+            Box::new(TestBlockchainClient::new(io, 0));
+
+        let (tx_packages, mut rx_packages): (
+            Sender<Result<Package, BlockchainError>>,
+            Receiver<Result<Package, BlockchainError>>,
+        ) = tokio::sync::mpsc::channel(1);
+
+        blockchain_client.read_packages(&tx_packages).await.unwrap();
+
+        let package = rx_packages.recv().await.unwrap().unwrap();
+
+        assert_eq!(package.integrity.algorithm, "SHA256");
+    }
+
+    /**
+     * It should read package at given sequence number
+     */
+    #[tokio::test]
+    async fn test_should_read_package_at() {
+        let expected_package = create_package_with_sig().unwrap();
+        let expected_sequence_number = 42;
+
+        let mut io_mock = MockBlockchainIO::default();
+
+        let shared_pkg = expected_package.clone();
+
+        io_mock.expect_read_at().returning(move |sequence_number| {
+            let encoded_pkg = rlp::encode(&shared_pkg).to_vec();
+
+            Box::pin(async move {
+                match sequence_number {
+                    42 => Ok(Some(encoded_pkg)),
+                    1 => Ok(Some(Vec::from("foobar"))),
+                    _ => Ok(None),
+                }
+            })
+        });
+
+        let io: Arc<Box<dyn BlockchainIO>> = Arc::new(Box::new(io_mock));
+
+        let blockchain_client: Box<dyn BlockchainClient> =
+            Box::new(TestBlockchainClient::new(io, 0));
+
+        let package = blockchain_client
+            .read_package_at(expected_sequence_number)
+            .await
+            .unwrap();
+
+        assert_eq!(package, Some(expected_package));
+        assert_eq!(blockchain_client.read_package_at(7).await, Ok(None));
+        assert_eq!(
+            blockchain_client.read_package_at(1).await,
+            Err(BlockchainError::UndecodablePackage)
+        );
+    }
+
+    /**
+     * It should get revocation signed by package maintainer and skip forged ones
+     */
+    #[tokio::test]
+    async fn test_should_get_revoked_package() {
+        let mut csprng = OsRng;
+        let maintainer_key = SigningKey::generate(&mut csprng);
+        let other_key = SigningKey::generate(&mut csprng);
+
+        let package = sign_package_now(
+            &create_package_without_sig(&maintainer_key.verifying_key()).unwrap(),
+            &maintainer_key,
+        );
+
+        let tombstone = sign_revocation_now(&package, &maintainer_key);
+
+        // Revoked flag is signed, so a regular mutation cannot be turned into a tombstone
+        let flipped_tombstone = PackageBuilder::from_package(&package)
+            .set_revoked(&true)
+            .build();
+
+        // Tombstone claiming original maintainer but signed by someone else
+        let foreign_tombstone = sign_revocation_now(&package, &other_key);
+
+        let mut io_mock = MockBlockchainIO::default();
+
+        let shared_tombstone = tombstone.clone();
+
+        io_mock.expect_read().returning(move |tx_packages, _, _| {
+            let packages = [
+                flipped_tombstone.clone(),
+                foreign_tombstone.clone(),
+                shared_tombstone.clone(),
+            ];
+            let tx = tx_packages.clone();
+
+            Box::pin(async move {
+                for pkg in packages {
+                    tx.send(Ok(rlp::encode(&pkg).to_vec().into()))
+                        .await
+                        .unwrap();
+                }
+            })
+        });
+
+        let io: Arc<Box<dyn BlockchainIO>> = Arc::new(Box::new(io_mock));
+
+        let blockchain_client: Box<dyn BlockchainClient> =
+            Box::new(TestBlockchainClient::new(io, 0));
+
+        let (tx_packages, mut rx_packages): (
+            Sender<Result<Package, BlockchainError>>,
+            Receiver<Result<Package, BlockchainError>>,
+        ) = tokio::sync::mpsc::channel(3);
+
+        blockchain_client.read_packages(&tx_packages).await.unwrap();
+
+        drop(tx_packages);
+
+        let package = rx_packages.recv().await.unwrap().unwrap();
+
+        assert_eq!(package, tombstone);
+        assert_eq!(package.revoked, true);
+        assert_eq!(rx_packages.recv().await.is_none(), true);
+    }
+
+    /**
+     * It should only write revocations signed by package maintainer
+     */
+    #[tokio::test]
+    async fn test_should_revoke_package() {
+        let mut csprng = OsRng;
+        let maintainer_key = SigningKey::generate(&mut csprng);
+        let other_key = SigningKey::generate(&mut csprng);
+
+        let package = sign_package_now(
+            &create_package_without_sig(&maintainer_key.verifying_key()).unwrap(),
+            &maintainer_key,
+        );
+
+        let mut io_mock = MockBlockchainIO::default();
+
+        io_mock
+            .expect_write()
+            .times(1)
+            .returning(|_| Box::pin(async move { Ok(()) }));
+
+        let io: Arc<Box<dyn BlockchainIO>> = Arc::new(Box::new(io_mock));
+
+        let blockchain_client: Box<dyn BlockchainClient> =
+            Box::new(TestBlockchainClient::new(io, 0));
+
+        assert_eq!(
+            blockchain_client.revoke_package(&package).await,
+            Err(BlockchainError::InvalidRevocation)
+        );
+        assert_eq!(
+            blockchain_client
+                .revoke_package(&sign_revocation_now(&package, &other_key))
+                .await,
+            Err(BlockchainError::InvalidSignature)
+        );
+        assert_eq!(
+            blockchain_client
+                .revoke_package(&sign_revocation_now(&package, &maintainer_key))
+                .await,
+            Ok(())
+        );
+    }
+
     /**
      * It should write package
      */
@@ -301,12 +778,12 @@ mod tests {
     async fn test_should_write_package() {
         let expected_package = create_package_with_sig().unwrap();
 
-        let mut hedera_io_mock = MockBlockchainIO::default();
+        let mut io_mock = MockBlockchainIO::default();
 
         let actual_written_package = Arc::new(Mutex::new(None));
         let shared_package: Arc<Mutex<Option<Package>>> = Arc::clone(&actual_written_package);
 
-        hedera_io_mock
+        io_mock
             .expect_write()
             .times(1)
             .returning(move |written_bytes| {
@@ -314,16 +791,26 @@ mod tests {
                 let pkg_clone = Arc::clone(&shared_package);
                 Box::pin(async move {
                     let mut pkg = pkg_clone.lock().await;
-                    *pkg = Some(PackageBuilder::from_rlp(&bytes.as_slice()).unwrap().build());
+                    let encoded_package = decode_payload(&bytes).unwrap();
+                    *pkg = Some(
+                        PackageBuilder::from_rlp(&encoded_package.as_slice())
+                            .unwrap()
+                            .build(),
+                    );
+
+                    Ok(())
                 })
             });
 
-        let hedera_io: Box<dyn BlockchainIO> = Box::new(hedera_io_mock);
+        let io: Arc<Box<dyn BlockchainIO>> = Arc::new(Box::new(io_mock));
 
         let blockchain_client: Box<dyn BlockchainClient> =
-            Box::new(HederaBlockchain::new(hedera_io));
+            Box::new(TestBlockchainClient::new(io, 0));
 
-        blockchain_client.write_package(&expected_package).await;
+        blockchain_client
+            .write_package(&expected_package)
+            .await
+            .unwrap();
 
         let actual_written_package = actual_written_package
             .lock()
@@ -334,4 +821,85 @@ mod tests {
 
         assert_eq!(expected_package, actual_written_package);
     }
+
+    /**
+     * It should write every package of batch using same IO
+     */
+    #[tokio::test]
+    async fn test_should_write_packages() {
+        let expected_packages = vec![
+            create_package_with_sig().unwrap(),
+            create_package_with_sig().unwrap(),
+            create_package_with_sig().unwrap(),
+        ];
+
+        let mut io_mock = MockBlockchainIO::default();
+
+        let actual_written_packages = Arc::new(Mutex::new(vec![]));
+        let shared_packages: Arc<Mutex<Vec<Package>>> = Arc::clone(&actual_written_packages);
+
+        io_mock
+            .expect_write()
+            .times(3)
+            .returning(move |written_bytes| {
+                let bytes = Vec::from(written_bytes);
+                let packages_clone = Arc::clone(&shared_packages);
+                Box::pin(async move {
+                    let encoded_package = decode_payload(&bytes).unwrap();
+                    packages_clone.lock().await.push(
+                        PackageBuilder::from_rlp(&encoded_package.as_slice())
+                            .unwrap()
+                            .build(),
+                    );
+
+                    Ok(())
+                })
+            });
+
+        let io: Arc<Box<dyn BlockchainIO>> = Arc::new(Box::new(io_mock));
+
+        let blockchain_client: Box<dyn BlockchainClient> =
+            Box::new(TestBlockchainClient::new(io, 0));
+
+        let results = blockchain_client.write_packages(&expected_packages).await;
+
+        assert_eq!(results, vec![Ok(()), Ok(()), Ok(())]);
+
+        assert_eq!(*actual_written_packages.lock().await, expected_packages);
+    }
+
+    /**
+     * It should refuse writing unsigned packages, without failing rest of batch
+     */
+    #[tokio::test]
+    async fn test_should_not_write_unsigned_package() {
+        let key = SigningKey::generate(&mut OsRng);
+
+        let unsigned_package = create_package_without_sig(&key.verifying_key()).unwrap();
+        let signed_package = create_package_with_sig().unwrap();
+
+        let mut io_mock = MockBlockchainIO::default();
+
+        // Only signed package should reach IO
+        io_mock
+            .expect_write()
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(()) }));
+
+        let io: Arc<Box<dyn BlockchainIO>> = Arc::new(Box::new(io_mock));
+
+        let blockchain_client: Box<dyn BlockchainClient> =
+            Box::new(TestBlockchainClient::new(io, 0));
+
+        assert_eq!(
+            blockchain_client.write_package(&unsigned_package).await,
+            Err(BlockchainError::UnsignedPackage)
+        );
+
+        let results = blockchain_client
+            .write_packages(&[unsigned_package, signed_package])
+            .await;
+
+        assert_eq!(results, vec![Err(BlockchainError::UnsignedPackage), Ok(())]);
+    }
 }
@@ -4,8 +4,61 @@ use thiserror::Error;
 pub enum BlockchainError {
     #[error("Could not configure blockchain connection properly")]
     ConnectionConfig,
+    #[error("Invalid topic ID: {0}")]
+    InvalidTopic(String),
     #[error("Could not establish connection to blockchain")]
     ConnectionFailure,
+    #[error("Subscription to blockchain timed out")]
+    SubscriptionTimeout,
+    #[error("Blockchain stream was interrupted: {0}")]
+    StreamInterrupted(String),
     #[error("No packages data")]
     NoPackagesData,
+    #[error("Message could not be decoded as a package")]
+    UndecodablePackage,
+    #[error("Package is not signed")]
+    UnsignedPackage,
+    #[error("Package signature does not match its maintainer")]
+    InvalidSignature,
+    #[error("Package is not a signed revocation")]
+    InvalidRevocation,
+    #[error("Unsupported integrity algorithm: {0}")]
+    UnsupportedIntegrityAlgorithm(String),
+    #[error("Could not write to blockchain: {0}")]
+    WriteFailure(String),
+    #[error("Blockchain update task did not complete: {0}")]
+    UpdateAborted(String),
+}
+
+impl BlockchainError {
+    /**
+     * Check if error comes from connectivity, so operation may succeed when retried
+     */
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            Self::ConnectionFailure | Self::SubscriptionTimeout | Self::StreamInterrupted(_)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /**
+     * It should only retry connectivity failures, not aborted updates
+     */
+    #[test]
+    fn test_is_transient() {
+        assert_eq!(BlockchainError::ConnectionFailure.is_transient(), true);
+        assert_eq!(
+            BlockchainError::StreamInterrupted(String::from("reset")).is_transient(),
+            true
+        );
+        assert_eq!(
+            BlockchainError::UpdateAborted(String::from("panicked")).is_transient(),
+            false
+        );
+    }
 }
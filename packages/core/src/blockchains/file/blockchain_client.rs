@@ -0,0 +1,410 @@
+use crate::blockchains::blockchain::{BlockchainClient, BlockchainIO};
+use crate::blockchains::errors::blockchain_error::BlockchainError;
+use crate::blockchains::message::BlockchainMessage;
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use log::{debug, error};
+use tokio::sync::{mpsc::Sender, Mutex};
+
+// Each record is consensus time ( nanoseconds ) then payload length, both big endian, then payload
+const RECORD_HEADER_LENGTH: usize = 8 + 4;
+
+/**
+ * Append-only log of payloads stored in a local file, record position acts as sequence number
+ */
+#[derive(Debug, Clone)]
+struct FileBlockchainIO {
+    log_path: PathBuf,
+}
+
+impl FileBlockchainIO {
+    /**
+     * Read every record of log, a missing log has no records
+     */
+    fn read_records(&self) -> Result<Vec<BlockchainMessage>, BlockchainError> {
+        if !self.log_path.exists() {
+            return Ok(vec![]);
+        }
+
+        let raw_log = fs::read(&self.log_path)
+            .map_err(|e| BlockchainError::StreamInterrupted(e.to_string()))?;
+
+        let mut records = vec![];
+        let mut cursor = 0;
+
+        while cursor < raw_log.len() {
+            let header = raw_log
+                .get(cursor..cursor + RECORD_HEADER_LENGTH)
+                .ok_or_else(|| BlockchainError::StreamInterrupted(String::from("Truncated log")))?;
+
+            let (raw_consensus_time, raw_length) = header.split_at(8);
+
+            let consensus_time = u64::from_be_bytes(raw_consensus_time.try_into().unwrap());
+            let length = u32::from_be_bytes(raw_length.try_into().unwrap()) as usize;
+
+            cursor += RECORD_HEADER_LENGTH;
+
+            let payload = raw_log
+                .get(cursor..cursor + length)
+                .ok_or_else(|| BlockchainError::StreamInterrupted(String::from("Truncated log")))?;
+
+            cursor += length;
+
+            records.push(BlockchainMessage {
+                payload: payload.to_vec(),
+                consensus_time: Some(consensus_time),
+            });
+        }
+
+        Ok(records)
+    }
+}
+
+#[async_trait::async_trait]
+impl BlockchainIO for FileBlockchainIO {
+    /**
+     * Append payload to log
+     */
+    async fn write(&self, data: &[u8]) -> Result<(), BlockchainError> {
+        let consensus_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_nanos() as u64;
+
+        let mut record = Vec::with_capacity(RECORD_HEADER_LENGTH + data.len());
+
+        record.extend_from_slice(&consensus_time.to_be_bytes());
+        record.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        record.extend_from_slice(data);
+
+        // Record is appended in a single write so records of concurrent writers do not interleave
+        let write_result = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.log_path)
+            .and_then(|mut log_file| log_file.write_all(&record));
+
+        write_result.map_err(|e| {
+            error!(
+                "Could not append to log {} : {}",
+                self.log_path.display(),
+                e
+            );
+            BlockchainError::WriteFailure(e.to_string())
+        })
+    }
+
+    /**
+     * Read records appended since last sync, up to end timestamp if any
+     */
+    async fn read(
+        &self,
+        tx_data: &Sender<Result<BlockchainMessage, BlockchainError>>,
+        last_sync: &u64,
+        end_timestamp: &Option<u64>,
+    ) {
+        let records = match self.read_records() {
+            Ok(records) => records,
+            Err(e) => {
+                let _ = tx_data.send(Err(e)).await;
+                return;
+            }
+        };
+
+        for record in records {
+            let published_at = record.consensus_time.unwrap_or_default() / 1_000_000_000;
+
+            if published_at < *last_sync {
+                continue;
+            }
+
+            if end_timestamp.is_some_and(|end_timestamp| published_at > end_timestamp) {
+                break;
+            }
+
+            if tx_data.send(Ok(record)).await.is_err() {
+                break;
+            }
+        }
+    }
+
+    /**
+     * Read single record, sequence numbers start at 1
+     */
+    async fn read_at(&self, sequence_number: u64) -> Result<Option<Vec<u8>>, BlockchainError> {
+        if sequence_number == 0 {
+            return Ok(None);
+        }
+
+        let record = self
+            .read_records()?
+            .into_iter()
+            .nth((sequence_number - 1) as usize);
+
+        Ok(record.map(|record| record.payload))
+    }
+}
+
+const DEFAULT_LABEL: &str = "file";
+
+/**
+ * Blockchain backed by a local log, eg: to run a private registry without network
+ */
+#[derive(Debug)]
+pub struct FileBlockchain {
+    label: String,
+    file_io: Arc<Box<dyn BlockchainIO>>,
+    last_sync: Arc<Mutex<u64>>,
+}
+
+impl FileBlockchain {
+    /**
+     * Set label, clients using distinct logs must use distinct labels to be synchronized separately
+     */
+    pub fn set_label(&mut self, label: &String) -> &mut Self {
+        self.label = label.clone();
+
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl BlockchainClient for FileBlockchain {
+    /**
+     * Get blockchain label
+     */
+    fn get_label(&self) -> String {
+        self.label.clone()
+    }
+
+    /**
+     * Create log IO
+     */
+    async fn create_io(&self) -> Arc<Box<dyn BlockchainIO>> {
+        Arc::clone(&self.file_io)
+    }
+
+    /**
+     * Get last sync
+     */
+    async fn get_last_sync(&self) -> u64 {
+        let last_sync = self.last_sync.lock().await;
+        *last_sync
+    }
+
+    /**
+     * Set last sync
+     */
+    async fn set_last_sync(&self, last_sync: u64) {
+        let mut last_sync_lock = self.last_sync.lock().await;
+
+        *last_sync_lock = last_sync;
+    }
+}
+
+impl From<&PathBuf> for FileBlockchain {
+    /**
+     * Build from log path, log is created on first write
+     */
+    fn from(log_path: &PathBuf) -> Self {
+        debug!("Creating File Blockchain Client...");
+
+        let file_io = Box::new(FileBlockchainIO {
+            log_path: log_path.clone(),
+        });
+
+        let client = Self {
+            label: String::from(DEFAULT_LABEL),
+            file_io: Arc::new(file_io),
+            last_sync: Arc::new(Mutex::new(0)),
+        };
+
+        debug!(
+            "Done creating File Blockchain Client using log : {} !",
+            log_path.display()
+        );
+
+        client
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+    use tokio::sync::mpsc;
+
+    use crate::{
+        blockchains::{blockchain::BlockchainClient, errors::blockchain_error::BlockchainError},
+        packages::{package::Package, package_builder::PackageBuilder},
+        test_utils::package::tests::create_package_with_sig,
+    };
+
+    use super::FileBlockchain;
+
+    /**
+     * Read every package of blockchain, since given last sync
+     */
+    async fn read_all(blockchain_client: &FileBlockchain) -> Result<Vec<Package>, BlockchainError> {
+        let (tx_packages, mut rx_packages) = mpsc::channel(10);
+
+        blockchain_client.read_packages(&tx_packages).await?;
+
+        drop(tx_packages);
+
+        let mut packages = vec![];
+
+        while let Some(package) = rx_packages.recv().await {
+            packages.push(package?);
+        }
+
+        Ok(packages)
+    }
+
+    /**
+     * It should report package that could not be appended to log
+     */
+    #[tokio::test]
+    async fn test_write_package_failure() -> Result<(), Box<dyn std::error::Error>> {
+        let test_dir = TempDir::new()?;
+
+        let blockchain_client =
+            FileBlockchain::from(&test_dir.path().join("missing").join("packages.log"));
+
+        let package = create_package_with_sig()?;
+
+        assert_eq!(
+            matches!(
+                blockchain_client.write_package(&package).await,
+                Err(BlockchainError::WriteFailure(_))
+            ),
+            true
+        );
+
+        Ok(())
+    }
+
+    /**
+     * It should round trip signed package through log
+     */
+    #[tokio::test]
+    async fn test_write_then_read_package() -> Result<(), Box<dyn std::error::Error>> {
+        let test_dir = TempDir::new()?;
+
+        let blockchain_client = FileBlockchain::from(&test_dir.path().join("packages.log"));
+
+        let package = create_package_with_sig()?;
+
+        blockchain_client.write_package(&package).await?;
+
+        let read_packages = read_all(&blockchain_client).await?;
+
+        assert_eq!(read_packages.len(), 1);
+
+        let read_package = &read_packages[0];
+
+        // Consensus time comes from log, everything else from package itself
+        let consensus_time = read_package
+            .consensus_time
+            .expect("Consensus time must be set");
+
+        let expected_package = PackageBuilder::from_package(&package)
+            .set_consensus_time(&consensus_time)
+            .build();
+
+        assert_eq!(*read_package, expected_package);
+
+        Ok(())
+    }
+
+    /**
+     * It should skip package whose signature does not match its maintainer
+     */
+    #[tokio::test]
+    async fn test_read_skips_forged_package() -> Result<(), Box<dyn std::error::Error>> {
+        let test_dir = TempDir::new()?;
+
+        let blockchain_client = FileBlockchain::from(&test_dir.path().join("packages.log"));
+
+        let forged_package = PackageBuilder::from_package(&create_package_with_sig()?)
+            .set_version(&String::from("6.6.6"))
+            .build();
+
+        blockchain_client.write_package(&forged_package).await?;
+
+        assert_eq!(read_all(&blockchain_client).await?.is_empty(), true);
+
+        Ok(())
+    }
+
+    /**
+     * It should read nothing from a log which does not exist yet, then persist last sync
+     */
+    #[tokio::test]
+    async fn test_read_missing_log() -> Result<(), Box<dyn std::error::Error>> {
+        let test_dir = TempDir::new()?;
+
+        let blockchain_client = FileBlockchain::from(&test_dir.path().join("packages.log"));
+
+        assert_eq!(read_all(&blockchain_client).await?.is_empty(), true);
+        assert_eq!(blockchain_client.get_last_sync().await > 0, true);
+
+        Ok(())
+    }
+
+    /**
+     * It should report truncated log instead of reading garbage
+     */
+    #[tokio::test]
+    async fn test_read_truncated_log() -> Result<(), Box<dyn std::error::Error>> {
+        let test_dir = TempDir::new()?;
+
+        let log_path = test_dir.path().join("packages.log");
+
+        fs::write(&log_path, [0u8; 5])?;
+
+        let blockchain_client = FileBlockchain::from(&log_path);
+
+        assert_eq!(
+            matches!(
+                read_all(&blockchain_client).await,
+                Err(BlockchainError::StreamInterrupted(_))
+            ),
+            true
+        );
+
+        Ok(())
+    }
+
+    /**
+     * It should read package using its sequence number
+     */
+    #[tokio::test]
+    async fn test_read_package_at() -> Result<(), Box<dyn std::error::Error>> {
+        let test_dir = TempDir::new()?;
+
+        let blockchain_client = FileBlockchain::from(&test_dir.path().join("packages.log"));
+
+        let first_package = create_package_with_sig()?;
+        let second_package = create_package_with_sig()?;
+
+        blockchain_client.write_package(&first_package).await?;
+        blockchain_client.write_package(&second_package).await?;
+
+        assert_eq!(
+            blockchain_client.read_package_at(2).await?,
+            Some(second_package)
+        );
+        assert_eq!(blockchain_client.read_package_at(0).await?, None);
+        assert_eq!(blockchain_client.read_package_at(3).await?, None);
+
+        Ok(())
+    }
+}
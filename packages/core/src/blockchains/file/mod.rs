@@ -0,0 +1 @@
+pub mod blockchain_client;
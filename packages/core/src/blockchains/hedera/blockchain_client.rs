@@ -1,9 +1,18 @@
 use crate::blockchains::blockchain::{BlockchainClient, BlockchainIO};
 use crate::blockchains::errors::blockchain_error::BlockchainError;
+use crate::blockchains::message::BlockchainMessage;
+use crate::blockchains::stream::{forward_until_silent, next_before};
+use crate::utils::retry::backoff_delay;
 use std::convert::TryFrom;
-use std::{env, str::FromStr, sync::Arc, time::Duration};
+use std::future::Future;
+use std::{
+    env,
+    str::FromStr,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
 
-use futures_util::TryStreamExt;
+use futures_util::StreamExt;
 use hedera::{AccountId, Client, PrivateKey, TopicId, TopicMessageSubmitTransaction};
 pub mod hedera_mirror {
     tonic::include_proto!("mirror");
@@ -19,7 +28,7 @@ use hedera_mirror::{
 
 use tokio::sync::{mpsc::Sender, Mutex};
 
-use log::{debug, trace};
+use log::debug;
 use tonic::{
     transport::{Channel, ClientTlsConfig},
     Streaming,
@@ -28,10 +37,102 @@ use tonic::{
 #[cfg(test)]
 use mockall::automock;
 
+/**
+ * Longest wait between two connection attempts, however many attempts are configured
+ */
+const MAX_CONNECT_RETRY_DELAY: Duration = Duration::from_secs(30);
+
+/**
+ * How many times connecting to HCS is attempted, waiting base delay then doubling it between attempts
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConnectRetry {
+    pub attempts: u32,
+    pub base_delay: Duration,
+}
+
+impl Default for ConnectRetry {
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            base_delay: Duration::from_millis(200),
+        }
+    }
+}
+
+/**
+ * Run connect until it succeeds or attempts are exhausted, backing off exponentially in between
+ */
+async fn connect_with_retry<T, F, Fut>(
+    connect_retry: &ConnectRetry,
+    mut connect: F,
+) -> Result<T, BlockchainError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, BlockchainError>>,
+{
+    let mut attempt = 1;
+
+    loop {
+        match connect().await {
+            Ok(connection) => return Ok(connection),
+            Err(e) if attempt >= connect_retry.attempts => return Err(e),
+            Err(e) => {
+                let delay = backoff_delay(
+                    connect_retry.base_delay,
+                    attempt - 1,
+                    MAX_CONNECT_RETRY_DELAY,
+                );
+
+                debug!(
+                    "Connection attempt {}/{} failed ( {} ), retrying in {} ms...",
+                    attempt,
+                    connect_retry.attempts,
+                    e,
+                    delay.as_millis()
+                );
+
+                tokio::time::sleep(delay).await;
+
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/**
+ * How long to wait for mirror node, subscribing then between two messages
+ *
+ * No next message timeout means waiting indefinitely until stream end
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HederaTimeouts {
+    pub subscribe: Duration,
+    pub next_message: Option<Duration>,
+}
+
+impl Default for HederaTimeouts {
+    fn default() -> Self {
+        Self {
+            subscribe: Duration::from_secs(10),
+            next_message: Some(Duration::from_secs(3)),
+        }
+    }
+}
+
+/**
+ * Parse HCS topic ID ( eg: 0.0.1234 or 1234 ), refusing invalid ones
+ */
+pub fn validate_topic_id(topic_id: &str) -> Result<TopicId, BlockchainError> {
+    TopicId::from_str(topic_id).map_err(|_| BlockchainError::InvalidTopic(topic_id.to_string()))
+}
+
 #[derive(Debug, Clone)]
 struct HederaBlockchainIO {
     packages_topic: TopicId,
     hedera_client: Client,
+    connect_retry: Arc<RwLock<ConnectRetry>>, // Shared with owning client so it can be tuned
+    timeouts: Arc<RwLock<HederaTimeouts>>,
 }
 
 #[cfg_attr(test, automock)]
@@ -50,19 +151,43 @@ impl HederaBlockchainIO {
 
         let remote_url = format!("https://{}", network.to_string()); // We must prefix scheme
 
-        let channel = Channel::from_shared(remote_url)
+        // Tonic transport has no HTTP proxy support, configured proxy only applies to downloads
+
+        let endpoint = Channel::from_shared(remote_url)
             .map_err(|_| BlockchainError::ConnectionConfig)?
             .tls_config(tls)
-            .map_err(|_| BlockchainError::ConnectionConfig)?
-            .connect()
-            .await
-            .map_err(|_| BlockchainError::ConnectionFailure)?;
+            .map_err(|_| BlockchainError::ConnectionConfig)?;
+
+        let connect_retry = *self
+            .connect_retry
+            .read()
+            .map_err(|_| BlockchainError::ConnectionConfig)?;
+
+        let endpoint = &endpoint;
+
+        let channel = connect_with_retry(&connect_retry, move || async move {
+            endpoint
+                .connect()
+                .await
+                .map_err(|_| BlockchainError::ConnectionFailure)
+        })
+        .await?;
 
         debug!("Done establishing new HCS channel !");
 
         Ok(channel)
     }
 
+    /**
+     * Get current mirror node timeouts
+     */
+    fn get_timeouts(&self) -> HederaTimeouts {
+        self.timeouts
+            .read()
+            .map(|timeouts| *timeouts)
+            .unwrap_or_default()
+    }
+
     /**
      * Subscribe to topic then return associated stream
      */
@@ -70,6 +195,8 @@ impl HederaBlockchainIO {
         &self,
         topic: TopicId,
         start_timestamp: u64,
+        end_timestamp: Option<u64>,
+        limit: u64, // 0 means unbounded
     ) -> Result<Streaming<ConsensusTopicResponse>, BlockchainError> {
         debug!("Creating new topic subscription...");
 
@@ -85,23 +212,27 @@ impl HederaBlockchainIO {
                 seconds: i64::try_from(start_timestamp)
                     .expect("Could not convert start time seconds to i64"),
             }),
-            consensus_end_time: None,
-            limit: 0,
+            consensus_end_time: end_timestamp.map(|end_timestamp| Timestamp {
+                nanos: 0,
+                seconds: i64::try_from(end_timestamp)
+                    .expect("Could not convert end time seconds to i64"),
+            }),
+            limit,
         };
 
         let reading_channel = self.new_channel().await?;
 
         let mut mirror_client = ConsensusServiceClient::new(reading_channel.clone());
 
-        const TIMEOUT: u64 = 1;
         let response = tokio::time::timeout(
-            Duration::from_secs(TIMEOUT),
+            self.get_timeouts().subscribe,
             mirror_client.subscribe_topic(query),
         )
         .await
-        .map_err(|_| BlockchainError::NoPackagesData)?;
+        .map_err(|_| BlockchainError::SubscriptionTimeout)?
+        .map_err(|_| BlockchainError::ConnectionFailure)?;
 
-        let stream = response.unwrap().into_inner();
+        let stream = response.into_inner();
         debug!("Done creating new topic subscription !");
         Ok(stream)
     }
@@ -112,24 +243,31 @@ impl BlockchainIO for HederaBlockchainIO {
     /**
      * Write to HCS
      */
-    async fn write(&self, data: &[u8]) {
+    async fn write(&self, data: &[u8]) -> Result<(), BlockchainError> {
         TopicMessageSubmitTransaction::new()
             .topic_id(self.packages_topic)
             .message(data)
             .execute(&self.hedera_client)
             .await
-            .unwrap();
+            .map_err(|e| BlockchainError::WriteFailure(e.to_string()))?;
+
+        Ok(())
     }
 
     /**
      * Read from HCS
      */
-    async fn read(&self, tx_data: &Sender<Result<Vec<u8>, BlockchainError>>, last_sync: &u64) {
+    async fn read(
+        &self,
+        tx_data: &Sender<Result<BlockchainMessage, BlockchainError>>,
+        last_sync: &u64,
+        end_timestamp: &Option<u64>,
+    ) {
         let stream_res = self
-            .new_topic_subscription(self.packages_topic, *last_sync)
+            .new_topic_subscription(self.packages_topic, *last_sync, *end_timestamp, 0)
             .await;
 
-        let mut stream = match stream_res {
+        let stream = match stream_res {
             Ok(stream) => stream,
             Err(e) => {
                 tx_data.send(Err(e)).await.unwrap();
@@ -137,19 +275,47 @@ impl BlockchainIO for HederaBlockchainIO {
             }
         };
 
-        const NEXT_MESSAGE_TIMEOUT: u64 = 1;
+        let messages = stream.map(|result| {
+            let response = result.map_err(|e| BlockchainError::StreamInterrupted(e.to_string()))?;
+
+            Ok(BlockchainMessage {
+                payload: response.message,
+                consensus_time: response.consensus_timestamp.and_then(|timestamp| {
+                    u64::try_from(timestamp.seconds)
+                        .ok()?
+                        .checked_mul(1_000_000_000)?
+                        .checked_add(u64::try_from(timestamp.nanos).ok()?)
+                }),
+            })
+        });
+
+        forward_until_silent(messages, self.get_timeouts().next_message, tx_data).await;
+    }
 
-        while let Ok(result) =
-            tokio::time::timeout(Duration::from_secs(NEXT_MESSAGE_TIMEOUT), stream.try_next()).await
-        {
-            trace!("Sending to channel...");
-            let response = result.unwrap().unwrap();
+    /**
+     * Read single HCS message, query is bounded to topic's first messages up to given one
+     */
+    async fn read_at(&self, sequence_number: u64) -> Result<Option<Vec<u8>>, BlockchainError> {
+        // Sequence numbers start at 1, a zero limit would not bound the query
+        if sequence_number == 0 {
+            return Ok(None);
+        }
 
-            let buf: Vec<u8> = Vec::from(response.message.as_slice());
+        let mut stream = self
+            .new_topic_subscription(self.packages_topic, 0, None, sequence_number)
+            .await?;
 
-            tx_data.send(Ok(buf)).await.unwrap();
-            trace!("Done sending to channel !");
+        let next_message_timeout = self.get_timeouts().next_message;
+
+        while let Some(result) = next_before(&mut stream, next_message_timeout).await {
+            let response = result.map_err(|_| BlockchainError::ConnectionFailure)?;
+
+            if response.sequence_number == sequence_number {
+                return Ok(Some(response.message));
+            }
         }
+
+        Ok(None)
     }
 }
 
@@ -172,27 +338,88 @@ impl From<&str> for HederaBlockchainIO {
         let instance = Self {
             hedera_client: blockchain_client,
             packages_topic: topic,
+            connect_retry: Arc::new(RwLock::new(ConnectRetry::default())),
+            timeouts: Arc::new(RwLock::new(HederaTimeouts::default())),
         };
 
         instance
     }
 }
 
+const DEFAULT_LABEL: &str = "hedera";
+
 #[derive(Debug)]
 pub struct HederaBlockchain {
+    label: String,
     hedera_io: Arc<Box<dyn BlockchainIO>>,
     last_sync: Arc<Mutex<u64>>,
+    connect_retry: Arc<RwLock<ConnectRetry>>,
+    timeouts: Arc<RwLock<HederaTimeouts>>,
 }
 
 impl HederaBlockchain {
     pub fn new(hedera_io: Box<dyn BlockchainIO>) -> Self {
         let instance = Self {
+            label: String::from(DEFAULT_LABEL),
             hedera_io: Arc::new(hedera_io),
             last_sync: Arc::new(Mutex::new(0)),
+            connect_retry: Arc::new(RwLock::new(ConnectRetry::default())),
+            timeouts: Arc::new(RwLock::new(HederaTimeouts::default())),
         };
 
         instance
     }
+
+    /**
+     * Set label, clients using distinct topics must use distinct labels to be synchronized separately
+     */
+    pub fn set_label(&mut self, label: &String) -> &mut Self {
+        self.label = label.clone();
+
+        self
+    }
+
+    /**
+     * Set how connecting to HCS is retried, eg: no delay in tests
+     */
+    pub fn set_connect_retry(&mut self, connect_retry: &ConnectRetry) -> &mut Self {
+        if let Ok(mut connect_retry_lock) = self.connect_retry.write() {
+            *connect_retry_lock = *connect_retry;
+        }
+
+        self
+    }
+
+    /**
+     * Get how connecting to HCS is retried
+     */
+    pub fn get_connect_retry(&self) -> ConnectRetry {
+        self.connect_retry
+            .read()
+            .map(|connect_retry| *connect_retry)
+            .unwrap_or_default()
+    }
+
+    /**
+     * Set mirror node timeouts, eg: longer ones on slow connections
+     */
+    pub fn set_timeouts(&mut self, timeouts: &HederaTimeouts) -> &mut Self {
+        if let Ok(mut timeouts_lock) = self.timeouts.write() {
+            *timeouts_lock = *timeouts;
+        }
+
+        self
+    }
+
+    /**
+     * Get mirror node timeouts
+     */
+    pub fn get_timeouts(&self) -> HederaTimeouts {
+        self.timeouts
+            .read()
+            .map(|timeouts| *timeouts)
+            .unwrap_or_default()
+    }
 }
 
 #[async_trait::async_trait]
@@ -202,7 +429,7 @@ impl BlockchainClient for HederaBlockchain {
      * Get blockchain label
      */
     fn get_label(&self) -> String {
-        String::from("hedera")
+        self.label.clone()
     }
 
     /**
@@ -240,6 +467,9 @@ impl From<&str> for HederaBlockchain {
 
         let hedera_io = Box::new(HederaBlockchainIO::from(package_topic_id));
 
+        let connect_retry = Arc::clone(&hedera_io.connect_retry);
+        let timeouts = Arc::clone(&hedera_io.timeouts);
+
         let net_addr = hedera_io
             .hedera_client
             .mirror_network()
@@ -248,8 +478,11 @@ impl From<&str> for HederaBlockchain {
             .to_string();
 
         let client = Self {
+            label: String::from(DEFAULT_LABEL),
             hedera_io: Arc::new(hedera_io),
             last_sync: Arc::new(Mutex::new(default_last_sync)),
+            connect_retry,
+            timeouts,
         };
 
         debug!(
@@ -263,9 +496,128 @@ impl From<&str> for HederaBlockchain {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::time::Duration;
+
     use crate::blockchains::blockchain::{BlockchainClient, BlockchainIO, MockBlockchainIO};
+    use crate::blockchains::errors::blockchain_error::BlockchainError;
+
+    use super::{
+        connect_with_retry, validate_topic_id, ConnectRetry, HederaBlockchain, HederaTimeouts,
+    };
+
+    /**
+     * It should accept topic ID with or without shard and realm, refusing anything else
+     */
+    #[test]
+    fn test_validate_topic_id() {
+        assert_eq!(validate_topic_id("4991716").is_ok(), true);
+        assert_eq!(validate_topic_id("0.0.4991716").is_ok(), true);
+        assert_eq!(
+            validate_topic_id("foo").err(),
+            Some(BlockchainError::InvalidTopic(String::from("foo")))
+        );
+    }
+
+    /**
+     * It should retry connection until it succeeds
+     */
+    #[tokio::test]
+    async fn test_connect_with_retry_succeeds_after_failures() {
+        let connect_retry = ConnectRetry {
+            attempts: 3,
+            base_delay: Duration::ZERO,
+        };
+
+        let attempts = &AtomicU32::new(0);
+
+        // Fails twice then succeeds
+        let connection = connect_with_retry(&connect_retry, move || async move {
+            match attempts.fetch_add(1, Ordering::SeqCst) {
+                0 | 1 => Err(BlockchainError::ConnectionFailure),
+                _ => Ok(42),
+            }
+        })
+        .await;
 
-    use super::HederaBlockchain;
+        assert_eq!(connection.ok(), Some(42));
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    /**
+     * It should give up once attempts are exhausted, returning last error
+     */
+    #[tokio::test]
+    async fn test_connect_with_retry_exhausted() {
+        let connect_retry = ConnectRetry {
+            attempts: 2,
+            base_delay: Duration::ZERO,
+        };
+
+        let attempts = &AtomicU32::new(0);
+
+        let connection: Result<u8, BlockchainError> =
+            connect_with_retry(&connect_retry, move || async move {
+                attempts.fetch_add(1, Ordering::SeqCst);
+
+                Err(BlockchainError::ConnectionFailure)
+            })
+            .await;
+
+        assert_eq!(
+            matches!(connection, Err(BlockchainError::ConnectionFailure)),
+            true
+        );
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+
+    /**
+     * It should set connect retry
+     */
+    #[tokio::test]
+    async fn test_should_set_connect_retry() {
+        let hedera_io: Box<dyn BlockchainIO> = Box::new(MockBlockchainIO::default());
+
+        let mut blockchain_client = HederaBlockchain::new(hedera_io);
+
+        assert_eq!(
+            blockchain_client.get_connect_retry(),
+            ConnectRetry::default()
+        );
+
+        let expected_connect_retry = ConnectRetry {
+            attempts: 1,
+            base_delay: Duration::ZERO,
+        };
+
+        blockchain_client.set_connect_retry(&expected_connect_retry);
+
+        assert_eq!(
+            blockchain_client.get_connect_retry(),
+            expected_connect_retry
+        );
+    }
+
+    /**
+     * It should set timeouts, including waiting indefinitely for next message
+     */
+    #[tokio::test]
+    async fn test_should_set_timeouts() {
+        let hedera_io: Box<dyn BlockchainIO> = Box::new(MockBlockchainIO::default());
+
+        let mut blockchain_client = HederaBlockchain::new(hedera_io);
+
+        assert_eq!(blockchain_client.get_timeouts(), HederaTimeouts::default());
+
+        let expected_timeouts = HederaTimeouts {
+            subscribe: Duration::from_secs(30),
+            next_message: None,
+        };
+
+        blockchain_client.set_timeouts(&expected_timeouts);
+
+        assert_eq!(blockchain_client.get_timeouts(), expected_timeouts);
+    }
 
     /**
      * It should get label
@@ -284,6 +636,24 @@ mod tests {
         assert_eq!(current_label, expected_label);
     }
 
+    /**
+     * It should set label
+     */
+    #[tokio::test]
+    async fn test_should_set_label() {
+        let hedera_io_mock = MockBlockchainIO::default();
+
+        let hedera_io: Box<dyn BlockchainIO> = Box::new(hedera_io_mock);
+
+        let mut blockchain_client = HederaBlockchain::new(hedera_io);
+
+        let expected_label = String::from("hedera-0.0.1234");
+
+        blockchain_client.set_label(&expected_label);
+
+        assert_eq!(blockchain_client.get_label(), expected_label);
+    }
+
     /**
      * It should set last sync
      */
@@ -293,7 +663,7 @@ mod tests {
 
         hedera_io_mock
             .expect_read()
-            .returning(|_, _| Box::pin(async {}));
+            .returning(|_, _, _| Box::pin(async {}));
 
         hedera_io_mock
             .expect_write()
@@ -320,7 +690,7 @@ mod tests {
 
         hedera_io_mock
             .expect_read()
-            .returning(|_, _| Box::pin(async {}));
+            .returning(|_, _, _| Box::pin(async {}));
 
         hedera_io_mock
             .expect_write()
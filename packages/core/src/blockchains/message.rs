@@ -0,0 +1,17 @@
+/**
+ * Raw message read from blockchain
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockchainMessage {
+    pub payload: Vec<u8>,
+    pub consensus_time: Option<u64>, // Nanoseconds since epoch, when blockchain provides it
+}
+
+impl From<Vec<u8>> for BlockchainMessage {
+    fn from(payload: Vec<u8>) -> Self {
+        Self {
+            payload,
+            consensus_time: None,
+        }
+    }
+}
@@ -1,14 +1,123 @@
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 
 use blockchain::BlockchainClient;
-use hedera::blockchain_client::HederaBlockchain;
+use errors::blockchain_error::BlockchainError;
+use file::blockchain_client::FileBlockchain;
+#[cfg(feature = "hedera")]
+use hedera::blockchain_client::{validate_topic_id, HederaBlockchain};
+
+use crate::config::core_config::CoreConfig;
 
 pub mod blockchain;
+#[cfg(feature = "hedera")]
 pub mod hedera;
 
 pub mod errors;
+pub mod file;
+pub mod message;
+pub mod payload;
+pub mod stream;
+
+/**
+ * HCS topic packages are read from unless another one is configured
+ */
+pub const DEFAULT_HEDERA_TOPIC: &str = "4991716";
+
+/**
+ * Build clients of every supported blockchain, using topic and log configured for each
+ */
+pub fn get_available_clients(
+    core_config: &CoreConfig,
+) -> Result<Vec<Arc<Box<dyn BlockchainClient>>>, BlockchainError> {
+    let mut clients: Vec<Arc<Box<dyn BlockchainClient>>> = vec![];
+
+    #[cfg(feature = "hedera")]
+    {
+        let topic_id = core_config
+            .hedera_topic
+            .as_deref()
+            .unwrap_or(DEFAULT_HEDERA_TOPIC);
+
+        validate_topic_id(topic_id)?;
+
+        clients.push(Arc::new(Box::new(HederaBlockchain::from(topic_id))));
+    }
+
+    if let Some(log_path) = &core_config.file_blockchain {
+        clients.push(Arc::new(Box::new(FileBlockchain::from(&PathBuf::from(
+            log_path,
+        )))));
+    }
+
+    Ok(clients)
+}
+
+/**
+ * Build client bound to given topic instead of default one
+ */
+#[cfg(all(feature = "hedera", not(tarpaulin_include)))]
+pub fn get_topic_client(topic_id: &str) -> Result<Arc<Box<dyn BlockchainClient>>, BlockchainError> {
+    validate_topic_id(topic_id)?;
+
+    let mut client = HederaBlockchain::from(topic_id);
+
+    client.set_label(&format!("hedera-{}", topic_id));
+
+    Ok(Arc::new(Box::new(client)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /**
+     * It should build Hedera client from configured topic
+     */
+    #[test]
+    #[cfg(feature = "hedera")]
+    fn test_get_available_clients_configured_topic() {
+        let core_config = CoreConfig {
+            hedera_topic: Some(String::from("0.0.1234")),
+            ..Default::default()
+        };
+
+        let clients = get_available_clients(&core_config).unwrap();
+
+        assert_eq!(clients.len(), 1);
+    }
+
+    /**
+     * It should refuse invalid configured topic instead of panicking
+     */
+    #[test]
+    #[cfg(feature = "hedera")]
+    fn test_get_available_clients_invalid_topic() {
+        let core_config = CoreConfig {
+            hedera_topic: Some(String::from("not-a-topic")),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            get_available_clients(&core_config).err(),
+            Some(BlockchainError::InvalidTopic(String::from("not-a-topic")))
+        );
+    }
+
+    /**
+     * It should offer file blockchain once its log is configured
+     */
+    #[test]
+    fn test_get_available_clients_file_blockchain() {
+        let core_config = CoreConfig {
+            file_blockchain: Some(String::from("/tmp/bpm-packages.log")),
+            ..Default::default()
+        };
+
+        let clients = get_available_clients(&core_config).unwrap();
 
-#[cfg(not(tarpaulin_include))]
-pub fn get_available_clients() -> Vec<Arc<Box<dyn BlockchainClient>>> {
-    vec![Arc::new(Box::new(HederaBlockchain::from("4991716")))]
+        assert_eq!(
+            clients.iter().any(|client| client.get_label() == "file"),
+            true
+        );
+    }
 }
@@ -0,0 +1,109 @@
+use crate::packages::package::Package;
+
+use super::errors::blockchain_error::BlockchainError;
+
+/**
+ * Header marking payload as raw RLP
+ */
+pub const PAYLOAD_UNCOMPRESSED: u8 = 0x00;
+
+/**
+ * Header marking payload as zstd compressed RLP
+ */
+pub const PAYLOAD_ZSTD: u8 = 0x01;
+
+const ZSTD_LEVEL: i32 = 19;
+
+// Decompressed payloads above this size are refused, packages are much smaller
+const MAX_DECOMPRESSED_SIZE: usize = 64 * 1024;
+
+/**
+ * Prefix encoded package with header, compressing it only when it makes payload smaller
+ */
+pub fn encode_payload(encoded_package: &[u8]) -> Vec<u8> {
+    let compressed = zstd::bulk::compress(encoded_package, ZSTD_LEVEL)
+        .ok()
+        .filter(|compressed| compressed.len() < encoded_package.len());
+
+    let (header, body) = match &compressed {
+        Some(compressed) => (PAYLOAD_ZSTD, compressed.as_slice()),
+        None => (PAYLOAD_UNCOMPRESSED, encoded_package),
+    };
+
+    let mut payload = Vec::with_capacity(body.len() + 1);
+
+    payload.push(header);
+    payload.extend_from_slice(body);
+
+    payload
+}
+
+/**
+ * Get encoded package back from payload
+ *
+ * Messages published before headers were introduced are raw RLP lists, whose first byte is never a header
+ */
+pub fn decode_payload(payload: &[u8]) -> Result<Vec<u8>, BlockchainError> {
+    match payload.split_first() {
+        Some((&PAYLOAD_UNCOMPRESSED, body)) => Ok(body.to_vec()),
+        Some((&PAYLOAD_ZSTD, body)) => zstd::bulk::decompress(body, MAX_DECOMPRESSED_SIZE)
+            .map_err(|_| BlockchainError::UndecodablePackage),
+        _ => Ok(payload.to_vec()),
+    }
+}
+
+/**
+ * Decode package from payload
+ */
+pub fn decode_package(payload: &[u8]) -> Result<Package, BlockchainError> {
+    Package::from_rlp(&decode_payload(payload)?).map_err(|_| BlockchainError::UndecodablePackage)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::package::tests::create_package_with_sig;
+
+    use super::*;
+
+    /**
+     * It should decode compressed, uncompressed and legacy payloads to same package
+     */
+    #[test]
+    fn test_payload_roundtrip() -> Result<(), Box<dyn std::error::Error>> {
+        let package = create_package_with_sig()?;
+
+        let encoded_package = package.to_rlp()?;
+
+        let mut compressed_payload = vec![PAYLOAD_ZSTD];
+        compressed_payload.extend(zstd::bulk::compress(&encoded_package, ZSTD_LEVEL)?);
+
+        let mut uncompressed_payload = vec![PAYLOAD_UNCOMPRESSED];
+        uncompressed_payload.extend_from_slice(&encoded_package);
+
+        let payloads = [
+            encode_payload(&encoded_package),
+            compressed_payload,
+            uncompressed_payload,
+            encoded_package,
+        ];
+
+        for payload in payloads {
+            let decoded_package = decode_package(&payload)?;
+
+            assert_eq!(decoded_package, package);
+        }
+
+        Ok(())
+    }
+
+    /**
+     * It should refuse corrupted compressed payload
+     */
+    #[test]
+    fn test_decode_corrupted_payload() {
+        assert_eq!(
+            decode_payload(&[PAYLOAD_ZSTD, 0xde, 0xad]).unwrap_err(),
+            BlockchainError::UndecodablePackage
+        );
+    }
+}
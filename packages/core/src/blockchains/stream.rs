@@ -0,0 +1,297 @@
+use std::time::Duration;
+
+use futures_util::{Stream, StreamExt};
+use log::trace;
+use tokio::sync::mpsc::Sender;
+
+use super::{errors::blockchain_error::BlockchainError, message::BlockchainMessage};
+
+/**
+ * Wait for next stream item, None when stream ends or stays silent longer than given timeout
+ *
+ * No timeout means waiting indefinitely until stream end
+ */
+pub async fn next_before<S>(
+    stream: &mut S,
+    next_message_timeout: Option<Duration>,
+) -> Option<S::Item>
+where
+    S: Stream + Unpin,
+{
+    match next_message_timeout {
+        Some(next_message_timeout) => tokio::time::timeout(next_message_timeout, stream.next())
+            .await
+            .ok()
+            .flatten(),
+        None => stream.next().await,
+    }
+}
+
+/**
+ * Forward messages from stream until it ends or stays silent longer than given timeout
+ *
+ * No timeout means forwarding until stream end, stream error is forwarded then ends forwarding
+ *
+ * Returns how many messages were forwarded
+ */
+pub async fn forward_until_silent<S>(
+    mut stream: S,
+    next_message_timeout: Option<Duration>,
+    tx_data: &Sender<Result<BlockchainMessage, BlockchainError>>,
+) -> usize
+where
+    S: Stream<Item = Result<BlockchainMessage, BlockchainError>> + Unpin,
+{
+    let mut forwarded_count = 0;
+
+    while let Some(message_res) = next_before(&mut stream, next_message_timeout).await {
+        let message = match message_res {
+            Ok(message) => message,
+            Err(e) => {
+                let _ = tx_data.send(Err(e)).await;
+                break;
+            }
+        };
+
+        trace!("Sending to channel...");
+
+        if tx_data.send(Ok(message)).await.is_err() {
+            break;
+        }
+
+        forwarded_count += 1;
+
+        trace!("Done sending to channel !");
+    }
+
+    forwarded_count
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use futures_util::stream;
+    use tokio::sync::mpsc;
+
+    use crate::{
+        blockchains::{
+            blockchain::{BlockchainClient, BlockchainIO, MockBlockchainIO},
+            errors::blockchain_error::BlockchainError,
+        },
+        test_utils::{
+            blockchain::tests::TestBlockchainClient, package::tests::create_package_with_sig,
+        },
+    };
+
+    use super::*;
+
+    const NEXT_MESSAGE_TIMEOUT: Option<Duration> = Some(Duration::from_secs(1));
+
+    /**
+     * Build stream sending given messages slowly then staying silent forever
+     */
+    fn slow_then_silent_stream(
+        messages: Vec<Vec<u8>>,
+        delay: Duration,
+    ) -> impl Stream<Item = Result<BlockchainMessage, BlockchainError>> + Unpin {
+        Box::pin(
+            stream::iter(messages)
+                .then(move |message| async move {
+                    tokio::time::sleep(delay).await;
+                    Ok(BlockchainMessage::from(message))
+                })
+                .chain(stream::pending()),
+        )
+    }
+
+    /**
+     * It should forward slow messages then stop once stream stays silent
+     */
+    #[tokio::test(start_paused = true)]
+    async fn test_forward_until_silent() {
+        let (tx_data, mut rx_data) = mpsc::channel(10);
+
+        let messages = vec![Vec::from("foo"), Vec::from("bar")];
+
+        let stream = slow_then_silent_stream(messages.clone(), Duration::from_millis(500));
+
+        let forwarded_count = forward_until_silent(stream, NEXT_MESSAGE_TIMEOUT, &tx_data).await;
+
+        drop(tx_data);
+
+        let mut received_messages = vec![];
+
+        while let Some(message) = rx_data.recv().await {
+            received_messages.push(message.unwrap().payload);
+        }
+
+        assert_eq!(forwarded_count, messages.len());
+        assert_eq!(received_messages, messages);
+    }
+
+    /**
+     * It should drop messages arriving after timeout
+     */
+    #[tokio::test(start_paused = true)]
+    async fn test_forward_until_silent_drops_late_messages() {
+        let (tx_data, _rx_data) = mpsc::channel(10);
+
+        let stream = slow_then_silent_stream(vec![Vec::from("foo")], Duration::from_secs(2));
+
+        let forwarded_count = forward_until_silent(stream, NEXT_MESSAGE_TIMEOUT, &tx_data).await;
+
+        assert_eq!(forwarded_count, 0);
+    }
+
+    /**
+     * It should keep forwarding slow messages as long as they arrive within custom timeout
+     */
+    #[tokio::test(start_paused = true)]
+    async fn test_forward_until_silent_custom_timeout() {
+        let (tx_data, _rx_data) = mpsc::channel(10);
+
+        let messages = vec![Vec::from("foo"), Vec::from("bar")];
+
+        let stream = slow_then_silent_stream(messages.clone(), Duration::from_secs(20));
+
+        let forwarded_count =
+            forward_until_silent(stream, Some(Duration::from_secs(30)), &tx_data).await;
+
+        assert_eq!(forwarded_count, messages.len());
+    }
+
+    /**
+     * It should wait for every message until stream end when there is no timeout
+     */
+    #[tokio::test(start_paused = true)]
+    async fn test_forward_until_silent_without_timeout() {
+        let (tx_data, _rx_data) = mpsc::channel(10);
+
+        let messages = vec![Vec::from("foo"), Vec::from("bar")];
+
+        // Stream ends after last message instead of staying silent
+        let stream = Box::pin(stream::iter(messages.clone()).then(|message| async move {
+            tokio::time::sleep(Duration::from_secs(3600)).await;
+            Ok(BlockchainMessage::from(message))
+        }));
+
+        let forwarded_count = forward_until_silent(stream, None, &tx_data).await;
+
+        assert_eq!(forwarded_count, messages.len());
+    }
+
+    /**
+     * It should persist last sync once stream stays silent
+     */
+    #[tokio::test(start_paused = true)]
+    async fn test_read_packages_persists_last_sync() {
+        let expected_last_sync = 1704067200;
+
+        let package = create_package_with_sig().unwrap();
+        let encoded_package = rlp::encode(&package).to_vec();
+
+        let mut io_mock = MockBlockchainIO::default();
+
+        io_mock.expect_read().returning(move |tx_data, _, _| {
+            let tx = tx_data.clone();
+            let stream =
+                slow_then_silent_stream(vec![encoded_package.clone()], Duration::from_millis(500));
+
+            Box::pin(async move {
+                forward_until_silent(stream, NEXT_MESSAGE_TIMEOUT, &tx).await;
+            })
+        });
+
+        let io: Arc<Box<dyn BlockchainIO>> = Arc::new(Box::new(io_mock));
+
+        let blockchain_client = TestBlockchainClient::new(io, expected_last_sync);
+
+        let (tx_packages, mut rx_packages) = mpsc::channel::<Result<_, BlockchainError>>(10);
+
+        blockchain_client.read_packages(&tx_packages).await.unwrap();
+
+        drop(tx_packages);
+
+        assert_eq!(rx_packages.recv().await.unwrap().unwrap(), package);
+        assert_eq!(blockchain_client.get_last_sync().await, expected_last_sync);
+    }
+
+    /**
+     * It should report stream error after forwarding good messages, without persisting last sync
+     */
+    #[tokio::test(start_paused = true)]
+    async fn test_read_packages_stream_error() {
+        let now = 1704067200;
+
+        let package = create_package_with_sig().unwrap();
+        let encoded_package = rlp::encode(&package).to_vec();
+
+        let mut io_mock = MockBlockchainIO::default();
+
+        io_mock.expect_read().returning(move |tx_data, _, _| {
+            let tx = tx_data.clone();
+
+            // One good message then transport error
+            let stream = stream::iter(vec![
+                Ok(BlockchainMessage::from(encoded_package.clone())),
+                Err(BlockchainError::StreamInterrupted(String::from("reset"))),
+            ]);
+
+            Box::pin(async move {
+                forward_until_silent(stream, NEXT_MESSAGE_TIMEOUT, &tx).await;
+            })
+        });
+
+        let io: Arc<Box<dyn BlockchainIO>> = Arc::new(Box::new(io_mock));
+
+        let blockchain_client = TestBlockchainClient::new(io, now);
+
+        let (tx_packages, mut rx_packages) = mpsc::channel::<Result<_, BlockchainError>>(10);
+
+        let read_result = blockchain_client.read_packages(&tx_packages).await;
+
+        drop(tx_packages);
+
+        assert_eq!(
+            read_result,
+            Err(BlockchainError::StreamInterrupted(String::from("reset")))
+        );
+        assert_eq!(rx_packages.recv().await.unwrap().unwrap(), package);
+        assert_eq!(blockchain_client.get_last_sync().await, 0);
+    }
+
+    /**
+     * It should read from start up to given timestamp, leaving last sync untouched
+     */
+    #[tokio::test(start_paused = true)]
+    async fn test_read_packages_as_of_keeps_last_sync() {
+        let now = 1704067200;
+        let last_sync = 1702000000;
+        let as_of = 1700000000;
+
+        let mut io_mock = MockBlockchainIO::default();
+
+        io_mock
+            .expect_read()
+            .withf(move |_, start_timestamp, end_timestamp| {
+                *start_timestamp == 0 && *end_timestamp == Some(as_of)
+            })
+            .returning(|_, _, _| Box::pin(async {}));
+
+        let io: Arc<Box<dyn BlockchainIO>> = Arc::new(Box::new(io_mock));
+
+        let blockchain_client = TestBlockchainClient::new(io, now);
+
+        blockchain_client.set_last_sync(last_sync).await;
+
+        let (tx_packages, _rx_packages) = mpsc::channel::<Result<_, BlockchainError>>(10);
+
+        blockchain_client
+            .read_packages_as_of(&tx_packages, as_of)
+            .await
+            .unwrap();
+
+        assert_eq!(blockchain_client.get_last_sync().await, last_sync);
+    }
+}
@@ -0,0 +1,24 @@
+use thiserror::Error;
+
+use crate::{
+    blockchains::errors::blockchain_error::BlockchainError,
+    db::errors::repository_error::RepositoryError,
+};
+
+/**
+ * Errors preventing BPM instance from being built
+ */
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum BpmError {
+    #[error(transparent)]
+    Db(#[from] RepositoryError),
+
+    #[error(transparent)]
+    Blockchain(#[from] BlockchainError),
+
+    #[error("Config file could not be read: {0}")]
+    Config(String),
+
+    #[error("Download client could not be built: {0}")]
+    HttpClient(String),
+}
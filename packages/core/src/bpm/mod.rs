@@ -0,0 +1,365 @@
+pub mod errors;
+
+use std::{path::PathBuf, sync::Arc};
+
+use log::{debug, error};
+use tokio::sync::mpsc::Sender;
+
+use crate::{
+    blockchains::{errors::blockchain_error::BlockchainError, get_available_clients},
+    bpm::errors::bpm_error::BpmError,
+    config::{init_config, manager::ConfigManager},
+    db::client::DbClient,
+    package_managers::{
+        errors::package_manager_error::PackageManagerError, init_http_client, init_package_managers,
+    },
+    packages::{package::Package, package_status::PackageStatus},
+    services::{
+        blockchains::{BlockchainsService, UpdateTally},
+        db::{
+            blockchains_repository::BlockchainsRepository, packages_repository::PackagesRepository,
+        },
+        package_managers::PackageManagersService,
+        packages::{PackagesPage, PackagesService},
+    },
+};
+
+/**
+ * BPM facade
+ *
+ * Builds and owns every service so embedders ( CLI, JS bindings... ) get a one-call setup
+ */
+pub struct Bpm {
+    config_manager: ConfigManager,
+    db_client: Arc<DbClient>,
+    packages_service: Arc<PackagesService>,
+    blockchains_service: Arc<BlockchainsService>,
+    package_managers_service: Arc<PackageManagersService>,
+}
+
+impl Bpm {
+    /**
+     * Create BPM instance using given config location
+     */
+    pub async fn new(config_dir: &PathBuf) -> Result<Self, BpmError> {
+        let config_manager = init_config(config_dir);
+
+        let db_client = Arc::new(DbClient::try_from(&config_manager.get_db_path())?);
+
+        Self::from_db_client(config_manager, db_client).await
+    }
+
+    /**
+     * Create BPM instance using given config location, packages being cached in a throwaway DB instead
+     */
+    pub async fn in_memory(config_dir: &PathBuf) -> Result<Self, BpmError> {
+        let config_manager = init_config(config_dir);
+
+        let db_client = Arc::new(DbClient::in_memory());
+
+        Self::from_db_client(config_manager, db_client).await
+    }
+
+    /**
+     * Build every service on top of given DB
+     */
+    async fn from_db_client(
+        config_manager: ConfigManager,
+        db_client: Arc<DbClient>,
+    ) -> Result<Self, BpmError> {
+        debug!("Building BPM instance...");
+
+        if let Err(e) = db_client.ensure_indexes().await {
+            error!("{}, lookups will be slower", e);
+        }
+
+        // Config file is created along with config directory, failing to read it must not silently reset settings
+        let core_config = config_manager
+            .read_config()
+            .map_err(|e| BpmError::Config(e.to_string()))?;
+
+        // Blockchains clients, invalid topic must not silently read another registry
+        let available_blockchains = get_available_clients(&core_config)?;
+
+        // Package managers, invalid proxy must not silently download through direct connection
+
+        let http_client =
+            init_http_client(&core_config).map_err(|e| BpmError::HttpClient(e.to_string()))?;
+
+        let available_package_managers = init_package_managers(&core_config, &http_client).await;
+
+        // Repositories
+        let blockchains_repository = Arc::new(BlockchainsRepository::from(&db_client));
+        let packages_repository = Arc::new(PackagesRepository::from(&db_client));
+
+        // Services
+        let mut package_managers_service = PackageManagersService::new(&available_package_managers);
+
+        package_managers_service.set_http_client(&http_client);
+
+        if let Some(url_variables) = &core_config.url_variables {
+            package_managers_service.set_url_variables(url_variables);
+        }
+
+        let package_managers_service = Arc::new(package_managers_service);
+
+        let packages_service = Arc::new(PackagesService::from(&packages_repository));
+
+        let blockchains_service = Arc::new(
+            BlockchainsService::new(
+                &available_blockchains,
+                &blockchains_repository,
+                &packages_service,
+            )
+            .await,
+        );
+
+        if let Some(update_concurrency) = core_config.update_concurrency {
+            blockchains_service
+                .set_update_concurrency(update_concurrency)
+                .await;
+        }
+
+        debug!("Done building BPM instance !");
+
+        Ok(Self {
+            config_manager,
+            db_client,
+            packages_service,
+            blockchains_service,
+            package_managers_service,
+        })
+    }
+
+    /**
+     * Get config manager
+     */
+    pub fn get_config_manager(&self) -> &ConfigManager {
+        &self.config_manager
+    }
+
+    /**
+     * Get mutable config manager
+     */
+    pub fn get_config_manager_mut(&mut self) -> &mut ConfigManager {
+        &mut self.config_manager
+    }
+
+    /**
+     * Get DB client
+     */
+    pub fn get_db_client(&self) -> Arc<DbClient> {
+        Arc::clone(&self.db_client)
+    }
+
+    /**
+     * Get packages service
+     */
+    pub fn get_packages_service(&self) -> Arc<PackagesService> {
+        Arc::clone(&self.packages_service)
+    }
+
+    /**
+     * Get blockchains service
+     */
+    pub fn get_blockchains_service(&self) -> Arc<BlockchainsService> {
+        Arc::clone(&self.blockchains_service)
+    }
+
+    /**
+     * Get package managers service
+     */
+    pub fn get_package_managers_service(&self) -> Arc<PackageManagersService> {
+        Arc::clone(&self.package_managers_service)
+    }
+
+    /**
+     * Find package in selected blockchain, leaving out ones below given status if any
+     */
+    pub async fn find_package(
+        &self,
+        package_name: &String,
+        package_version: &String,
+        min_status: &Option<PackageStatus>,
+    ) -> Vec<Package> {
+        self.blockchains_service
+            .find_package(package_name, package_version, min_status)
+            .await
+    }
+
+    /**
+     * List one page of cached packages whose name contains query, leaving out ones below given status if any
+     */
+    pub async fn list_packages(
+        &self,
+        query: &Option<String>,
+        min_status: &Option<PackageStatus>,
+        offset: u64,
+        limit: u64,
+    ) -> PackagesPage {
+        self.packages_service
+            .get_page(query, min_status, offset, limit)
+            .await
+    }
+
+    /**
+     * Get known versions of package in selected blockchain
+     */
+    pub async fn get_versions(&self, package_name: &String) -> Vec<String> {
+        self.blockchains_service.get_versions(package_name).await
+    }
+
+    /**
+     * Submit package signed elsewhere to selected blockchain, refusing it unless correctly signed
+     */
+    pub async fn submit_signed(&self, package: &Package) -> Result<(), BlockchainError> {
+        self.blockchains_service.submit_signed(package).await
+    }
+
+    /**
+     * Submit packages signed elsewhere to selected blockchain at once, reporting each refused package
+     */
+    pub async fn submit_packages(&self, packages: &[Package]) -> Vec<Result<(), BlockchainError>> {
+        self.blockchains_service.submit_packages(packages).await
+    }
+
+    /**
+     * Synchronize local DB with selected blockchain
+     */
+    pub async fn sync(
+        &self,
+        tx_packages_update: &Sender<Package>,
+    ) -> Result<UpdateTally, BlockchainError> {
+        self.blockchains_service.update(tx_packages_update).await
+    }
+
+    /**
+     * Install package using selected package manager
+     */
+    pub async fn install(&self, package: &Package) -> Result<PathBuf, PackageManagerError> {
+        // Packages only carry a single archive url for now
+        let archive_urls = vec![package.archive_url.clone()];
+
+        self.package_managers_service
+            .install_from_mirrors(package, &archive_urls)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use crate::config::core_config::CoreConfig;
+
+    use super::*;
+
+    /**
+     * It should build every service from config directory
+     */
+    #[tokio::test]
+    async fn test_bpm_new() {
+        let test_dir = TempDir::new().unwrap();
+
+        let test_dir_path = test_dir.path().to_path_buf();
+
+        let bpm = Bpm::new(&test_dir_path).await.unwrap();
+
+        let expected_config_path = init_config(&test_dir_path).get_path();
+
+        assert_eq!(bpm.get_config_manager().get_path(), expected_config_path);
+
+        #[cfg(feature = "hedera")]
+        let clients = bpm.get_blockchains_service().get_clients();
+
+        #[cfg(feature = "hedera")]
+        assert_eq!(clients.lock().await.is_empty(), false);
+    }
+
+    /**
+     * It should cache packages in a throwaway DB
+     */
+    #[tokio::test]
+    async fn test_bpm_in_memory() {
+        let test_dir = TempDir::new().unwrap();
+
+        let test_dir_path = test_dir.path().to_path_buf();
+
+        let bpm = Bpm::in_memory(&test_dir_path).await.unwrap();
+
+        assert_eq!(bpm.db_client.is_in_memory(), true);
+        assert_eq!(bpm.get_packages_service().get_all().await.is_empty(), true);
+    }
+
+    /**
+     * It should refuse invalid configured proxy instead of downloading without it
+     */
+    #[tokio::test]
+    async fn test_bpm_invalid_proxy() {
+        let test_dir = TempDir::new().unwrap();
+
+        let test_dir_path = test_dir.path().to_path_buf();
+
+        init_config(&test_dir_path)
+            .write_config(&CoreConfig {
+                proxy: Some(String::from("not a proxy")),
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert_eq!(
+            matches!(
+                Bpm::in_memory(&test_dir_path).await.err(),
+                Some(BpmError::HttpClient(_))
+            ),
+            true
+        );
+    }
+
+    /**
+     * It should refuse unreadable config file instead of using default settings
+     */
+    #[tokio::test]
+    async fn test_bpm_invalid_config() {
+        let test_dir = TempDir::new().unwrap();
+
+        let test_dir_path = test_dir.path().to_path_buf();
+
+        let config_path = init_config(&test_dir_path).get_path();
+
+        std::fs::write(config_path.join("config.json"), "{ not json").unwrap();
+
+        assert_eq!(
+            matches!(
+                Bpm::in_memory(&test_dir_path).await.err(),
+                Some(BpmError::Config(_))
+            ),
+            true
+        );
+    }
+
+    /**
+     * It should refuse invalid configured topic instead of reading default one
+     */
+    #[tokio::test]
+    #[cfg(feature = "hedera")]
+    async fn test_bpm_invalid_topic() {
+        let test_dir = TempDir::new().unwrap();
+
+        let test_dir_path = test_dir.path().to_path_buf();
+
+        init_config(&test_dir_path)
+            .write_config(&CoreConfig {
+                hedera_topic: Some(String::from("not-a-topic")),
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert_eq!(
+            Bpm::in_memory(&test_dir_path).await.err(),
+            Some(BpmError::Blockchain(BlockchainError::InvalidTopic(
+                String::from("not-a-topic")
+            )))
+        );
+    }
+}
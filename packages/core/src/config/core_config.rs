@@ -1,8 +1,20 @@
+use std::collections::HashMap;
+
+use crate::package_managers::pacman::pacman_config::PacmanConfig;
+
 /**
  * Represents application's configuration
  */
 #[serde_with::skip_serializing_none]
-#[derive(serde::Serialize, serde::Deserialize)]
+#[derive(serde::Serialize, serde::Deserialize, Default)]
 pub struct CoreConfig {
-    pub proxy: Option<String>,
+    pub proxy: Option<String>, // Proxy url used for archive downloads, e.g. http://127.0.0.1:3128
+    pub offline: Option<bool>, // Skip blockchain synchronization by default
+    pub cache_max_age: Option<u64>, // Skip blockchain synchronization when last one is more recent, in seconds
+    pub pacman: Option<PacmanConfig>,
+    pub url_variables: Option<HashMap<String, String>>, // Values of archive url placeholders, environment takes precedence
+    pub selected_blockchain: Option<String>, // Label of blockchain used by default, saved on first selection
+    pub hedera_topic: Option<String>,        // HCS topic packages are read from, eg: 0.0.4991716
+    pub file_blockchain: Option<String>, // Path of local log offered as another blockchain, eg: private registry
+    pub update_concurrency: Option<usize>, // How many packages are persisted in parallel while updating
 }
@@ -0,0 +1,13 @@
+use thiserror::Error;
+
+/**
+ * Process lock errors
+ */
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum LockError {
+    #[error("Another BPM process is running, try again once it is done")]
+    AlreadyLocked,
+
+    #[error("Could not open lock file: {0}")]
+    LockFileError(String),
+}
@@ -0,0 +1,103 @@
+use std::{
+    fs::{File, OpenOptions},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use fs2::FileExt;
+use log::debug;
+
+use super::errors::lock_error::LockError;
+
+const LOCK_FILENAME: &str = "bpm.lock";
+
+// Delay between two attempts while waiting for lock
+const LOCK_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/**
+ * Advisory inter-process lock held in config directory, released when dropped
+ *
+ * Lock is taken by OS on an open file, so it is released even if process crashes
+ */
+#[derive(Debug)]
+pub struct ProcessLock {
+    file: File,
+}
+
+impl ProcessLock {
+    /**
+     * Acquire lock, failing right away if another process holds it
+     */
+    pub fn try_acquire(config_dir: &Path) -> Result<Self, LockError> {
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .open(config_dir.join(LOCK_FILENAME))
+            .map_err(|e| LockError::LockFileError(e.to_string()))?;
+
+        file.try_lock_exclusive()
+            .map_err(|_| LockError::AlreadyLocked)?;
+
+        debug!("Acquired process lock");
+
+        Ok(Self { file })
+    }
+
+    /**
+     * Acquire lock, waiting up to timeout for another process to release it
+     */
+    pub async fn acquire(config_dir: &Path, timeout: Duration) -> Result<Self, LockError> {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            match Self::try_acquire(config_dir) {
+                Err(LockError::AlreadyLocked) if Instant::now() < deadline => {
+                    debug!("Waiting for another BPM process to release lock...");
+
+                    tokio::time::sleep(LOCK_POLL_INTERVAL).await;
+                }
+                lock_result => return lock_result,
+            }
+        }
+    }
+}
+
+impl Drop for ProcessLock {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+
+        debug!("Released process lock");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    /**
+     * It should refuse second lock until first one is dropped
+     */
+    #[tokio::test]
+    async fn test_process_lock() {
+        let test_dir = TempDir::new().unwrap();
+
+        let first_lock = ProcessLock::try_acquire(test_dir.path()).unwrap();
+
+        assert_eq!(
+            ProcessLock::try_acquire(test_dir.path()).unwrap_err(),
+            LockError::AlreadyLocked
+        );
+        assert_eq!(
+            ProcessLock::acquire(test_dir.path(), Duration::from_millis(200))
+                .await
+                .unwrap_err(),
+            LockError::AlreadyLocked
+        );
+
+        drop(first_lock);
+
+        assert_eq!(ProcessLock::try_acquire(test_dir.path()).is_ok(), true);
+    }
+}
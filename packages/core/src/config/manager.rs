@@ -3,6 +3,7 @@ use std::{
     io::{BufWriter, Error as IOError, Write},
     os::unix::fs::PermissionsExt,
     path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 use ed25519::{
@@ -12,9 +13,23 @@ use ed25519::{
 use ed25519_dalek::{SigningKey, VerifyingKey};
 use log::debug;
 
-use super::core_config::CoreConfig;
+use crate::packages::utils::signer::Signer;
 
-const DEFAULT_CONFIG: CoreConfig = CoreConfig { proxy: None };
+use super::{core_config::CoreConfig, errors::lock_error::LockError, lock::ProcessLock};
+
+const DEFAULT_CONFIG: CoreConfig = CoreConfig {
+    proxy: None,
+    offline: None,
+    cache_max_age: None,
+    pacman: None,
+    url_variables: None,
+    selected_blockchain: None,
+    hedera_topic: None,
+    file_blockchain: None,
+    update_concurrency: None,
+};
+
+const CONFIG_FILENAME: &str = "config.json";
 
 const PRIVATE_KEY_FILENAME: &str = "key.pem";
 
@@ -89,12 +104,12 @@ impl ConfigManager {
     /**
      * Write key file
      */
-    fn write_key_file(key_path: &PathBuf) -> Result<File, Box<dyn std::error::Error>> {
+    fn write_key_file(
+        key_path: &PathBuf,
+        maintainer_signing_key: &SigningKey,
+    ) -> Result<File, Box<dyn std::error::Error>> {
         debug!("Writing key file...");
 
-        // Generate new key
-        let maintainer_signing_key = ConfigManager::generate_key();
-
         let encoded_private_key = maintainer_signing_key.to_pkcs8_pem(LineEnding::LF)?;
 
         let mut key_file = File::create(&key_path)?;
@@ -141,13 +156,16 @@ impl ConfigManager {
 
         if config_exists == false {
             debug!("Creating default config file...");
-            let config_file_path = directory_path.join("config.json");
+            let config_file_path = directory_path.join(CONFIG_FILENAME);
 
             ConfigManager::create_config_file(&config_file_path)?;
             debug!("Done creating default config file !");
 
+            // Generate new key
+            let maintainer_signing_key = ConfigManager::generate_key();
+
             let key_path = directory_path.join(PRIVATE_KEY_FILENAME);
-            ConfigManager::write_key_file(&key_path)?;
+            ConfigManager::write_key_file(&key_path, &maintainer_signing_key)?;
 
             debug!("Done initializing config directory !");
         }
@@ -162,6 +180,73 @@ impl ConfigManager {
         self.path.clone()
     }
 
+    /**
+     * Read config file
+     */
+    pub fn read_config(&self) -> Result<CoreConfig, Box<dyn std::error::Error>> {
+        debug!("Reading config file...");
+
+        let config_file = File::open(self.path.join(CONFIG_FILENAME))?;
+
+        let config: CoreConfig = serde_json::from_reader(config_file)?;
+
+        debug!("Done reading config file !");
+
+        Ok(config)
+    }
+
+    /**
+     * Write config file, replacing previous one at once so a crash cannot leave it half written
+     */
+    pub fn write_config(&self, config: &CoreConfig) -> Result<(), Box<dyn std::error::Error>> {
+        debug!("Writing config file...");
+
+        let config_file_path = self.path.join(CONFIG_FILENAME);
+        let tmp_config_file_path = self.path.join(format!("{}.tmp", CONFIG_FILENAME));
+
+        let tmp_config_file = File::create(&tmp_config_file_path)?;
+
+        let mut writer = BufWriter::new(&tmp_config_file);
+
+        serde_json::to_writer_pretty(&mut writer, config)?;
+
+        writer.flush()?;
+
+        fs::rename(&tmp_config_file_path, &config_file_path)?;
+
+        debug!("Done writing config file !");
+
+        Ok(())
+    }
+
+    /**
+     * Get label of blockchain saved as default one, if any
+     */
+    pub fn get_selected_blockchain(&self) -> Result<Option<String>, Box<dyn std::error::Error>> {
+        Ok(self.read_config()?.selected_blockchain)
+    }
+
+    /**
+     * Save blockchain used by default, keeping other config values
+     */
+    pub fn set_selected_blockchain(
+        &self,
+        blockchain_label: &String,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut config = self.read_config()?;
+
+        config.selected_blockchain = Some(blockchain_label.clone());
+
+        self.write_config(&config)
+    }
+
+    /**
+     * Acquire inter-process lock on config directory, waiting up to timeout for other BPM processes
+     */
+    pub async fn lock(&self, timeout: Duration) -> Result<ProcessLock, LockError> {
+        ProcessLock::acquire(&self.path, timeout).await
+    }
+
     /**
      * Get DB config path
      */
@@ -169,6 +254,17 @@ impl ConfigManager {
         self.path.join(DB_DIR_NAME)
     }
 
+    /**
+     * Read signing key from PEM file
+     */
+    pub fn read_signing_key(key_path: &PathBuf) -> Result<SigningKey, Box<dyn std::error::Error>> {
+        let key_buf = fs::read_to_string(key_path)?;
+
+        let key = SigningKey::from_pkcs8_pem(key_buf.as_str())?;
+
+        Ok(key)
+    }
+
     /**
      * Retrieve signing key
      */
@@ -177,15 +273,89 @@ impl ConfigManager {
 
         let key_file_path = self.path.join(PRIVATE_KEY_FILENAME);
 
-        let key_buf = fs::read_to_string(key_file_path)?;
-
-        let key = SigningKey::from_pkcs8_pem(key_buf.as_str())?;
+        let key = ConfigManager::read_signing_key(&key_file_path)?;
 
         debug!("Done retrieving signing key !");
 
         Ok(key)
     }
 
+    /**
+     * Retrieve signer backed by maintainer key stored in config directory
+     */
+    pub fn get_signer(&self) -> Result<Box<dyn Signer>, Box<dyn std::error::Error>> {
+        let signing_key = self.get_signing_key()?;
+
+        Ok(Box::new(signing_key))
+    }
+
+    /**
+     * Generate new signing key without persisting it
+     */
+    pub fn new_signing_key(&self) -> SigningKey {
+        ConfigManager::generate_key()
+    }
+
+    /**
+     * Replace current signing key with given one
+     *
+     * Previous key is kept next to the new one, returns backup location
+     */
+    pub fn rotate_signing_key(
+        &self,
+        new_signing_key: &SigningKey,
+    ) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        debug!("Rotating signing key...");
+
+        let key_file_path = self.path.join(PRIVATE_KEY_FILENAME);
+
+        let rotation_timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+        let backup_key_file_path = self.path.join(format!(
+            "{}.{}.old",
+            PRIVATE_KEY_FILENAME, rotation_timestamp
+        ));
+
+        fs::rename(&key_file_path, &backup_key_file_path)?;
+
+        ConfigManager::write_key_file(&key_file_path, new_signing_key)?;
+
+        debug!(
+            "Done rotating signing key ! ( previous key : {} )",
+            backup_key_file_path.display()
+        );
+
+        Ok(backup_key_file_path)
+    }
+
+    /**
+     * Save signing key next to current one without replacing it, returns its location
+     *
+     * Used when a rotation could not be completed, so it can be resumed by importing this key
+     */
+    pub fn save_pending_signing_key(
+        &self,
+        pending_signing_key: &SigningKey,
+    ) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        debug!("Saving pending signing key...");
+
+        let saving_timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+        let pending_key_file_path = self.path.join(format!(
+            "{}.{}.pending",
+            PRIVATE_KEY_FILENAME, saving_timestamp
+        ));
+
+        ConfigManager::write_key_file(&pending_key_file_path, pending_signing_key)?;
+
+        debug!(
+            "Done saving pending signing key ! ( {} )",
+            pending_key_file_path.display()
+        );
+
+        Ok(pending_key_file_path)
+    }
+
     /**
      * Retrieve verifying key
      */
@@ -305,6 +475,25 @@ mod tests {
         assert_eq!(config_manager.get_path(), *expected_config_file_path);
     }
 
+    /**
+     * It should read default config
+     */
+    #[test]
+    fn test_read_config() -> Result<(), Box<dyn std::error::Error>> {
+        let test_dir = TempDir::new().unwrap();
+
+        let config_path = &test_dir.into_path();
+
+        let config_manager = ConfigManager::from(config_path);
+
+        let config = config_manager.read_config()?;
+
+        assert_eq!(config.proxy, None);
+        assert_eq!(config.offline, None);
+
+        Ok(())
+    }
+
     /**
      * It should get db path
      */
@@ -337,6 +526,24 @@ mod tests {
         Ok(())
     }
 
+    /**
+     * It should get signer backed by maintainer key
+     */
+    #[test]
+    fn test_get_signer() -> Result<(), Box<dyn std::error::Error>> {
+        let test_dir = TempDir::new().unwrap();
+
+        let expected_config_file_path = &test_dir.into_path().join("config.json");
+
+        let config_manager = ConfigManager::from(expected_config_file_path);
+
+        let signer = config_manager.get_signer()?;
+
+        assert_eq!(signer.public_key(), config_manager.get_verifying_key()?);
+
+        Ok(())
+    }
+
     /**
      * It should get verifying key
      */
@@ -352,4 +559,90 @@ mod tests {
 
         Ok(())
     }
+
+    /**
+     * It should rotate signing key and keep previous one as backup
+     */
+    #[test]
+    fn test_rotate_signing_key() -> Result<(), Box<dyn std::error::Error>> {
+        let test_dir = TempDir::new().unwrap();
+
+        let config_path = &test_dir.into_path();
+
+        let config_manager = ConfigManager::from(config_path);
+
+        let previous_verifying_key = config_manager.get_verifying_key()?;
+
+        let new_signing_key = config_manager.new_signing_key();
+
+        let backup_key_path = config_manager.rotate_signing_key(&new_signing_key)?;
+
+        assert_eq!(
+            config_manager.get_verifying_key()?,
+            new_signing_key.verifying_key()
+        );
+
+        let backup_key = ConfigManager::read_signing_key(&backup_key_path)?;
+
+        assert_eq!(backup_key.verifying_key(), previous_verifying_key);
+
+        Ok(())
+    }
+
+    /**
+     * It should save pending signing key while keeping current one
+     */
+    #[test]
+    fn test_save_pending_signing_key() -> Result<(), Box<dyn std::error::Error>> {
+        let test_dir = TempDir::new().unwrap();
+
+        let config_path = &test_dir.into_path();
+
+        let config_manager = ConfigManager::from(config_path);
+
+        let current_verifying_key = config_manager.get_verifying_key()?;
+
+        let pending_signing_key = config_manager.new_signing_key();
+
+        let pending_key_path = config_manager.save_pending_signing_key(&pending_signing_key)?;
+
+        assert_eq!(config_manager.get_verifying_key()?, current_verifying_key);
+
+        let pending_key = ConfigManager::read_signing_key(&pending_key_path)?;
+
+        assert_eq!(
+            pending_key.verifying_key(),
+            pending_signing_key.verifying_key()
+        );
+
+        Ok(())
+    }
+
+    /**
+     * It should save selected blockchain without losing other config values
+     */
+    #[test]
+    fn test_selected_blockchain() -> Result<(), Box<dyn std::error::Error>> {
+        let test_dir = TempDir::new().unwrap();
+
+        let config_path = &test_dir.into_path();
+
+        let config_manager = ConfigManager::from(config_path);
+
+        assert_eq!(config_manager.get_selected_blockchain()?, None);
+
+        let mut config = config_manager.read_config()?;
+        config.offline = Some(true);
+        config_manager.write_config(&config)?;
+
+        config_manager.set_selected_blockchain(&"hedera".to_string())?;
+
+        assert_eq!(
+            config_manager.get_selected_blockchain()?,
+            Some("hedera".to_string())
+        );
+        assert_eq!(config_manager.read_config()?.offline, Some(true));
+
+        Ok(())
+    }
 }
@@ -1,4 +1,6 @@
 pub mod core_config;
+pub mod errors;
+pub mod lock;
 pub mod manager;
 
 use std::path::PathBuf;
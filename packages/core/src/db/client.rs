@@ -1,28 +1,72 @@
 use std::{path::PathBuf, sync::Arc};
 
-use polodb_core::{Collection, Database};
+use log::{debug, error};
+use polodb_core::{bson::doc, Collection, CollectionT, Database, IndexModel, IndexOptions};
+use tempfile::TempDir;
 use tokio::sync::Mutex;
 
-use super::documents::{
-    blockchain_document::BlockchainDocument, package_document::PackageDocument,
+use super::{
+    documents::{blockchain_document::BlockchainDocument, package_document::PackageDocument},
+    errors::repository_error::RepositoryError,
+    migrations::migrate,
 };
 
+pub const PACKAGES_COLLECTION_NAME: &str = "packages";
+
+pub const BLOCKCHAINS_COLLECTION_NAME: &str = "blockchains";
+
+/**
+ * Upgrade stored documents once DB is opened, so they can be read by current version
+ */
+fn open_migrated(db: Database) -> Database {
+    if let Err(e) = migrate(&db) {
+        error!("Could not migrate DB : {}", e);
+    }
+
+    db
+}
+
 pub struct DbClient {
     instance: Arc<Mutex<Database>>,
+    temp_dir: Option<TempDir>, // Kept alive so throwaway DB is only removed when client is dropped
 }
 
 impl DbClient {
+    /**
+     * New throwaway instance
+     *
+     * PoloDB does not provide a memory backend anymore, DB is stored in a temporary directory
+     * removed when client is dropped
+     */
+    pub fn in_memory() -> Self {
+        let temp_dir = TempDir::new().expect("Could not create temporary DB directory");
+
+        let db = open_migrated(Database::open_path(temp_dir.path().join("db")).unwrap());
+
+        let instance = Self {
+            instance: Arc::new(Mutex::new(db)),
+            temp_dir: Some(temp_dir),
+        };
+
+        instance
+    }
+
+    /**
+     * Check if DB is throwaway
+     */
+    pub fn is_in_memory(&self) -> bool {
+        self.temp_dir.is_some()
+    }
+
     /**
      * Get packages collection
      */
     pub async fn get_packages_collection(&self) -> Collection<PackageDocument> {
-        let packages_collection_name: &str = "packages";
-
         let packages_collection = self
             .instance
             .lock()
             .await
-            .collection(packages_collection_name);
+            .collection(PACKAGES_COLLECTION_NAME);
 
         packages_collection
     }
@@ -31,36 +75,73 @@ impl DbClient {
      * Get blockchains collection
      */
     pub async fn get_blockchains_collection(&self) -> Collection<BlockchainDocument> {
-        let blockchains_collection_name: &str = "blockchains";
-
         let blockchains_collection = self
             .instance
             .lock()
             .await
-            .collection(blockchains_collection_name);
+            .collection(BLOCKCHAINS_COLLECTION_NAME);
 
         blockchains_collection
     }
+
+    /**
+     * Create indexes used by repositories lookups, already created indexes are left untouched
+     *
+     * PoloDB only supports single field indexes, packages are indexed by name since every key lookup filters on it
+     */
+    pub async fn ensure_indexes(&self) -> Result<(), RepositoryError> {
+        debug!("Ensuring DB indexes...");
+
+        self.get_packages_collection()
+            .await
+            .create_index(IndexModel {
+                keys: doc! { "name": 1 },
+                options: None,
+            })
+            .map_err(|e| RepositoryError::IndexFailure(e.to_string()))?;
+
+        self.get_blockchains_collection()
+            .await
+            .create_index(IndexModel {
+                keys: doc! { "label": 1 },
+                options: Some(IndexOptions {
+                    unique: Some(true),
+                    ..Default::default()
+                }),
+            })
+            .map_err(|e| RepositoryError::IndexFailure(e.to_string()))?;
+
+        debug!("Done ensuring DB indexes !");
+
+        Ok(())
+    }
 }
 
-impl From<&PathBuf> for DbClient {
+impl TryFrom<&PathBuf> for DbClient {
+    type Error = RepositoryError;
+
     /**
-     * New instance from DB path
+     * New instance from DB path, failing when DB cannot be opened ( eg: already opened by another process )
      */
-    fn from(db_path: &PathBuf) -> Self {
-        let db = Arc::new(Mutex::new(Database::open_path(db_path).unwrap()));
+    fn try_from(db_path: &PathBuf) -> Result<Self, Self::Error> {
+        let db = Database::open_path(db_path)
+            .map_err(|e| RepositoryError::OpenFailure(e.to_string()))?;
 
-        let instance = Self { instance: db };
+        let instance = Self {
+            instance: Arc::new(Mutex::new(open_migrated(db))),
+            temp_dir: None,
+        };
 
-        instance
+        Ok(instance)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use polodb_core::CollectionT;
     use tempfile::TempDir;
 
+    use crate::db::documents::blockchain_document_builder::BlockchainDocumentBuilder;
+
     use super::*;
 
     /**
@@ -74,11 +155,88 @@ mod tests {
 
         let test_dir_path = test_dir.path().join(db_dir);
 
-        let _ = DbClient::from(&test_dir_path);
+        let _ = DbClient::try_from(&test_dir_path).unwrap();
 
         assert_eq!(test_dir_path.exists(), true);
     }
 
+    /**
+     * It should report DB which cannot be opened instead of panicking
+     */
+    #[test]
+    fn test_db_init_failure() {
+        let test_dir = TempDir::new().unwrap();
+
+        // DB cannot be created below a regular file
+        let file_path = test_dir.path().join("file");
+
+        std::fs::write(&file_path, b"").unwrap();
+
+        let db_client = DbClient::try_from(&file_path.join("db"));
+
+        assert_eq!(
+            matches!(db_client, Err(RepositoryError::OpenFailure(_))),
+            true
+        );
+    }
+
+    /**
+     * It should initialize throwaway DB
+     */
+    #[tokio::test]
+    async fn test_db_in_memory() -> Result<(), Box<dyn std::error::Error>> {
+        let client = DbClient::in_memory();
+
+        assert_eq!(client.is_in_memory(), true);
+
+        let collection = client.get_blockchains_collection().await;
+
+        let expected_items_count = 0;
+
+        let items_count = collection.count_documents()?;
+
+        assert_eq!(items_count, expected_items_count);
+
+        Ok(())
+    }
+
+    /**
+     * It should write then read documents using throwaway DB
+     */
+    #[tokio::test]
+    async fn test_db_in_memory_collections() -> Result<(), Box<dyn std::error::Error>> {
+        let client = DbClient::in_memory();
+
+        let collection = client.get_blockchains_collection().await;
+
+        let expected_blockchain_doc = BlockchainDocumentBuilder::default()
+            .set_label(&String::from("hedera"))
+            .set_last_synchronization(&42)
+            .build();
+
+        collection.insert_one(&expected_blockchain_doc)?;
+
+        let actual_blockchain_doc = collection.find_one(doc! { "label": "hedera" })?;
+
+        assert_eq!(actual_blockchain_doc, Some(expected_blockchain_doc));
+        assert_eq!(client.get_packages_collection().await.count_documents()?, 0);
+
+        Ok(())
+    }
+
+    /**
+     * It should ensure indexes as many times as needed
+     */
+    #[tokio::test]
+    async fn test_ensure_indexes() -> Result<(), Box<dyn std::error::Error>> {
+        let client = DbClient::in_memory();
+
+        assert_eq!(client.ensure_indexes().await, Ok(()));
+        assert_eq!(client.ensure_indexes().await, Ok(()));
+
+        Ok(())
+    }
+
     /**
      * It should get packages collection
      */
@@ -90,7 +248,7 @@ mod tests {
 
         let test_dir_path = test_dir.path().join(db_dir);
 
-        let client = DbClient::from(&test_dir_path);
+        let client = DbClient::try_from(&test_dir_path).unwrap();
 
         let collection = client.get_packages_collection().await;
 
@@ -113,7 +271,7 @@ mod tests {
 
         let test_dir_path = test_dir.path().join(db_dir);
 
-        let client = DbClient::from(&test_dir_path);
+        let client = DbClient::try_from(&test_dir_path).unwrap();
 
         let collection = client.get_blockchains_collection().await;
 
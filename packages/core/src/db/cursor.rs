@@ -0,0 +1,36 @@
+use log::error;
+
+/**
+ * Skip documents that could not be read instead of failing whole query
+ */
+pub fn skip_errored_documents<T, E, I>(documents: I) -> impl Iterator<Item = T>
+where
+    I: Iterator<Item = Result<T, E>>,
+    E: std::fmt::Display,
+{
+    documents.filter_map(|document| match document {
+        Ok(document) => Some(document),
+        Err(e) => {
+            error!("Skipping document that could not be read, reason : {}", e);
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /**
+     * It should skip errored documents
+     */
+    #[test]
+    fn test_skip_errored_documents() {
+        let documents: Vec<Result<u8, String>> =
+            vec![Ok(1), Err(String::from("corrupted document")), Ok(2)];
+
+        let readable_documents: Vec<u8> = skip_errored_documents(documents.into_iter()).collect();
+
+        assert_eq!(readable_documents, vec![1, 2]);
+    }
+}
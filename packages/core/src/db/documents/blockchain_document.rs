@@ -1,5 +1,7 @@
 use super::blockchain_document_builder::BlockchainDocumentBuilder;
+use log::warn;
 use polodb_core::bson::{Bson, Document};
+use serde::{Deserialize, Deserializer};
 
 /**
  * Represents how blockchain is stored in DB
@@ -8,7 +10,47 @@ use polodb_core::bson::{Bson, Document};
 #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq, Eq)]
 pub struct BlockchainDocument {
     pub label: String,
-    pub last_synchronization: String,
+    #[serde(deserialize_with = "deserialize_last_synchronization")]
+    pub last_synchronization: u64,
+    #[serde(default)]
+    pub last_attempt: i64, // Time of last synchronization attempt, 0 if never attempted
+    #[serde(default)]
+    pub last_error: Option<String>, // Why last synchronization attempt failed, if it did
+    #[serde(default)]
+    pub schema_version: u32, // 0 until migrated when written before schema versioning
+}
+
+/**
+ * Read last synchronization, migrating timestamps previously stored as string
+ *
+ * Values that cannot be parsed restart synchronization from scratch instead of panicking
+ */
+fn deserialize_last_synchronization<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum RawLastSynchronization {
+        Timestamp(u64),
+        Legacy(String),
+    }
+
+    let last_synchronization = match RawLastSynchronization::deserialize(deserializer)? {
+        RawLastSynchronization::Timestamp(timestamp) => timestamp,
+        RawLastSynchronization::Legacy(raw_timestamp) => match raw_timestamp.parse() {
+            Ok(timestamp) => timestamp,
+            Err(_) => {
+                warn!(
+                    "Invalid last synchronization timestamp \"{}\", synchronizing from scratch",
+                    raw_timestamp
+                );
+                0
+            }
+        },
+    };
+
+    Ok(last_synchronization)
 }
 
 impl BlockchainDocument {
@@ -25,7 +67,17 @@ impl Into<Bson> for &BlockchainDocument {
         let mut doc = Document::new();
 
         doc.insert("label", &self.label);
-        doc.insert("last_synchronization", &self.last_synchronization);
+        doc.insert("last_synchronization", self.last_synchronization as i64);
+        doc.insert("last_attempt", self.last_attempt);
+
+        // Stored as null so successful attempt clears previous error
+        let last_error = match &self.last_error {
+            Some(last_error) => Bson::String(last_error.clone()),
+            None => Bson::Null,
+        };
+        doc.insert("last_error", last_error);
+
+        doc.insert("schema_version", self.schema_version as i64);
 
         Bson::Document(doc)
     }
@@ -33,6 +85,10 @@ impl Into<Bson> for &BlockchainDocument {
 
 #[cfg(test)]
 mod tests {
+    use polodb_core::bson::{doc, from_document};
+
+    use crate::db::migrations::SCHEMA_VERSION;
+
     use super::*;
 
     #[test]
@@ -48,10 +104,13 @@ mod tests {
     #[test]
     fn test_should_convert_to_bson() {
         let expected_label = "foo";
-        let expected_last_sync = "1704067200";
+        let expected_last_sync = 1704067200;
         let doc = BlockchainDocument {
             label: expected_label.to_string(),
-            last_synchronization: expected_last_sync.to_string(),
+            last_synchronization: expected_last_sync,
+            last_attempt: 1704067300,
+            last_error: Some("ConnectionFailure".to_string()),
+            schema_version: SCHEMA_VERSION,
         };
 
         let bson_repr: Bson = (&doc).into();
@@ -59,8 +118,50 @@ mod tests {
 
         assert_eq!(doc.label, bson_doc.get_str("label").unwrap());
         assert_eq!(
-            doc.last_synchronization,
-            bson_doc.get_str("last_synchronization").unwrap()
+            doc.last_synchronization as i64,
+            bson_doc.get_i64("last_synchronization").unwrap()
+        );
+        assert_eq!(doc.last_attempt, bson_doc.get_i64("last_attempt").unwrap());
+        assert_eq!(
+            doc.last_error.as_deref(),
+            Some(bson_doc.get_str("last_error").unwrap())
+        );
+        assert_eq!(
+            doc.schema_version as i64,
+            bson_doc.get_i64("schema_version").unwrap()
         );
     }
+
+    /**
+     * It should migrate last synchronization stored as string
+     */
+    #[test]
+    fn test_should_migrate_legacy_last_synchronization() {
+        let legacy_doc = doc! {
+            "label": "hedera",
+            "last_synchronization": "1704067200",
+        };
+
+        let doc: BlockchainDocument = from_document(legacy_doc).unwrap();
+
+        assert_eq!(doc.last_synchronization, 1704067200);
+        assert_eq!(doc.last_attempt, 0);
+        assert_eq!(doc.last_error, None);
+        assert_eq!(doc.schema_version, 0);
+    }
+
+    /**
+     * It should restart synchronization when stored timestamp is invalid
+     */
+    #[test]
+    fn test_should_reset_invalid_last_synchronization() {
+        let corrupted_doc = doc! {
+            "label": "hedera",
+            "last_synchronization": "foo",
+        };
+
+        let doc: BlockchainDocument = from_document(corrupted_doc).unwrap();
+
+        assert_eq!(doc.last_synchronization, 0);
+    }
 }
@@ -1,9 +1,12 @@
 use super::blockchain_document::BlockchainDocument;
+use crate::db::migrations::SCHEMA_VERSION;
 
 #[derive(Debug)]
 pub struct BlockchainDocumentBuilder {
     label: Option<String>,
-    last_synchronization: Option<String>,
+    last_synchronization: Option<u64>,
+    last_attempt: Option<i64>,
+    last_error: Option<String>,
 }
 
 impl BlockchainDocumentBuilder {
@@ -19,8 +22,35 @@ impl BlockchainDocumentBuilder {
     /**
      * Set blockchain last synchronization
      */
-    pub fn set_last_synchronization(&mut self, timestamp: &String) -> &mut Self {
-        self.last_synchronization = Some(timestamp.clone());
+    pub fn set_last_synchronization(&mut self, timestamp: &u64) -> &mut Self {
+        self.last_synchronization = Some(*timestamp);
+
+        self
+    }
+
+    /**
+     * Set time of last synchronization attempt
+     */
+    pub fn set_last_attempt(&mut self, timestamp: &i64) -> &mut Self {
+        self.last_attempt = Some(*timestamp);
+
+        self
+    }
+
+    /**
+     * Set why last synchronization attempt failed
+     */
+    pub fn set_last_error(&mut self, last_error: &String) -> &mut Self {
+        self.last_error = Some(last_error.clone());
+
+        self
+    }
+
+    /**
+     * Clear last synchronization error
+     */
+    pub fn clear_last_error(&mut self) -> &mut Self {
+        self.last_error = None;
 
         self
     }
@@ -31,6 +61,8 @@ impl BlockchainDocumentBuilder {
     pub fn reset(&mut self) -> &mut Self {
         self.label = None;
         self.last_synchronization = None;
+        self.last_attempt = None;
+        self.last_error = None;
 
         self
     }
@@ -41,7 +73,9 @@ impl BlockchainDocumentBuilder {
     pub fn from_document(doc: &BlockchainDocument) -> Self {
         let instance = Self {
             label: Some(doc.label.clone()),
-            last_synchronization: Some(doc.last_synchronization.clone()),
+            last_synchronization: Some(doc.last_synchronization),
+            last_attempt: Some(doc.last_attempt),
+            last_error: doc.last_error.clone(),
         };
 
         instance
@@ -55,8 +89,10 @@ impl BlockchainDocumentBuilder {
             label: self.label.clone().expect("Label must be set"),
             last_synchronization: self
                 .last_synchronization
-                .clone()
                 .expect("Last synchronization must be set"),
+            last_attempt: self.last_attempt.unwrap_or_default(),
+            last_error: self.last_error.clone(),
+            schema_version: SCHEMA_VERSION,
         };
 
         self.reset();
@@ -70,6 +106,8 @@ impl Default for BlockchainDocumentBuilder {
         let instance = Self {
             label: None,
             last_synchronization: None,
+            last_attempt: None,
+            last_error: None,
         };
 
         instance
@@ -86,11 +124,11 @@ mod tests {
         let mut builder = BlockchainDocumentBuilder::default();
 
         let expected_label = "hedera";
-        let expected_last_synchronization = "1704067200";
+        let expected_last_synchronization = 1704067200;
 
         let doc = builder
             .set_label(&expected_label.to_string())
-            .set_last_synchronization(&expected_last_synchronization.to_string())
+            .set_last_synchronization(&expected_last_synchronization)
             .build();
 
         assert_eq!(doc.label, expected_label);
@@ -102,11 +140,11 @@ mod tests {
         let mut builder = BlockchainDocumentBuilder::default();
 
         let expected_label = "hedera";
-        let expected_last_synchronization = "1704067200";
+        let expected_last_synchronization = 1704067200;
 
         let doc = builder
             .set_label(&expected_label.to_string())
-            .set_last_synchronization(&expected_last_synchronization.to_string())
+            .set_last_synchronization(&expected_last_synchronization)
             .reset();
 
         assert_eq!(doc.label, None);
@@ -118,16 +156,20 @@ mod tests {
         let mut builder = BlockchainDocumentBuilder::default();
 
         let label_mock = "hedera";
-        let last_sync_mock = "1704067200";
+        let last_sync_mock = 1704067200;
 
         let doc = builder
             .set_label(&label_mock.to_string())
-            .set_last_synchronization(&last_sync_mock.to_string())
+            .set_last_synchronization(&last_sync_mock)
+            .set_last_attempt(&1704067300)
+            .set_last_error(&"ConnectionFailure".to_string())
             .build();
 
         let new_doc = BlockchainDocumentBuilder::from_document(&doc).build();
 
         assert_eq!(new_doc.label, doc.label);
         assert_eq!(new_doc.last_synchronization, doc.last_synchronization);
+        assert_eq!(new_doc.last_attempt, doc.last_attempt);
+        assert_eq!(new_doc.last_error, doc.last_error);
     }
 }
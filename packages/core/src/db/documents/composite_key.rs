@@ -0,0 +1,93 @@
+use std::fmt;
+
+use super::package_document::PackageDocument;
+
+const COMPOSITE_KEY_SEPARATOR: &str = ":";
+
+/**
+ * Uniquely identifies a package document
+ * Formatted as blockchain_label:package_name:package_version:maintainer_key
+ */
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CompositeKey {
+    pub blockchain_label: String,
+    pub name: String,
+    pub version: String,
+    pub maintainer: String,
+}
+
+impl CompositeKey {
+    /**
+     * Parse key from its string representation
+     */
+    pub fn parse(key: &str) -> Option<Self> {
+        let parts: Vec<&str> = key.split(COMPOSITE_KEY_SEPARATOR).collect();
+
+        match parts[..] {
+            [blockchain_label, name, version, maintainer] => Some(Self {
+                blockchain_label: String::from(blockchain_label),
+                name: String::from(name),
+                version: String::from(version),
+                maintainer: String::from(maintainer),
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl From<&PackageDocument> for CompositeKey {
+    fn from(document: &PackageDocument) -> Self {
+        Self {
+            blockchain_label: document.blockchain_label.clone(),
+            name: document.name.clone(),
+            version: document.version.clone(),
+            maintainer: document.maintainer.clone(),
+        }
+    }
+}
+
+impl fmt::Display for CompositeKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{sep}{}{sep}{}{sep}{}",
+            self.blockchain_label,
+            self.name,
+            self.version,
+            self.maintainer,
+            sep = COMPOSITE_KEY_SEPARATOR
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /**
+     * It should parse back formatted key
+     */
+    #[test]
+    fn test_composite_key_roundtrip() {
+        let key = CompositeKey {
+            blockchain_label: String::from("hedera"),
+            name: String::from("foo"),
+            version: String::from("1.2.3"),
+            maintainer: String::from("ABCDEF"),
+        };
+
+        let formatted_key = key.to_string();
+
+        assert_eq!(formatted_key, "hedera:foo:1.2.3:ABCDEF");
+        assert_eq!(CompositeKey::parse(&formatted_key), Some(key));
+    }
+
+    /**
+     * It should refuse malformed keys
+     */
+    #[test]
+    fn test_composite_key_parse_malformed() {
+        assert_eq!(CompositeKey::parse("hedera:foo:1.2.3"), None);
+        assert_eq!(CompositeKey::parse("hedera:foo:1:2.3:ABCDEF"), None);
+    }
+}
@@ -1,5 +1,6 @@
 pub mod blockchain_document;
 pub mod blockchain_document_builder;
+pub mod composite_key;
 pub mod package_document;
 pub mod package_document_builder;
 pub mod package_integrity_document;
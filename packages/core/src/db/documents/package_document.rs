@@ -13,8 +13,18 @@ pub struct PackageDocument {
     pub maintainer: String,
     pub archive_url: String,
     pub integrity: PackageIntegrityDocument,
+    pub signed_at: Option<u64>,
+    #[serde(default)]
+    pub dependencies: Vec<(String, String)>, // Name and version constraint of required packages
+    #[serde(default)]
+    pub revoked: bool, // Tombstones are kept so revoked mutations cannot be replayed
     pub sig: String,
     pub blockchain_label: String,
+    pub mutation_time: Option<u64>, // Time at which blockchain reached consensus on latest mutation
+    pub consensus_time: Option<u64>, // Time at which blockchain reached consensus on mutation
+    pub raw: Option<String>,        // Hex encoded original RLP read from blockchain
+    #[serde(default)]
+    pub schema_version: u32, // 0 until migrated when written before schema versioning
 }
 
 impl Into<Bson> for &PackageDocument {
@@ -34,10 +44,38 @@ impl Into<Bson> for &PackageDocument {
         let integrity: Bson = (&self.integrity).into();
         doc.insert("integrity", integrity);
 
+        if let Some(signed_at) = self.signed_at {
+            doc.insert("signed_at", signed_at as i64);
+        }
+
+        // Always stored so a mutation dropping dependencies overwrites previous ones
+        let dependencies: Vec<Bson> = self
+            .dependencies
+            .iter()
+            .map(|(name, version)| Bson::Array(vec![name.into(), version.into()]))
+            .collect();
+        doc.insert("dependencies", dependencies);
+
+        doc.insert("revoked", self.revoked);
+
         doc.insert("sig", &self.sig);
 
         doc.insert("blockchain_label", &self.blockchain_label);
 
+        if let Some(mutation_time) = self.mutation_time {
+            doc.insert("mutation_time", mutation_time as i64);
+        }
+
+        if let Some(consensus_time) = self.consensus_time {
+            doc.insert("consensus_time", consensus_time as i64);
+        }
+
+        if let Some(raw) = &self.raw {
+            doc.insert("raw", raw);
+        }
+
+        doc.insert("schema_version", self.schema_version as i64);
+
         Bson::Document(doc)
     }
 }
@@ -49,7 +87,10 @@ mod tests {
     use sha2::{Digest, Sha256};
 
     use crate::{
-        db::documents::package_integrity_document_builder::PackageIntegrityDocumentBuilder,
+        db::{
+            documents::package_integrity_document_builder::PackageIntegrityDocumentBuilder,
+            migrations::SCHEMA_VERSION,
+        },
         packages::{
             package_integrity_builder::PackageIntegrityBuilder, package_status::PackageStatus,
         },
@@ -111,8 +152,15 @@ mod tests {
             maintainer: hex::encode(maintainer),
             archive_url: archive_url.to_string(),
             integrity: package_integrity.clone(),
+            signed_at: Some(1704067100),
+            dependencies: vec![(String::from("bar"), String::from("4.5.6"))],
+            revoked: true,
             sig: hex::encode(package_sig).clone(),
             blockchain_label: blockchain_label.to_string(),
+            mutation_time: Some(1704067200),
+            consensus_time: Some(1704067150000000000),
+            raw: Some(String::from("c0")),
+            schema_version: SCHEMA_VERSION,
         };
 
         let bson_repr: Bson = (&package_document).into();
@@ -120,6 +168,11 @@ mod tests {
 
         assert_eq!(package_document.name, bson_doc.get_str("name").unwrap());
 
+        assert_eq!(
+            package_document.schema_version as i64,
+            bson_doc.get_i64("schema_version").unwrap()
+        );
+
         assert_eq!(
             package_document.version,
             bson_doc.get_str("version").unwrap()
@@ -135,5 +188,43 @@ mod tests {
             package_document.archive_url,
             bson_doc.get_str("archive_url").unwrap()
         );
+
+        assert_eq!(
+            package_document.signed_at,
+            Some(bson_doc.get_i64("signed_at").unwrap() as u64)
+        );
+
+        assert_eq!(
+            package_document.revoked,
+            bson_doc.get_bool("revoked").unwrap()
+        );
+
+        let dependency = bson_doc.get_array("dependencies").unwrap()[0]
+            .as_array()
+            .unwrap();
+
+        assert_eq!(
+            package_document.dependencies[0].0,
+            dependency[0].as_str().unwrap()
+        );
+        assert_eq!(
+            package_document.dependencies[0].1,
+            dependency[1].as_str().unwrap()
+        );
+
+        assert_eq!(
+            package_document.mutation_time,
+            Some(bson_doc.get_i64("mutation_time").unwrap() as u64)
+        );
+
+        assert_eq!(
+            package_document.consensus_time,
+            Some(bson_doc.get_i64("consensus_time").unwrap() as u64)
+        );
+
+        assert_eq!(
+            package_document.raw.as_deref(),
+            Some(bson_doc.get_str("raw").unwrap())
+        );
     }
 }
@@ -4,6 +4,7 @@ use url::Url;
 
 use crate::{
     blockchains::blockchain::BlockchainClient,
+    db::migrations::SCHEMA_VERSION,
     packages::{package::Package, package_status::PackageStatus},
 };
 
@@ -19,8 +20,14 @@ pub struct PackageDocumentBuilder {
     pub maintainer: Option<String>,
     pub archive_url: Option<String>,
     pub integrity: Option<PackageIntegrityDocument>,
+    pub signed_at: Option<u64>,
+    pub dependencies: Vec<(String, String)>,
+    pub revoked: bool,
     pub sig: Option<Vec<u8>>,
     pub blockchain_label: Option<String>,
+    pub mutation_time: Option<u64>,
+    pub consensus_time: Option<u64>,
+    pub raw: Option<Vec<u8>>,
 }
 
 impl PackageDocumentBuilder {
@@ -48,9 +55,22 @@ impl PackageDocumentBuilder {
 
             integrity: Some(integrity),
 
+            signed_at: package.signed_at,
+
+            dependencies: package.dependencies.clone(),
+
+            revoked: package.revoked,
+
             sig: Some(package.sig.unwrap().to_vec()),
 
             blockchain_label: Some(blockchain_client.get_label()),
+
+            // Mutation happened when blockchain reached consensus on it, not when it got cached
+            mutation_time: package.consensus_time,
+
+            consensus_time: package.consensus_time,
+
+            raw: package.raw.clone(),
         };
 
         instance
@@ -108,6 +128,22 @@ impl PackageDocumentBuilder {
         self
     }
 
+    /**
+     * Set time at which package was signed
+     */
+    pub fn set_signed_at(&mut self, signed_at: &u64) -> &mut Self {
+        self.signed_at = Some(*signed_at);
+        self
+    }
+
+    /**
+     * Set whether package is a tombstone revoking release
+     */
+    pub fn set_revoked(&mut self, revoked: &bool) -> &mut Self {
+        self.revoked = *revoked;
+        self
+    }
+
     /**
      * Set package signature
      */
@@ -124,6 +160,38 @@ impl PackageDocumentBuilder {
         self
     }
 
+    /**
+     * Set mutation time
+     */
+    pub fn set_mutation_time(&mut self, mutation_time: &u64) -> &mut Self {
+        self.mutation_time = Some(*mutation_time);
+        self
+    }
+
+    /**
+     * Set package dependencies, name and version constraint
+     */
+    pub fn set_dependencies(&mut self, dependencies: &[(String, String)]) -> &mut Self {
+        self.dependencies = dependencies.to_vec();
+        self
+    }
+
+    /**
+     * Set time at which blockchain reached consensus on mutation
+     */
+    pub fn set_consensus_time(&mut self, consensus_time: &u64) -> &mut Self {
+        self.consensus_time = Some(*consensus_time);
+        self
+    }
+
+    /**
+     * Set original RLP read from blockchain
+     */
+    pub fn set_raw(&mut self, raw: &[u8]) -> &mut Self {
+        self.raw = Some(raw.to_vec());
+        self
+    }
+
     /**
      * Reset builder
      */
@@ -134,8 +202,14 @@ impl PackageDocumentBuilder {
         self.maintainer = None;
         self.archive_url = None;
         self.integrity = None;
+        self.signed_at = None;
+        self.dependencies = vec![];
+        self.revoked = false;
         self.sig = None;
         self.blockchain_label = None;
+        self.mutation_time = None;
+        self.consensus_time = None;
+        self.raw = None;
 
         self
     }
@@ -152,8 +226,17 @@ impl PackageDocumentBuilder {
             maintainer: Some(doc.maintainer.clone()),
             archive_url: Some(doc.archive_url.clone()),
             integrity: Some(doc.integrity.clone()),
+            signed_at: doc.signed_at,
+            dependencies: doc.dependencies.clone(),
+            revoked: doc.revoked,
             sig: Some(sig),
             blockchain_label: Some(doc.blockchain_label.clone()),
+            mutation_time: doc.mutation_time,
+            consensus_time: doc.consensus_time,
+            raw: doc
+                .raw
+                .as_ref()
+                .map(|raw| hex::decode(raw).expect("Could not decode package raw bytes")),
         };
 
         instance
@@ -181,11 +264,18 @@ impl PackageDocumentBuilder {
                 .integrity
                 .clone()
                 .expect("Package integrity must be set"),
+            signed_at: self.signed_at,
+            dependencies: self.dependencies.clone(),
+            revoked: self.revoked,
             sig: encoded_sig,
             blockchain_label: self
                 .blockchain_label
                 .clone()
                 .expect("Blockchain label must be set"),
+            mutation_time: self.mutation_time,
+            consensus_time: self.consensus_time,
+            raw: self.raw.as_ref().map(hex::encode),
+            schema_version: SCHEMA_VERSION,
         };
 
         self.reset();
@@ -203,8 +293,14 @@ impl Default for PackageDocumentBuilder {
             maintainer: None,
             archive_url: None,
             integrity: None,
+            signed_at: None,
+            dependencies: vec![],
+            revoked: false,
             sig: None,
             blockchain_label: None,
+            mutation_time: None,
+            consensus_time: None,
+            raw: None,
         };
 
         instance
@@ -218,8 +314,8 @@ mod tests {
     use sha2::{Digest, Sha256};
 
     use crate::{
-        blockchains::hedera::blockchain_client::HederaBlockchain,
         packages::{package_builder::PackageBuilder, package_status::PackageStatus},
+        test_utils::blockchain::tests::create_labelled_client,
     };
 
     use super::*;
@@ -273,8 +369,7 @@ mod tests {
 
         // Pkg related blockchain
 
-        let blockhain_client: Box<dyn BlockchainClient> =
-            Box::new(HederaBlockchain::from("4991716"));
+        let blockhain_client: Box<dyn BlockchainClient> = create_labelled_client("MockBlockchain");
 
         let expected_blockchain_label = blockhain_client.get_label();
 
@@ -342,6 +437,8 @@ mod tests {
 
         let expected_sig = key.sign(&package_data_hash);
 
+        let expected_consensus_time = 1704067200;
+
         // Package builder
 
         let package = PackageBuilder::default()
@@ -355,10 +452,10 @@ mod tests {
                 &expected_archive_hash,
             )
             .set_signature(&expected_sig)
+            .set_consensus_time(&expected_consensus_time)
             .build();
 
-        let blockhain_client: Box<dyn BlockchainClient> =
-            Box::new(HederaBlockchain::from("4991716"));
+        let blockhain_client: Box<dyn BlockchainClient> = create_labelled_client("MockBlockchain");
 
         let expected_blockchain_label = blockhain_client.get_label();
 
@@ -374,6 +471,7 @@ mod tests {
         );
         assert_eq!(package_doc.archive_url, expected_archive_url.to_string());
         assert_eq!(package_doc.sig, hex::encode(expected_sig.to_vec()));
+        assert_eq!(package_doc.mutation_time, Some(expected_consensus_time));
 
         Ok(())
     }
@@ -7,6 +7,8 @@ use polodb_core::bson::{Bson, Document};
 pub struct PackageIntegrityDocument {
     pub algorithm: String,
     pub archive_hash: String,
+    #[serde(default)]
+    pub source_code_hash: String, // Hex encoded, empty for packages published without sources
 }
 
 impl Into<Bson> for &PackageIntegrityDocument {
@@ -17,6 +19,8 @@ impl Into<Bson> for &PackageIntegrityDocument {
 
         doc.insert("archive_hash", &self.archive_hash);
 
+        doc.insert("source_code_hash", &self.source_code_hash);
+
         Bson::Document(doc)
     }
 }
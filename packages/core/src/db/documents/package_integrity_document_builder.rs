@@ -5,6 +5,7 @@ use super::package_integrity_document::PackageIntegrityDocument;
 pub struct PackageIntegrityDocumentBuilder {
     algorithm: Option<String>,
     archive_hash: Option<Vec<u8>>,
+    source_code_hash: Vec<u8>,
 }
 
 impl PackageIntegrityDocumentBuilder {
@@ -15,6 +16,7 @@ impl PackageIntegrityDocumentBuilder {
         let instance = Self {
             algorithm: Some(package_integrity.algorithm.clone()),
             archive_hash: Some(package_integrity.archive_hash.clone()),
+            source_code_hash: package_integrity.source_code_hash.clone(),
         };
 
         instance
@@ -38,12 +40,22 @@ impl PackageIntegrityDocumentBuilder {
         self
     }
 
+    /**
+     * Set source code hash
+     */
+    pub fn set_source_code_hash(&mut self, source_code_hash: &Vec<u8>) -> &mut Self {
+        self.source_code_hash = source_code_hash.clone();
+
+        self
+    }
+
     /**
      * Reset builder
      */
     pub fn reset(&mut self) -> &mut Self {
         self.algorithm = None;
         self.archive_hash = None;
+        self.source_code_hash = vec![];
 
         self
     }
@@ -65,6 +77,8 @@ impl PackageIntegrityDocumentBuilder {
                 .expect("Package integrity algorithm must be set"),
 
             archive_hash: encoded_archive_hash,
+
+            source_code_hash: hex::encode(&self.source_code_hash),
         };
 
         self.reset();
@@ -78,6 +92,7 @@ impl Default for PackageIntegrityDocumentBuilder {
         let instance = Self {
             algorithm: None,
             archive_hash: None,
+            source_code_hash: vec![],
         };
 
         instance
@@ -104,13 +119,20 @@ mod tests {
         let expected_algorithm = "SHA256";
         let expected_archive_hash = hasher.finalize().to_vec();
 
+        let expected_source_code_hash = Sha256::digest("bar").to_vec();
+
         let doc = builder
             .set_algorithm(&expected_algorithm.to_string())
             .set_archive_hash(&expected_archive_hash)
+            .set_source_code_hash(&expected_source_code_hash)
             .build();
 
         assert_eq!(doc.algorithm, expected_algorithm);
         assert_eq!(doc.archive_hash, hex::encode(&expected_archive_hash));
+        assert_eq!(
+            doc.source_code_hash,
+            hex::encode(&expected_source_code_hash)
+        );
     }
 
     #[test]
@@ -145,6 +167,7 @@ mod tests {
         let package_integrity: PackageIntegrity = PackageIntegrity {
             algorithm: expected_algorithm.to_string(),
             archive_hash: expected_archive_hash.clone(),
+            source_code_hash: vec![],
         };
 
         let mut builder =
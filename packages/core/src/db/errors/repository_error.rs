@@ -0,0 +1,22 @@
+use thiserror::Error;
+
+/**
+ * DB repositories errors
+ */
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum RepositoryError {
+    #[error("Document already exists: {0}")]
+    DuplicateKey(String),
+
+    #[error("Document key is malformed: {0}")]
+    MalformedKey(String),
+
+    #[error("Could not write document: {0}")]
+    WriteFailure(String),
+
+    #[error("Could not create index: {0}")]
+    IndexFailure(String),
+
+    #[error("Could not open DB: {0}")]
+    OpenFailure(String),
+}
@@ -0,0 +1,210 @@
+use log::{debug, warn};
+use polodb_core::{
+    bson::{doc, Bson, Document},
+    Collection, CollectionT, Database,
+};
+
+use super::{
+    client::{BLOCKCHAINS_COLLECTION_NAME, PACKAGES_COLLECTION_NAME},
+    errors::repository_error::RepositoryError,
+};
+
+/**
+ * Upgrade document of given collection from previous schema version, in place
+ */
+type Migration = fn(collection_name: &str, document: &mut Document);
+
+/**
+ * Baseline, fields added so far all have defaults so documents only need to be stamped
+ */
+fn baseline(_collection_name: &str, _document: &mut Document) {}
+
+// Migration at index N upgrades documents from schema version N to N + 1, new ones must be appended
+const MIGRATIONS: [Migration; 1] = [baseline];
+
+/**
+ * Schema version of documents written by this version
+ */
+pub const SCHEMA_VERSION: u32 = MIGRATIONS.len() as u32;
+
+/**
+ * Get schema version of raw document, documents written before versioning are version 0
+ */
+fn get_schema_version(document: &Document) -> u32 {
+    match document.get("schema_version") {
+        Some(Bson::Int32(schema_version)) => u32::try_from(*schema_version).unwrap_or_default(),
+        Some(Bson::Int64(schema_version)) => u32::try_from(*schema_version).unwrap_or_default(),
+        _ => 0,
+    }
+}
+
+/**
+ * Upgrade every outdated document of collection, returns how many were upgraded
+ */
+fn migrate_collection(
+    collection: &Collection<Document>,
+    collection_name: &str,
+) -> Result<usize, RepositoryError> {
+    let documents = collection
+        .find(doc! {})
+        .run()
+        .map_err(|e| RepositoryError::WriteFailure(e.to_string()))?;
+
+    let mut migrated_count = 0;
+
+    for document_res in documents {
+        let mut document = match document_res {
+            Ok(document) => document,
+            Err(e) => {
+                warn!("Skipping unreadable {} document : {}", collection_name, e);
+                continue;
+            }
+        };
+
+        let schema_version = get_schema_version(&document);
+
+        if schema_version >= SCHEMA_VERSION {
+            continue;
+        }
+
+        for migration in &MIGRATIONS[schema_version as usize..] {
+            migration(collection_name, &mut document);
+        }
+
+        document.insert("schema_version", SCHEMA_VERSION as i64);
+
+        let id = match document.remove("_id") {
+            Some(id) => id,
+            None => continue,
+        };
+
+        collection
+            .update_one(doc! { "_id": id }, doc! { "$set": document })
+            .map_err(|e| RepositoryError::WriteFailure(e.to_string()))?;
+
+        migrated_count += 1;
+    }
+
+    Ok(migrated_count)
+}
+
+/**
+ * Upgrade stored documents to current schema version, already upgraded ones are left untouched
+ */
+pub fn migrate(db: &Database) -> Result<usize, RepositoryError> {
+    debug!("Migrating DB to schema version {}...", SCHEMA_VERSION);
+
+    let mut migrated_count = 0;
+
+    for collection_name in [PACKAGES_COLLECTION_NAME, BLOCKCHAINS_COLLECTION_NAME] {
+        let collection: Collection<Document> = db.collection(collection_name);
+
+        migrated_count += migrate_collection(&collection, collection_name)?;
+    }
+
+    debug!(
+        "Done migrating DB to schema version {} ! ({} documents upgraded)",
+        SCHEMA_VERSION, migrated_count
+    );
+
+    Ok(migrated_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use polodb_core::bson::{to_document, Bson};
+    use tempfile::TempDir;
+
+    use crate::{
+        blockchains::blockchain::BlockchainClient,
+        blockchains::file::blockchain_client::FileBlockchain,
+        db::{
+            client::DbClient,
+            documents::{
+                blockchain_document_builder::BlockchainDocumentBuilder,
+                package_document_builder::PackageDocumentBuilder,
+            },
+        },
+        test_utils::package::tests::create_package_with_sig,
+    };
+
+    use super::*;
+
+    /**
+     * It should stamp documents written before versioning with current schema version
+     */
+    #[tokio::test]
+    async fn test_migrate_v0_documents() -> Result<(), Box<dyn std::error::Error>> {
+        let test_dir = TempDir::new()?;
+
+        let db_path = test_dir.path().join("db");
+
+        let blockchain_client: Box<dyn BlockchainClient> =
+            Box::new(FileBlockchain::from(&test_dir.path().join("packages.log")));
+
+        let package_doc =
+            PackageDocumentBuilder::from_package(&create_package_with_sig()?, &blockchain_client)
+                .build();
+
+        let blockchain_doc = BlockchainDocumentBuilder::default()
+            .set_label(&blockchain_client.get_label())
+            .set_last_synchronization(&42)
+            .build();
+
+        // Write documents the way versions before schema versioning did
+        {
+            let db = Database::open_path(&db_path)?;
+
+            let mut v0_package_doc = to_document(&package_doc)?;
+            v0_package_doc.remove("schema_version");
+
+            let mut v0_blockchain_doc = to_document(&blockchain_doc)?;
+            v0_blockchain_doc.remove("schema_version");
+
+            db.collection::<Document>(PACKAGES_COLLECTION_NAME)
+                .insert_one(v0_package_doc)?;
+            db.collection::<Document>(BLOCKCHAINS_COLLECTION_NAME)
+                .insert_one(v0_blockchain_doc)?;
+
+            assert_eq!(migrate(&db)?, 2);
+
+            // Already migrated documents are left untouched
+            assert_eq!(migrate(&db)?, 0);
+        }
+
+        let db_client = DbClient::try_from(&db_path).unwrap();
+
+        let migrated_package_doc = db_client
+            .get_packages_collection()
+            .await
+            .find_one(doc! {})?
+            .expect("Package document must still exist");
+
+        let migrated_blockchain_doc = db_client
+            .get_blockchains_collection()
+            .await
+            .find_one(doc! {})?
+            .expect("Blockchain document must still exist");
+
+        assert_eq!(migrated_package_doc, package_doc);
+        assert_eq!(migrated_package_doc.schema_version, SCHEMA_VERSION);
+        assert_eq!(migrated_blockchain_doc, blockchain_doc);
+        assert_eq!(migrated_blockchain_doc.schema_version, SCHEMA_VERSION);
+
+        Ok(())
+    }
+
+    /**
+     * It should read schema version whatever its integer representation
+     */
+    #[test]
+    fn test_get_schema_version() {
+        assert_eq!(get_schema_version(&doc! {}), 0);
+        assert_eq!(get_schema_version(&doc! { "schema_version": 1i32 }), 1);
+        assert_eq!(get_schema_version(&doc! { "schema_version": 1i64 }), 1);
+        assert_eq!(
+            get_schema_version(&doc! { "schema_version": Bson::String(String::from("1")) }),
+            0
+        );
+    }
+}
@@ -1,3 +1,6 @@
 pub mod client;
+pub mod cursor;
 pub mod documents;
+pub mod errors;
+pub mod migrations;
 pub mod traits;
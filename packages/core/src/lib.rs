@@ -1,7 +1,9 @@
 pub mod blockchains;
+pub mod bpm;
 pub mod config;
 pub mod db;
 pub mod logging;
+pub mod metrics;
 pub mod package_managers;
 pub mod packages;
 pub mod services;
@@ -0,0 +1,99 @@
+#[cfg(feature = "metrics")]
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/**
+ * Counters updated while synchronizing from blockchain
+ */
+#[derive(Debug, Clone, Copy)]
+pub enum Counter {
+    PackagesFetched,
+    PackagesSkipped,
+    DbWrites,
+    BlockchainErrors,
+}
+
+/**
+ * Counters values at a given time
+ */
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MetricsSnapshot {
+    pub packages_fetched: u64,
+    pub packages_skipped: u64,
+    pub db_writes: u64,
+    pub blockchain_errors: u64,
+}
+
+#[cfg(feature = "metrics")]
+static COUNTERS: [AtomicU64; 4] = [
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+    AtomicU64::new(0),
+];
+
+/**
+ * Increment counter, does nothing when metrics feature is disabled
+ */
+#[inline]
+pub fn increment(counter: Counter) {
+    #[cfg(feature = "metrics")]
+    COUNTERS[counter as usize].fetch_add(1, Ordering::Relaxed);
+
+    #[cfg(not(feature = "metrics"))]
+    let _ = counter;
+}
+
+/**
+ * Get counters values, always zero when metrics feature is disabled
+ */
+pub fn snapshot() -> MetricsSnapshot {
+    #[cfg(feature = "metrics")]
+    {
+        MetricsSnapshot {
+            packages_fetched: COUNTERS[Counter::PackagesFetched as usize].load(Ordering::Relaxed),
+            packages_skipped: COUNTERS[Counter::PackagesSkipped as usize].load(Ordering::Relaxed),
+            db_writes: COUNTERS[Counter::DbWrites as usize].load(Ordering::Relaxed),
+            blockchain_errors: COUNTERS[Counter::BlockchainErrors as usize].load(Ordering::Relaxed),
+        }
+    }
+
+    #[cfg(not(feature = "metrics"))]
+    MetricsSnapshot::default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /**
+     * It should increment counters
+     */
+    #[test]
+    #[cfg(feature = "metrics")]
+    fn test_increment() {
+        let before = snapshot();
+
+        increment(Counter::PackagesFetched);
+        increment(Counter::PackagesSkipped);
+        increment(Counter::DbWrites);
+        increment(Counter::BlockchainErrors);
+
+        let after = snapshot();
+
+        assert!(after.packages_fetched > before.packages_fetched);
+        assert!(after.packages_skipped > before.packages_skipped);
+        assert!(after.db_writes > before.db_writes);
+        assert!(after.blockchain_errors > before.blockchain_errors);
+    }
+
+    /**
+     * It should not count anything when metrics are disabled
+     */
+    #[test]
+    #[cfg(not(feature = "metrics"))]
+    fn test_increment_disabled() {
+        increment(Counter::PackagesFetched);
+
+        assert_eq!(snapshot(), MetricsSnapshot::default());
+    }
+}
@@ -0,0 +1,85 @@
+const TRANSACTION_STARTED: &str = "Start-Date:";
+
+const TRANSACTION_ACTIONS: [&str; 6] = [
+    "Install:",
+    "Upgrade:",
+    "Downgrade:",
+    "Reinstall:",
+    "Remove:",
+    "Purge:",
+];
+
+/**
+ * Parse apt history log then return names of packages affected by most recent transaction
+ *
+ * Action lines look like : Install: foo:amd64 (1.0-1), libbar:amd64 (2.0-1, automatic)
+ */
+pub fn parse_last_transaction(log: &str) -> Vec<String> {
+    let last_transaction = match log.rfind(TRANSACTION_STARTED) {
+        Some(transaction_start) => &log[transaction_start..],
+        None => return vec![],
+    };
+
+    last_transaction
+        .lines()
+        .filter_map(|line| {
+            let action = TRANSACTION_ACTIONS
+                .iter()
+                .find(|action| line.starts_with(*action))?;
+
+            Some(&line[action.len()..])
+        })
+        .flat_map(|packages| packages.split("), "))
+        .filter_map(|package| {
+            // Package name is followed by its architecture, then its versions
+            package
+                .trim()
+                .split(|c: char| c == ':' || c == ' ')
+                .next()
+                .filter(|package_name| !package_name.is_empty())
+                .map(String::from)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /**
+     * It should only return packages from last transaction
+     */
+    #[test]
+    fn test_parse_last_transaction() {
+        let log = "\
+Start-Date: 2024-01-01  00:00:00
+Commandline: apt-get install -y /tmp/foo_1.0-1_amd64.deb
+Install: foo:amd64 (1.0-1)
+End-Date: 2024-01-01  00:00:01
+
+Start-Date: 2024-01-02  00:00:00
+Commandline: apt-get install -y /tmp/bar_2.0-1_amd64.deb
+Requested-By: user (1000)
+Install: bar:amd64 (2.0-1), libbar:amd64 (2.0-1, automatic)
+Upgrade: baz:amd64 (1.0-1, 1.1-1)
+End-Date: 2024-01-02  00:00:01
+";
+
+        assert_eq!(
+            parse_last_transaction(log),
+            vec![
+                String::from("bar"),
+                String::from("libbar"),
+                String::from("baz")
+            ]
+        );
+    }
+
+    /**
+     * It should return nothing when no transaction happened
+     */
+    #[test]
+    fn test_parse_last_transaction_empty() {
+        assert_eq!(parse_last_transaction(""), Vec::<String>::new());
+    }
+}
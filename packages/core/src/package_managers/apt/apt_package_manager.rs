@@ -0,0 +1,234 @@
+use crate::{
+    package_managers::{
+        archive::{ensure_archive_format, ArchiveFormat},
+        download_verified_archive,
+        errors::package_manager_error::PackageManagerError,
+        installed_package::{parse_installed_packages, InstalledPackage},
+        traits::package_manager::PackageManager,
+        validate_package_name,
+    },
+    packages::package_integrity::PackageIntegrity,
+};
+
+use super::apt_log::parse_last_transaction;
+use log::debug;
+use std::{
+    path::PathBuf,
+    process::Command,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+use url::Url;
+
+use tempfile::tempdir;
+
+const APT_HISTORY_LOG_PATH: &str = "/var/log/apt/history.log";
+
+pub struct AptPackageManager {
+    download_cancelled: Arc<AtomicBool>,
+    http_client: reqwest::Client,
+}
+
+impl AptPackageManager {
+    /**
+     * Set HTTP client used to fetch archives, e.g. one going through configured proxy
+     */
+    pub fn set_http_client(&mut self, http_client: &reqwest::Client) -> &mut Self {
+        self.http_client = http_client.clone();
+        self
+    }
+}
+
+#[cfg(not(tarpaulin_include))] // TODO : Figure out way to test on multiple envs
+impl AptPackageManager {
+    /**
+     * Install using local archive, apt resolving its dependencies unlike dpkg
+     */
+    fn install_archive(&self, archive_path: &PathBuf) -> Result<(), PackageManagerError> {
+        debug!(
+            "Install archive using apt ( location : {} )",
+            archive_path.display()
+        );
+
+        // Archive path is absolute, so apt reads it as a file rather than a package name
+        let apt_process = Command::new("apt-get")
+            .arg("install")
+            .arg("-y")
+            .arg(archive_path)
+            .spawn()
+            .map_err(|e| PackageManagerError::InstallationError(e.to_string()))?;
+
+        let output = apt_process
+            .wait_with_output()
+            .map_err(|e| PackageManagerError::InstallationError(e.to_string()))?;
+
+        if !output.status.success() {
+            let output_str = String::from_utf8_lossy(&output.stderr).to_string();
+            Err(PackageManagerError::InstallationError(output_str))
+        } else {
+            debug!(
+                "Done installing archive using apt ( location : {} ) !",
+                archive_path.display()
+            );
+
+            Ok(())
+        }
+    }
+}
+
+#[async_trait::async_trait]
+#[cfg(not(tarpaulin_include))] // TODO : Figure out way to test on multiple envs
+impl PackageManager for AptPackageManager {
+    /**
+     * Get package manager name
+     */
+    fn get_name(&self) -> String {
+        String::from("apt")
+    }
+
+    /**
+     * Fetch package content then install it only if it matches integrity
+     */
+    async fn install_verified(
+        &self,
+        package_url: &Url,
+        integrity: &PackageIntegrity,
+    ) -> Result<PathBuf, PackageManagerError> {
+        debug!(
+            "Installing verified package from url (location: {})...",
+            package_url.to_string()
+        );
+
+        let temp_package_dir =
+            tempdir().map_err(|e| PackageManagerError::InstallationError(e.to_string()))?;
+
+        let archive_path = download_verified_archive(
+            &self.http_client,
+            package_url,
+            integrity,
+            temp_package_dir.path(),
+            &self.download_cancelled,
+        )
+        .await?;
+
+        ensure_archive_format(&archive_path, &ArchiveFormat::Deb)?;
+
+        self.install_archive(&archive_path)?;
+
+        debug!("Done installing verified package from url !");
+
+        Ok(archive_path)
+    }
+
+    /**
+     * Abort download in progress, partial archive gets removed
+     */
+    fn cancel_download(&self) {
+        self.download_cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /**
+     * Forget previous cancellation so next downloads can proceed
+     */
+    fn reset_download(&self) {
+        self.download_cancelled.store(false, Ordering::SeqCst);
+    }
+
+    /**
+     * Remove package using apt
+     */
+    async fn remove(&self, package_name: &String) -> Result<(), PackageManagerError> {
+        validate_package_name(package_name)?;
+
+        let apt_process = Command::new("apt-get")
+            .arg("remove")
+            .arg("-y")
+            .arg(package_name)
+            .spawn()
+            .map_err(|e| PackageManagerError::RemovalError(e.to_string()))?;
+
+        let output = apt_process
+            .wait_with_output()
+            .map_err(|e| PackageManagerError::RemovalError(e.to_string()))?;
+
+        if !output.status.success() {
+            let output_str = String::from_utf8_lossy(&output.stderr).to_string();
+            Err(PackageManagerError::RemovalError(output_str))
+        } else {
+            debug!("Done removing package {} using apt !", package_name);
+
+            Ok(())
+        }
+    }
+
+    /**
+     * Get packages installed on system using dpkg-query
+     */
+    async fn list_installed(&self) -> Result<Vec<InstalledPackage>, PackageManagerError> {
+        debug!("Listing packages installed using apt...");
+
+        let output = Command::new("dpkg-query")
+            .arg("--show")
+            .arg("--showformat=${Package} ${Version}\\n")
+            .output()
+            .map_err(|e| PackageManagerError::ListingError(e.to_string()))?;
+
+        if !output.status.success() {
+            let output_str = String::from_utf8_lossy(&output.stderr).to_string();
+            return Err(PackageManagerError::ListingError(output_str));
+        }
+
+        let installed_packages = parse_installed_packages(&String::from_utf8_lossy(&output.stdout));
+
+        debug!(
+            "Done listing packages installed using apt ! ( {} packages found )",
+            installed_packages.len()
+        );
+
+        Ok(installed_packages)
+    }
+
+    /**
+     * Get packages affected by most recent apt transaction using its history log
+     */
+    async fn last_transaction(&self) -> Result<Vec<String>, PackageManagerError> {
+        debug!("Reading last apt transaction...");
+
+        let log = tokio::fs::read_to_string(APT_HISTORY_LOG_PATH)
+            .await
+            .map_err(|e| PackageManagerError::TransactionLogError(e.to_string()))?;
+
+        let affected_packages = parse_last_transaction(&log);
+
+        debug!(
+            "Done reading last apt transaction ! ( {} packages affected )",
+            affected_packages.len()
+        );
+
+        Ok(affected_packages)
+    }
+}
+
+impl Default for AptPackageManager {
+    fn default() -> Self {
+        Self {
+            download_cancelled: Arc::new(AtomicBool::new(false)),
+            http_client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /**
+     * It should be named after apt
+     */
+    #[test]
+    fn test_get_name() {
+        assert_eq!(AptPackageManager::default().get_name(), "apt");
+    }
+}
@@ -0,0 +1,2 @@
+pub mod apt_log;
+pub mod apt_package_manager;
@@ -0,0 +1,172 @@
+use std::{fs::File, io::Read, path::Path};
+
+use strum_macros::Display;
+
+use super::errors::package_manager_error::PackageManagerError;
+
+const DEB_MAGIC: &[u8] = b"!<arch>\n";
+
+const PACMAN_MAGICS: [&[u8]; 4] = [
+    &[0x28, 0xB5, 0x2F, 0xFD],             // zstd
+    &[0xFD, 0x37, 0x7A, 0x58, 0x5A, 0x00], // xz
+    &[0x1F, 0x8B],                         // gzip
+    b"BZh",                                // bzip2
+];
+
+const MAGIC_LENGTH: usize = 8;
+
+/**
+ * Archive formats handled by package managers
+ */
+#[derive(Debug, Clone, PartialEq, Eq, Display)]
+pub enum ArchiveFormat {
+    #[strum(to_string = "pacman ( .pkg.tar.* )")]
+    Pacman,
+    #[strum(to_string = "debian ( .deb )")]
+    Deb,
+    #[strum(to_string = "unknown")]
+    Unknown,
+}
+
+/**
+ * Guess archive format from file name
+ */
+fn detect_from_extension(path: &Path) -> ArchiveFormat {
+    let filename = path
+        .file_name()
+        .map(|filename| filename.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    if filename.contains(".pkg.tar") {
+        ArchiveFormat::Pacman
+    } else if filename.ends_with(".deb") {
+        ArchiveFormat::Deb
+    } else {
+        ArchiveFormat::Unknown
+    }
+}
+
+/**
+ * Check archive content starts as expected by format
+ */
+fn matches_magic(format: &ArchiveFormat, header: &[u8]) -> bool {
+    match format {
+        ArchiveFormat::Pacman => PACMAN_MAGICS.iter().any(|magic| header.starts_with(magic)),
+        ArchiveFormat::Deb => header.starts_with(DEB_MAGIC),
+        ArchiveFormat::Unknown => false,
+    }
+}
+
+/**
+ * Detect archive format using its extension, confirmed by its magic bytes
+ */
+pub fn detect_archive_format(path: &Path) -> ArchiveFormat {
+    let format = detect_from_extension(path);
+
+    let mut header = [0u8; MAGIC_LENGTH];
+
+    let read_bytes = File::open(path)
+        .and_then(|mut file| file.read(&mut header))
+        .unwrap_or(0);
+
+    if matches_magic(&format, &header[..read_bytes]) {
+        format
+    } else {
+        ArchiveFormat::Unknown
+    }
+}
+
+/**
+ * Make sure archive is in format expected by package manager
+ */
+pub fn ensure_archive_format(
+    path: &Path,
+    expected_format: &ArchiveFormat,
+) -> Result<(), PackageManagerError> {
+    let format = detect_archive_format(path);
+
+    if format != *expected_format {
+        return Err(PackageManagerError::UnsupportedArchive(format!(
+            "expected {} archive, got {}",
+            expected_format, format
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use tempfile::TempDir;
+
+    use super::*;
+
+    /**
+     * Write archive with given name and content in given dir
+     */
+    fn write_archive(dir: &TempDir, filename: &str, content: &[u8]) -> std::path::PathBuf {
+        let path = dir.path().join(filename);
+
+        File::create(&path).unwrap().write_all(content).unwrap();
+
+        path
+    }
+
+    /**
+     * It should detect archive formats
+     */
+    #[test]
+    fn test_detect_archive_format() {
+        let dir = TempDir::new().unwrap();
+
+        let pacman_archive = write_archive(
+            &dir,
+            "neofetch-7.1.0-2-any.pkg.tar.zst",
+            &[0x28, 0xB5, 0x2F, 0xFD, 0x00],
+        );
+        let deb_archive = write_archive(&dir, "neofetch_7.1.0-2_all.deb", b"!<arch>\ndebian");
+
+        assert_eq!(
+            detect_archive_format(&pacman_archive),
+            ArchiveFormat::Pacman
+        );
+        assert_eq!(detect_archive_format(&deb_archive), ArchiveFormat::Deb);
+    }
+
+    /**
+     * It should not trust extension when magic bytes do not match
+     */
+    #[test]
+    fn test_detect_mismatching_archive_format() {
+        let dir = TempDir::new().unwrap();
+
+        let disguised_deb =
+            write_archive(&dir, "neofetch-7.1.0-2-any.pkg.tar.zst", b"!<arch>\ndebian");
+
+        assert_eq!(
+            detect_archive_format(&disguised_deb),
+            ArchiveFormat::Unknown
+        );
+    }
+
+    /**
+     * It should reject archives not matching expected format
+     */
+    #[test]
+    fn test_ensure_archive_format() {
+        let dir = TempDir::new().unwrap();
+
+        let deb_archive = write_archive(&dir, "neofetch_7.1.0-2_all.deb", b"!<arch>\ndebian");
+
+        assert_eq!(
+            ensure_archive_format(&deb_archive, &ArchiveFormat::Deb).is_ok(),
+            true
+        );
+        assert_eq!(
+            ensure_archive_format(&deb_archive, &ArchiveFormat::Pacman).is_err(),
+            true
+        );
+    }
+}
@@ -0,0 +1,323 @@
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use log::debug;
+use url::Url;
+
+use crate::packages::{package::Package, utils::signatures::verify_package};
+
+use super::{download_verified_archive, errors::package_manager_error::PackageManagerError};
+
+/**
+ * Build HTTP client used for downloads, routing every request through proxy when given
+ */
+pub fn build_http_client(proxy: &Option<String>) -> Result<reqwest::Client, PackageManagerError> {
+    let mut client_builder = reqwest::Client::builder();
+
+    if let Some(proxy_url) = proxy {
+        debug!("Using proxy {} for downloads...", proxy_url);
+
+        let proxy = reqwest::Proxy::all(proxy_url)
+            .map_err(|e| PackageManagerError::InvalidProxy(e.to_string()))?;
+
+        client_builder = client_builder.proxy(proxy);
+    }
+
+    client_builder
+        .build()
+        .map_err(|e| PackageManagerError::InvalidProxy(e.to_string()))
+}
+
+/**
+ * Download url content to file chunk by chunk, stopping as soon as cancelled is set
+ *
+ * Partially written file is removed on cancellation or failure
+ */
+pub async fn download_to_file(
+    http_client: &reqwest::Client,
+    url: &Url,
+    file_path: &Path,
+    cancelled: &AtomicBool,
+) -> Result<(), PackageManagerError> {
+    debug!("Downloading {} to {}...", url, file_path.display());
+
+    let download_result = write_chunks(http_client, url, file_path, cancelled).await;
+
+    if download_result.is_err() && file_path.exists() {
+        let _ = std::fs::remove_file(file_path);
+    }
+
+    debug!("Done downloading {} !", url);
+
+    download_result
+}
+
+/**
+ * Download package archive from given url to directory, checking maintainer signature and archive integrity
+ *
+ * Nothing gets installed, archive not matching integrity is removed
+ */
+pub async fn download_verified(
+    http_client: &reqwest::Client,
+    package: &Package,
+    archive_url: &Url,
+    target_dir: &Path,
+    cancelled: &AtomicBool,
+) -> Result<PathBuf, PackageManagerError> {
+    debug!("Downloading and verifying {}...", package.name);
+
+    // No need to download anything if package was not signed by its maintainer
+    if verify_package(package).is_none() {
+        return Err(PackageManagerError::InvalidSignature(format!(
+            "{}:{}",
+            package.name, package.version
+        )));
+    }
+
+    let archive_path = download_verified_archive(
+        http_client,
+        archive_url,
+        &package.integrity,
+        target_dir,
+        cancelled,
+    )
+    .await?;
+
+    debug!("Done downloading and verifying {} !", package.name);
+
+    Ok(archive_path)
+}
+
+/**
+ * Write response chunks to file, checking cancellation between chunks
+ */
+async fn write_chunks(
+    http_client: &reqwest::Client,
+    url: &Url,
+    file_path: &Path,
+    cancelled: &AtomicBool,
+) -> Result<(), PackageManagerError> {
+    if cancelled.load(Ordering::SeqCst) {
+        return Err(PackageManagerError::DownloadCancelled);
+    }
+
+    let mut response = http_client
+        .get(url.as_str())
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|_| PackageManagerError::DownloadError)?;
+
+    let mut file =
+        std::fs::File::create(file_path).map_err(|_| PackageManagerError::DownloadError)?;
+
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|_| PackageManagerError::DownloadError)?
+    {
+        if cancelled.load(Ordering::SeqCst) {
+            return Err(PackageManagerError::DownloadCancelled);
+        }
+
+        file.write_all(&chunk)
+            .map_err(|_| PackageManagerError::DownloadError)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader},
+        net::TcpListener,
+        sync::Arc,
+    };
+
+    use tempfile::TempDir;
+
+    use crate::{
+        packages::{package_builder::PackageBuilder, package_status::PackageStatus},
+        test_utils::package::tests::create_package_with_sig,
+    };
+
+    use super::*;
+
+    /**
+     * It should give up before fetching anything once cancelled, leaving no file behind
+     */
+    #[tokio::test]
+    async fn test_download_to_file_cancelled() {
+        let test_dir = TempDir::new().unwrap();
+
+        let file_path = test_dir.path().join("foo.pkg.tar.zst");
+
+        let url = Url::parse("http://127.0.0.1:9/foo.pkg.tar.zst").unwrap();
+
+        let cancelled = AtomicBool::new(true);
+
+        let download_result =
+            download_to_file(&reqwest::Client::new(), &url, &file_path, &cancelled).await;
+
+        assert_eq!(
+            matches!(download_result, Err(PackageManagerError::DownloadCancelled)),
+            true
+        );
+        assert_eq!(file_path.exists(), false);
+    }
+
+    /**
+     * It should stop once cancelled in the middle of a download, leaving no file behind
+     */
+    #[tokio::test]
+    async fn test_download_to_file_cancelled_mid_download() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let test_dir = TempDir::new()?;
+
+        let file_path = test_dir.path().join("foo.pkg.tar.zst");
+
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+
+        let url = Url::parse(&format!(
+            "http://{}/foo.pkg.tar.zst",
+            listener.local_addr()?
+        ))?;
+
+        let cancelled = Arc::new(AtomicBool::new(false));
+
+        let shared_cancelled = Arc::clone(&cancelled);
+
+        // Fake server sends first chunk, cancels, then sends the rest
+        let server_thread = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+
+            let mut request_line = String::new();
+
+            BufReader::new(stream.try_clone().unwrap())
+                .read_line(&mut request_line)
+                .unwrap();
+
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 6\r\nConnection: close\r\n\r\nfoo")
+                .unwrap();
+            stream.flush().unwrap();
+
+            std::thread::sleep(std::time::Duration::from_millis(100));
+
+            shared_cancelled.store(true, Ordering::SeqCst);
+
+            let _ = stream.write_all(b"bar");
+        });
+
+        let download_result =
+            download_to_file(&reqwest::Client::new(), &url, &file_path, &cancelled).await;
+
+        server_thread.join().unwrap();
+
+        assert_eq!(
+            matches!(download_result, Err(PackageManagerError::DownloadCancelled)),
+            true
+        );
+        assert_eq!(file_path.exists(), false);
+
+        Ok(())
+    }
+
+    /**
+     * It should send download request to configured proxy instead of archive host
+     */
+    #[tokio::test]
+    async fn test_download_to_file_through_proxy() -> Result<(), Box<dyn std::error::Error>> {
+        let test_dir = TempDir::new()?;
+
+        let file_path = test_dir.path().join("foo.pkg.tar.zst");
+
+        let proxy_listener = TcpListener::bind("127.0.0.1:0")?;
+
+        let proxy_url = format!("http://{}", proxy_listener.local_addr()?);
+
+        // Fake proxy answers first request and hands back its request line
+        let proxy_thread = std::thread::spawn(move || {
+            let (mut stream, _) = proxy_listener.accept().unwrap();
+
+            let mut request_line = String::new();
+
+            BufReader::new(stream.try_clone().unwrap())
+                .read_line(&mut request_line)
+                .unwrap();
+
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 3\r\nConnection: close\r\n\r\nfoo")
+                .unwrap();
+
+            request_line
+        });
+
+        let http_client = build_http_client(&Some(proxy_url))?;
+
+        // Host does not exist, only proxy can answer
+        let url = Url::parse("http://archives.bpm.invalid/foo.pkg.tar.zst")?;
+
+        download_to_file(&http_client, &url, &file_path, &AtomicBool::new(false)).await?;
+
+        let request_line = proxy_thread.join().unwrap();
+
+        assert_eq!(
+            request_line.starts_with("GET http://archives.bpm.invalid/foo.pkg.tar.zst"),
+            true
+        );
+        assert_eq!(std::fs::read(&file_path)?, b"foo");
+
+        Ok(())
+    }
+
+    /**
+     * It should refuse proxy which is not a valid url
+     */
+    #[test]
+    fn test_build_http_client_invalid_proxy() {
+        let build_result = build_http_client(&Some(String::from("not a proxy")));
+
+        assert_eq!(
+            matches!(build_result, Err(PackageManagerError::InvalidProxy(_))),
+            true
+        );
+    }
+
+    /**
+     * It should refuse package with invalid signature before downloading anything
+     */
+    #[tokio::test]
+    async fn test_download_verified_invalid_signature() -> Result<(), Box<dyn std::error::Error>> {
+        let test_dir = TempDir::new()?;
+
+        // Mutating signed package without signing it again invalidates signature
+        let forged_package = PackageBuilder::from_package(&create_package_with_sig()?)
+            .set_status(&PackageStatus::Prohibited)
+            .build();
+
+        let download_result = download_verified(
+            &reqwest::Client::new(),
+            &forged_package,
+            &forged_package.archive_url,
+            test_dir.path(),
+            &AtomicBool::new(false),
+        )
+        .await;
+
+        assert_eq!(
+            matches!(
+                download_result,
+                Err(PackageManagerError::InvalidSignature(_))
+            ),
+            true
+        );
+        assert_eq!(test_dir.path().read_dir()?.count(), 0);
+
+        Ok(())
+    }
+}
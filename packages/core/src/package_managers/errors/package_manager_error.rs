@@ -1,16 +1,65 @@
 use thiserror::Error;
 
+use crate::packages::errors::integrity_error::IntegrityError;
+
 /**
  * Package manager errors
  */
 #[derive(Error, Debug)]
 pub enum PackageManagerError {
+    #[error("No supported package manager detected on this system")]
+    NoPackageManager,
+
+    #[error("Proxy could not be used for downloads: {0}")]
+    InvalidProxy(String),
+
     #[error("Package manager could not download package")]
     DownloadError,
 
+    #[error("Package download was cancelled")]
+    DownloadCancelled,
+
     #[error("Package manager could not install package: {0}")]
     InstallationError(String),
 
     #[error("Package manager could not remove package: {0}")]
     RemovalError(String),
+
+    #[error("Package manager could not list installed packages: {0}")]
+    ListingError(String),
+
+    #[error("Package manager transaction log could not be read: {0}")]
+    TransactionLogError(String),
+
+    #[error("Package manager configuration is invalid: {0}")]
+    InvalidConfiguration(String),
+
+    #[error("Package name is invalid: {0}")]
+    InvalidPackageName(String),
+
+    #[error("Archive is not supported by package manager: {0}")]
+    UnsupportedArchive(String),
+
+    #[error("Downloaded archive does not match package integrity: {0}")]
+    IntegrityMismatch(String),
+
+    #[error("Package signature does not match its maintainer: {0}")]
+    InvalidSignature(String),
+
+    #[error("Archive url could not be expanded: {0}")]
+    InvalidArchiveUrl(String),
+
+    #[error("Package integrity algorithm is not supported, refusing to install: {0}")]
+    UnsupportedIntegrityAlgorithm(String),
+}
+
+impl From<IntegrityError> for PackageManagerError {
+    fn from(value: IntegrityError) -> Self {
+        match value {
+            IntegrityError::UnsupportedAlgorithm(algorithm) => {
+                PackageManagerError::UnsupportedIntegrityAlgorithm(algorithm)
+            }
+            IntegrityError::ReadError(e) => PackageManagerError::InstallationError(e),
+        }
+    }
 }
@@ -0,0 +1,55 @@
+/**
+ * Package installed on system, as reported by package manager
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstalledPackage {
+    pub name: String,
+    pub version: String,
+}
+
+/**
+ * Parse package manager listing made of one "name version" line per installed package
+ */
+pub fn parse_installed_packages(output: &str) -> Vec<InstalledPackage> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+
+            let name = fields.next()?;
+            let version = fields.next()?;
+
+            Some(InstalledPackage {
+                name: name.to_string(),
+                version: version.to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /**
+     * It should parse one package per line, ignoring incomplete ones
+     */
+    #[test]
+    fn test_parse_installed_packages() {
+        let output = "foo 1.0.0-1\nbar 2:2.1-3\n\nbaz\n";
+
+        assert_eq!(
+            parse_installed_packages(output),
+            vec![
+                InstalledPackage {
+                    name: String::from("foo"),
+                    version: String::from("1.0.0-1"),
+                },
+                InstalledPackage {
+                    name: String::from("bar"),
+                    version: String::from("2:2.1-3"),
+                },
+            ]
+        );
+    }
+}
@@ -1,15 +1,27 @@
 use std::{
+    path::{Path, PathBuf},
     process::{Command, Stdio},
-    sync::Arc,
+    sync::{atomic::AtomicBool, Arc},
 };
 
+use apt::apt_package_manager::AptPackageManager;
+use download::{build_http_client, download_to_file};
+use errors::package_manager_error::PackageManagerError;
 use log::{debug, error};
 use pacman::pacman_package_manager::PacmanPackageManager;
 use traits::package_manager::PackageManager;
+use url::Url;
 
+use crate::{config::core_config::CoreConfig, packages::package_integrity::PackageIntegrity};
+
+pub mod apt;
+pub mod archive;
+pub mod download;
 pub mod errors;
+pub mod installed_package;
 pub mod pacman;
 pub mod traits;
+pub mod url_template;
 
 /**
  * Check if package manager exists
@@ -33,15 +45,101 @@ fn check_package_manager_exists(command_name: &str) -> bool {
     }
 }
 
+/**
+ * Make sure package name cannot be interpreted as a package manager flag
+ */
+pub(crate) fn validate_package_name(package_name: &str) -> Result<(), PackageManagerError> {
+    if package_name.is_empty() || package_name.starts_with('-') {
+        return Err(PackageManagerError::InvalidPackageName(
+            package_name.to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/**
+ * Download archive from url into directory, naming it after last url path segment
+ *
+ * Shared by every package manager so they all go through the same download path
+ */
+pub async fn download_archive(
+    http_client: &reqwest::Client,
+    archive_url: &Url,
+    target_dir: &Path,
+    cancelled: &AtomicBool,
+) -> Result<PathBuf, PackageManagerError> {
+    let archive_filename = PathBuf::from(archive_url.path())
+        .file_name()
+        .ok_or(PackageManagerError::DownloadError)?
+        .to_owned();
+
+    let archive_path = target_dir.join(archive_filename);
+
+    debug!("Writing archive at {}...", archive_path.display());
+
+    download_to_file(http_client, archive_url, &archive_path, cancelled).await?;
+
+    debug!("Done writing archive !");
+
+    Ok(archive_path)
+}
+
+/**
+ * Download archive from url into directory then make sure it matches integrity
+ *
+ * Archive not matching integrity is removed so it cannot be installed by mistake
+ */
+pub async fn download_verified_archive(
+    http_client: &reqwest::Client,
+    archive_url: &Url,
+    integrity: &PackageIntegrity,
+    target_dir: &Path,
+    cancelled: &AtomicBool,
+) -> Result<PathBuf, PackageManagerError> {
+    // Archive must never be installed without being verified
+    integrity.ensure_supported_algorithm()?;
+
+    let archive_path = download_archive(http_client, archive_url, target_dir, cancelled).await?;
+
+    debug!("Verifying archive at {}...", archive_path.display());
+
+    let is_archive_valid = integrity.verify_file(&archive_path).await?;
+
+    if !is_archive_valid {
+        let _ = std::fs::remove_file(&archive_path);
+
+        return Err(PackageManagerError::IntegrityMismatch(
+            archive_url.to_string(),
+        ));
+    }
+
+    debug!("Done verifying archive !");
+
+    Ok(archive_path)
+}
+
+/**
+ * Build HTTP client from configured proxy
+ *
+ * Invalid proxy is refused, downloads must never bypass it through a direct connection
+ */
+pub fn init_http_client(core_config: &CoreConfig) -> Result<reqwest::Client, PackageManagerError> {
+    build_http_client(&core_config.proxy)
+}
+
 /**
  * Probe and init package managers
  */
 
 #[cfg(not(tarpaulin_include))] // TODO : Figure out way to test on multiple envs
-pub async fn init_package_managers() -> Vec<Arc<Box<dyn PackageManager>>> {
+pub async fn init_package_managers(
+    core_config: &CoreConfig,
+    http_client: &reqwest::Client,
+) -> Vec<Arc<Box<dyn PackageManager>>> {
     debug!("Probing installed package managers...");
 
-    let supported_package_managers = vec!["pacman"];
+    let supported_package_managers = vec!["pacman", "apt"];
 
     let mut package_managers: Vec<Arc<Box<dyn PackageManager>>> = vec![];
 
@@ -55,7 +153,27 @@ pub async fn init_package_managers() -> Vec<Arc<Box<dyn PackageManager>>> {
 
         // If so, build struct then cast to PackageManager trait
         let package_manager: Arc<Box<dyn PackageManager>> = match package_manager_cmd {
-            "pacman" => Arc::new(Box::new(PacmanPackageManager::default())),
+            "pacman" => {
+                let pacman_config = core_config.pacman.clone().unwrap_or_default();
+
+                let mut pacman =
+                    PacmanPackageManager::from_config(&pacman_config).unwrap_or_else(|e| {
+                        error!("{}, using default pacman configuration", e);
+
+                        PacmanPackageManager::default()
+                    });
+
+                pacman.set_http_client(http_client);
+
+                Arc::new(Box::new(pacman))
+            }
+            "apt" => {
+                let mut apt = AptPackageManager::default();
+
+                apt.set_http_client(http_client);
+
+                Arc::new(Box::new(apt))
+            }
             _ => {
                 error!(
                     "Package manager {} exists, but does not match any known struct",
@@ -76,3 +194,167 @@ pub async fn init_package_managers() -> Vec<Arc<Box<dyn PackageManager>>> {
 
     package_managers
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Write, net::TcpListener};
+
+    use sha2::{Digest, Sha256};
+    use tempfile::TempDir;
+
+    use super::*;
+
+    /**
+     * Serve single response on local port, returning its url
+     */
+    fn serve_once(response: &'static [u8]) -> Result<Url, Box<dyn std::error::Error>> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+
+        let url = Url::parse(&format!(
+            "http://{}/archives/foo.pkg.tar.zst",
+            listener.local_addr()?
+        ))?;
+
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+
+            // Read request before answering, content does not matter
+            let mut request = [0; 1024];
+            let _ = std::io::Read::read(&mut stream, &mut request);
+
+            stream.write_all(response).unwrap();
+        });
+
+        Ok(url)
+    }
+
+    /**
+     * It should download archive into directory, named after url
+     */
+    #[tokio::test]
+    async fn test_download_archive() -> Result<(), Box<dyn std::error::Error>> {
+        let test_dir = TempDir::new()?;
+
+        let url =
+            serve_once(b"HTTP/1.1 200 OK\r\nContent-Length: 3\r\nConnection: close\r\n\r\nfoo")?;
+
+        let archive_path = download_archive(
+            &reqwest::Client::new(),
+            &url,
+            test_dir.path(),
+            &AtomicBool::new(false),
+        )
+        .await?;
+
+        assert_eq!(archive_path, test_dir.path().join("foo.pkg.tar.zst"));
+        assert_eq!(std::fs::read(&archive_path)?, b"foo");
+
+        Ok(())
+    }
+
+    /**
+     * It should fail on missing archive, leaving no file behind
+     */
+    #[tokio::test]
+    async fn test_download_archive_not_found() -> Result<(), Box<dyn std::error::Error>> {
+        let test_dir = TempDir::new()?;
+
+        let url = serve_once(
+            b"HTTP/1.1 404 Not Found\r\nContent-Length: 9\r\nConnection: close\r\n\r\nnot found",
+        )?;
+
+        let download_result = download_archive(
+            &reqwest::Client::new(),
+            &url,
+            test_dir.path(),
+            &AtomicBool::new(false),
+        )
+        .await;
+
+        assert_eq!(
+            matches!(download_result, Err(PackageManagerError::DownloadError)),
+            true
+        );
+        assert_eq!(test_dir.path().read_dir()?.count(), 0);
+
+        Ok(())
+    }
+
+    /**
+     * It should keep archive matching integrity hash
+     */
+    #[tokio::test]
+    async fn test_download_verified_archive() -> Result<(), Box<dyn std::error::Error>> {
+        let test_dir = TempDir::new()?;
+
+        let url =
+            serve_once(b"HTTP/1.1 200 OK\r\nContent-Length: 3\r\nConnection: close\r\n\r\nfoo")?;
+
+        let integrity = PackageIntegrity {
+            algorithm: String::from("SHA256"),
+            archive_hash: Sha256::digest("foo").to_vec(),
+            source_code_hash: vec![],
+        };
+
+        let archive_path = download_verified_archive(
+            &reqwest::Client::new(),
+            &url,
+            &integrity,
+            test_dir.path(),
+            &AtomicBool::new(false),
+        )
+        .await?;
+
+        assert_eq!(std::fs::read(&archive_path)?, b"foo");
+
+        Ok(())
+    }
+
+    /**
+     * It should refuse archive not matching integrity hash, leaving no file behind
+     */
+    #[tokio::test]
+    async fn test_download_verified_archive_mismatch() -> Result<(), Box<dyn std::error::Error>> {
+        let test_dir = TempDir::new()?;
+
+        // Mirror serves different content than the one which was signed
+        let url =
+            serve_once(b"HTTP/1.1 200 OK\r\nContent-Length: 3\r\nConnection: close\r\n\r\nbar")?;
+
+        let integrity = PackageIntegrity {
+            algorithm: String::from("SHA256"),
+            archive_hash: Sha256::digest("foo").to_vec(),
+            source_code_hash: vec![],
+        };
+
+        let download_result = download_verified_archive(
+            &reqwest::Client::new(),
+            &url,
+            &integrity,
+            test_dir.path(),
+            &AtomicBool::new(false),
+        )
+        .await;
+
+        assert_eq!(
+            matches!(
+                download_result,
+                Err(PackageManagerError::IntegrityMismatch(_))
+            ),
+            true
+        );
+        assert_eq!(test_dir.path().read_dir()?.count(), 0);
+
+        Ok(())
+    }
+
+    /**
+     * It should refuse package names which could be interpreted as flags
+     */
+    #[test]
+    fn test_validate_package_name() {
+        assert_eq!(validate_package_name("foo").is_ok(), true);
+        assert_eq!(validate_package_name("-Rns").is_err(), true);
+        assert_eq!(validate_package_name("").is_err(), true);
+    }
+}
@@ -1 +1,3 @@
+pub mod pacman_config;
+pub mod pacman_log;
 pub mod pacman_package_manager;
@@ -0,0 +1,107 @@
+use crate::package_managers::errors::package_manager_error::PackageManagerError;
+
+/**
+ * Flags which may be passed to pacman when installing ( -U )
+ */
+const ALLOWED_INSTALL_FLAGS: [&str; 4] = ["--noconfirm", "--needed", "--asdeps", "--asexplicit"];
+
+/**
+ * Flags which may be passed to pacman when removing ( -Rsn )
+ */
+const ALLOWED_REMOVE_FLAGS: [&str; 2] = ["--noconfirm", "--nosave"];
+
+/**
+ * Pacman configuration
+ */
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PacmanConfig {
+    pub install_flags: Vec<String>,
+    pub remove_flags: Vec<String>,
+}
+
+impl PacmanConfig {
+    /**
+     * Make sure every flag is allowlisted so config cannot inject arbitrary arguments
+     */
+    pub fn validate(&self) -> Result<(), PackageManagerError> {
+        let invalid_install_flag = self
+            .install_flags
+            .iter()
+            .find(|flag| !ALLOWED_INSTALL_FLAGS.contains(&flag.as_str()));
+
+        if let Some(flag) = invalid_install_flag {
+            return Err(PackageManagerError::InvalidConfiguration(format!(
+                "install flag {} is not allowed",
+                flag
+            )));
+        }
+
+        let invalid_remove_flag = self
+            .remove_flags
+            .iter()
+            .find(|flag| !ALLOWED_REMOVE_FLAGS.contains(&flag.as_str()));
+
+        if let Some(flag) = invalid_remove_flag {
+            return Err(PackageManagerError::InvalidConfiguration(format!(
+                "remove flag {} is not allowed",
+                flag
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for PacmanConfig {
+    fn default() -> Self {
+        Self {
+            install_flags: vec![String::from("--noconfirm")],
+            remove_flags: vec![String::from("--noconfirm")],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /**
+     * It should accept default config
+     */
+    #[test]
+    fn test_validate_default_config() {
+        assert_eq!(PacmanConfig::default().validate().is_ok(), true);
+    }
+
+    /**
+     * It should accept allowlisted flags
+     */
+    #[test]
+    fn test_validate_allowed_flags() {
+        let config = PacmanConfig {
+            install_flags: vec![String::from("--needed")],
+            remove_flags: vec![],
+        };
+
+        assert_eq!(config.validate().is_ok(), true);
+    }
+
+    /**
+     * It should reject flags which are not allowlisted
+     */
+    #[test]
+    fn test_validate_rejects_unknown_flags() {
+        let install_config = PacmanConfig {
+            install_flags: vec![String::from("--noconfirm; rm -rf /")],
+            remove_flags: vec![],
+        };
+
+        let remove_config = PacmanConfig {
+            install_flags: vec![],
+            remove_flags: vec![String::from("--needed")],
+        };
+
+        assert_eq!(install_config.validate().is_err(), true);
+        assert_eq!(remove_config.validate().is_err(), true);
+    }
+}
@@ -0,0 +1,76 @@
+const TRANSACTION_STARTED: &str = "[ALPM] transaction started";
+
+const TRANSACTION_ACTIONS: [&str; 5] = [
+    "installed",
+    "upgraded",
+    "downgraded",
+    "reinstalled",
+    "removed",
+];
+
+/**
+ * Parse pacman log then return names of packages affected by most recent transaction
+ *
+ * Log lines look like : [2024-01-01T00:00:00+0000] [ALPM] installed foo (1.0-1)
+ */
+pub fn parse_last_transaction(log: &str) -> Vec<String> {
+    let last_transaction = match log.rfind(TRANSACTION_STARTED) {
+        Some(transaction_start) => &log[transaction_start..],
+        None => return vec![],
+    };
+
+    last_transaction
+        .lines()
+        .filter_map(|line| {
+            let (_, alpm_message) = line.split_once("[ALPM] ")?;
+
+            let mut message_parts = alpm_message.split_whitespace();
+
+            let action = message_parts.next()?;
+
+            if !TRANSACTION_ACTIONS.contains(&action) {
+                return None;
+            }
+
+            message_parts.next().map(String::from)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /**
+     * It should only return packages from last transaction
+     */
+    #[test]
+    fn test_parse_last_transaction() {
+        let log = "\
+[2024-01-01T00:00:00+0000] [PACMAN] Running 'pacman -U foo.pkg.tar.zst'
+[2024-01-01T00:00:00+0000] [ALPM] transaction started
+[2024-01-01T00:00:00+0000] [ALPM] installed foo (1.0-1)
+[2024-01-01T00:00:00+0000] [ALPM] transaction completed
+[2024-01-02T00:00:00+0000] [PACMAN] Running 'pacman -U bar.pkg.tar.zst'
+[2024-01-02T00:00:00+0000] [ALPM] transaction started
+[2024-01-02T00:00:00+0000] [ALPM] installed bar (2.0-1)
+[2024-01-02T00:00:00+0000] [ALPM] upgraded baz (1.0-1 -> 1.1-1)
+[2024-01-02T00:00:00+0000] [ALPM-SCRIPTLET] some hook output
+[2024-01-02T00:00:00+0000] [ALPM] running 'some-hook.hook'...
+[2024-01-02T00:00:00+0000] [ALPM] transaction completed
+";
+
+        assert_eq!(
+            parse_last_transaction(log),
+            vec![String::from("bar"), String::from("baz")]
+        );
+    }
+
+    /**
+     * It should return nothing when no transaction happened
+     */
+    #[test]
+    fn test_parse_last_transaction_empty() {
+        assert_eq!(parse_last_transaction(""), Vec::<String>::new());
+    }
+}
@@ -1,17 +1,61 @@
-use crate::package_managers::{
-    errors::package_manager_error::PackageManagerError, traits::package_manager::PackageManager,
+use crate::{
+    package_managers::{
+        archive::{ensure_archive_format, ArchiveFormat},
+        download_verified_archive,
+        errors::package_manager_error::PackageManagerError,
+        installed_package::{parse_installed_packages, InstalledPackage},
+        traits::package_manager::PackageManager,
+        validate_package_name,
+    },
+    packages::package_integrity::PackageIntegrity,
 };
+
+use super::{pacman_config::PacmanConfig, pacman_log::parse_last_transaction};
 use log::debug;
 use std::{
-    io::Cursor,
-    path::{Path, PathBuf},
+    path::PathBuf,
     process::Command,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
 };
 use url::Url;
 
 use tempfile::tempdir;
 
-pub struct PacmanPackageManager;
+const PACMAN_LOG_PATH: &str = "/var/log/pacman.log";
+
+pub struct PacmanPackageManager {
+    config: PacmanConfig,
+    download_cancelled: Arc<AtomicBool>,
+    http_client: reqwest::Client,
+}
+
+impl PacmanPackageManager {
+    /**
+     * Create using given config, rejecting flags which are not allowlisted
+     */
+    pub fn from_config(config: &PacmanConfig) -> Result<Self, PackageManagerError> {
+        config.validate()?;
+
+        let instance = Self {
+            config: config.clone(),
+            download_cancelled: Arc::new(AtomicBool::new(false)),
+            http_client: reqwest::Client::new(),
+        };
+
+        Ok(instance)
+    }
+
+    /**
+     * Set HTTP client used to fetch archives, e.g. one going through configured proxy
+     */
+    pub fn set_http_client(&mut self, http_client: &reqwest::Client) -> &mut Self {
+        self.http_client = http_client.clone();
+        self
+    }
+}
 
 #[cfg(not(tarpaulin_include))] // TODO : Figure out way to test on multiple envs
 impl PacmanPackageManager {
@@ -24,7 +68,10 @@ impl PacmanPackageManager {
             archive_path.display()
         );
         let pacman_process = Command::new("pacman")
-            .args(["-U", archive_path.to_str().unwrap(), "--noconfirm"])
+            .arg("-U")
+            .args(&self.config.install_flags)
+            .arg("--")
+            .arg(archive_path)
             .spawn()
             .map_err(|e| PackageManagerError::InstallationError(e.to_string()))?;
 
@@ -33,7 +80,7 @@ impl PacmanPackageManager {
             .map_err(|e| PackageManagerError::InstallationError(e.to_string()))?;
 
         if !output.status.success() {
-            let output_str = String::from_utf8(output.stderr).unwrap();
+            let output_str = String::from_utf8_lossy(&output.stderr).to_string();
             Err(PackageManagerError::InstallationError(output_str))
         } else {
             debug!(
@@ -44,52 +91,6 @@ impl PacmanPackageManager {
             Ok(())
         }
     }
-
-    /**
-     * Fetch package archive
-     */
-    async fn fetch_archive(
-        &self,
-        package_url: &Url,
-        temp_dir_path: &Path,
-    ) -> Result<PathBuf, PackageManagerError> {
-        let package_path = PathBuf::from(package_url.as_str());
-
-        let package_path_components = package_path.components();
-
-        let package_filename = package_path_components
-            .last()
-            .unwrap()
-            .as_os_str()
-            .to_str()
-            .unwrap();
-
-        let temp_package_path = temp_dir_path.join(package_filename);
-
-        debug!("Writing package at {}...", temp_package_path.display());
-
-        // Fetch package, save it
-
-        let response = reqwest::get(package_url.as_str())
-            .await
-            .map_err(|_| PackageManagerError::DownloadError)?;
-
-        let mut file = std::fs::File::create(&temp_package_path)
-            .map_err(|_| PackageManagerError::DownloadError)?;
-
-        let mut content = Cursor::new(
-            response
-                .bytes()
-                .await
-                .map_err(|_| PackageManagerError::DownloadError)?,
-        );
-
-        std::io::copy(&mut content, &mut file).map_err(|_| PackageManagerError::DownloadError)?;
-
-        debug!("Done writing package !");
-
-        Ok(temp_package_path)
-    }
 }
 
 #[async_trait::async_trait]
@@ -103,37 +104,64 @@ impl PackageManager for PacmanPackageManager {
     }
 
     /**
-     * Fetch package content ( binaries, manpages... )
+     * Fetch package content then install it only if it matches integrity
      */
-    async fn install_from_url(&self, package_url: &Url) -> Result<PathBuf, PackageManagerError> {
+    async fn install_verified(
+        &self,
+        package_url: &Url,
+        integrity: &PackageIntegrity,
+    ) -> Result<PathBuf, PackageManagerError> {
         debug!(
-            "Installing from url (location: {})...",
+            "Installing verified package from url (location: {})...",
             package_url.to_string()
         );
 
         let temp_package_dir =
             tempdir().map_err(|e| PackageManagerError::InstallationError(e.to_string()))?;
 
-        let temp_package_dir_path = temp_package_dir.path();
+        let compressed_archive_path = download_verified_archive(
+            &self.http_client,
+            package_url,
+            integrity,
+            temp_package_dir.path(),
+            &self.download_cancelled,
+        )
+        .await?;
 
-        // Download package
-        let compressed_archive_path = self
-            .fetch_archive(package_url, temp_package_dir_path)
-            .await?;
+        ensure_archive_format(&compressed_archive_path, &ArchiveFormat::Pacman)?;
 
         self.install_archive(&compressed_archive_path)?;
 
-        debug!("Done installing package from url !");
+        debug!("Done installing verified package from url !");
 
         Ok(compressed_archive_path)
     }
 
+    /**
+     * Abort download in progress, partial archive gets removed
+     */
+    fn cancel_download(&self) {
+        self.download_cancelled.store(true, Ordering::SeqCst);
+    }
+
+    /**
+     * Forget previous cancellation so next downloads can proceed
+     */
+    fn reset_download(&self) {
+        self.download_cancelled.store(false, Ordering::SeqCst);
+    }
+
     /**
      * Remove package using pacman
      */
     async fn remove(&self, package_name: &String) -> Result<(), PackageManagerError> {
+        validate_package_name(package_name)?;
+
         let pacman_process = Command::new("pacman")
-            .args(["-Rsn", package_name.as_str(), "--noconfirm"])
+            .arg("-Rsn")
+            .args(&self.config.remove_flags)
+            .arg("--")
+            .arg(package_name)
             .spawn()
             .map_err(|e| PackageManagerError::RemovalError(e.to_string()))?;
 
@@ -142,7 +170,7 @@ impl PackageManager for PacmanPackageManager {
             .map_err(|e| PackageManagerError::RemovalError(e.to_string()))?;
 
         if !output.status.success() {
-            let output_str = String::from_utf8(output.stderr).unwrap();
+            let output_str = String::from_utf8_lossy(&output.stderr).to_string();
             Err(PackageManagerError::RemovalError(output_str))
         } else {
             debug!("Done removing package {} using pacman !", package_name);
@@ -150,13 +178,78 @@ impl PackageManager for PacmanPackageManager {
             Ok(())
         }
     }
+
+    /**
+     * Get packages installed on system using pacman
+     */
+    async fn list_installed(&self) -> Result<Vec<InstalledPackage>, PackageManagerError> {
+        debug!("Listing packages installed using pacman...");
+
+        let output = Command::new("pacman")
+            .arg("-Q")
+            .output()
+            .map_err(|e| PackageManagerError::ListingError(e.to_string()))?;
+
+        if !output.status.success() {
+            let output_str = String::from_utf8_lossy(&output.stderr).to_string();
+            return Err(PackageManagerError::ListingError(output_str));
+        }
+
+        let installed_packages = parse_installed_packages(&String::from_utf8_lossy(&output.stdout));
+
+        debug!(
+            "Done listing packages installed using pacman ! ( {} packages found )",
+            installed_packages.len()
+        );
+
+        Ok(installed_packages)
+    }
+
+    /**
+     * Get packages affected by most recent pacman transaction using its log
+     */
+    async fn last_transaction(&self) -> Result<Vec<String>, PackageManagerError> {
+        debug!("Reading last pacman transaction...");
+
+        let log = tokio::fs::read_to_string(PACMAN_LOG_PATH)
+            .await
+            .map_err(|e| PackageManagerError::TransactionLogError(e.to_string()))?;
+
+        let affected_packages = parse_last_transaction(&log);
+
+        debug!(
+            "Done reading last pacman transaction ! ( {} packages affected )",
+            affected_packages.len()
+        );
+
+        Ok(affected_packages)
+    }
 }
 
 impl Default for PacmanPackageManager {
     fn default() -> Self {
-        Self {}
+        Self {
+            config: PacmanConfig::default(),
+            download_cancelled: Arc::new(AtomicBool::new(false)),
+            http_client: reqwest::Client::new(),
+        }
     }
 }
 
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use super::*;
+
+    /**
+     * It should refuse config with flags which are not allowlisted
+     */
+    #[test]
+    fn test_from_invalid_config() {
+        let config = PacmanConfig {
+            install_flags: vec![String::from("--overwrite=*")],
+            remove_flags: vec![],
+        };
+
+        assert_eq!(PacmanPackageManager::from_config(&config).is_err(), true);
+    }
+}
@@ -2,19 +2,50 @@ use std::path::PathBuf;
 
 use url::Url;
 
-use crate::package_managers::errors::package_manager_error::PackageManagerError;
+use crate::{
+    package_managers::{
+        errors::package_manager_error::PackageManagerError, installed_package::InstalledPackage,
+    },
+    packages::package_integrity::PackageIntegrity,
+};
 
 #[cfg(test)]
 use mockall::automock;
 
 #[async_trait::async_trait]
 #[cfg_attr(test, automock)]
-pub trait PackageManager {
-
+pub trait PackageManager: Send + Sync {
     fn get_name(&self) -> String;
 
-    async fn install_from_url(&self, package_url: &Url) -> Result<PathBuf, PackageManagerError>;
+    /**
+     * Install from url, refusing archive not matching given integrity
+     */
+    async fn install_verified(
+        &self,
+        package_url: &Url,
+        integrity: &PackageIntegrity,
+    ) -> Result<PathBuf, PackageManagerError>;
+
+    /**
+     * Abort download in progress, if any
+     */
+    fn cancel_download(&self);
+
+    /**
+     * Forget previous cancellation, done once before each install operation
+     */
+    fn reset_download(&self);
 
     // TODO : When feature to fetch installed packages implement use Package object instead
     async fn remove(&self, package_name: &String) -> Result<(), PackageManagerError>;
+
+    /**
+     * Get packages installed on system along with their versions
+     */
+    async fn list_installed(&self) -> Result<Vec<InstalledPackage>, PackageManagerError>;
+
+    /**
+     * Get names of packages affected by most recent package manager operation
+     */
+    async fn last_transaction(&self) -> Result<Vec<String>, PackageManagerError>;
 }
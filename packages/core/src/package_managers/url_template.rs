@@ -0,0 +1,242 @@
+use url::Url;
+
+use super::errors::package_manager_error::PackageManagerError;
+
+/**
+ * Variables archive urls may reference as ${NAME}
+ *
+ * Restricted so a published package cannot make installers leak arbitrary environment values to its mirror
+ */
+pub const ALLOWED_URL_VARIABLES: [&str; 3] =
+    ["BPM_MIRROR", "BPM_MIRROR_REGION", "BPM_MIRROR_TOKEN"];
+
+// Variable holding configured mirror host, eg: mirror.example.com:8080
+const MIRROR_URL_VARIABLE: &str = "BPM_MIRROR";
+
+// Variables only sent to configured mirror, never to a host chosen by publisher
+const SECRET_URL_VARIABLES: [&str; 1] = ["BPM_MIRROR_TOKEN"];
+
+// Url parser percent-encodes braces found in path
+const ENCODED_OPENING_BRACE: &str = "%7B";
+const ENCODED_CLOSING_BRACE: &str = "%7D";
+
+/**
+ * Replace ${NAME} placeholders in archive url using given lookup
+ *
+ * Names are matched ignoring case as url parser lowercases hosts.
+ * Signed package keeps templated url, only url used to download gets expanded
+ */
+pub fn expand_archive_url(
+    archive_url: &Url,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> Result<Url, PackageManagerError> {
+    let mut rest = archive_url.as_str();
+    let mut expanded = String::with_capacity(rest.len());
+    let mut uses_secret = false;
+
+    while let Some(start) = rest.find('$') {
+        expanded.push_str(&rest[..start]);
+
+        let after_dollar = &rest[start + 1..];
+
+        let name_start = if after_dollar.starts_with('{') {
+            1
+        } else if starts_with_ignore_case(after_dollar, ENCODED_OPENING_BRACE) {
+            ENCODED_OPENING_BRACE.len()
+        } else {
+            // Not a placeholder, keep dollar as is
+            expanded.push('$');
+            rest = after_dollar;
+            continue;
+        };
+
+        let after_brace = &after_dollar[name_start..];
+
+        let name_length = after_brace
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(after_brace.len());
+
+        let (name, after_name) = after_brace.split_at(name_length);
+
+        let name_end = if after_name.starts_with('}') {
+            1
+        } else if starts_with_ignore_case(after_name, ENCODED_CLOSING_BRACE) {
+            ENCODED_CLOSING_BRACE.len()
+        } else {
+            return Err(PackageManagerError::InvalidArchiveUrl(format!(
+                "unterminated placeholder in {}",
+                archive_url
+            )));
+        };
+
+        let name = ALLOWED_URL_VARIABLES
+            .iter()
+            .find(|allowed_name| allowed_name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| {
+                PackageManagerError::InvalidArchiveUrl(format!(
+                    "{} is not an allowed url variable",
+                    name
+                ))
+            })?;
+
+        uses_secret |= SECRET_URL_VARIABLES.contains(name);
+
+        let value = lookup(name).ok_or_else(|| {
+            PackageManagerError::InvalidArchiveUrl(format!("{} is not set", name))
+        })?;
+
+        expanded.push_str(&value);
+
+        rest = &after_name[name_end..];
+    }
+
+    expanded.push_str(rest);
+
+    let expanded_url =
+        Url::parse(&expanded).map_err(|e| PackageManagerError::InvalidArchiveUrl(e.to_string()))?;
+
+    if uses_secret && !is_mirror_url(&expanded_url, lookup) {
+        return Err(PackageManagerError::InvalidArchiveUrl(format!(
+            "{} is only sent to configured mirror",
+            SECRET_URL_VARIABLES.join(", ")
+        )));
+    }
+
+    Ok(expanded_url)
+}
+
+/**
+ * Check if url points to configured mirror, port included when mirror has one
+ */
+fn is_mirror_url(url: &Url, lookup: &dyn Fn(&str) -> Option<String>) -> bool {
+    let mirror = match lookup(MIRROR_URL_VARIABLE) {
+        Some(mirror) => mirror,
+        None => return false,
+    };
+
+    let host = match (url.host_str(), url.port()) {
+        (Some(host), Some(port)) if mirror.contains(':') => format!("{}:{}", host, port),
+        (Some(host), _) => host.to_string(),
+        (None, _) => return false,
+    };
+
+    host.eq_ignore_ascii_case(&mirror)
+}
+
+fn starts_with_ignore_case(value: &str, prefix: &str) -> bool {
+    value
+        .get(..prefix.len())
+        .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(name: &str) -> Option<String> {
+        match name {
+            "BPM_MIRROR" => Some(String::from("mirror.example.com")),
+            "BPM_MIRROR_REGION" => Some(String::from("eu-west")),
+            "BPM_MIRROR_TOKEN" => Some(String::from("s3cr3t")),
+            _ => None,
+        }
+    }
+
+    /**
+     * It should expand allowed placeholders, whether braces were percent-encoded or not
+     */
+    #[test]
+    fn test_expand_archive_url() {
+        let archive_url =
+            Url::parse("https://packages.example.com/${BPM_MIRROR_REGION}/n/neofetch.pkg.tar.zst")
+                .unwrap();
+
+        let expanded_url = expand_archive_url(&archive_url, &lookup).unwrap();
+
+        assert_eq!(
+            expanded_url.as_str(),
+            "https://packages.example.com/eu-west/n/neofetch.pkg.tar.zst"
+        );
+    }
+
+    /**
+     * It should expand placeholder in host, even though url parser lowercased it
+     */
+    #[test]
+    fn test_expand_archive_url_in_host() {
+        let archive_url = Url::parse("https://${BPM_MIRROR}/n/neofetch.pkg.tar.zst").unwrap();
+
+        let expanded_url = expand_archive_url(&archive_url, &lookup).unwrap();
+
+        assert_eq!(
+            expanded_url.as_str(),
+            "https://mirror.example.com/n/neofetch.pkg.tar.zst"
+        );
+    }
+
+    /**
+     * It should only send secret to configured mirror
+     */
+    #[test]
+    fn test_expand_archive_url_secret() {
+        let mirror_url =
+            Url::parse("https://${BPM_MIRROR}/neofetch.pkg.tar.zst?token=${BPM_MIRROR_TOKEN}")
+                .unwrap();
+
+        assert_eq!(
+            expand_archive_url(&mirror_url, &lookup).unwrap().as_str(),
+            "https://mirror.example.com/neofetch.pkg.tar.zst?token=s3cr3t"
+        );
+
+        for leaking_url in [
+            "https://evil.example.com/?t=${BPM_MIRROR_TOKEN}",
+            "https://${BPM_MIRROR}.evil.example.com/?t=${BPM_MIRROR_TOKEN}",
+            "https://${BPM_MIRROR}@evil.example.com/?t=${BPM_MIRROR_TOKEN}",
+        ] {
+            assert_eq!(
+                matches!(
+                    expand_archive_url(&Url::parse(leaking_url).unwrap(), &lookup),
+                    Err(PackageManagerError::InvalidArchiveUrl(_))
+                ),
+                true
+            );
+        }
+    }
+
+    /**
+     * It should leave url without placeholder untouched
+     */
+    #[test]
+    fn test_expand_archive_url_without_placeholder() {
+        let archive_url = Url::parse("https://example.com/foo$bar.pkg.tar.zst").unwrap();
+
+        assert_eq!(
+            expand_archive_url(&archive_url, &lookup).unwrap(),
+            archive_url
+        );
+    }
+
+    /**
+     * It should refuse variables outside allowed ones or unset
+     */
+    #[test]
+    fn test_expand_archive_url_rejected_variables() {
+        let forbidden_url = Url::parse("https://example.com/?leak=${HOME}").unwrap();
+        let unset_url = Url::parse("https://example.com/${BPM_MIRROR_REGION}/").unwrap();
+
+        assert_eq!(
+            matches!(
+                expand_archive_url(&forbidden_url, &lookup),
+                Err(PackageManagerError::InvalidArchiveUrl(_))
+            ),
+            true
+        );
+        assert_eq!(
+            matches!(
+                expand_archive_url(&unset_url, &|_| None),
+                Err(PackageManagerError::InvalidArchiveUrl(_))
+            ),
+            true
+        );
+    }
+}
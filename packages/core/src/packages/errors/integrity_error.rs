@@ -0,0 +1,13 @@
+use thiserror::Error;
+
+/**
+ * Package integrity errors
+ */
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum IntegrityError {
+    #[error("Unsupported integrity algorithm: {0}")]
+    UnsupportedAlgorithm(String),
+
+    #[error("Could not read file to verify: {0}")]
+    ReadError(String),
+}
@@ -0,0 +1,3 @@
+pub mod integrity_error;
+pub mod package_builder_error;
+pub mod package_error;
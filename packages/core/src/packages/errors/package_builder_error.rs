@@ -0,0 +1,10 @@
+use thiserror::Error;
+
+/**
+ * Package builder errors
+ */
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum PackageBuilderError {
+    #[error("Package is missing required fields: {}", .0.join(", "))]
+    MissingFields(Vec<String>),
+}
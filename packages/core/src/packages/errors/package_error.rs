@@ -0,0 +1,25 @@
+use rlp::DecoderError;
+use thiserror::Error;
+
+use super::package_builder_error::PackageBuilderError;
+
+/**
+ * Package errors
+ */
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum PackageError {
+    #[error("Signature must be attached to package when encoding it")]
+    MissingSignature,
+
+    #[error("Could not decode package: {0}")]
+    DecodingError(#[from] DecoderError),
+
+    #[error("Invalid package name: {0}")]
+    InvalidName(String),
+
+    #[error("Invalid package version: {0}")]
+    InvalidVersion(String),
+
+    #[error(transparent)]
+    BuilderError(#[from] PackageBuilderError),
+}
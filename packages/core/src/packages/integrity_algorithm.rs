@@ -0,0 +1,44 @@
+use strum_macros::{Display, EnumIter, EnumString};
+
+/**
+ * Hash algorithm package archives and sources are committed with
+ */
+#[derive(EnumIter, EnumString, PartialEq, Eq, Display, Debug, Clone, Copy, Default)]
+#[strum(ascii_case_insensitive)]
+pub enum IntegrityAlgorithm {
+    #[default]
+    #[strum(to_string = "SHA256")]
+    Sha256,
+    #[strum(to_string = "SHA512")]
+    Sha512,
+    #[strum(to_string = "BLAKE3")]
+    Blake3,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use strum::IntoEnumIterator;
+
+    use super::*;
+
+    /**
+     * It should parse back every algorithm label, whatever its case, and reject unknown ones
+     */
+    #[test]
+    fn test_from_str() {
+        for algorithm in IntegrityAlgorithm::iter() {
+            assert_eq!(
+                IntegrityAlgorithm::from_str(&algorithm.to_string()),
+                Ok(algorithm)
+            );
+        }
+
+        assert_eq!(
+            IntegrityAlgorithm::from_str("blake3"),
+            Ok(IntegrityAlgorithm::Blake3)
+        );
+        assert_eq!(IntegrityAlgorithm::from_str("MD5").is_err(), true);
+    }
+}
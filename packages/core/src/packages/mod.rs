@@ -1,3 +1,5 @@
+pub mod errors;
+pub mod integrity_algorithm;
 pub mod package;
 pub mod package_builder;
 pub mod package_integrity;
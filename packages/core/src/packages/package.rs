@@ -1,25 +1,58 @@
 use crate::packages::package_integrity::PackageIntegrity;
 
+use super::errors::package_error::PackageError;
+use super::utils::decoding::{
+    decode_maintainer, decode_signature, ensure_field_size, MAX_ARCHIVE_URL_LENGTH,
+    MAX_DEPENDENCIES, MAX_NAME_LENGTH, MAX_VERSION_LENGTH,
+};
+use super::utils::maintainers::compute_maintainer_fingerprint;
+use super::utils::validation::{
+    validate_dependency, validate_package_name, validate_package_version,
+};
+
 use super::package_builder::PackageBuilder;
 use super::package_status::PackageStatus;
 use core::fmt;
 use ed25519::Signature;
-use ed25519_dalek::{VerifyingKey, PUBLIC_KEY_LENGTH, SIGNATURE_LENGTH};
+use ed25519_dalek::VerifyingKey;
 use rlp::{Decodable, DecoderError, Encodable, RlpStream};
 use serde::de::{self, Deserializer, MapAccess, Visitor};
-use serde::{
-    ser::{Error, SerializeStruct},
-    Deserialize, Serialize,
-};
+use serde::{ser::SerializeStruct, Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use url::Url;
 
 pub const DEFAULT_PACKAGE_STATUS: PackageStatus = PackageStatus::Fine;
 
+// Encoded package items before optional ones, data then signature
+const LEGACY_ITEM_COUNT: usize = 7;
+
+// Index of maintainer key among encoded package items
+const MAINTAINER_INDEX: usize = 3;
+
+// Encoded revocation flag, only appended to tombstones
+const REVOKED_FLAG: u8 = 1;
+
+// Index of first optional item, signed time, dependencies then revocation flag
+const OPTIONAL_ITEMS_INDEX: usize = 6;
+
+// Serialized package fields, in order
+const FIELDS: &[&str] = &[
+    "name",
+    "version",
+    "status",
+    "maintainer",
+    "archive_url",
+    "integrity",
+    "signed_at",
+    "dependencies",
+    "revoked",
+    "sig",
+];
+
 /**
  * Package
  */
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq)]
 pub struct Package {
     pub name: String,
     pub version: String,
@@ -27,7 +60,31 @@ pub struct Package {
     pub maintainer: VerifyingKey, // Maintainer is identified by its public key
     pub archive_url: Url,         // TODO: Convert to list
     pub integrity: PackageIntegrity,
+    pub signed_at: Option<u64>, // Signed time in milliseconds, so a replayed older mutation can be told apart
+    pub dependencies: Vec<(String, String)>, // Name and version constraint of required packages ( exact or minimum, eg: >=2.40 )
+    pub revoked: bool, // Tombstone prohibiting release in caches, signed like any other mutation
     pub sig: Option<Signature>,
+    pub consensus_time: Option<u64>, // Set by blockchain when mutation was read, neither signed nor encoded
+    pub raw: Option<Vec<u8>>, // Original RLP read from blockchain, kept to re-publish exact same bytes
+}
+
+impl PartialEq for Package {
+    /**
+     * Compare packages, leaving raw bytes out as same package may have been encoded differently
+     */
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.version == other.version
+            && self.status == other.status
+            && self.maintainer == other.maintainer
+            && self.archive_url == other.archive_url
+            && self.integrity == other.integrity
+            && self.signed_at == other.signed_at
+            && self.dependencies == other.dependencies
+            && self.revoked == other.revoked
+            && self.sig == other.sig
+            && self.consensus_time == other.consensus_time
+    }
 }
 
 impl Package {
@@ -68,12 +125,119 @@ impl Package {
             // Package integrity
             .append_list(&encoded_package_integrity);
 
+        // Packages signed before signed time was introduced keep their original data
+        if let Some(signed_at) = self.signed_at {
+            stream.append(&signed_at);
+        }
+
+        // Same goes for packages without dependencies, so their signature remains valid
+        if !self.dependencies.is_empty() {
+            stream.begin_list(self.dependencies.len());
+
+            for (dependency_name, dependency_version) in &self.dependencies {
+                stream
+                    .begin_list(2)
+                    .append(dependency_name)
+                    .append(dependency_version);
+            }
+        }
+
+        // Revocation flag is signed so a regular mutation cannot be replayed as a tombstone
+        if self.revoked {
+            stream.append(&REVOKED_FLAG);
+        }
+
         stream
     }
 
+    /**
+     * Check if both packages carry same signed mutation, whenever blockchain ordered them
+     */
+    pub fn is_same_mutation(&self, other_package: &Package) -> bool {
+        self.sig == other_package.sig
+            && self.compute_data_integrity() == other_package.compute_data_integrity()
+    }
+
+    /**
+     * Check if both packages describe same release content, whenever and however they were signed
+     */
+    pub fn content_eq(&self, other_package: &Package) -> bool {
+        self.name == other_package.name
+            && self.version == other_package.version
+            && self.status == other_package.status
+            && self.maintainer == other_package.maintainer
+            && self.archive_url == other_package.archive_url
+            && self.integrity == other_package.integrity
+            && self.dependencies == other_package.dependencies
+    }
+
+    /**
+     * Check if package mutation was ordered and signed after given stored one
+     *
+     * Consensus time is checked first when both have it, so a maintainer cannot push an older mutation
+     * with a forged signed time past a newer one. Mutations without signed time cannot replace one which has it
+     */
+    pub fn supersedes(&self, stored_package: &Package) -> bool {
+        if let (Some(consensus_time), Some(stored_consensus_time)) =
+            (self.consensus_time, stored_package.consensus_time)
+        {
+            if consensus_time <= stored_consensus_time {
+                return false;
+            }
+        }
+
+        match (self.signed_at, stored_package.signed_at) {
+            (_, None) => true,
+            (Some(signed_at), Some(stored_signed_at)) => signed_at > stored_signed_at,
+            (None, Some(_)) => false,
+        }
+    }
+
     pub fn builder() -> PackageBuilder {
         PackageBuilder::default()
     }
+
+    /**
+     * Get short maintainer fingerprint
+     */
+    pub fn get_maintainer_fingerprint(&self) -> String {
+        compute_maintainer_fingerprint(&self.maintainer)
+    }
+
+    /**
+     * Render package on a single short line, using maintainer fingerprint instead of full key
+     */
+    pub fn to_compact_string(&self) -> String {
+        format!(
+            "{}:{} ( Status : {}, Maintainer : {} )",
+            self.name,
+            self.version,
+            self.status,
+            self.get_maintainer_fingerprint()
+        )
+    }
+
+    /**
+     * Encode package to RLP, package must be signed
+     */
+    pub fn to_rlp(&self) -> Result<Vec<u8>, PackageError> {
+        if self.sig.is_none() {
+            return Err(PackageError::MissingSignature);
+        }
+
+        let encoded_package = rlp::encode(self).to_vec();
+
+        Ok(encoded_package)
+    }
+
+    /**
+     * Decode package from RLP
+     */
+    pub fn from_rlp(raw_package: &[u8]) -> Result<Package, PackageError> {
+        let package = PackageBuilder::from_rlp(raw_package)?.try_build()?;
+
+        Ok(package)
+    }
 }
 
 // Serde encoding / decoding
@@ -82,7 +246,7 @@ impl Serialize for Package {
     where
         S: serde::Serializer,
     {
-        let mut state = serializer.serialize_struct("Package", 5)?;
+        let mut state = serializer.serialize_struct("Package", FIELDS.len())?;
         state.serialize_field("name", &self.name)?;
 
         state.serialize_field("version", &self.version)?;
@@ -96,16 +260,16 @@ impl Serialize for Package {
 
         state.serialize_field("integrity", &self.integrity)?;
 
-        let sig = match self.sig {
-            Some(v) => v,
-            None => {
-                return Err(S::Error::custom(
-                    "Signature must be attached to package when serializing it",
-                ))
-            }
-        };
+        state.serialize_field("signed_at", &self.signed_at)?;
+
+        state.serialize_field("dependencies", &self.dependencies)?;
+
+        state.serialize_field("revoked", &self.revoked)?;
 
-        state.serialize_field("sig", &sig.to_bytes().as_slice())?;
+        // Unsigned packages are serialized with a null signature
+        let sig_bytes = self.sig.map(|sig| sig.to_bytes().to_vec());
+
+        state.serialize_field("sig", &sig_bytes)?;
 
         state.end()
     }
@@ -117,14 +281,17 @@ impl<'de> Deserialize<'de> for Package {
         D: Deserializer<'de>,
     {
         #[derive(Deserialize)]
-        #[serde(field_identifier, rename_all = "lowercase")]
+        #[serde(field_identifier, rename_all = "snake_case")]
         enum Field {
             Name,
             Version,
             Status,
             Maintainer,
-            Archive_Url,
+            ArchiveUrl,
             Integrity,
+            SignedAt,
+            Dependencies,
+            Revoked,
             Sig,
         }
         struct PackageVisitor;
@@ -146,6 +313,9 @@ impl<'de> Deserialize<'de> for Package {
                 let mut maintainer = None;
                 let mut archive_url = None;
                 let mut integrity = None;
+                let mut signed_at = None;
+                let mut dependencies = None;
+                let mut revoked = None;
                 let mut sig = None;
                 while let Some(key) = map.next_key()? {
                     match key {
@@ -178,27 +348,22 @@ impl<'de> Deserialize<'de> for Package {
                                 return Err(de::Error::duplicate_field("maintainer"));
                             }
 
-                            let mut maintainer_raw_key_buf: [u8; PUBLIC_KEY_LENGTH] =
-                                [0; PUBLIC_KEY_LENGTH];
-
                             let maintainer_key_bytes: Vec<u8> = map.next_value()?;
 
-                            maintainer_raw_key_buf.copy_from_slice(&maintainer_key_bytes);
-
                             maintainer = Some(
-                                VerifyingKey::from_bytes(&maintainer_raw_key_buf)
-                                    .map_err(|_| DecoderError::RlpExpectedToBeData)
-                                    .unwrap(),
+                                decode_maintainer(&maintainer_key_bytes)
+                                    .map_err(|e| de::Error::custom(e))?,
                             );
                         }
 
-                        Field::Archive_Url => {
+                        Field::ArchiveUrl => {
                             if archive_url.is_some() {
                                 return Err(de::Error::duplicate_field("archive_url"));
                             }
 
-                            let raw_url = map.next_value()?;
-                            archive_url = Some(Url::parse(raw_url).unwrap());
+                            let raw_url: String = map.next_value()?;
+                            archive_url =
+                                Some(Url::parse(&raw_url).map_err(|e| de::Error::custom(e))?);
                         }
 
                         Field::Integrity => {
@@ -208,18 +373,42 @@ impl<'de> Deserialize<'de> for Package {
                             integrity = Some(map.next_value()?);
                         }
 
-                        Field::Sig => {
-                            if sig.is_some() {
-                                return Err(de::Error::duplicate_field("maintainer"));
+                        Field::SignedAt => {
+                            if signed_at.is_some() {
+                                return Err(de::Error::duplicate_field("signed_at"));
+                            }
+                            signed_at = Some(map.next_value()?);
+                        }
+
+                        Field::Dependencies => {
+                            if dependencies.is_some() {
+                                return Err(de::Error::duplicate_field("dependencies"));
                             }
+                            dependencies = Some(map.next_value()?);
+                        }
 
-                            let mut sig_buf: [u8; SIGNATURE_LENGTH] = [0; SIGNATURE_LENGTH];
+                        Field::Revoked => {
+                            if revoked.is_some() {
+                                return Err(de::Error::duplicate_field("revoked"));
+                            }
+                            revoked = Some(map.next_value()?);
+                        }
 
-                            let sig_bytes: Vec<u8> = map.next_value()?;
+                        Field::Sig => {
+                            if sig.is_some() {
+                                return Err(de::Error::duplicate_field("sig"));
+                            }
 
-                            sig_buf.copy_from_slice(&sig_bytes);
+                            let sig_bytes: Option<Vec<u8>> = map.next_value()?;
 
-                            sig = Some(Some(Signature::from_bytes(&sig_buf)));
+                            sig = match sig_bytes {
+                                Some(sig_bytes) => Some(Some(
+                                    decode_signature(&sig_bytes)
+                                        .map_err(|e| de::Error::custom(e))?,
+                                )),
+                                // Unsigned package
+                                None => Some(None),
+                            };
                         }
                     }
                 }
@@ -235,6 +424,13 @@ impl<'de> Deserialize<'de> for Package {
                 let integrity = integrity.ok_or_else(|| de::Error::missing_field("integrity"))?;
                 let sig = sig.ok_or_else(|| de::Error::missing_field("sig"))?;
 
+                // Packages serialized before signed time was introduced do not have it
+                let signed_at = signed_at.flatten();
+
+                // Same goes for dependencies and revocation flag
+                let dependencies = dependencies.unwrap_or_default();
+                let revoked = revoked.unwrap_or_default();
+
                 let package = Package {
                     name,
                     version,
@@ -242,13 +438,17 @@ impl<'de> Deserialize<'de> for Package {
                     maintainer,
                     archive_url,
                     integrity,
+                    signed_at,
+                    dependencies,
+                    revoked,
                     sig,
+                    consensus_time: None,
+                    raw: None,
                 };
                 Ok(package)
             }
         }
 
-        const FIELDS: &[&str] = &["secs", "nanos"];
         deserializer.deserialize_struct("Package", FIELDS, PackageVisitor)
     }
 }
@@ -272,14 +472,49 @@ impl Encodable for Package {
     }
 }
 
+/**
+ * Decode dependencies list, each dependency being a name and version constraint pair
+ */
+fn decode_dependencies(rlp: &rlp::Rlp) -> Result<Vec<(String, String)>, DecoderError> {
+    if rlp.item_count()? > MAX_DEPENDENCIES {
+        return Err(DecoderError::Custom("Too many dependencies"));
+    }
+
+    rlp.iter()
+        .map(|dependency| {
+            if dependency.item_count()? != 2 {
+                return Err(DecoderError::RlpIncorrectListLen);
+            }
+
+            ensure_field_size(&dependency, 0, MAX_NAME_LENGTH)?;
+            ensure_field_size(&dependency, 1, MAX_VERSION_LENGTH)?;
+
+            let (name, version): (String, String) = (dependency.val_at(0)?, dependency.val_at(1)?);
+
+            validate_dependency(&name, &version)
+                .map_err(|_| DecoderError::Custom("Invalid package dependency"))?;
+
+            Ok((name, version))
+        })
+        .collect()
+}
+
 impl Decodable for Package {
     fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
         // Parse name
+        ensure_field_size(rlp, 0, MAX_NAME_LENGTH)?;
         let name: String = rlp.val_at(0)?;
 
+        // Name and version end up in composite keys, refuse ones which would break them
+        validate_package_name(&name).map_err(|_| DecoderError::Custom("Invalid package name"))?;
+
         // Parse version
+        ensure_field_size(rlp, 1, MAX_VERSION_LENGTH)?;
         let version: String = rlp.val_at(1)?;
 
+        validate_package_version(&version)
+            .map_err(|_| DecoderError::Custom("Invalid package version"))?;
+
         // Parse status
         let raw_status: u8 = rlp.val_at(2)?;
 
@@ -287,35 +522,71 @@ impl Decodable for Package {
             .map_err(|_| rlp::DecoderError::RlpInconsistentLengthAndData)?;
 
         // Parse maintainer verifying key
-        let mut maintainer_raw_key_buf: [u8; PUBLIC_KEY_LENGTH] = [0; PUBLIC_KEY_LENGTH];
-
-        let maintainer_key_bytes: Vec<u8> = rlp.val_at(3)?;
-
-        maintainer_raw_key_buf.copy_from_slice(&maintainer_key_bytes);
+        let maintainer_key_bytes: Vec<u8> = rlp.val_at(MAINTAINER_INDEX)?;
 
-        let maintainer: VerifyingKey = VerifyingKey::from_bytes(&maintainer_raw_key_buf)
-            .map_err(|_| DecoderError::RlpExpectedToBeData)
-            .unwrap();
+        let maintainer = decode_maintainer(&maintainer_key_bytes)?;
 
         // Parse archive url
+        ensure_field_size(rlp, 4, MAX_ARCHIVE_URL_LENGTH)?;
         let raw_archive_url: String = rlp.val_at(4)?;
 
-        let archive_url = Url::parse(raw_archive_url.as_str()).unwrap();
+        let archive_url = Url::parse(raw_archive_url.as_str())
+            .map_err(|_| DecoderError::Custom("Invalid archive url"))?;
 
         // Parse integrity struct
         let raw_package_integrity = rlp.list_at(5)?;
 
         let package_integrity: PackageIntegrity = rlp::decode(&raw_package_integrity)?;
 
-        // Parse signature
+        // Signature is always last, optional items sit between integrity and signature
+        let item_count = rlp.item_count()?;
+
+        if item_count < LEGACY_ITEM_COUNT {
+            return Err(DecoderError::RlpIncorrectListLen);
+        }
+
+        let sig_index = item_count - 1;
+        let mut item_index = OPTIONAL_ITEMS_INDEX;
+
+        // Parse signed time, only present in packages signed once it was introduced
+        let signed_at = if item_index < sig_index && rlp.at(item_index)?.is_data() {
+            item_index += 1;
+            Some(rlp.val_at(item_index - 1)?)
+        } else {
+            None
+        };
 
-        let mut sig_buf: [u8; SIGNATURE_LENGTH] = [0; SIGNATURE_LENGTH];
+        // Parse dependencies, only present when package has some
+        let dependencies = if item_index < sig_index && rlp.at(item_index)?.is_list() {
+            item_index += 1;
+            decode_dependencies(&rlp.at(item_index - 1)?)?
+        } else {
+            vec![]
+        };
+
+        // Parse revocation flag, only present in tombstones
+        let revoked = if item_index < sig_index {
+            item_index += 1;
+
+            let raw_revoked: u8 = rlp.val_at(item_index - 1)?;
+
+            if raw_revoked != REVOKED_FLAG {
+                return Err(DecoderError::Custom("Invalid revocation flag"));
+            }
+
+            true
+        } else {
+            false
+        };
 
-        let sig_bytes: Vec<u8> = rlp.val_at(6)?;
+        if item_index != sig_index {
+            return Err(DecoderError::RlpIncorrectListLen);
+        }
 
-        sig_buf.copy_from_slice(&sig_bytes);
+        // Parse signature
+        let sig_bytes: Vec<u8> = rlp.val_at(sig_index)?;
 
-        let sig = Signature::from_bytes(&sig_buf);
+        let sig = decode_signature(&sig_bytes)?;
 
         // Build package
         let package = Self {
@@ -325,7 +596,12 @@ impl Decodable for Package {
             maintainer,
             archive_url,
             integrity: package_integrity,
+            signed_at,
+            dependencies,
+            revoked,
             sig: Some(sig),
+            consensus_time: None,
+            raw: None,
         };
 
         Ok(package)
@@ -354,7 +630,11 @@ mod tests {
     use serde_json::json;
     use std::any::{type_name, type_name_of_val};
 
-    use crate::test_utils::package::tests::{create_package_with_sig, create_package_without_sig};
+    use crate::packages::utils::signatures::{sign_package_now, verify_package};
+    use crate::test_utils::package::tests::{
+        create_package_with_sig, create_package_without_sig, replace_encoded_package_item,
+    };
+    use ed25519_dalek::{PUBLIC_KEY_LENGTH, SIGNATURE_LENGTH};
 
     use super::*;
 
@@ -390,6 +670,459 @@ mod tests {
         ()
     }
 
+    /**
+     * It should encode and decode package to RLP using convenience methods
+     */
+    #[test]
+    fn test_package_to_rlp_from_rlp() -> Result<(), Box<dyn std::error::Error>> {
+        let package = create_package_with_sig()?;
+
+        let encoded_package = package.to_rlp()?;
+
+        let decoded_package = Package::from_rlp(&encoded_package)?;
+
+        assert_eq!(decoded_package, package);
+
+        Ok(())
+    }
+
+    /**
+     * It should keep signed time when encoding to RLP, and sign over it
+     */
+    #[test]
+    fn test_package_rlp_signed_at() -> Result<(), Box<dyn std::error::Error>> {
+        let mut csprng = OsRng;
+        let mut key = SigningKey::generate(&mut csprng);
+
+        let package = create_package_without_sig(&key.verifying_key())?;
+
+        let signed_package = sign_package_now(&package, &mut key);
+
+        let decoded_package = Package::from_rlp(&signed_package.to_rlp()?)?;
+
+        assert_eq!(decoded_package.signed_at.is_some(), true);
+        assert_eq!(decoded_package, signed_package);
+        assert_eq!(verify_package(&decoded_package).is_some(), true);
+
+        // Signed time cannot be changed without breaking signature
+        let replayed_package = PackageBuilder::from_package(&signed_package)
+            .set_signed_at(&(signed_package.signed_at.unwrap() + 1))
+            .build();
+
+        assert_eq!(verify_package(&replayed_package).is_none(), true);
+
+        Ok(())
+    }
+
+    /**
+     * It should encode revocation flag to RLP and sign over it
+     */
+    #[test]
+    fn test_package_rlp_revoked() -> Result<(), Box<dyn std::error::Error>> {
+        let mut csprng = OsRng;
+        let mut key = SigningKey::generate(&mut csprng);
+
+        let package =
+            PackageBuilder::from_package(&create_package_without_sig(&key.verifying_key())?)
+                .add_dependency(&String::from("bar"), &String::from("4.5.6"))
+                .set_status(&PackageStatus::Prohibited)
+                .set_revoked(&true)
+                .build();
+
+        let tombstone = sign_package_now(&package, &mut key);
+
+        let decoded_tombstone = Package::from_rlp(&tombstone.to_rlp()?)?;
+
+        assert_eq!(decoded_tombstone.revoked, true);
+        assert_eq!(decoded_tombstone, tombstone);
+        assert_eq!(verify_package(&decoded_tombstone).is_some(), true);
+
+        // Regular mutation cannot be turned into a tombstone without signing it again
+        let regular_package = PackageBuilder::from_package(&tombstone)
+            .set_revoked(&false)
+            .build();
+
+        assert_eq!(verify_package(&regular_package).is_none(), true);
+
+        Ok(())
+    }
+
+    /**
+     * It should refuse revocation flag having any other value
+     */
+    #[test]
+    fn test_package_rlp_invalid_revoked_flag() -> Result<(), Box<dyn std::error::Error>> {
+        let mut csprng = OsRng;
+        let mut key = SigningKey::generate(&mut csprng);
+
+        let package =
+            PackageBuilder::from_package(&create_package_without_sig(&key.verifying_key())?)
+                .set_revoked(&true)
+                .build();
+
+        let encoded_tombstone = sign_package_now(&package, &mut key).to_rlp()?;
+
+        // Signed time then revocation flag follow integrity
+        let encoded_package =
+            replace_encoded_package_item(&encoded_tombstone, OPTIONAL_ITEMS_INDEX + 1, &[2])?;
+
+        assert_eq!(
+            matches!(
+                Package::from_rlp(&encoded_package),
+                Err(PackageError::DecodingError(DecoderError::Custom(
+                    "Invalid revocation flag"
+                )))
+            ),
+            true
+        );
+
+        Ok(())
+    }
+
+    /**
+     * It should encode dependencies to RLP and sign over them, leaving packages without any untouched
+     */
+    #[test]
+    fn test_package_rlp_dependencies() -> Result<(), Box<dyn std::error::Error>> {
+        let mut csprng = OsRng;
+        let key = SigningKey::generate(&mut csprng);
+
+        let package = create_package_without_sig(&key.verifying_key())?;
+
+        // Without dependencies, encoding is the one used before they were introduced
+        let signed_package = sign_package_now(&package, &key);
+
+        let encoded_package = signed_package.to_rlp()?;
+
+        assert_eq!(rlp::Rlp::new(&encoded_package).item_count()?, 8);
+
+        let dependent_package = PackageBuilder::from_package(&package)
+            .add_dependency(&String::from("bar"), &String::from("4.5.6"))
+            .add_dependency(&String::from("baz"), &String::from(">=7.8.9"))
+            .build();
+
+        let signed_dependent_package = sign_package_now(&dependent_package, &key);
+
+        let decoded_package = Package::from_rlp(&signed_dependent_package.to_rlp()?)?;
+
+        assert_eq!(decoded_package, signed_dependent_package);
+        assert_eq!(decoded_package.dependencies.len(), 2);
+        assert_eq!(verify_package(&decoded_package).is_some(), true);
+
+        // Dependencies cannot be changed without breaking signature
+        let tampered_package = PackageBuilder::from_package(&signed_dependent_package)
+            .set_dependencies(&[])
+            .build();
+
+        assert_eq!(verify_package(&tampered_package).is_none(), true);
+
+        Ok(())
+    }
+
+    /**
+     * It should sign source code hash along with other package data
+     */
+    #[test]
+    fn test_package_rlp_source_code_hash() -> Result<(), Box<dyn std::error::Error>> {
+        let mut csprng = OsRng;
+        let key = SigningKey::generate(&mut csprng);
+
+        let package = create_package_without_sig(&key.verifying_key())?;
+
+        let package_with_sources = PackageBuilder::from_package(&package)
+            .set_source_code_hash(&Sha256::digest("bar"))
+            .build();
+
+        assert_ne!(
+            package.compute_data_integrity(),
+            package_with_sources.compute_data_integrity()
+        );
+
+        let signed_package = sign_package_now(&package_with_sources, &key);
+
+        let decoded_package = Package::from_rlp(&signed_package.to_rlp()?)?;
+
+        assert_eq!(decoded_package, signed_package);
+        assert_eq!(verify_package(&decoded_package).is_some(), true);
+
+        // Source code hash cannot be changed without breaking signature
+        let tampered_package = PackageBuilder::from_package(&signed_package)
+            .set_source_code_hash(&Sha256::digest("baz"))
+            .build();
+
+        assert_eq!(verify_package(&tampered_package).is_none(), true);
+
+        Ok(())
+    }
+
+    /**
+     * It should only supersede stored package when signed after it
+     */
+    #[test]
+    fn test_package_supersedes() -> Result<(), Box<dyn std::error::Error>> {
+        let package = create_package_with_sig()?;
+
+        let with_signed_at = |signed_at: u64| {
+            PackageBuilder::from_package(&package)
+                .set_signed_at(&signed_at)
+                .build()
+        };
+
+        assert_eq!(with_signed_at(2).supersedes(&with_signed_at(1)), true);
+        assert_eq!(with_signed_at(1).supersedes(&with_signed_at(2)), false);
+        assert_eq!(with_signed_at(1).supersedes(&with_signed_at(1)), false);
+        assert_eq!(with_signed_at(1).supersedes(&package), true);
+        assert_eq!(package.supersedes(&with_signed_at(1)), false);
+        assert_eq!(package.supersedes(&package), true);
+
+        Ok(())
+    }
+
+    /**
+     * It should render package with maintainer fingerprint instead of full key
+     */
+    #[test]
+    fn test_package_to_compact_string() -> Result<(), Box<dyn std::error::Error>> {
+        let package = create_package_with_sig()?;
+
+        let expected_compact_string = format!(
+            "{}:{} ( Status : {}, Maintainer : {} )",
+            package.name,
+            package.version,
+            package.status,
+            package.get_maintainer_fingerprint()
+        );
+
+        assert_eq!(package.to_compact_string(), expected_compact_string);
+        assert_eq!(
+            package
+                .to_compact_string()
+                .contains(&hex::encode_upper(package.maintainer)),
+            false
+        );
+
+        Ok(())
+    }
+
+    /**
+     * It should compare release content regardless of signature and signed time
+     */
+    #[test]
+    fn test_package_content_eq() -> Result<(), Box<dyn std::error::Error>> {
+        let package = create_package_with_sig()?;
+
+        let resigned_package = PackageBuilder::from_package(&package)
+            .set_signed_at(&1)
+            .build();
+
+        let mutated_package = PackageBuilder::from_package(&package)
+            .set_status(&PackageStatus::Prohibited)
+            .build();
+
+        assert_eq!(resigned_package.content_eq(&package), true);
+        assert_eq!(mutated_package.content_eq(&package), false);
+
+        Ok(())
+    }
+
+    /**
+     * It should not supersede stored package ordered later by blockchain, whatever its signed time
+     */
+    #[test]
+    fn test_package_supersedes_consensus_time() -> Result<(), Box<dyn std::error::Error>> {
+        let package = create_package_with_sig()?;
+
+        let with_times = |signed_at: u64, consensus_time: u64| {
+            PackageBuilder::from_package(&package)
+                .set_signed_at(&signed_at)
+                .set_consensus_time(&consensus_time)
+                .build()
+        };
+
+        assert_eq!(with_times(2, 20).supersedes(&with_times(1, 10)), true);
+        assert_eq!(with_times(2, 10).supersedes(&with_times(1, 20)), false);
+        assert_eq!(with_times(2, 10).supersedes(&with_times(1, 10)), false);
+        assert_eq!(with_times(2, 10).is_same_mutation(&with_times(2, 20)), true);
+
+        Ok(())
+    }
+
+    /**
+     * It should return error instead of panicking when encoding unsigned package
+     */
+    #[test]
+    fn test_package_to_rlp_sig_missing() {
+        let mut csprng = OsRng;
+        let key = SigningKey::generate(&mut csprng);
+
+        let package = create_package_without_sig(&key.verifying_key()).unwrap();
+
+        assert_eq!(
+            package.to_rlp().unwrap_err(),
+            PackageError::MissingSignature
+        );
+    }
+
+    /**
+     * It should reject package with oversized fields
+     */
+    #[test]
+    fn test_package_rlp_decode_oversized_name() -> Result<(), Box<dyn std::error::Error>> {
+        let package = create_package_with_sig()?;
+
+        let oversized_package = PackageBuilder::from_package(&package)
+            .set_name(&"a".repeat(MAX_NAME_LENGTH + 1))
+            .build();
+
+        let encoded_package = rlp::encode(&oversized_package);
+
+        let decoding_result: Result<Package, DecoderError> = rlp::decode(&encoded_package);
+
+        assert_eq!(
+            decoding_result,
+            Err(DecoderError::Custom("Field exceeds maximum size"))
+        );
+
+        Ok(())
+    }
+
+    /**
+     * It should reject package whose name or version contains key separator
+     */
+    #[test]
+    fn test_package_rlp_decode_key_separator() -> Result<(), Box<dyn std::error::Error>> {
+        let package = create_package_with_sig()?;
+
+        let epoch_version_package = PackageBuilder::from_package(&package)
+            .set_version(&String::from("1:2.3-1"))
+            .build();
+
+        let decoding_result: Result<Package, DecoderError> =
+            rlp::decode(&rlp::encode(&epoch_version_package));
+
+        assert_eq!(
+            decoding_result,
+            Err(DecoderError::Custom("Invalid package version"))
+        );
+
+        let separator_name_package = PackageBuilder::from_package(&package)
+            .set_name(&String::from("foo:bar"))
+            .build();
+
+        let decoding_result: Result<Package, DecoderError> =
+            rlp::decode(&rlp::encode(&separator_name_package));
+
+        assert_eq!(
+            decoding_result,
+            Err(DecoderError::Custom("Invalid package name"))
+        );
+
+        Ok(())
+    }
+
+    /**
+     * It should fail decoding maintainer key of wrong size instead of panicking
+     */
+    #[test]
+    fn test_package_rlp_decode_wrong_size_maintainer() -> Result<(), Box<dyn std::error::Error>> {
+        let encoded_package = create_package_with_sig()?.to_rlp()?;
+
+        for maintainer_length in [PUBLIC_KEY_LENGTH - 1, PUBLIC_KEY_LENGTH + 1] {
+            let malformed_package = replace_encoded_package_item(
+                &encoded_package,
+                MAINTAINER_INDEX,
+                &vec![1u8; maintainer_length],
+            )?;
+
+            assert_eq!(
+                rlp::decode::<Package>(&malformed_package),
+                Err(DecoderError::Custom("Invalid maintainer key length"))
+            );
+        }
+
+        Ok(())
+    }
+
+    /**
+     * It should fail deserializing maintainer key of wrong size instead of panicking
+     */
+    #[test]
+    fn test_package_deserialization_wrong_size_maintainer() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let mut json_value = serde_json::to_value(&create_package_with_sig()?)?;
+
+        for maintainer_length in [PUBLIC_KEY_LENGTH - 1, PUBLIC_KEY_LENGTH + 1] {
+            json_value["maintainer"] = json!(vec![1u8; maintainer_length]);
+
+            let decoding_result: Result<Package, _> = serde_json::from_value(json_value.clone());
+
+            assert_eq!(decoding_result.is_err(), true);
+        }
+
+        Ok(())
+    }
+
+    /**
+     * It should fail decoding truncated or padded signature instead of panicking
+     */
+    #[test]
+    fn test_package_rlp_decode_wrong_size_signature() -> Result<(), Box<dyn std::error::Error>> {
+        let package = create_package_with_sig()?;
+        let encoded_package = package.to_rlp()?;
+
+        let sig_index = rlp::Rlp::new(&encoded_package).item_count()? - 1;
+        let sig_bytes = package.sig.unwrap().to_bytes();
+
+        let truncated_package = replace_encoded_package_item(
+            &encoded_package,
+            sig_index,
+            &sig_bytes[..SIGNATURE_LENGTH - 1],
+        )?;
+
+        let mut padded_sig_bytes = sig_bytes.to_vec();
+        padded_sig_bytes.push(0);
+
+        let padded_package =
+            replace_encoded_package_item(&encoded_package, sig_index, &padded_sig_bytes)?;
+
+        for malformed_package in [truncated_package, padded_package] {
+            assert_eq!(
+                rlp::decode::<Package>(&malformed_package),
+                Err(DecoderError::Custom("Invalid signature length"))
+            );
+            assert_eq!(Package::from_rlp(&malformed_package).is_err(), true);
+        }
+
+        Ok(())
+    }
+
+    /**
+     * It should fail deserializing signature of wrong size instead of panicking
+     */
+    #[test]
+    fn test_package_deserialization_wrong_size_signature() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let mut json_value = serde_json::to_value(&create_package_with_sig()?)?;
+
+        json_value["sig"] = json!(vec![1u8; SIGNATURE_LENGTH - 1]);
+
+        let decoding_result: Result<Package, _> = serde_json::from_value(json_value);
+
+        assert_eq!(decoding_result.is_err(), true);
+
+        Ok(())
+    }
+
+    /**
+     * It should return error when decoding garbage
+     */
+    #[test]
+    fn test_package_from_rlp_garbage() {
+        let decoding_result = Package::from_rlp("foobar".as_bytes());
+
+        assert_eq!(decoding_result.is_err(), true);
+    }
+
     /**
      * It should serialize and deserialize json-encoded package without panic
      */
@@ -405,7 +1138,68 @@ mod tests {
     }
 
     /**
-     * It should return error when serializing package with sig missing
+     * Deserializer only exposing struct fields it is told about, like schema based formats do
+     */
+    struct DeclaredFieldsDeserializer(serde_json::Value);
+
+    impl<'de> Deserializer<'de> for DeclaredFieldsDeserializer {
+        type Error = serde_json::Error;
+
+        fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            self.0.deserialize_any(visitor)
+        }
+
+        fn deserialize_struct<V: Visitor<'de>>(
+            self,
+            name: &'static str,
+            fields: &'static [&'static str],
+            visitor: V,
+        ) -> Result<V::Value, Self::Error> {
+            let declared_object: serde_json::Map<String, serde_json::Value> = self
+                .0
+                .as_object()
+                .expect("Package must be serialized as an object")
+                .iter()
+                .filter(|(key, _)| fields.contains(&key.as_str()))
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect();
+
+            serde_json::Value::Object(declared_object).deserialize_struct(name, fields, visitor)
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf option unit unit_struct newtype_struct seq tuple
+            tuple_struct map enum identifier ignored_any
+        }
+    }
+
+    /**
+     * It should round trip package through a format relying on declared struct fields
+     */
+    #[test]
+    fn test_package_serialization_declared_fields() -> Result<(), Box<dyn std::error::Error>> {
+        let package = PackageBuilder::from_package(&create_package_with_sig()?)
+            .set_signed_at(&1704067100)
+            .add_dependency(&String::from("bar"), &String::from("4.5.6"))
+            .build();
+
+        let json_value = serde_json::to_value(&package)?;
+
+        assert_eq!(
+            json_value["archive_url"],
+            json!(package.archive_url.to_string())
+        );
+
+        let decoded_package = Package::deserialize(DeclaredFieldsDeserializer(json_value))?;
+
+        assert_eq!(decoded_package, package);
+
+        Ok(())
+    }
+
+    /**
+     * It should serialize and deserialize unsigned package
      */
     #[test]
     fn test_package_serialization_sig_missing() -> Result<(), Box<dyn std::error::Error>> {
@@ -416,12 +1210,15 @@ mod tests {
 
         let package = create_package_without_sig(&expected_maintainer).unwrap();
 
-        let json_encoded_package_result = serde_json::to_string(&package);
+        let json_encoded_package = serde_json::to_string(&package)?;
 
-        assert_eq!(
-            json_encoded_package_result.unwrap_err().to_string(),
-            "Signature must be attached to package when serializing it"
-        );
+        let json_value: serde_json::Value = serde_json::from_str(&json_encoded_package)?;
+
+        assert_eq!(json_value["sig"], json!(null));
+
+        let decoded_package: Package = serde_json::from_str(json_encoded_package.as_str())?;
+
+        assert_eq!(decoded_package, package);
 
         Ok(())
     }
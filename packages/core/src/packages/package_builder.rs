@@ -1,13 +1,17 @@
 use ed25519::Signature;
-use ed25519_dalek::{VerifyingKey, PUBLIC_KEY_LENGTH, SIGNATURE_LENGTH};
+use ed25519_dalek::VerifyingKey;
 use rlp::DecoderError;
 use url::Url;
 
 use crate::db::documents::package_document::PackageDocument;
 
 use super::{
-    package::Package, package_integrity::PackageIntegrity,
-    package_integrity_builder::PackageIntegrityBuilder, package_status::PackageStatus,
+    errors::package_builder_error::PackageBuilderError,
+    package::Package,
+    package_integrity::PackageIntegrity,
+    package_integrity_builder::PackageIntegrityBuilder,
+    package_status::PackageStatus,
+    utils::decoding::{decode_maintainer, decode_signature},
 };
 
 pub struct PackageBuilder {
@@ -40,10 +44,35 @@ pub struct PackageBuilder {
      */
     integrity: Option<PackageIntegrity>,
 
+    /**
+     * Time at which package was signed
+     */
+    signed_at: Option<u64>,
+
+    /**
+     * Package dependencies, name and version constraint
+     */
+    dependencies: Vec<(String, String)>,
+
+    /**
+     * Whether package is a tombstone revoking its release
+     */
+    revoked: bool,
+
     /**
      * Package signature
      */
     sig: Option<Signature>,
+
+    /**
+     * Time at which blockchain reached consensus on package mutation
+     */
+    consensus_time: Option<u64>,
+
+    /**
+     * Original RLP read from blockchain
+     */
+    raw: Option<Vec<u8>>,
 }
 
 impl PackageBuilder {
@@ -51,47 +80,55 @@ impl PackageBuilder {
      * Build from document
      */
     pub fn from_document(document: &PackageDocument) -> PackageBuilder {
+        Self::try_from_document(document).expect("Could not decode stored package")
+    }
+
+    /**
+     * Build from document, failing instead of panicking when it is malformed
+     */
+    pub fn try_from_document(document: &PackageDocument) -> Result<PackageBuilder, DecoderError> {
         // Package status
         let package_status_integer = document.status as u8;
         let package_status = PackageStatus::try_from(package_status_integer)
-            .expect("Could not convert package status from integer to enum");
+            .map_err(|_| DecoderError::Custom("Invalid package status"))?;
 
         // Package maintainer
-        let package_maintainer_decoded =
-            hex::decode(document.maintainer.clone()).expect("Could not decode package maintainer");
-
-        let mut package_maintainer_buf: [u8; PUBLIC_KEY_LENGTH] = [0; PUBLIC_KEY_LENGTH];
+        let package_maintainer_decoded = hex::decode(&document.maintainer)
+            .map_err(|_| DecoderError::Custom("Invalid maintainer key encoding"))?;
 
-        package_maintainer_buf.copy_from_slice(package_maintainer_decoded.as_slice());
-
-        let package_maintainer = VerifyingKey::from_bytes(&package_maintainer_buf)
-            .expect("Could not build key from decoded maintainer key");
+        let package_maintainer = decode_maintainer(&package_maintainer_decoded)?;
 
         // Package archive url
-        let archive_url = Url::parse(&document.archive_url.as_str()).unwrap();
+        let archive_url = Url::parse(&document.archive_url.as_str())
+            .map_err(|_| DecoderError::Custom("Invalid archive url"))?;
 
         // Package integrity
 
         let package_integrity = PackageIntegrityBuilder::from_document(&document.integrity).build();
 
         // Package signature
+        let decoded_sig = hex::decode(&document.sig)
+            .map_err(|_| DecoderError::Custom("Invalid signature encoding"))?;
 
-        let mut package_signature_buf: [u8; SIGNATURE_LENGTH] = [0; SIGNATURE_LENGTH];
-
-        let decoded_sig = hex::decode(&document.sig).unwrap();
-        package_signature_buf.copy_from_slice(&decoded_sig);
+        let package_signature = decode_signature(&decoded_sig)?;
 
-        let package_signature = Signature::from_bytes(&package_signature_buf);
-
-        Self {
+        Ok(Self {
             name: Some(document.name.clone()),
             version: Some(document.version.clone()),
             status: Some(package_status),
             maintainer: Some(package_maintainer),
             archive_url: Some(archive_url),
             integrity: Some(package_integrity),
+            signed_at: document.signed_at,
+            dependencies: document.dependencies.clone(),
+            revoked: document.revoked,
             sig: Some(package_signature),
-        }
+            consensus_time: document.consensus_time,
+            raw: document
+                .raw
+                .as_ref()
+                .map(|raw| hex::decode(raw).expect("Could not decode package raw bytes")),
+        })
     }
 
     /**
@@ -104,7 +141,12 @@ impl PackageBuilder {
         self.maintainer = None;
         self.archive_url = None;
         self.integrity = None;
+        self.signed_at = None;
+        self.dependencies = vec![];
+        self.revoked = false;
         self.sig = None;
+        self.consensus_time = None;
+        self.raw = None;
         self
     }
 
@@ -119,7 +161,12 @@ impl PackageBuilder {
             maintainer: Some(package.maintainer),
             archive_url: Some(package.archive_url.clone()),
             integrity: Some(package.integrity.clone()),
+            signed_at: package.signed_at,
+            dependencies: package.dependencies.clone(),
+            revoked: package.revoked,
             sig: package.sig,
+            consensus_time: package.consensus_time,
+            raw: package.raw.clone(),
         };
 
         instance
@@ -138,7 +185,12 @@ impl PackageBuilder {
             maintainer: Some(package.maintainer),
             archive_url: Some(package.archive_url),
             integrity: Some(package.integrity),
+            signed_at: package.signed_at,
+            dependencies: package.dependencies,
+            revoked: package.revoked,
             sig: package.sig,
+            consensus_time: None,
+            raw: None,
         };
 
         Ok(instance)
@@ -191,6 +243,7 @@ impl PackageBuilder {
         let integrity = PackageIntegrity {
             algorithm: integrity_alg.clone(),
             archive_hash: Vec::from(archive_hash),
+            source_code_hash: vec![],
         };
 
         self.integrity = Some(integrity);
@@ -198,6 +251,50 @@ impl PackageBuilder {
         self
     }
 
+    /**
+     * Set hash of sources package was built from, integrity must be set first
+     */
+    pub fn set_source_code_hash(&mut self, source_code_hash: &[u8]) -> &mut Self {
+        self.integrity
+            .as_mut()
+            .expect("Package integrity must be set before source code hash")
+            .source_code_hash = Vec::from(source_code_hash);
+
+        self
+    }
+
+    /**
+     * Set time at which package is signed
+     */
+    pub fn set_signed_at(&mut self, signed_at: &u64) -> &mut Self {
+        self.signed_at = Some(*signed_at);
+        self
+    }
+
+    /**
+     * Set package dependencies, name and version constraint
+     */
+    pub fn set_dependencies(&mut self, dependencies: &[(String, String)]) -> &mut Self {
+        self.dependencies = dependencies.to_vec();
+        self
+    }
+
+    /**
+     * Add package dependency
+     */
+    pub fn add_dependency(&mut self, name: &String, version: &String) -> &mut Self {
+        self.dependencies.push((name.clone(), version.clone()));
+        self
+    }
+
+    /**
+     * Mark package as tombstone revoking its release, it must be signed again afterwards
+     */
+    pub fn set_revoked(&mut self, revoked: &bool) -> &mut Self {
+        self.revoked = *revoked;
+        self
+    }
+
     /**
      * Set package signature
      */
@@ -207,32 +304,78 @@ impl PackageBuilder {
     }
 
     /**
-     * Build package
+     * Set time at which blockchain reached consensus on package mutation
      */
-    pub fn build(&mut self) -> Package {
-        let package = Package {
-            name: self.name.clone().expect("Package name must be set"),
-            version: self.version.clone().expect("Package version must be set"),
-            status: self.status.clone().expect("Package status must be set"),
-            maintainer: self
-                .maintainer
-                .clone()
-                .expect("Package maintainer must be set"),
-            archive_url: self
-                .archive_url
-                .clone()
-                .expect("Package archive url must be set"),
-            integrity: self
-                .integrity
-                .clone()
-                .expect("Package integrity must be set"),
+    pub fn set_consensus_time(&mut self, consensus_time: &u64) -> &mut Self {
+        self.consensus_time = Some(*consensus_time);
+        self
+    }
+
+    /**
+     * Set original RLP read from blockchain
+     */
+    pub fn set_raw(&mut self, raw: &[u8]) -> &mut Self {
+        self.raw = Some(raw.to_vec());
+        self
+    }
+
+    /**
+     * Check every required field is set, reporting all missing ones at once
+     */
+    pub fn validate(&self) -> Result<(), PackageBuilderError> {
+        let required_fields = [
+            ("name", self.name.is_some()),
+            ("version", self.version.is_some()),
+            ("status", self.status.is_some()),
+            ("maintainer", self.maintainer.is_some()),
+            ("archive url", self.archive_url.is_some()),
+            ("integrity", self.integrity.is_some()),
+        ];
+
+        let missing_fields: Vec<String> = required_fields
+            .iter()
+            .filter(|(_, is_set)| !is_set)
+            .map(|(field, _)| field.to_string())
+            .collect();
+
+        if !missing_fields.is_empty() {
+            return Err(PackageBuilderError::MissingFields(missing_fields));
+        }
 
+        Ok(())
+    }
+
+    /**
+     * Build package, without panicking when required fields are missing
+     */
+    pub fn try_build(&mut self) -> Result<Package, PackageBuilderError> {
+        self.validate()?;
+
+        let package = Package {
+            name: self.name.clone().unwrap(),
+            version: self.version.clone().unwrap(),
+            status: self.status.clone().unwrap(),
+            maintainer: self.maintainer.unwrap(),
+            archive_url: self.archive_url.clone().unwrap(),
+            integrity: self.integrity.clone().unwrap(),
+            signed_at: self.signed_at,
+            dependencies: self.dependencies.clone(),
+            revoked: self.revoked,
             sig: self.sig.clone(),
+            consensus_time: self.consensus_time,
+            raw: self.raw.clone(),
         };
 
         self.reset();
 
-        package
+        Ok(package)
+    }
+
+    /**
+     * Build package, panicking when required fields are missing
+     */
+    pub fn build(&mut self) -> Package {
+        self.try_build().unwrap_or_else(|e| panic!("{}", e))
     }
 }
 
@@ -248,7 +391,12 @@ impl Default for PackageBuilder {
             maintainer: None,
             archive_url: None,
             integrity: None,
+            signed_at: None,
+            dependencies: vec![],
+            revoked: false,
             sig: None,
+            consensus_time: None,
+            raw: None,
         }
     }
 }
@@ -257,15 +405,18 @@ impl Default for PackageBuilder {
 mod tests {
 
     use ed25519::signature::{rand_core::OsRng, SignerMut};
-    use ed25519_dalek::SigningKey;
+    use ed25519_dalek::{SigningKey, PUBLIC_KEY_LENGTH, SIGNATURE_LENGTH};
     use sha2::{Digest, Sha256};
 
     use crate::{
-        blockchains::{blockchain::BlockchainClient, hedera::blockchain_client::HederaBlockchain},
+        blockchains::blockchain::{BlockchainClient, MockBlockchainClient},
         db::documents::{
             package_document_builder::PackageDocumentBuilder,
             package_integrity_document_builder::PackageIntegrityDocumentBuilder,
         },
+        test_utils::{
+            blockchain::tests::create_labelled_client, package::tests::create_package_with_sig,
+        },
     };
 
     use super::*;
@@ -517,8 +668,7 @@ mod tests {
 
         // Pkg related blockchain
 
-        let blockhain_client: Box<dyn BlockchainClient> =
-            Box::new(HederaBlockchain::from("4991716"));
+        let blockhain_client: Box<dyn BlockchainClient> = create_labelled_client("MockBlockchain");
 
         let package_doc = doc_builder
             .set_name(&name_mock)
@@ -559,6 +709,50 @@ mod tests {
         Ok(())
     }
 
+    /**
+     * It should fail building from document with maintainer key of wrong size instead of panicking
+     */
+    #[test]
+    fn test_package_try_from_document_wrong_size_maintainer(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut blockchain_mock = MockBlockchainClient::default();
+
+        blockchain_mock
+            .expect_get_label()
+            .returning(|| "MockBlockchain".to_string());
+
+        let blockchain_client: Box<dyn BlockchainClient> = Box::new(blockchain_mock);
+
+        let package_doc =
+            PackageDocumentBuilder::from_package(&create_package_with_sig()?, &blockchain_client)
+                .build();
+
+        assert_eq!(
+            PackageBuilder::try_from_document(&package_doc)?.build(),
+            PackageBuilder::from_document(&package_doc).build()
+        );
+
+        for maintainer_length in [PUBLIC_KEY_LENGTH - 1, PUBLIC_KEY_LENGTH + 1] {
+            let mut malformed_doc = package_doc.clone();
+            malformed_doc.maintainer = hex::encode(vec![1u8; maintainer_length]);
+
+            assert_eq!(
+                PackageBuilder::try_from_document(&malformed_doc).err(),
+                Some(DecoderError::Custom("Invalid maintainer key length"))
+            );
+        }
+
+        let mut malformed_doc = package_doc.clone();
+        malformed_doc.sig = hex::encode(vec![1u8; SIGNATURE_LENGTH - 1]);
+
+        assert_eq!(
+            PackageBuilder::try_from_document(&malformed_doc).err(),
+            Some(DecoderError::Custom("Invalid signature length"))
+        );
+
+        Ok(())
+    }
+
     /**
      * It should build from rlp data
      */
@@ -629,4 +823,85 @@ mod tests {
 
         Ok(())
     }
+
+    /**
+     * It should report every missing field at once
+     */
+    #[test]
+    fn test_package_validate_missing_fields() {
+        let mut builder = PackageBuilder::default();
+
+        builder.set_name(&String::from("foo"));
+
+        let expected_missing_fields: Vec<String> = [
+            "version",
+            "status",
+            "maintainer",
+            "archive url",
+            "integrity",
+        ]
+        .iter()
+        .map(|field| field.to_string())
+        .collect();
+
+        assert_eq!(
+            builder.validate(),
+            Err(PackageBuilderError::MissingFields(
+                expected_missing_fields.clone()
+            ))
+        );
+        assert_eq!(
+            builder.try_build(),
+            Err(PackageBuilderError::MissingFields(expected_missing_fields))
+        );
+    }
+
+    /**
+     * It should report each required field when it is the only one missing
+     */
+    #[test]
+    fn test_package_try_build_missing_field() -> Result<(), Box<dyn std::error::Error>> {
+        let package = create_package_with_sig()?;
+
+        let unset_fields: [(&str, fn(&mut PackageBuilder)); 6] = [
+            ("name", |builder| builder.name = None),
+            ("version", |builder| builder.version = None),
+            ("status", |builder| builder.status = None),
+            ("maintainer", |builder| builder.maintainer = None),
+            ("archive url", |builder| builder.archive_url = None),
+            ("integrity", |builder| builder.integrity = None),
+        ];
+
+        for (field, unset_field) in unset_fields {
+            let mut builder = PackageBuilder::from_package(&package);
+
+            unset_field(&mut builder);
+
+            assert_eq!(
+                builder.try_build(),
+                Err(PackageBuilderError::MissingFields(vec![field.to_string()]))
+            );
+        }
+
+        assert_eq!(
+            PackageBuilder::from_package(&package).try_build(),
+            Ok(package)
+        );
+
+        Ok(())
+    }
+
+    /**
+     * It should keep panicking when building with missing fields
+     */
+    #[test]
+    #[should_panic(expected = "Package is missing required fields: name")]
+    fn test_package_build_missing_field_panic() {
+        let package = create_package_with_sig().unwrap();
+
+        let mut builder = PackageBuilder::from_package(&package);
+        builder.name = None;
+
+        builder.build();
+    }
 }
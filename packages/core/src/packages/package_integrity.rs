@@ -1,5 +1,16 @@
+use std::{path::Path, str::FromStr};
+
 use rlp::{Decodable, Encodable};
 
+use super::{
+    errors::integrity_error::IntegrityError,
+    integrity_algorithm::IntegrityAlgorithm,
+    utils::{
+        decoding::{ensure_field_size, MAX_INTEGRITY_ALGORITHM_LENGTH, MAX_INTEGRITY_HASH_LENGTH},
+        integrity::compute_file_hash_streaming,
+    },
+};
+
 /**
  * Package integrity fields
  */
@@ -8,7 +19,40 @@ use rlp::{Decodable, Encodable};
 pub struct PackageIntegrity {
     pub algorithm: String,
     pub archive_hash: Vec<u8>,
-    //pub source_code_hash: String,
+    #[serde(default)]
+    pub source_code_hash: Vec<u8>, // Empty for packages published without sources
+}
+
+impl PackageIntegrity {
+    /**
+     * Parse stored algorithm label
+     */
+    pub fn get_algorithm(&self) -> Result<IntegrityAlgorithm, IntegrityError> {
+        IntegrityAlgorithm::from_str(&self.algorithm)
+            .map_err(|_| IntegrityError::UnsupportedAlgorithm(self.algorithm.clone()))
+    }
+
+    /**
+     * Make sure archive can be verified with integrity algorithm, before fetching anything
+     */
+    pub fn ensure_supported_algorithm(&self) -> Result<(), IntegrityError> {
+        self.get_algorithm()?;
+
+        Ok(())
+    }
+
+    /**
+     * Hash file with integrity algorithm and compare it to archive hash
+     */
+    pub async fn verify_file(&self, path: &Path) -> Result<bool, IntegrityError> {
+        let algorithm = self.get_algorithm()?;
+
+        let file_hash = compute_file_hash_streaming(path, &algorithm)
+            .await
+            .map_err(|e| IntegrityError::ReadError(e.to_string()))?;
+
+        Ok(file_hash == self.archive_hash)
+    }
 }
 
 impl Encodable for PackageIntegrity {
@@ -17,21 +61,143 @@ impl Encodable for PackageIntegrity {
             // Algorithm
             .append(&self.algorithm)
             // Archive hash
-            .append(&self.archive_hash)
-            .finalize_unbounded_list();
+            .append(&self.archive_hash);
+
+        // Packages published without sources keep their original encoding, so their signature remains valid
+        if !self.source_code_hash.is_empty() {
+            s.append(&self.source_code_hash);
+        }
+
+        s.finalize_unbounded_list();
     }
 }
 
 impl Decodable for PackageIntegrity {
     fn decode(rlp: &rlp::Rlp) -> Result<Self, rlp::DecoderError> {
+        ensure_field_size(rlp, 0, MAX_INTEGRITY_ALGORITHM_LENGTH)?;
         let algorithm: String = rlp.val_at(0)?;
+
+        ensure_field_size(rlp, 1, MAX_INTEGRITY_HASH_LENGTH)?;
         let archive_hash: Vec<u8> = rlp.val_at(1)?;
 
+        let source_code_hash: Vec<u8> = match rlp.item_count()? {
+            2 => vec![],
+            3 => {
+                ensure_field_size(rlp, 2, MAX_INTEGRITY_HASH_LENGTH)?;
+                rlp.val_at(2)?
+            }
+            _ => return Err(rlp::DecoderError::RlpIncorrectListLen),
+        };
+
         let package_integrity = Self {
             algorithm,
             archive_hash,
+            source_code_hash,
         };
 
         Ok(package_integrity)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use sha2::{Digest, Sha256};
+    use tempfile::TempDir;
+
+    use super::*;
+
+    /**
+     * It should accept matching file, reject tampered one and refuse unknown algorithms
+     */
+    #[tokio::test]
+    async fn test_verify_file() {
+        let test_dir = TempDir::new().unwrap();
+
+        let archive_path = test_dir.path().join("archive.pkg.tar.zst");
+        let tampered_path = test_dir.path().join("tampered.pkg.tar.zst");
+
+        fs::write(&archive_path, "foo").unwrap();
+        fs::write(&tampered_path, "bar").unwrap();
+
+        let integrity = PackageIntegrity {
+            algorithm: String::from("SHA256"),
+            archive_hash: Sha256::digest("foo").to_vec(),
+            source_code_hash: vec![],
+        };
+
+        assert_eq!(integrity.verify_file(&archive_path).await, Ok(true));
+        assert_eq!(integrity.verify_file(&tampered_path).await, Ok(false));
+
+        let unknown_integrity = PackageIntegrity {
+            algorithm: String::from("MD5"),
+            archive_hash: integrity.archive_hash.clone(),
+            source_code_hash: vec![],
+        };
+
+        assert_eq!(
+            unknown_integrity.verify_file(&archive_path).await,
+            Err(IntegrityError::UnsupportedAlgorithm(String::from("MD5")))
+        );
+
+        assert_eq!(integrity.ensure_supported_algorithm(), Ok(()));
+
+        let blake3_integrity = PackageIntegrity {
+            algorithm: String::from("BLAKE3"),
+            archive_hash: blake3::hash(b"foo").as_bytes().to_vec(),
+            source_code_hash: vec![],
+        };
+
+        assert_eq!(blake3_integrity.verify_file(&archive_path).await, Ok(true));
+
+        // SHA256 digest must not be accepted once algorithm is changed
+        let mislabeled_integrity = PackageIntegrity {
+            algorithm: String::from("SHA512"),
+            archive_hash: integrity.archive_hash.clone(),
+            source_code_hash: vec![],
+        };
+
+        assert_eq!(
+            mislabeled_integrity.verify_file(&archive_path).await,
+            Ok(false)
+        );
+        assert_eq!(
+            unknown_integrity.ensure_supported_algorithm(),
+            Err(IntegrityError::UnsupportedAlgorithm(String::from("MD5")))
+        );
+    }
+
+    /**
+     * It should keep legacy encoding without source code hash and round trip with it
+     */
+    #[test]
+    fn test_source_code_hash_rlp() {
+        let legacy_integrity = PackageIntegrity {
+            algorithm: String::from("SHA256"),
+            archive_hash: Sha256::digest("foo").to_vec(),
+            source_code_hash: vec![],
+        };
+
+        let encoded_legacy_integrity = rlp::encode(&legacy_integrity);
+
+        assert_eq!(rlp::Rlp::new(&encoded_legacy_integrity).item_count(), Ok(2));
+        assert_eq!(
+            rlp::decode::<PackageIntegrity>(&encoded_legacy_integrity),
+            Ok(legacy_integrity.clone())
+        );
+
+        let integrity = PackageIntegrity {
+            source_code_hash: Sha256::digest("bar").to_vec(),
+            ..legacy_integrity
+        };
+
+        let encoded_integrity = rlp::encode(&integrity);
+
+        assert_eq!(rlp::Rlp::new(&encoded_integrity).item_count(), Ok(3));
+        assert_eq!(
+            rlp::decode::<PackageIntegrity>(&encoded_integrity),
+            Ok(integrity)
+        );
+    }
+}
@@ -7,6 +7,7 @@ use super::package_integrity::PackageIntegrity;
 pub struct PackageIntegrityBuilder {
     algorithm: Option<String>,
     archive_hash: Option<Vec<u8>>,
+    source_code_hash: Vec<u8>,
 }
 
 impl PackageIntegrityBuilder {
@@ -15,9 +16,11 @@ impl PackageIntegrityBuilder {
      */
     pub fn from_document(document: &PackageIntegrityDocument) -> PackageIntegrityBuilder {
         let decoded_archive_hash = hex::decode(&document.archive_hash).unwrap();
+        let decoded_source_code_hash = hex::decode(&document.source_code_hash).unwrap();
         Self {
             algorithm: Some(document.algorithm.clone()),
             archive_hash: Some(decoded_archive_hash),
+            source_code_hash: decoded_source_code_hash,
         }
     }
 
@@ -27,6 +30,7 @@ impl PackageIntegrityBuilder {
     pub fn reset(&mut self) -> &Self {
         self.algorithm = None;
         self.archive_hash = None;
+        self.source_code_hash = vec![];
         self
     }
 
@@ -37,6 +41,7 @@ impl PackageIntegrityBuilder {
         let instance = Self {
             algorithm: Some(package_integrity.algorithm.clone()),
             archive_hash: Some(package_integrity.archive_hash.clone()),
+            source_code_hash: package_integrity.source_code_hash.clone(),
         };
 
         instance
@@ -51,6 +56,7 @@ impl PackageIntegrityBuilder {
         let instance = Self {
             algorithm: Some(package_integrity.algorithm),
             archive_hash: Some(package_integrity.archive_hash),
+            source_code_hash: package_integrity.source_code_hash,
         };
 
         Ok(instance)
@@ -74,6 +80,15 @@ impl PackageIntegrityBuilder {
         self
     }
 
+    /**
+     * Set source code hash
+     */
+    pub fn set_source_code_hash(&mut self, source_code_hash: &Vec<u8>) -> &mut Self {
+        self.source_code_hash = source_code_hash.clone();
+
+        self
+    }
+
     /**
      * Build package integrity
      */
@@ -87,6 +102,7 @@ impl PackageIntegrityBuilder {
                 .archive_hash
                 .clone()
                 .expect("Package archive hash must be set"),
+            source_code_hash: self.source_code_hash.clone(),
         };
 
         self.reset();
@@ -100,6 +116,7 @@ impl Default for PackageIntegrityBuilder {
         Self {
             algorithm: None,
             archive_hash: None,
+            source_code_hash: vec![],
         }
     }
 }
@@ -146,6 +163,7 @@ mod tests {
 
         assert_eq!(package_integrity.algorithm, None);
         assert_eq!(package_integrity.archive_hash, None);
+        assert_eq!(package_integrity.source_code_hash.is_empty(), true);
     }
 
     /**
@@ -214,9 +232,14 @@ mod tests {
         package_archive_hasher.update("foo");
         let expected_archive_hash = package_archive_hasher.finalize().to_vec();
 
+        let mut source_code_hasher = Sha256::new();
+        source_code_hasher.update("bar");
+        let expected_source_code_hash = source_code_hasher.finalize().to_vec();
+
         let package_integrity = PackageIntegrityBuilder::default()
             .set_algorithm(&expected_algorithm)
             .set_archive_hash(&expected_archive_hash)
+            .set_source_code_hash(&expected_source_code_hash)
             .build();
 
         let encoded_package_integrity = rlp::encode(&package_integrity);
@@ -232,6 +255,10 @@ mod tests {
             decoded_package_integrity.archive_hash,
             package_integrity.archive_hash
         );
+        assert_eq!(
+            decoded_package_integrity.source_code_hash,
+            expected_source_code_hash
+        );
 
         Ok(())
     }
@@ -3,7 +3,7 @@ use strum_macros::{Display, EnumIter, EnumString};
 /**
  * Package status
  */
-#[derive(EnumIter, EnumString, PartialEq, Eq, PartialOrd, Display, Debug, Clone)]
+#[derive(EnumIter, EnumString, PartialEq, Eq, PartialOrd, Ord, Display, Debug, Clone)]
 #[repr(u8)]
 pub enum PackageStatus {
     #[strum(to_string = "NA")]
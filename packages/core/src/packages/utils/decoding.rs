@@ -0,0 +1,148 @@
+use ed25519::Signature;
+use ed25519_dalek::{VerifyingKey, PUBLIC_KEY_LENGTH, SIGNATURE_LENGTH};
+use rlp::{DecoderError, Rlp};
+
+/**
+ * Maximum package name length in bytes
+ */
+pub const MAX_NAME_LENGTH: usize = 256;
+
+/**
+ * Maximum package version length in bytes
+ */
+pub const MAX_VERSION_LENGTH: usize = 128;
+
+/**
+ * Maximum archive url length in bytes
+ */
+pub const MAX_ARCHIVE_URL_LENGTH: usize = 2048;
+
+/**
+ * Maximum integrity algorithm name length in bytes
+ */
+pub const MAX_INTEGRITY_ALGORITHM_LENGTH: usize = 32;
+
+/**
+ * Maximum integrity hash length in bytes ( large enough for SHA-512 )
+ */
+pub const MAX_INTEGRITY_HASH_LENGTH: usize = 64;
+
+/**
+ * Maximum dependencies count of a package
+ */
+pub const MAX_DEPENDENCIES: usize = 256;
+
+/**
+ * Ensure field at given index does not exceed max size before decoding it
+ */
+pub fn ensure_field_size(rlp: &Rlp, index: usize, max_size: usize) -> Result<(), DecoderError> {
+    let field_size = rlp.at(index)?.data()?.len();
+
+    if field_size > max_size {
+        return Err(DecoderError::Custom("Field exceeds maximum size"));
+    }
+
+    Ok(())
+}
+
+/**
+ * Build maintainer key from untrusted bytes, rejecting wrong sized or invalid keys instead of panicking
+ */
+pub fn decode_maintainer(maintainer_key_bytes: &[u8]) -> Result<VerifyingKey, DecoderError> {
+    let maintainer_raw_key_buf: [u8; PUBLIC_KEY_LENGTH] = maintainer_key_bytes
+        .try_into()
+        .map_err(|_| DecoderError::Custom("Invalid maintainer key length"))?;
+
+    VerifyingKey::from_bytes(&maintainer_raw_key_buf)
+        .map_err(|_| DecoderError::Custom("Invalid maintainer key"))
+}
+
+/**
+ * Build signature from untrusted bytes, rejecting wrong sized ones instead of panicking
+ */
+pub fn decode_signature(sig_bytes: &[u8]) -> Result<Signature, DecoderError> {
+    let sig_buf: [u8; SIGNATURE_LENGTH] = sig_bytes
+        .try_into()
+        .map_err(|_| DecoderError::Custom("Invalid signature length"))?;
+
+    Ok(Signature::from_bytes(&sig_buf))
+}
+
+#[cfg(test)]
+mod tests {
+    use ed25519::signature::rand_core::OsRng;
+    use ed25519_dalek::SigningKey;
+    use rlp::RlpStream;
+
+    use super::*;
+
+    /**
+     * It should accept fields within max size
+     */
+    #[test]
+    fn test_ensure_field_size() {
+        let mut stream = RlpStream::new_list(1);
+        stream.append(&"foo");
+
+        let raw = stream.out();
+
+        let rlp = Rlp::new(&raw);
+
+        assert_eq!(ensure_field_size(&rlp, 0, 3), Ok(()));
+    }
+
+    /**
+     * It should reject oversized fields
+     */
+    #[test]
+    fn test_ensure_field_size_oversized() {
+        let mut stream = RlpStream::new_list(1);
+        stream.append(&"foobar");
+
+        let raw = stream.out();
+
+        let rlp = Rlp::new(&raw);
+
+        assert_eq!(
+            ensure_field_size(&rlp, 0, 3),
+            Err(DecoderError::Custom("Field exceeds maximum size"))
+        );
+    }
+
+    /**
+     * It should decode maintainer key of expected size only
+     */
+    #[test]
+    fn test_decode_maintainer() {
+        let maintainer = SigningKey::generate(&mut OsRng).verifying_key();
+
+        assert_eq!(decode_maintainer(maintainer.as_bytes()), Ok(maintainer));
+
+        for invalid_length in [PUBLIC_KEY_LENGTH - 1, PUBLIC_KEY_LENGTH + 1] {
+            assert_eq!(
+                decode_maintainer(&vec![1u8; invalid_length]),
+                Err(DecoderError::Custom("Invalid maintainer key length"))
+            );
+        }
+    }
+
+    /**
+     * It should decode signature of expected size only
+     */
+    #[test]
+    fn test_decode_signature() {
+        let sig_bytes = [1u8; SIGNATURE_LENGTH];
+
+        assert_eq!(
+            decode_signature(&sig_bytes),
+            Ok(Signature::from_bytes(&sig_bytes))
+        );
+
+        for invalid_length in [SIGNATURE_LENGTH - 1, SIGNATURE_LENGTH + 1] {
+            assert_eq!(
+                decode_signature(&vec![1u8; invalid_length]),
+                Err(DecoderError::Custom("Invalid signature length"))
+            );
+        }
+    }
+}
@@ -1,32 +1,163 @@
-use sha2::{Digest, Sha256};
-use std::path::PathBuf;
+use sha2::{Digest, Sha256, Sha512};
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncReadExt;
+
+use crate::packages::integrity_algorithm::IntegrityAlgorithm;
+
+const HASH_CHUNK_SIZE: usize = 64 * 1024;
 
 /**
- * Compute hash for single file
+ * Incremental hasher matching an integrity algorithm
+ */
+pub enum IntegrityHasher {
+    Sha256(Sha256),
+    Sha512(Sha512),
+    Blake3(Box<blake3::Hasher>),
+}
+
+impl IntegrityHasher {
+    /**
+     * Create hasher for given algorithm
+     */
+    pub fn new(algorithm: &IntegrityAlgorithm) -> Self {
+        match algorithm {
+            IntegrityAlgorithm::Sha256 => Self::Sha256(Sha256::new()),
+            IntegrityAlgorithm::Sha512 => Self::Sha512(Sha512::new()),
+            IntegrityAlgorithm::Blake3 => Self::Blake3(Box::new(blake3::Hasher::new())),
+        }
+    }
+
+    /**
+     * Feed data to hasher
+     */
+    pub fn update(&mut self, data: &[u8]) {
+        match self {
+            Self::Sha256(hasher) => hasher.update(data),
+            Self::Sha512(hasher) => hasher.update(data),
+            Self::Blake3(hasher) => {
+                hasher.update(data);
+            }
+        }
+    }
+
+    /**
+     * Consume hasher and return digest
+     */
+    pub fn finalize(self) -> Vec<u8> {
+        match self {
+            Self::Sha256(hasher) => hasher.finalize().to_vec(),
+            Self::Sha512(hasher) => hasher.finalize().to_vec(),
+            Self::Blake3(hasher) => hasher.finalize().as_bytes().to_vec(),
+        }
+    }
+}
+
+/**
+ * Compute hash for single file, returning it along with algorithm label
  */
 pub async fn compute_package_file_hash(
     path: &PathBuf,
+    algorithm: &IntegrityAlgorithm,
 ) -> Result<(Vec<u8>, String), Box<dyn std::error::Error>> {
-    let mut hasher = Sha256::new(); // TODO : pass hasher through params
+    let hash = compute_file_hash_streaming(path, algorithm).await?;
+
+    Ok((hash, algorithm.to_string()))
+}
+
+/**
+ * Compute hash for directory, walking it recursively
+ *
+ * Files are hashed sorted by relative path along with their path and size, so hash does not depend on filesystem order
+ */
+pub async fn compute_package_directory_hash(
+    path: &PathBuf,
+    algorithm: &IntegrityAlgorithm,
+) -> Result<(Vec<u8>, String), Box<dyn std::error::Error>> {
+    let mut files: Vec<(String, PathBuf)> = Vec::new();
+    let mut pending_directories = vec![path.clone()];
+
+    while let Some(directory) = pending_directories.pop() {
+        let mut entries = tokio::fs::read_dir(&directory).await?;
+
+        while let Some(entry) = entries.next_entry().await? {
+            let entry_path = entry.path();
+            let file_type = entry.file_type().await?;
+
+            if file_type.is_dir() {
+                pending_directories.push(entry_path);
+            } else if file_type.is_file() {
+                let relative_path = entry_path
+                    .strip_prefix(path)?
+                    .components()
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/");
+
+                files.push((relative_path, entry_path));
+            }
+        }
+    }
+
+    files.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let mut hasher = IntegrityHasher::new(algorithm);
+    let mut buffer = vec![0u8; HASH_CHUNK_SIZE];
+
+    for (relative_path, file_path) in files {
+        let mut file = tokio::fs::File::open(&file_path).await?;
+        let file_size = file.metadata().await?.len();
+
+        hasher.update(relative_path.as_bytes());
+        hasher.update(&[0u8]);
+        hasher.update(&file_size.to_be_bytes());
+
+        loop {
+            let read_count = file.read(&mut buffer).await?;
+
+            if read_count == 0 {
+                break;
+            }
+
+            hasher.update(&buffer[..read_count]);
+        }
+    }
 
-    let path = path.as_path().to_str().unwrap();
+    Ok((hasher.finalize(), algorithm.to_string()))
+}
 
-    let content = tokio::fs::read(path).await.unwrap();
+/**
+ * Compute hash of file with given algorithm, reading it chunk by chunk
+ */
+pub async fn compute_file_hash_streaming(
+    path: &Path,
+    algorithm: &IntegrityAlgorithm,
+) -> Result<Vec<u8>, std::io::Error> {
+    let mut hasher = IntegrityHasher::new(algorithm);
 
-    hasher.update(content);
+    let mut file = tokio::fs::File::open(path).await?;
 
-    let result = hasher.finalize();
+    let mut buffer = vec![0u8; HASH_CHUNK_SIZE];
 
-    let hash = Vec::from(result.as_slice());
-    let algorithm = "SHA256".to_string();
+    loop {
+        let read_count = file.read(&mut buffer).await?;
 
-    Ok((hash, algorithm))
+        if read_count == 0 {
+            break;
+        }
+
+        hasher.update(&buffer[..read_count]);
+    }
+
+    Ok(hasher.finalize())
 }
 
 #[cfg(test)]
 mod tests {
 
-    use std::{fs::File, io::Write};
+    use std::{
+        fs::{self, File},
+        io::Write,
+    };
 
     use tempfile::TempDir;
 
@@ -48,10 +179,96 @@ mod tests {
 
         file.write(hashed_content.as_bytes())?;
 
-        let (hash, _) = compute_package_file_hash(&test_file_path).await?;
+        let (hash, _) =
+            compute_package_file_hash(&test_file_path, &IntegrityAlgorithm::Sha256).await?;
 
         assert_eq!(hash, expected_hash);
 
         Ok(())
     }
+
+    /**
+     * It should hash directory content whatever creation order, and change when any file does
+     */
+    #[tokio::test]
+    async fn test_compute_package_directory_hash() -> Result<(), Box<dyn std::error::Error>> {
+        let first_dir = TempDir::new().unwrap();
+        let second_dir = TempDir::new().unwrap();
+
+        fs::create_dir(first_dir.path().join("src"))?;
+        fs::write(first_dir.path().join("README"), "foo")?;
+        fs::write(first_dir.path().join("src").join("main.rs"), "bar")?;
+
+        fs::create_dir(second_dir.path().join("src"))?;
+        fs::write(second_dir.path().join("src").join("main.rs"), "bar")?;
+        fs::write(second_dir.path().join("README"), "foo")?;
+
+        let (first_hash, algorithm) = compute_package_directory_hash(
+            &first_dir.path().to_path_buf(),
+            &IntegrityAlgorithm::Sha256,
+        )
+        .await?;
+        let (second_hash, _) = compute_package_directory_hash(
+            &second_dir.path().to_path_buf(),
+            &IntegrityAlgorithm::Sha256,
+        )
+        .await?;
+
+        assert_eq!(algorithm, "SHA256");
+        assert_eq!(first_hash, second_hash);
+
+        fs::write(second_dir.path().join("src").join("main.rs"), "baz")?;
+
+        let (tampered_hash, _) = compute_package_directory_hash(
+            &second_dir.path().to_path_buf(),
+            &IntegrityAlgorithm::Sha256,
+        )
+        .await?;
+
+        assert_ne!(first_hash, tampered_hash);
+
+        // Moving content between files must change hash too
+        fs::write(second_dir.path().join("src").join("main.rs"), "")?;
+        fs::write(second_dir.path().join("README"), "foobar")?;
+
+        let (moved_hash, _) = compute_package_directory_hash(
+            &second_dir.path().to_path_buf(),
+            &IntegrityAlgorithm::Sha256,
+        )
+        .await?;
+
+        assert_ne!(first_hash, moved_hash);
+
+        Ok(())
+    }
+
+    /**
+     * It should hash file with requested algorithm and label it accordingly
+     */
+    #[tokio::test]
+    async fn test_compute_package_file_hash_algorithms() -> Result<(), Box<dyn std::error::Error>> {
+        let test_dir = TempDir::new().unwrap();
+
+        let test_file_path = test_dir.path().join("test.txt");
+
+        fs::write(&test_file_path, "foo")?;
+
+        let expected_hashes = [
+            (IntegrityAlgorithm::Sha256, Sha256::digest("foo").to_vec()),
+            (IntegrityAlgorithm::Sha512, Sha512::digest("foo").to_vec()),
+            (
+                IntegrityAlgorithm::Blake3,
+                blake3::hash(b"foo").as_bytes().to_vec(),
+            ),
+        ];
+
+        for (algorithm, expected_hash) in expected_hashes {
+            let (hash, label) = compute_package_file_hash(&test_file_path, &algorithm).await?;
+
+            assert_eq!(hash, expected_hash);
+            assert_eq!(label, algorithm.to_string());
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,166 @@
+use std::collections::HashSet;
+
+use ed25519_dalek::VerifyingKey;
+use sha2::{Digest, Sha256};
+
+use crate::packages::package::Package;
+
+const FINGERPRINT_LENGTH: usize = 8;
+
+/**
+ * Compute short maintainer fingerprint, easier to compare than full key
+ */
+pub fn compute_maintainer_fingerprint(maintainer: &VerifyingKey) -> String {
+    let mut hasher = Sha256::new();
+
+    hasher.update(maintainer.to_bytes());
+
+    let hash = hasher.finalize();
+
+    hex::encode_upper(&hash[..FINGERPRINT_LENGTH])
+}
+
+/**
+ * Check if packages were published by several maintainers
+ */
+pub fn has_maintainers_conflict(packages: &[Package]) -> bool {
+    let maintainers: HashSet<[u8; 32]> = packages
+        .iter()
+        .map(|package| package.maintainer.to_bytes())
+        .collect();
+
+    maintainers.len() > 1
+}
+
+/**
+ * Check if maintainer matches given hex key or fingerprint ( case insensitive )
+ */
+pub fn matches_maintainer(maintainer: &VerifyingKey, key_or_fingerprint: &str) -> bool {
+    let key_or_fingerprint = key_or_fingerprint.to_uppercase();
+
+    hex::encode_upper(maintainer.to_bytes()) == key_or_fingerprint
+        || compute_maintainer_fingerprint(maintainer) == key_or_fingerprint
+}
+
+/**
+ * Sort packages so most recommended comes first, ties broken by maintainer fingerprint
+ */
+pub fn sort_by_recommendation(packages: &mut [Package]) {
+    packages.sort_by_cached_key(|package| {
+        (
+            std::cmp::Reverse(package.status.clone()),
+            package.get_maintainer_fingerprint(),
+        )
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        packages::{package_builder::PackageBuilder, package_status::PackageStatus},
+        test_utils::package::tests::create_package_with_sig,
+    };
+
+    use super::*;
+
+    /**
+     * It should compute same fingerprint for same maintainer
+     */
+    #[test]
+    fn test_compute_maintainer_fingerprint() -> Result<(), Box<dyn std::error::Error>> {
+        let package = create_package_with_sig()?;
+        let other_package = create_package_with_sig()?;
+
+        let fingerprint = compute_maintainer_fingerprint(&package.maintainer);
+
+        assert_eq!(fingerprint.len(), FINGERPRINT_LENGTH * 2);
+        assert_eq!(
+            fingerprint,
+            compute_maintainer_fingerprint(&package.maintainer)
+        );
+        assert_ne!(
+            fingerprint,
+            compute_maintainer_fingerprint(&other_package.maintainer)
+        );
+
+        Ok(())
+    }
+
+    /**
+     * It should detect packages published by several maintainers
+     */
+    #[test]
+    fn test_has_maintainers_conflict() -> Result<(), Box<dyn std::error::Error>> {
+        let package = create_package_with_sig()?;
+        let other_package = create_package_with_sig()?;
+
+        assert_eq!(has_maintainers_conflict(&[]), false);
+        assert_eq!(
+            has_maintainers_conflict(&[package.clone(), package.clone()]),
+            false
+        );
+        assert_eq!(has_maintainers_conflict(&[package, other_package]), true);
+
+        Ok(())
+    }
+
+    /**
+     * It should match maintainer using its hex key or fingerprint
+     */
+    #[test]
+    fn test_matches_maintainer() -> Result<(), Box<dyn std::error::Error>> {
+        let package = create_package_with_sig()?;
+        let other_package = create_package_with_sig()?;
+
+        let hex_key = hex::encode(package.maintainer.to_bytes());
+        let fingerprint = compute_maintainer_fingerprint(&package.maintainer);
+
+        assert_eq!(matches_maintainer(&package.maintainer, &hex_key), true);
+        assert_eq!(matches_maintainer(&package.maintainer, &fingerprint), true);
+        assert_eq!(
+            matches_maintainer(&package.maintainer, &fingerprint.to_lowercase()),
+            true
+        );
+        assert_eq!(
+            matches_maintainer(&other_package.maintainer, &fingerprint),
+            false
+        );
+
+        Ok(())
+    }
+
+    /**
+     * It should order packages by status descending then maintainer fingerprint
+     */
+    #[test]
+    fn test_sort_by_recommendation() -> Result<(), Box<dyn std::error::Error>> {
+        let with_status = |status: PackageStatus| -> Result<Package, Box<dyn std::error::Error>> {
+            Ok(PackageBuilder::from_package(&create_package_with_sig()?)
+                .set_status(&status)
+                .build())
+        };
+
+        let outdated = with_status(PackageStatus::Outdated)?;
+        let recommended = with_status(PackageStatus::Recommended)?;
+        let fine = with_status(PackageStatus::Fine)?;
+        let other_fine = with_status(PackageStatus::Fine)?;
+
+        let (first_fine, second_fine) =
+            if fine.get_maintainer_fingerprint() < other_fine.get_maintainer_fingerprint() {
+                (fine.clone(), other_fine.clone())
+            } else {
+                (other_fine.clone(), fine.clone())
+            };
+
+        let mut packages = vec![fine, outdated.clone(), other_fine, recommended.clone()];
+
+        sort_by_recommendation(&mut packages);
+
+        assert_eq!(
+            packages,
+            vec![recommended, first_fine, second_fine, outdated]
+        );
+
+        Ok(())
+    }
+}
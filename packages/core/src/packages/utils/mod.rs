@@ -1,2 +1,7 @@
+pub mod decoding;
 pub mod integrity;
+pub mod maintainers;
 pub mod signatures;
+pub mod signer;
+pub mod validation;
+pub mod versions;
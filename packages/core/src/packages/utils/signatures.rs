@@ -1,20 +1,81 @@
-use ed25519::{signature::SignerMut, Signature};
-use ed25519_dalek::SigningKey;
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use ed25519::Signature;
 use log::debug;
 
-use crate::packages::package::Package;
+use crate::packages::{
+    package::Package, package_builder::PackageBuilder, package_status::PackageStatus,
+};
+
+// Not imported, its sign method would be ambiguous with ed25519 one wherever both are in scope
+use super::signer;
 
 /**
  * Sign given package
  */
-pub fn sign_package(package: &Package, signing_key: &mut SigningKey) -> Signature {
+pub fn sign_package(package: &Package, signer: &dyn signer::Signer) -> Signature {
     let data_integrity_bytes = package.compute_data_integrity();
 
-    let sig = signing_key.sign(&data_integrity_bytes);
+    let sig = signer.sign(&data_integrity_bytes);
 
     sig
 }
 
+/**
+ * Last signed time handed out, in milliseconds
+ */
+static LAST_SIGNED_AT: AtomicU64 = AtomicU64::new(0);
+
+/**
+ * Get current time in milliseconds, always after previous call so mutations signed in a row stay ordered
+ */
+fn next_signed_at() -> u64 {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_millis() as u64;
+
+    let previous = LAST_SIGNED_AT
+        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |last| {
+            Some(now.max(last + 1))
+        })
+        .expect("Signed time update cannot be refused");
+
+    now.max(previous + 1)
+}
+
+/**
+ * Stamp package with current time then sign it, so it supersedes previously signed mutations
+ */
+pub fn sign_package_now(package: &Package, signer: &dyn signer::Signer) -> Package {
+    let signed_at = next_signed_at();
+
+    let stamped_package = PackageBuilder::from_package(package)
+        .set_signed_at(&signed_at)
+        .build();
+
+    let sig = sign_package(&stamped_package, signer);
+
+    PackageBuilder::from_package(&stamped_package)
+        .set_signature(&sig)
+        .build()
+}
+
+/**
+ * Build tombstone of package then sign it, so it supersedes stored package and prohibits it in readers caches
+ */
+pub fn sign_revocation_now(package: &Package, signer: &dyn signer::Signer) -> Package {
+    let tombstone = PackageBuilder::from_package(package)
+        .set_status(&PackageStatus::Prohibited)
+        .set_revoked(&true)
+        .build();
+
+    sign_package_now(&tombstone, signer)
+}
+
 /**
  * Verify given package
  */
@@ -91,4 +152,31 @@ mod tests {
 
         Ok(())
     }
+
+    /**
+     * It should sign mutations made in a row so each one supersedes previous one
+     */
+    #[test]
+    fn test_sign_package_now_in_a_row() -> Result<(), Box<dyn std::error::Error>> {
+        let key = SigningKey::generate(&mut OsRng);
+
+        let package = PackageBuilder::from_package(&create_package_with_sig()?)
+            .set_maintainer(&key.verifying_key())
+            .build();
+
+        let first_mutation = sign_package_now(&package, &key);
+
+        let second_mutation = sign_package_now(
+            &PackageBuilder::from_package(&first_mutation)
+                .set_status(&PackageStatus::Outdated)
+                .build(),
+            &key,
+        );
+
+        assert_eq!(second_mutation.supersedes(&first_mutation), true);
+        assert_eq!(first_mutation.supersedes(&second_mutation), false);
+        assert_eq!(verify_package(&second_mutation).is_some(), true);
+
+        Ok(())
+    }
 }
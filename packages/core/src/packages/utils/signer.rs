@@ -0,0 +1,55 @@
+use ed25519::Signature;
+use ed25519_dalek::{SigningKey, VerifyingKey};
+
+/**
+ * Sign packages without requiring key material to be loaded in memory ( eg: hardware key, KMS... )
+ */
+pub trait Signer: Send + Sync {
+    /**
+     * Get maintainer key signatures can be verified with
+     */
+    fn public_key(&self) -> VerifyingKey;
+
+    /**
+     * Sign given message
+     */
+    fn sign(&self, msg: &[u8]) -> Signature;
+}
+
+/**
+ * In memory key, such as the one stored in config directory
+ */
+impl Signer for SigningKey {
+    fn public_key(&self) -> VerifyingKey {
+        self.verifying_key()
+    }
+
+    fn sign(&self, msg: &[u8]) -> Signature {
+        ed25519::signature::Signer::sign(self, msg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ed25519::signature::rand_core::OsRng;
+
+    use super::*;
+
+    /**
+     * It should sign using in memory key
+     */
+    #[test]
+    fn test_signing_key_signer() {
+        let key = SigningKey::generate(&mut OsRng);
+
+        let signer: &dyn Signer = &key;
+
+        let sig = signer.sign(b"foo");
+
+        assert_eq!(signer.public_key(), key.verifying_key());
+        assert_eq!(
+            signer.public_key().verify_strict(b"foo", &sig).is_ok(),
+            true
+        );
+    }
+}
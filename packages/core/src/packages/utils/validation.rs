@@ -0,0 +1,123 @@
+use crate::packages::errors::package_error::PackageError;
+
+use super::{
+    decoding::{MAX_NAME_LENGTH, MAX_VERSION_LENGTH},
+    versions::VersionConstraint,
+};
+
+/**
+ * Separator used by packages composite keys, must not appear in names or versions
+ */
+const KEY_SEPARATOR: char = ':';
+
+/**
+ * Check field is non-empty, has no whitespace nor key separator and fits in given length
+ */
+fn validate_field(value: &str, max_length: usize) -> Result<(), String> {
+    if value.is_empty() {
+        return Err(String::from("must not be empty"));
+    }
+
+    if value.len() > max_length {
+        return Err(format!("must not exceed {} bytes", max_length));
+    }
+
+    if value.chars().any(char::is_whitespace) {
+        return Err(String::from("must not contain whitespaces"));
+    }
+
+    if value.contains(KEY_SEPARATOR) {
+        return Err(format!("must not contain '{}'", KEY_SEPARATOR));
+    }
+
+    Ok(())
+}
+
+/**
+ * Validate package name
+ */
+pub fn validate_package_name(name: &str) -> Result<(), PackageError> {
+    validate_field(name, MAX_NAME_LENGTH)
+        .map_err(|reason| PackageError::InvalidName(format!("{:?} {}", name, reason)))
+}
+
+/**
+ * Validate package version
+ */
+pub fn validate_package_version(version: &str) -> Result<(), PackageError> {
+    validate_field(version, MAX_VERSION_LENGTH)
+        .map_err(|reason| PackageError::InvalidVersion(format!("{:?} {}", version, reason)))
+}
+
+/**
+ * Validate dependency, which must require an exact or a minimum version of given package
+ */
+pub fn validate_dependency(name: &str, version_constraint: &str) -> Result<(), PackageError> {
+    validate_package_name(name)?;
+
+    VersionConstraint::parse(version_constraint)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /**
+     * It should accept valid names and versions
+     */
+    #[test]
+    fn test_validate_valid_release() {
+        assert_eq!(validate_package_name("neofetch").is_ok(), true);
+        assert_eq!(validate_package_version("7.1.0-2").is_ok(), true);
+    }
+
+    /**
+     * It should reject invalid names
+     */
+    #[test]
+    fn test_validate_invalid_names() {
+        let too_long_name = "a".repeat(MAX_NAME_LENGTH + 1);
+
+        for name in [
+            "",
+            " neofetch",
+            "neo fetch",
+            "neo:fetch",
+            too_long_name.as_str(),
+        ] {
+            assert_eq!(validate_package_name(name).is_err(), true);
+        }
+    }
+
+    /**
+     * It should reject invalid versions
+     */
+    #[test]
+    fn test_validate_invalid_versions() {
+        let too_long_version = "1".repeat(MAX_VERSION_LENGTH + 1);
+
+        for version in ["", "7.1.0 ", "1:7.1.0", too_long_version.as_str()] {
+            assert_eq!(validate_package_version(version).is_err(), true);
+        }
+    }
+
+    /**
+     * It should only accept dependencies on exact or minimum versions
+     */
+    #[test]
+    fn test_validate_dependency() {
+        assert_eq!(validate_dependency("glibc", "2.40").is_ok(), true);
+        assert_eq!(validate_dependency("glibc", "2.40~rc1").is_ok(), true);
+        assert_eq!(validate_dependency("glibc", ">=2.40").is_ok(), true);
+
+        for version in [
+            ">2.40", "<2.40", "=2.40", "^2.40", "~2.40", "2.*", "*", ">=", ">= 2.40",
+        ] {
+            assert_eq!(validate_dependency("glibc", version).is_err(), true);
+        }
+
+        assert_eq!(validate_dependency("gli bc", "2.40").is_err(), true);
+    }
+}
@@ -0,0 +1,171 @@
+use std::{cmp::Ordering, fmt};
+
+use crate::packages::errors::package_error::PackageError;
+
+use super::validation::validate_package_version;
+
+/**
+ * Operator of minimum version constraints
+ */
+const MIN_VERSION_OPERATOR: &str = ">=";
+
+/**
+ * Leading characters of version constraints which are not supported
+ */
+const UNSUPPORTED_OPERATORS: [char; 6] = ['<', '>', '=', '^', '~', '*'];
+
+/**
+ * Version required by a dependency, either an exact one or a minimum one ( eg: >=2.40 )
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionConstraint {
+    Exact(String),
+    AtLeast(String),
+}
+
+impl VersionConstraint {
+    /**
+     * Parse constraint, refusing operators other than >= and wildcards
+     */
+    pub fn parse(constraint: &str) -> Result<Self, PackageError> {
+        let parsed = match constraint.strip_prefix(MIN_VERSION_OPERATOR) {
+            Some(min_version) => VersionConstraint::AtLeast(min_version.to_string()),
+            None => VersionConstraint::Exact(constraint.to_string()),
+        };
+
+        let version = parsed.get_version();
+
+        validate_package_version(version)?;
+
+        if version.starts_with(UNSUPPORTED_OPERATORS) || version.contains('*') {
+            return Err(PackageError::InvalidVersion(format!(
+                "{:?} must be an exact version or a minimum one ( eg: >=2.40 )",
+                constraint
+            )));
+        }
+
+        Ok(parsed)
+    }
+
+    /**
+     * Get version constraint is about
+     */
+    pub fn get_version(&self) -> &str {
+        match self {
+            VersionConstraint::Exact(version) => version,
+            VersionConstraint::AtLeast(version) => version,
+        }
+    }
+
+    /**
+     * Check whether given version satisfies constraint
+     */
+    pub fn matches(&self, version: &str) -> bool {
+        match self {
+            VersionConstraint::Exact(exact_version) => version == exact_version,
+            VersionConstraint::AtLeast(min_version) => {
+                compare_versions(version, min_version) != Ordering::Less
+            }
+        }
+    }
+}
+
+impl fmt::Display for VersionConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VersionConstraint::Exact(version) => write!(f, "{}", version),
+            VersionConstraint::AtLeast(version) => write!(f, "{}{}", MIN_VERSION_OPERATOR, version),
+        }
+    }
+}
+
+/**
+ * Compare package versions segment by segment, numeric segments being compared as numbers
+ *
+ * Not bound to any versioning scheme, eg: 1.10.0 > 1.9.2 and 2.0-2 > 2.0-1
+ */
+pub fn compare_versions(version_a: &str, version_b: &str) -> Ordering {
+    let segments_a = version_a.split(|c: char| !c.is_ascii_alphanumeric());
+    let mut segments_b = version_b.split(|c: char| !c.is_ascii_alphanumeric());
+
+    for segment_a in segments_a {
+        let segment_b = match segments_b.next() {
+            Some(segment_b) => segment_b,
+            None => return Ordering::Greater,
+        };
+
+        let ordering = match (segment_a.parse::<u64>(), segment_b.parse::<u64>()) {
+            (Ok(number_a), Ok(number_b)) => number_a.cmp(&number_b),
+            _ => segment_a.cmp(segment_b),
+        };
+
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+
+    match segments_b.next() {
+        Some(_) => Ordering::Less,
+        None => Ordering::Equal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /**
+     * It should compare numeric segments as numbers and longer versions as greater
+     */
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("1.10.0", "1.9.2"), Ordering::Greater);
+        assert_eq!(compare_versions("2.0-1", "2.0-2"), Ordering::Less);
+        assert_eq!(compare_versions("1.0.0", "1.0.0"), Ordering::Equal);
+        assert_eq!(compare_versions("1.0", "1.0.1"), Ordering::Less);
+        assert_eq!(compare_versions("1.0.1", "1.0"), Ordering::Greater);
+        assert_eq!(
+            compare_versions("1.0.0-rc2", "1.0.0-rc1"),
+            Ordering::Greater
+        );
+    }
+
+    /**
+     * It should parse exact and minimum version constraints, refusing other operators
+     */
+    #[test]
+    fn test_parse_version_constraint() {
+        assert_eq!(
+            VersionConstraint::parse("2.40"),
+            Ok(VersionConstraint::Exact(String::from("2.40")))
+        );
+        assert_eq!(
+            VersionConstraint::parse(">=2.40"),
+            Ok(VersionConstraint::AtLeast(String::from("2.40")))
+        );
+
+        for constraint in [
+            ">2.40", "<2.40", "=2.40", ">==2.40", "^2.40", "~2.40", "2.*", ">=",
+        ] {
+            assert_eq!(VersionConstraint::parse(constraint).is_err(), true);
+        }
+    }
+
+    /**
+     * It should match exact version only, or any version from minimum one
+     */
+    #[test]
+    fn test_version_constraint_matches() {
+        let exact = VersionConstraint::parse("2.40").unwrap();
+
+        assert_eq!(exact.matches("2.40"), true);
+        assert_eq!(exact.matches("2.41"), false);
+
+        let at_least = VersionConstraint::parse(">=2.9").unwrap();
+
+        assert_eq!(at_least.matches("2.9"), true);
+        assert_eq!(at_least.matches("2.10"), true);
+        assert_eq!(at_least.matches("2.8.9"), false);
+        assert_eq!(at_least.to_string(), ">=2.9");
+    }
+}
@@ -1,28 +1,183 @@
-use std::sync::Arc;
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
-use log::{debug, trace};
+use ed25519_dalek::VerifyingKey;
+use futures_util::future::join_all;
+use log::{debug, trace, warn};
 use tokio::sync::mpsc::{self, Receiver, Sender};
 
 use crate::{
-    blockchains::{blockchain::BlockchainClient, errors::blockchain_error::BlockchainError},
+    blockchains::{
+        blockchain::BlockchainClient, errors::blockchain_error::BlockchainError,
+        payload::decode_package,
+    },
     db::{
-        documents::blockchain_document_builder::BlockchainDocumentBuilder,
+        client::DbClient,
+        documents::{
+            blockchain_document::BlockchainDocument,
+            blockchain_document_builder::BlockchainDocumentBuilder,
+        },
+        errors::repository_error::RepositoryError,
         traits::repository::Repository,
     },
-    packages::package::Package,
+    metrics::{self, Counter, MetricsSnapshot},
+    packages::{
+        package::Package,
+        package_status::PackageStatus,
+        utils::{
+            maintainers::{has_maintainers_conflict, sort_by_recommendation},
+            signatures::verify_package,
+            versions::VersionConstraint,
+        },
+    },
     types::asynchronous::AsyncMutex,
 };
 
-use super::{db::blockchains_repository::BlockchainsRepository, packages::PackagesService};
+use super::{
+    db::{blockchains_repository::BlockchainsRepository, packages_repository::PackagesRepository},
+    errors::install_error::InstallError,
+    packages::PackagesService,
+};
 
 #[cfg(test)]
 use mockall::automock;
 
+/**
+ * What happened to a package while updating from blockchain
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PackageUpdateKind {
+    Added,
+    Updated,
+    Unchanged,
+    Rejected, // Not signed after stored package, most likely replayed
+    Revoked,  // Replaced by a tombstone signed by its maintainer
+}
+
+/**
+ * How many packages were added, updated, left unchanged, rejected or revoked while updating from blockchain
+ */
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UpdateTally {
+    pub added: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    pub rejected: usize,
+    pub revoked: usize,
+}
+
+impl UpdateTally {
+    /**
+     * Count processed package
+     */
+    pub fn record(&mut self, update_kind: &PackageUpdateKind) {
+        match update_kind {
+            PackageUpdateKind::Added => self.added += 1,
+            PackageUpdateKind::Updated => self.updated += 1,
+            PackageUpdateKind::Unchanged => self.unchanged += 1,
+            PackageUpdateKind::Rejected => self.rejected += 1,
+            PackageUpdateKind::Revoked => self.revoked += 1,
+        }
+    }
+
+    /**
+     * Add other tally counts to this one
+     */
+    pub fn merge(&mut self, other: &UpdateTally) {
+        self.added += other.added;
+        self.updated += other.updated;
+        self.unchanged += other.unchanged;
+        self.rejected += other.rejected;
+        self.revoked += other.revoked;
+    }
+}
+
+/**
+ * Whether package could be submitted to given blockchain
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainSubmission {
+    pub blockchain_label: String,
+    pub submitted: bool,
+}
+
+/**
+ * Get current epoch timestamp
+ */
+fn current_timestamp() -> i64 {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_secs();
+
+    i64::try_from(timestamp).expect("Could not convert timestamp to i64")
+}
+
+/**
+ * How many packages are persisted in parallel while updating from blockchain, unless configured otherwise
+ */
+pub const DEFAULT_UPDATE_CONCURRENCY: usize = 4;
+
+/**
+ * Get worker processing given package, mutations of a same package always going to the same worker so they are applied in order
+ */
+fn get_update_worker(package: &Package, workers_count: usize) -> usize {
+    let mut hasher = DefaultHasher::new();
+
+    package.name.hash(&mut hasher);
+    package.version.hash(&mut hasher);
+    package.maintainer.as_bytes().hash(&mut hasher);
+
+    (hasher.finish() % workers_count as u64) as usize
+}
+
+/**
+ * Callback invoked for each package processed while updating from blockchain
+ */
+pub type PackageUpdateCallback = Box<dyn Fn(&Package, &PackageUpdateKind) + Send + Sync>;
+
 pub struct BlockchainsService {
     blockchains_clients: Arc<AsyncMutex<Vec<Arc<Box<dyn BlockchainClient>>>>>,
     selected_client: Arc<AsyncMutex<Option<usize>>>, // TODO : change to ref
     blockchains_repository: Arc<BlockchainsRepository>,
     packages_service: Arc<PackagesService>,
+    package_update_callbacks: Arc<AsyncMutex<Vec<PackageUpdateCallback>>>,
+    update_concurrency: Arc<AsyncMutex<usize>>,
+}
+
+impl BlockchainsService {
+    /**
+     * Open DB at given path and wire blockchains service, along with its packages service, on top of it
+     */
+    pub async fn open(
+        db_path: &PathBuf,
+        available_blockchains: &Vec<Arc<Box<dyn BlockchainClient>>>,
+    ) -> Result<Arc<BlockchainsService>, RepositoryError> {
+        let db_client = Arc::new(DbClient::try_from(db_path)?);
+
+        if let Err(e) = db_client.ensure_indexes().await {
+            warn!("{}, lookups will be slower", e);
+        }
+
+        let blockchains_repository = Arc::new(BlockchainsRepository::from(&db_client));
+        let packages_repository = Arc::new(PackagesRepository::from(&db_client));
+
+        let packages_service = Arc::new(PackagesService::from(&packages_repository));
+
+        Ok(Arc::new(
+            BlockchainsService::new(
+                available_blockchains,
+                &blockchains_repository,
+                &packages_service,
+            )
+            .await,
+        ))
+    }
 }
 
 #[cfg_attr(test, automock)]
@@ -40,6 +195,8 @@ impl BlockchainsService {
             blockchains_clients: Arc::new(AsyncMutex::new(available_blockchains.clone())),
             selected_client: Arc::new(AsyncMutex::new(None)),
             packages_service: Arc::clone(&packages_service),
+            package_update_callbacks: Arc::new(AsyncMutex::new(vec![])),
+            update_concurrency: Arc::new(AsyncMutex::new(DEFAULT_UPDATE_CONCURRENCY)),
         };
 
         instance.init_blockchains().await;
@@ -54,41 +211,65 @@ impl BlockchainsService {
         let clients = self.blockchains_clients.lock().await;
 
         for client in clients.iter() {
-            let blockchain_document_opt = self
-                .blockchains_repository
-                .read_by_key(&client.get_label())
-                .await;
+            self.init_blockchain(client).await;
+        }
+    }
+
+    /**
+     * Initialize blockchain, registering it if needed
+     */
+    async fn init_blockchain(&self, client: &Arc<Box<dyn BlockchainClient>>) {
+        let blockchain_document_opt = self
+            .blockchains_repository
+            .read_by_key(&client.get_label())
+            .await;
 
-            let exists = blockchain_document_opt.is_some();
+        let exists = blockchain_document_opt.is_some();
 
-            if exists {
-                debug!("Blockchain is already registered");
-                let blockchain_document =
-                    blockchain_document_opt.expect("Blockchain document should have been defined");
+        if exists {
+            debug!("Blockchain is already registered");
+            let blockchain_document =
+                blockchain_document_opt.expect("Blockchain document should have been defined");
 
-                let last_sync: u64 = blockchain_document
-                    .last_synchronization
-                    .parse()
-                    .expect("Could not parse last sync timestamp from blockchain document");
+            // Rewrite document so timestamps stored as string get migrated
+            self.blockchains_repository
+                .update(&blockchain_document.label, &blockchain_document)
+                .await;
 
-                client.set_last_sync(last_sync).await;
-            } else {
-                debug!("Blockchain will be registered...");
+            client
+                .set_last_sync(blockchain_document.last_synchronization)
+                .await;
+        } else {
+            debug!("Blockchain will be registered...");
 
-                let mut builder = BlockchainDocumentBuilder::default();
+            let mut builder = BlockchainDocumentBuilder::default();
 
-                let last_sync = 0;
+            let last_sync = 0;
 
-                let doc = builder
-                    .set_label(&client.get_label())
-                    .set_last_synchronization(&last_sync.to_string())
-                    .build();
-                self.blockchains_repository.create(&doc).await;
-                debug!("Done registering blockchain !");
+            let doc = builder
+                .set_label(&client.get_label())
+                .set_last_synchronization(&last_sync)
+                .build();
+            match self.blockchains_repository.create(&doc).await {
+                Ok(_) => debug!("Done registering blockchain !"),
+                Err(e) => warn!("Could not register blockchain : {}", e),
             }
         }
     }
 
+    /**
+     * Add client ( eg: one-off topic ) then return its index
+     */
+    pub async fn add_client(&self, client: &Arc<Box<dyn BlockchainClient>>) -> usize {
+        self.init_blockchain(client).await;
+
+        let mut clients = self.blockchains_clients.lock().await;
+
+        clients.push(Arc::clone(client));
+
+        clients.len() - 1
+    }
+
     /**
      * Get available clients
      */
@@ -105,6 +286,26 @@ impl BlockchainsService {
         *selected_client_lock = Some(client_idx);
     }
 
+    /**
+     * Set current client using its label, returns false when no client has this label
+     */
+    pub async fn set_client_by_label(&self, label: &String) -> bool {
+        let client_idx = self
+            .blockchains_clients
+            .lock()
+            .await
+            .iter()
+            .position(|client| client.get_label() == *label);
+
+        match client_idx {
+            Some(client_idx) => {
+                self.set_client(client_idx).await;
+                true
+            }
+            None => false,
+        }
+    }
+
     /**
      * Get current client
      */
@@ -124,6 +325,24 @@ impl BlockchainsService {
         Arc::clone(client)
     }
 
+    /**
+     * Register callback invoked for each package processed while updating from blockchain
+     */
+    pub async fn on_package_update(&self, callback: PackageUpdateCallback) {
+        let mut callbacks = self.package_update_callbacks.lock().await;
+
+        callbacks.push(callback);
+    }
+
+    /**
+     * Set how many packages are persisted in parallel while updating from blockchain, at least one
+     */
+    pub async fn set_update_concurrency(&self, update_concurrency: usize) {
+        let mut update_concurrency_lock = self.update_concurrency.lock().await;
+
+        *update_concurrency_lock = update_concurrency.max(1);
+    }
+
     /**
      * This method is used to process package when updating from blockchain
      */
@@ -131,27 +350,99 @@ impl BlockchainsService {
         &self,
         package: &Package,
         selected_client: &Box<dyn BlockchainClient>,
-    ) {
-        let package_exists = self
-            .packages_service
-            .exists(&package, selected_client)
-            .await;
+    ) -> PackageUpdateKind {
+        let stored_package = self.packages_service.get(&package, selected_client).await;
 
-        if package_exists {
-            trace!("Package already exists, updating it...");
+        let update_kind = match stored_package {
+            Some(stored_package) if stored_package.is_same_mutation(package) => {
+                trace!("Package already exists and did not change, skipping");
 
-            self.packages_service
-                .update_package(&package, selected_client)
-                .await;
+                PackageUpdateKind::Unchanged
+            }
+            // Tombstone must not leave an installable release behind
+            _ if package.revoked && package.status != PackageStatus::Prohibited => {
+                warn!(
+                    "Package {}:{} revocation does not prohibit release, rejecting it",
+                    package.name, package.version
+                );
+
+                PackageUpdateKind::Rejected
+            }
+            // Stored package shares tombstone maintainer as it is part of DB key, so only its maintainer can revoke it
+            // Tombstone replaces it, so mutations signed before revocation are rejected if replayed
+            Some(stored_package) if package.revoked && package.supersedes(&stored_package) => {
+                trace!("Package was revoked by its maintainer, storing tombstone...");
 
-            trace!("Done updating already existing package !");
-        } else {
-            trace!("Package doesn't exist, adding it...");
+                self.packages_service
+                    .update_package(&package, selected_client)
+                    .await;
+
+                trace!("Done storing tombstone !");
+
+                PackageUpdateKind::Revoked
+            }
+            Some(_) if package.revoked => {
+                warn!(
+                    "Package {}:{} revocation was not signed after stored one, rejecting it",
+                    package.name, package.version
+                );
+
+                PackageUpdateKind::Rejected
+            }
+            // Nothing to revoke yet, tombstone is still kept so revoked mutation cannot be added later
+            None if package.revoked => {
+                trace!("Revoked package was never stored, storing tombstone...");
+
+                self.packages_service.add(&package, selected_client).await;
+
+                trace!("Done storing tombstone !");
+
+                PackageUpdateKind::Revoked
+            }
+            Some(stored_package) if !package.supersedes(&stored_package) => {
+                warn!(
+                    "Package {}:{} mutation was not signed after stored one, rejecting it",
+                    package.name, package.version
+                );
+
+                PackageUpdateKind::Rejected
+            }
+            Some(_) => {
+                trace!("Package already exists, updating it...");
+
+                self.packages_service
+                    .update_package(&package, selected_client)
+                    .await;
+
+                trace!("Done updating already existing package !");
+
+                PackageUpdateKind::Updated
+            }
+            None => {
+                trace!("Package doesn't exist, adding it...");
+
+                self.packages_service.add(&package, selected_client).await;
 
-            self.packages_service.add(&package, selected_client).await;
+                trace!("Done adding new package !");
 
-            trace!("Done adding new package !");
+                PackageUpdateKind::Added
+            }
+        };
+
+        if matches!(
+            update_kind,
+            PackageUpdateKind::Added | PackageUpdateKind::Updated | PackageUpdateKind::Revoked
+        ) {
+            metrics::increment(Counter::DbWrites);
+        }
+
+        let callbacks = self.package_update_callbacks.lock().await;
+
+        for callback in callbacks.iter() {
+            callback(package, &update_kind);
         }
+
+        update_kind
     }
 
     /**
@@ -160,7 +451,48 @@ impl BlockchainsService {
     pub async fn update(
         &self,
         tx_packages_update: &Sender<Package>,
-    ) -> Result<(), BlockchainError> {
+    ) -> Result<UpdateTally, BlockchainError> {
+        self.apply_updates(tx_packages_update, None).await
+    }
+
+    /**
+     * Reconstruct packages as they were at given timestamp into a throwaway view, returning its packages service
+     *
+     * Every mutation published before timestamp is read from start, local cache and last sync are left untouched
+     */
+    pub async fn view_as_of(
+        &self,
+        tx_packages_update: &Sender<Package>,
+        as_of: u64,
+    ) -> Result<(Arc<PackagesService>, UpdateTally), BlockchainError> {
+        let db_client = Arc::new(DbClient::in_memory());
+
+        let packages_repository = Arc::new(PackagesRepository::from(&db_client));
+        let packages_service = Arc::new(PackagesService::from(&packages_repository));
+
+        // Same clients, but packages and sync state go to throwaway DB
+        let view = Self {
+            blockchains_clients: Arc::clone(&self.blockchains_clients),
+            selected_client: Arc::clone(&self.selected_client),
+            blockchains_repository: Arc::new(BlockchainsRepository::from(&db_client)),
+            packages_service: Arc::clone(&packages_service),
+            package_update_callbacks: Arc::new(AsyncMutex::new(vec![])),
+            update_concurrency: Arc::clone(&self.update_concurrency),
+        };
+
+        let tally = view.apply_updates(tx_packages_update, Some(as_of)).await?;
+
+        Ok((packages_service, tally))
+    }
+
+    /**
+     * Apply packages mutations read from blockchain, every one published before given timestamp if any, new ones otherwise
+     */
+    async fn apply_updates(
+        &self,
+        tx_packages_update: &Sender<Package>,
+        as_of: Option<u64>,
+    ) -> Result<UpdateTally, BlockchainError> {
         debug!("Updating package manager from blockchain...");
         let (tx_packages, mut rx_packages): (
             Sender<Result<Package, BlockchainError>>,
@@ -172,7 +504,10 @@ impl BlockchainsService {
 
         // Start to read packages from blockchain
         tokio::spawn(async move {
-            let task_res = task_client.read_packages(&tx_packages).await;
+            let task_res = match as_of {
+                Some(as_of) => task_client.read_packages_as_of(&tx_packages, as_of).await,
+                None => task_client.read_packages(&tx_packages).await,
+            };
 
             match task_res {
                 Ok(_) => (),
@@ -185,208 +520,2153 @@ impl BlockchainsService {
 
         let selected_client = self.get_selected_client().await;
 
-        // Send notifications to upper scopes
-        while let Some(package_res) = rx_packages.recv().await {
-            let package = match package_res {
-                Ok(package) => package,
-                Err(e) => {
-                    return Err(e);
+        let workers_count = *self.update_concurrency.lock().await;
+
+        let (tx_workers, rx_workers): (Vec<Sender<Package>>, Vec<Receiver<Package>>) =
+            (0..workers_count).map(|_| mpsc::channel(1)).unzip();
+
+        // Each worker persists its packages then sends notifications to upper scopes
+        let workers = rx_workers.into_iter().map(|mut rx_worker| {
+            let selected_client = &selected_client;
+
+            async move {
+                let mut tally = UpdateTally::default();
+
+                while let Some(package) = rx_worker.recv().await {
+                    let update_kind = self.process_package_update(&package, selected_client).await;
+
+                    tally.record(&update_kind);
+
+                    tx_packages_update.send(package).await.unwrap();
                 }
-            };
-            self.process_package_update(&package, &selected_client)
-                .await;
 
-            tx_packages_update.send(package).await.unwrap();
+                tally
+            }
+        });
+
+        // Workers stop once every package was dispatched, as their senders are dropped
+        let dispatch = async move {
+            while let Some(package_res) = rx_packages.recv().await {
+                let package = match package_res {
+                    Ok(package) => package,
+                    // Empty topic is not an error, there is just nothing to update
+                    Err(BlockchainError::NoPackagesData) => {
+                        debug!("No packages data found on blockchain");
+                        break;
+                    }
+                    Err(e) => return Err(e),
+                };
+
+                let worker = get_update_worker(&package, workers_count);
+
+                tx_workers[worker].send(package).await.unwrap();
+            }
+
+            Ok(())
+        };
+
+        // In-flight writes always complete, even when blockchain failed meanwhile
+        let (dispatch_result, workers_tallies) = futures_util::join!(dispatch, join_all(workers));
+
+        let mut tally = UpdateTally::default();
+
+        for worker_tally in &workers_tallies {
+            tally.merge(worker_tally);
+        }
+
+        if let Err(e) = dispatch_result {
+            metrics::increment(Counter::BlockchainErrors);
+            self.record_sync_failure(&client, &e).await;
+            return Err(e);
         }
 
-        // Update current blockchain's doc to set last sync time to now
+        // Update current blockchain's doc to set last sync time to now, clearing previous error
         let doc = BlockchainDocumentBuilder::default()
             .set_label(&client.get_label())
-            .set_last_synchronization(&client.get_last_sync().await.to_string())
+            .set_last_synchronization(&client.get_last_sync().await)
+            .set_last_attempt(&current_timestamp())
             .build();
 
         self.blockchains_repository.update(&doc.label, &doc).await;
 
-        debug!("Done updating package manager from blockchain !");
+        debug!(
+            "Done updating package manager from blockchain ! ({:?})",
+            tally
+        );
 
-        Ok(())
+        Ok(tally)
     }
 
     /**
-     * Find package
+     * Persist failed synchronization attempt on blockchain document, keeping last successful sync time
      */
-    pub async fn find_package(
+    async fn record_sync_failure(
         &self,
-        package_name: &String,
-        package_version: &String,
-    ) -> Vec<Package> {
-        let selected_client = self.get_selected_client().await;
-        let matching_packages = self
-            .packages_service
-            .get_by_release(&package_name, &package_version, &selected_client)
-            .await;
+        client: &Arc<Box<dyn BlockchainClient>>,
+        error: &BlockchainError,
+    ) {
+        let label = client.get_label();
 
-        matching_packages
+        let mut builder = match self.blockchains_repository.read_by_key(&label).await {
+            Some(doc) => BlockchainDocumentBuilder::from_document(&doc),
+            None => {
+                let mut builder = BlockchainDocumentBuilder::default();
+                builder
+                    .set_label(&label)
+                    .set_last_synchronization(&client.get_last_sync().await);
+                builder
+            }
+        };
+
+        let doc = builder
+            .set_last_attempt(&current_timestamp())
+            .set_last_error(&error.to_string())
+            .build();
+
+        self.blockchains_repository.update(&doc.label, &doc).await;
     }
 
     /**
-     * Submit package to blockchain
+     * Get epoch timestamp selected blockchain was last synchronized at, 0 if never synchronized
      */
-    pub async fn submit_package(&self, package: &Package) {
-        debug!("Submitting package to blockchain IO...");
+    pub async fn get_last_sync(&self) -> u64 {
+        self.get_selected_client().await.get_last_sync().await
+    }
 
+    /**
+     * Forget last synchronization of selected blockchain, so next update re-reads it from the very beginning
+     *
+     * Useful once local DB got corrupted, stored packages are kept and only overwritten by newer mutations
+     */
+    pub async fn reset_last_sync(&self) {
         let client = self.get_selected_client().await;
-        client.write_package(package).await;
+        let label = client.get_label();
 
-        debug!("Done submitting package to blockchain IO !");
-    }
-}
+        debug!("Resetting last sync of blockchain {}...", label);
 
-#[cfg(test)]
-mod tests {
+        client.set_last_sync(0).await;
 
-    use crate::{
-        blockchains::blockchain::MockBlockchainClient,
-        services::db::packages_repository::PackagesRepository,
-        test_utils::{db::tests::create_test_db, package::tests::create_package_with_sig},
-    };
-    use mockall::{mock, predicate::*};
+        let mut builder = match self.blockchains_repository.read_by_key(&label).await {
+            Some(doc) => BlockchainDocumentBuilder::from_document(&doc),
+            None => {
+                let mut builder = BlockchainDocumentBuilder::default();
+                builder.set_label(&label);
+                builder
+            }
+        };
 
-    use super::*;
+        let doc = builder.set_last_synchronization(&0).build();
+
+        self.blockchains_repository.update(&doc.label, &doc).await;
+
+        debug!("Done resetting last sync of blockchain {} !", label);
+    }
 
     /**
-     * It should get client
+     * Check if selected blockchain was successfully synchronized less than max age ago
      */
-    #[tokio::test]
-    async fn test_get_clients() -> Result<(), Box<dyn std::error::Error>> {
-        let db_client = create_test_db();
+    pub async fn is_cache_fresh(&self, max_age: &Duration) -> bool {
+        let last_sync = self.get_selected_client().await.get_last_sync().await;
 
-        // Instantiate required resources
+        // Never synchronized
+        if last_sync == 0 {
+            return false;
+        }
 
-        let blockchains_repository = Arc::new(BlockchainsRepository::from(&db_client));
-        let packages_repository = Arc::new(PackagesRepository::from(&db_client));
+        let age = u64::try_from(current_timestamp())
+            .unwrap_or_default()
+            .saturating_sub(last_sync);
 
-        let packages_service = Arc::new(PackagesService::from(&packages_repository));
+        age < max_age.as_secs()
+    }
 
-        let mut blockchain_mock = MockBlockchainClient::default();
+    /**
+     * Get stored state of every registered blockchain, including last synchronization error if any
+     */
+    pub async fn get_blockchains_status(&self) -> Vec<BlockchainDocument> {
+        self.blockchains_repository.read_all().await
+    }
 
-        blockchain_mock
-            .expect_get_label()
-            .returning(|| "MockBlockchain".to_string());
+    /**
+     * Keep updating from blockchain on given interval, until packages update receiver is dropped
+     *
+     * Transient blockchain errors are retried on next round, others stop watching
+     */
+    pub async fn watch(
+        &self,
+        interval: Duration,
+        tx_packages_update: &Sender<Package>,
+    ) -> Result<UpdateTally, BlockchainError> {
+        debug!("Watching blockchain...");
 
-        let expected_label = blockchain_mock.get_label().clone();
+        let mut tally = UpdateTally::default();
 
-        let blockchain_client: Box<dyn BlockchainClient> = Box::new(blockchain_mock);
+        while !tx_packages_update.is_closed() {
+            let (tx_round_update, mut rx_round_update) = mpsc::channel(1);
 
-        let blockchains_clients_mock = vec![Arc::new(blockchain_client)];
+            // Forward round mutations, draining them even once receiver is gone so update can complete
+            let forward_updates = async {
+                while let Some(package) = rx_round_update.recv().await {
+                    let _ = tx_packages_update.send(package).await;
+                }
+            };
 
-        let blockchains_service = BlockchainsService::new(
-            &blockchains_clients_mock,
-            &blockchains_repository,
-            &packages_service,
-        )
-        .await;
+            let (round_res, _) = tokio::join!(
+                async move { self.update(&tx_round_update).await },
+                forward_updates
+            );
 
-        let clients = blockchains_service.get_clients();
+            match round_res {
+                Ok(round_tally) => tally.merge(&round_tally),
+                Err(e) if e.is_transient() => warn!("Could not update from blockchain : {}", e),
+                Err(e) => return Err(e),
+            }
 
-        let label = clients.lock().await[0].get_label();
-        assert_eq!(label, expected_label);
+            tokio::select! {
+                _ = tokio::time::sleep(interval) => (),
+                _ = tx_packages_update.closed() => (),
+            }
+        }
 
-        Ok(())
+        debug!("Done watching blockchain ! ({:?})", tally);
+
+        Ok(tally)
     }
 
     /**
-     * It should initialize blockchains
+     * Get synchronization metrics, always zero unless metrics feature is enabled
      */
-    #[tokio::test]
-    async fn test_init_blockchains() -> Result<(), Box<dyn std::error::Error>> {
-        let db_client = create_test_db();
-
-        // Instantiate required resources
-
-        let blockchains_repository = Arc::new(BlockchainsRepository::from(&db_client));
-        let packages_repository = Arc::new(PackagesRepository::from(&db_client));
-
-        let packages_service = Arc::new(PackagesService::from(&packages_repository));
+    pub fn metrics(&self) -> MetricsSnapshot {
+        metrics::snapshot()
+    }
 
-        let mut blockchain_mock = MockBlockchainClient::default();
+    /**
+     * Wait until given package can be read back from blockchain since given timestamp
+     */
+    pub async fn confirm_package(&self, package: &Package, since: u64, timeout: Duration) -> bool {
+        debug!("Waiting for package to be readable from blockchain...");
 
-        blockchain_mock
-            .expect_get_label()
-            .returning(|| "MockBlockchain".to_string());
+        let client = self.get_selected_client().await;
+        let io = client.create_io().await;
+
+        let confirmation = tokio::time::timeout(timeout, async {
+            loop {
+                let (tx_messages, mut rx_messages) = mpsc::channel(1);
+
+                let task_io = Arc::clone(&io);
+                tokio::spawn(async move {
+                    task_io.read(&tx_messages, &since, &None).await;
+                });
+
+                while let Some(message_res) = rx_messages.recv().await {
+                    let message = match message_res {
+                        Ok(message) => message,
+                        Err(_) => continue,
+                    };
+
+                    let read_package = match decode_package(&message.payload) {
+                        Ok(read_package) => read_package,
+                        Err(_) => continue,
+                    };
+
+                    if verify_package(&read_package).is_some() && read_package == *package {
+                        return;
+                    }
+                }
 
-        blockchain_mock
-            .expect_get_last_sync()
-            .returning(|| Box::pin(async { 0 }));
+                // Mirror nodes may lag behind consensus, try again
+                tokio::time::sleep(Duration::from_millis(500)).await;
+            }
+        })
+        .await;
 
-        blockchain_mock
-            .expect_set_last_sync()
-            .returning(|_| Box::pin(async { println!("set_last_sync mock executed") }));
+        let confirmed = confirmation.is_ok();
 
-        let blockchain_client: Box<dyn BlockchainClient> = Box::new(blockchain_mock);
+        debug!("Done waiting for package ! ({})", confirmed);
 
-        let blockchains_clients_mock = vec![Arc::new(blockchain_client)];
+        confirmed
+    }
 
-        let blockchains_service = BlockchainsService::new(
-            &blockchains_clients_mock,
+    /**
+     * Find package, leaving out ones below given status if any, sorted by status descending then maintainer fingerprint
+     */
+    pub async fn find_package(
+        &self,
+        package_name: &String,
+        package_version: &String,
+        min_status: &Option<PackageStatus>,
+    ) -> Vec<Package> {
+        let selected_client = self.get_selected_client().await;
+        let mut matching_packages = self
+            .packages_service
+            .get_by_release(
+                &package_name,
+                &package_version,
+                min_status,
+                &selected_client,
+            )
+            .await;
+
+        // Keep selection list stable, most recommended first
+        sort_by_recommendation(&mut matching_packages);
+
+        matching_packages
+    }
+
+    /**
+     * Find releases of package satisfying given version constraint, the ones of newest version having an installable
+     * release, or of newest version when none has, sorted most recommended first
+     */
+    pub async fn find_satisfying(
+        &self,
+        package_name: &String,
+        version_constraint: &String,
+    ) -> Vec<Package> {
+        let version_constraint = match VersionConstraint::parse(version_constraint) {
+            Ok(version_constraint) => version_constraint,
+            Err(_) => return vec![],
+        };
+
+        let mut releases = self
+            .find_satisfying_releases(package_name, &[version_constraint])
+            .await;
+
+        let installable_idx = releases.iter().position(|(_, candidates)| {
+            candidates
+                .iter()
+                .any(|candidate| candidate.status >= PackageStatus::Outdated)
+        });
+
+        match installable_idx {
+            Some(idx) => releases.swap_remove(idx).1,
+            None => releases
+                .into_iter()
+                .next()
+                .map(|(_, candidates)| candidates)
+                .unwrap_or_default(),
+        }
+    }
+
+    /**
+     * Find releases of package satisfying every given version constraint, grouped by version, newest version first
+     */
+    async fn find_satisfying_releases(
+        &self,
+        package_name: &String,
+        version_constraints: &[VersionConstraint],
+    ) -> Vec<(String, Vec<Package>)> {
+        let mut releases = vec![];
+
+        // Versions are sorted oldest first
+        for version in self.get_versions(package_name).await.into_iter().rev() {
+            let is_satisfying = version_constraints
+                .iter()
+                .all(|version_constraint| version_constraint.matches(&version));
+
+            if !is_satisfying {
+                continue;
+            }
+
+            let candidates = self.find_package(package_name, &version, &None).await;
+
+            if !candidates.is_empty() {
+                releases.push((version, candidates));
+            }
+        }
+
+        releases
+    }
+
+    /**
+     * Pick release of dependency satisfying every given version constraint
+     *
+     * Newest installable release published by maintainer of package requiring it is picked when there is one, most
+     * recommended one of newest installable version otherwise ( refused in strict mode when maintainers conflict )
+     */
+    async fn pick_dependency(
+        &self,
+        dependency_name: &String,
+        version_constraints: &[VersionConstraint],
+        requiring_maintainer: &VerifyingKey,
+        strict: bool,
+    ) -> Result<Package, InstallError> {
+        let releases = self
+            .find_satisfying_releases(dependency_name, version_constraints)
+            .await;
+
+        let same_maintainer_candidate = releases
+            .iter()
+            .flat_map(|(_, candidates)| candidates.iter())
+            .find(|candidate| {
+                candidate.maintainer == *requiring_maintainer
+                    && candidate.status >= PackageStatus::Outdated
+            })
+            .cloned();
+
+        if let Some(candidate) = same_maintainer_candidate {
+            return Ok(candidate);
+        }
+
+        let constraints_description = version_constraints
+            .iter()
+            .map(|version_constraint| version_constraint.to_string())
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        let full_dependency_name = format!("{}:{}", dependency_name, constraints_description);
+
+        // Same threshold as install
+        let installable_release = releases.iter().find(|(_, candidates)| {
+            candidates
+                .iter()
+                .any(|candidate| candidate.status >= PackageStatus::Outdated)
+        });
+
+        let (version, candidates) = match installable_release {
+            Some(release) => release,
+            None => {
+                // Most recommended release being first, every other one of its version is below it too
+                return match releases.first() {
+                    Some((_, candidates)) => Err(InstallError::ForbiddenDependency(format!(
+                        "{} ( {} )",
+                        full_dependency_name, candidates[0].status
+                    ))),
+                    None => Err(InstallError::MissingDependency(full_dependency_name)),
+                };
+            }
+        };
+
+        if has_maintainers_conflict(candidates) {
+            let picked_dependency_name = format!("{}:{}", dependency_name, version);
+
+            if strict {
+                return Err(InstallError::MaintainersConflict(picked_dependency_name));
+            }
+
+            warn!(
+                "Several maintainers published dependency {}, picking most recommended one",
+                picked_dependency_name
+            );
+        }
+
+        Ok(candidates[0].clone())
+    }
+
+    /**
+     * Resolve package dependencies recursively, returning install order ending with package itself
+     *
+     * Each dependency comes before packages requiring it, constraints of every package requiring it being satisfied
+     * by the single release picked for it ( see pick_dependency )
+     */
+    pub async fn resolve_dependencies(
+        &self,
+        package: &Package,
+        strict: bool,
+    ) -> Result<Vec<Package>, InstallError> {
+        debug!(
+            "Resolving dependencies of {}:{}...",
+            package.name, package.version
+        );
+
+        // Constraints learned so far by dependency name, resolution starts over whenever one invalidates a picked release
+        let mut version_constraints: HashMap<String, Vec<VersionConstraint>> = HashMap::new();
+
+        let install_order = loop {
+            if let Some(install_order) = self
+                .try_resolve_dependencies(package, strict, &mut version_constraints)
+                .await?
+            {
+                break install_order;
+            }
+
+            trace!("Release picked does not satisfy every constraint, resolving again...");
+        };
+
+        debug!(
+            "Done resolving dependencies of {}:{} ! ( {} packages to install )",
+            package.name,
+            package.version,
+            install_order.len()
+        );
+
+        Ok(install_order)
+    }
+
+    /**
+     * Resolve package dependencies given constraints learned so far, returning none when a new constraint is not
+     * satisfied by a release already picked
+     */
+    async fn try_resolve_dependencies(
+        &self,
+        package: &Package,
+        strict: bool,
+        version_constraints: &mut HashMap<String, Vec<VersionConstraint>>,
+    ) -> Result<Option<Vec<Package>>, InstallError> {
+        let mut install_order: Vec<Package> = vec![];
+
+        // Version picked for each dependency already resolved
+        let mut resolved: HashMap<String, String> = HashMap::new();
+
+        // Packages being resolved along with index of their next dependency, a dependency already in there means a cycle
+        let mut pending: Vec<(Package, usize)> = vec![(package.clone(), 0)];
+
+        while let Some((current_package, dependency_idx)) = pending.last_mut() {
+            let next_dependency = current_package.dependencies.get(*dependency_idx).cloned();
+            let requiring_maintainer = current_package.maintainer;
+
+            *dependency_idx += 1;
+
+            let (dependency_name, dependency_constraint) = match next_dependency {
+                Some(dependency) => dependency,
+                None => {
+                    // Every dependency resolved, package can be installed
+                    let (current_package, _) = pending.pop().unwrap();
+
+                    resolved.insert(
+                        current_package.name.clone(),
+                        current_package.version.clone(),
+                    );
+                    install_order.push(current_package);
+                    continue;
+                }
+            };
+
+            let full_dependency_name = format!("{}:{}", dependency_name, dependency_constraint);
+
+            let version_constraint = VersionConstraint::parse(&dependency_constraint)
+                .map_err(|_| InstallError::InvalidDependency(full_dependency_name.clone()))?;
+
+            let known_constraints = version_constraints
+                .entry(dependency_name.clone())
+                .or_default();
+
+            let is_new_constraint = !known_constraints.contains(&version_constraint);
+
+            if is_new_constraint {
+                known_constraints.push(version_constraint.clone());
+            }
+
+            if let Some(resolved_version) = resolved.get(&dependency_name) {
+                if version_constraint.matches(resolved_version) {
+                    continue;
+                }
+
+                if is_new_constraint {
+                    return Ok(None);
+                }
+
+                return Err(InstallError::MissingDependency(full_dependency_name));
+            }
+
+            let is_pending = pending
+                .iter()
+                .any(|(pending_package, _)| pending_package.name == dependency_name);
+
+            if is_pending {
+                let cycle: Vec<String> = pending
+                    .iter()
+                    .map(|(pending_package, _)| {
+                        format!("{}:{}", pending_package.name, pending_package.version)
+                    })
+                    .chain(std::iter::once(full_dependency_name))
+                    .collect();
+
+                return Err(InstallError::DependencyCycle(cycle.join(" -> ")));
+            }
+
+            let dependency = self
+                .pick_dependency(
+                    &dependency_name,
+                    &version_constraints[&dependency_name],
+                    &requiring_maintainer,
+                    strict,
+                )
+                .await?;
+
+            pending.push((dependency, 0));
+        }
+
+        Ok(Some(install_order))
+    }
+
+    /**
+     * Get known versions of package in selected blockchain
+     */
+    pub async fn get_versions(&self, package_name: &String) -> Vec<String> {
+        let selected_client = self.get_selected_client().await;
+
+        self.packages_service
+            .get_versions(&package_name, &selected_client)
+            .await
+    }
+
+    /**
+     * Submit package signed elsewhere to blockchain, checking its signature first
+     */
+    pub async fn submit_signed(&self, package: &Package) -> Result<(), BlockchainError> {
+        Self::ensure_submittable(package)?;
+
+        debug!("Submitting package to blockchain IO...");
+
+        let client = self.get_selected_client().await;
+        client.write_package(package).await?;
+
+        debug!("Done submitting package to blockchain IO !");
+
+        Ok(())
+    }
+
+    /**
+     * Check package is signed by its maintainer and could be verified by readers
+     */
+    fn ensure_submittable(package: &Package) -> Result<(), BlockchainError> {
+        if package.sig.is_none() {
+            return Err(BlockchainError::UnsignedPackage);
+        }
+
+        if verify_package(package).is_none() {
+            return Err(BlockchainError::InvalidSignature);
+        }
+
+        // Readers would skip it anyway, avoid paying for a mutation nobody can verify
+        if package.integrity.ensure_supported_algorithm().is_err() {
+            return Err(BlockchainError::UnsupportedIntegrityAlgorithm(
+                package.integrity.algorithm.clone(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /**
+     * Submit packages signed elsewhere in a single batch, result of each package is given in same order
+     *
+     * Packages failing signature checks are left out of batch instead of aborting it
+     */
+    pub async fn submit_packages(&self, packages: &[Package]) -> Vec<Result<(), BlockchainError>> {
+        debug!("Submitting {} packages to blockchain IO...", packages.len());
+
+        let mut results: Vec<Result<(), BlockchainError>> =
+            packages.iter().map(Self::ensure_submittable).collect();
+
+        let valid_packages: Vec<Package> = packages
+            .iter()
+            .zip(&results)
+            .filter(|(_, result)| result.is_ok())
+            .map(|(package, _)| package.clone())
+            .collect();
+
+        if !valid_packages.is_empty() {
+            let client = self.get_selected_client().await;
+            let mut write_results = client.write_packages(&valid_packages).await.into_iter();
+
+            // Valid packages were written in order, so their write results fill remaining slots
+            for result in results.iter_mut().filter(|result| result.is_ok()) {
+                *result = write_results
+                    .next()
+                    .unwrap_or(Err(BlockchainError::WriteFailure(String::from(
+                        "Package write result is missing",
+                    ))));
+            }
+        }
+
+        debug!(
+            "Done submitting {} packages to blockchain IO !",
+            valid_packages.len()
+        );
+
+        results
+    }
+
+    /**
+     * Check if package with same content is already stored for given client
+     */
+    async fn is_published_on(
+        &self,
+        package: &Package,
+        client: &Arc<Box<dyn BlockchainClient>>,
+    ) -> bool {
+        self.packages_service
+            .get(package, client)
+            .await
+            .is_some_and(|stored_package| stored_package.content_eq(package))
+    }
+
+    /**
+     * Check if package with same content is already published to selected blockchain
+     */
+    pub async fn is_published(&self, package: &Package) -> bool {
+        let client = self.get_selected_client().await;
+
+        self.is_published_on(package, &client).await
+    }
+
+    /**
+     * Get labels of registered blockchains package with same content is already published to
+     */
+    pub async fn get_published_chains(&self, package: &Package) -> Vec<String> {
+        let clients: Vec<Arc<Box<dyn BlockchainClient>>> = self
+            .blockchains_clients
+            .lock()
+            .await
+            .iter()
+            .cloned()
+            .collect();
+
+        let mut published_chains = vec![];
+
+        for client in clients {
+            if self.is_published_on(package, &client).await {
+                published_chains.push(client.get_label());
+            }
+        }
+
+        published_chains
+    }
+
+    /**
+     * Submit package to every registered blockchain but skipped ones ( eg: already published to ), signed package
+     * being chain independent
+     *
+     * Each write runs in its own task so one failing client does not prevent others from being submitted to
+     */
+    pub async fn submit_package_to_all(
+        &self,
+        package: &Package,
+        skipped_chains: &[String],
+    ) -> Vec<ChainSubmission> {
+        debug!("Submitting package to every blockchain IO...");
+
+        let clients: Vec<Arc<Box<dyn BlockchainClient>>> = self
+            .blockchains_clients
+            .lock()
+            .await
+            .iter()
+            .filter(|client| !skipped_chains.contains(&client.get_label()))
+            .cloned()
+            .collect();
+
+        let mut submissions = Vec::with_capacity(clients.len());
+
+        for client in clients {
+            let blockchain_label = client.get_label();
+
+            let task_client = Arc::clone(&client);
+            let task_package = package.clone();
+
+            let write_result =
+                tokio::spawn(async move { task_client.write_package(&task_package).await }).await;
+
+            let submitted = match write_result {
+                Ok(Ok(())) => true,
+                Ok(Err(e)) => {
+                    warn!(
+                        "Package could not be submitted to {} : {}",
+                        blockchain_label, e
+                    );
+                    false
+                }
+                Err(_) => {
+                    warn!("Package could not be submitted to {}", blockchain_label);
+                    false
+                }
+            };
+
+            submissions.push(ChainSubmission {
+                blockchain_label,
+                submitted,
+            });
+        }
+
+        debug!("Done submitting package to every blockchain IO !");
+
+        submissions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{
+        blockchains::blockchain::{BlockchainIO, MockBlockchainClient, MockBlockchainIO},
+        packages::{
+            package_builder::PackageBuilder,
+            package_status::PackageStatus,
+            utils::signatures::{sign_package, sign_package_now},
+        },
+        test_utils::{
+            blockchain::tests::{create_labelled_client, TestBlockchainClient},
+            fixtures::tests::{TestEnv, TEST_BLOCKCHAIN_LABEL},
+            package::tests::{create_package_with_sig, create_package_without_sig},
+        },
+    };
+    use ed25519::signature::rand_core::OsRng;
+    use ed25519_dalek::SigningKey;
+    use mockall::{mock, predicate::*};
+
+    use super::*;
+
+    /**
+     * It should get client
+     */
+    #[tokio::test]
+    async fn test_get_clients() -> Result<(), Box<dyn std::error::Error>> {
+        let TestEnv {
+            blockchains_repository,
+            packages_service,
+            blockchain_client,
+            ..
+        } = TestEnv::new();
+
+        let blockchains_clients_mock = vec![Arc::new(blockchain_client)];
+
+        let blockchains_service = BlockchainsService::new(
+            &blockchains_clients_mock,
+            &blockchains_repository,
+            &packages_service,
+        )
+        .await;
+
+        let clients = blockchains_service.get_clients();
+
+        let label = clients.lock().await[0].get_label();
+        assert_eq!(label, TEST_BLOCKCHAIN_LABEL);
+
+        Ok(())
+    }
+
+    /**
+     * It should open service on top of DB stored at given path
+     */
+    #[tokio::test]
+    async fn test_open() -> Result<(), Box<dyn std::error::Error>> {
+        let test_dir = tempfile::TempDir::new()?;
+
+        let mut blockchain_mock = MockBlockchainClient::default();
+
+        blockchain_mock
+            .expect_get_label()
+            .returning(|| "MockBlockchain".to_string());
+
+        let blockchain_client: Box<dyn BlockchainClient> = Box::new(blockchain_mock);
+
+        let blockchains_service = BlockchainsService::open(
+            &test_dir.path().join("bpm.db"),
+            &vec![Arc::new(blockchain_client)],
+        )
+        .await?;
+
+        let blockchains = blockchains_service.get_blockchains_status().await;
+
+        assert_eq!(blockchains.len(), 1);
+        assert_eq!(blockchains[0].label, "MockBlockchain");
+
+        Ok(())
+    }
+
+    /**
+     * It should initialize blockchains
+     */
+    #[tokio::test]
+    async fn test_init_blockchains() -> Result<(), Box<dyn std::error::Error>> {
+        let mut blockchain_mock = MockBlockchainClient::default();
+
+        blockchain_mock
+            .expect_get_last_sync()
+            .returning(|| Box::pin(async { 0 }));
+
+        blockchain_mock
+            .expect_set_last_sync()
+            .returning(|_| Box::pin(async { println!("set_last_sync mock executed") }));
+
+        let TestEnv {
+            blockchains_repository,
+            packages_service,
+            blockchain_client,
+            ..
+        } = TestEnv::with_client(blockchain_mock);
+
+        let blockchains_clients_mock = vec![Arc::new(blockchain_client)];
+
+        let blockchains_service = BlockchainsService::new(
+            &blockchains_clients_mock,
+            &blockchains_repository,
+            &packages_service,
+        )
+        .await;
+
+        let mut blockhains_docs_count = blockchains_repository.read_all().await.len();
+
+        assert_eq!(blockhains_docs_count, 1);
+
+        // If blockchain doc already exists it should not add it twice
+        blockchains_service.init_blockchains().await;
+        blockhains_docs_count = blockchains_repository.read_all().await.len();
+
+        assert_eq!(blockhains_docs_count, 1);
+
+        Ok(())
+    }
+
+    /**
+     * It should update package
+     */
+    #[tokio::test]
+    async fn test_update_blockchain() -> Result<(), Box<dyn std::error::Error>> {
+        let mut blockchain_mock = MockBlockchainClient::default();
+
+        blockchain_mock
+            .expect_get_last_sync()
+            .returning(|| Box::pin(async { 0 }));
+
+        let expected_package = create_package_with_sig().unwrap();
+        // Return one package mutation
+        blockchain_mock
+            .expect_read_packages()
+            .returning(move |tx_packages| {
+                let tx_packages = tx_packages.clone();
+
+                let package = expected_package.clone();
+
+                Box::pin(async move {
+                    tx_packages.send(Ok(package.clone())).await.unwrap();
+                    Ok(())
+                })
+            });
+
+        let TestEnv {
+            blockchains_repository,
+            packages_service,
+            blockchain_client,
+            ..
+        } = TestEnv::with_client(blockchain_mock);
+
+        let blockchains_clients_mock = vec![Arc::new(blockchain_client)];
+
+        let blockchains_service = BlockchainsService::new(
+            &blockchains_clients_mock,
+            &blockchains_repository,
+            &packages_service,
+        )
+        .await;
+
+        blockchains_service.set_client(0).await;
+
+        // Get packages mutations
+        let (tx_packages, mut _rx_packages): (Sender<Package>, Receiver<Package>) =
+            mpsc::channel(1);
+        let first_tally = blockchains_service.update(&tx_packages).await.unwrap();
+
+        _rx_packages.recv().await;
+
+        // Only one mutation should be found now
+
+        let mut packages_docs_count = packages_service.get_all().await.len();
+
+        let expected_packages_count = 1;
+
+        assert_eq!(packages_docs_count, expected_packages_count);
+
+        let second_tally = blockchains_service.update(&tx_packages).await.unwrap();
+
+        _rx_packages.recv().await;
+
+        packages_docs_count = packages_service.get_all().await.len();
+
+        assert_eq!(packages_docs_count, expected_packages_count);
+
+        // Same mutation read twice should only be added once
+
+        assert_eq!(
+            first_tally,
+            UpdateTally {
+                added: 1,
+                updated: 0,
+                unchanged: 0,
+                rejected: 0,
+                revoked: 0
+            }
+        );
+        assert_eq!(
+            second_tally,
+            UpdateTally {
+                added: 0,
+                updated: 0,
+                unchanged: 1,
+                rejected: 0,
+                revoked: 0
+            }
+        );
+
+        Ok(())
+    }
+
+    /**
+     * It should reject replayed mutation signed before stored one
+     */
+    #[tokio::test]
+    async fn test_update_rejects_replayed_mutation() -> Result<(), Box<dyn std::error::Error>> {
+        let mut blockchain_mock = MockBlockchainClient::default();
+
+        blockchain_mock
+            .expect_get_last_sync()
+            .returning(|| Box::pin(async { 0 }));
+
+        // Same release, first marked fine then prohibited later on
+
+        let mut key = SigningKey::generate(&mut OsRng);
+
+        let base_package = create_package_without_sig(&key.verifying_key())?;
+
+        let sign_at = |status: PackageStatus, signed_at: u64, key: &mut SigningKey| {
+            let package = PackageBuilder::from_package(&base_package)
+                .set_status(&status)
+                .set_signed_at(&signed_at)
+                .build();
+
+            let sig = sign_package(&package, key);
+
+            PackageBuilder::from_package(&package)
+                .set_signature(&sig)
+                .build()
+        };
+
+        let stale_package = sign_at(PackageStatus::Fine, 1, &mut key);
+        let prohibited_package = sign_at(PackageStatus::Prohibited, 2, &mut key);
+
+        let shared_packages = vec![prohibited_package.clone(), stale_package];
+
+        blockchain_mock
+            .expect_read_packages()
+            .returning(move |tx_packages| {
+                let tx_packages = tx_packages.clone();
+
+                let packages = shared_packages.clone();
+
+                Box::pin(async move {
+                    for package in packages {
+                        tx_packages.send(Ok(package)).await.unwrap();
+                    }
+                    Ok(())
+                })
+            });
+
+        let TestEnv {
+            blockchains_repository,
+            packages_service,
+            blockchain_client,
+            ..
+        } = TestEnv::with_client(blockchain_mock);
+
+        let blockchains_clients_mock = vec![Arc::new(blockchain_client)];
+
+        let blockchains_service = BlockchainsService::new(
+            &blockchains_clients_mock,
+            &blockchains_repository,
+            &packages_service,
+        )
+        .await;
+
+        blockchains_service.set_client(0).await;
+
+        let (tx_packages, mut rx_packages): (Sender<Package>, Receiver<Package>) = mpsc::channel(2);
+
+        let tally = blockchains_service.update(&tx_packages).await.unwrap();
+
+        while rx_packages.try_recv().is_ok() {}
+
+        assert_eq!(
+            tally,
+            UpdateTally {
+                added: 1,
+                updated: 0,
+                unchanged: 0,
+                rejected: 1,
+                revoked: 0
+            }
+        );
+        assert_eq!(packages_service.get_all().await, vec![prohibited_package]);
+
+        Ok(())
+    }
+
+    /**
+     * Build blockchains service reading given packages from a mocked blockchain
+     */
+    async fn create_service_reading(
+        packages: Vec<Package>,
+        packages_service: &Arc<PackagesService>,
+        blockchains_repository: &Arc<BlockchainsRepository>,
+    ) -> BlockchainsService {
+        let mut blockchain_mock = MockBlockchainClient::default();
+
+        blockchain_mock
+            .expect_get_label()
+            .returning(|| "MockBlockchain".to_string());
+
+        blockchain_mock
+            .expect_get_last_sync()
+            .returning(|| Box::pin(async { 0 }));
+
+        blockchain_mock
+            .expect_read_packages()
+            .returning(move |tx_packages| {
+                let tx_packages = tx_packages.clone();
+
+                let packages = packages.clone();
+
+                Box::pin(async move {
+                    for package in packages {
+                        tx_packages.send(Ok(package)).await.unwrap();
+                    }
+                    Ok(())
+                })
+            });
+
+        let blockchain_client: Box<dyn BlockchainClient> = Box::new(blockchain_mock);
+
+        let blockchains_service = BlockchainsService::new(
+            &vec![Arc::new(blockchain_client)],
+            blockchains_repository,
+            packages_service,
+        )
+        .await;
+
+        blockchains_service.set_client(0).await;
+
+        blockchains_service
+    }
+
+    /**
+     * Sign package with given revoked flag, at given time, tombstones prohibiting release
+     */
+    fn sign_at(package: &Package, revoked: bool, signed_at: u64, key: &mut SigningKey) -> Package {
+        let mut builder = PackageBuilder::from_package(package);
+
+        if revoked {
+            builder.set_status(&PackageStatus::Prohibited);
+        }
+
+        let package = builder
+            .set_revoked(&revoked)
+            .set_signed_at(&signed_at)
+            .build();
+
+        let sig = sign_package(&package, key);
+
+        PackageBuilder::from_package(&package)
+            .set_signature(&sig)
+            .build()
+    }
+
+    /**
+     * It should replace package revoked by its maintainer with its tombstone
+     */
+    #[tokio::test]
+    async fn test_update_revokes_package() -> Result<(), Box<dyn std::error::Error>> {
+        let TestEnv {
+            blockchains_repository,
+            packages_service,
+            ..
+        } = TestEnv::new();
+
+        let mut key = SigningKey::generate(&mut OsRng);
+
+        let base_package = create_package_without_sig(&key.verifying_key())?;
+
+        let package = sign_at(&base_package, false, 1, &mut key);
+        let tombstone = sign_at(&base_package, true, 2, &mut key);
+
+        let blockchains_service = create_service_reading(
+            vec![package, tombstone.clone()],
+            &packages_service,
+            &blockchains_repository,
+        )
+        .await;
+
+        let (tx_packages, mut rx_packages): (Sender<Package>, Receiver<Package>) = mpsc::channel(2);
+
+        let tally = blockchains_service.update(&tx_packages).await.unwrap();
+
+        while rx_packages.try_recv().is_ok() {}
+
+        assert_eq!(
+            tally,
+            UpdateTally {
+                added: 1,
+                updated: 0,
+                unchanged: 0,
+                rejected: 0,
+                revoked: 1
+            }
+        );
+        assert_eq!(packages_service.get_all().await, vec![tombstone]);
+
+        Ok(())
+    }
+
+    /**
+     * It should reject revoked mutation replayed after its revocation, whether release was stored or not
+     */
+    #[tokio::test]
+    async fn test_update_rejects_replay_after_revocation() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let mut key = SigningKey::generate(&mut OsRng);
+
+        let base_package = create_package_without_sig(&key.verifying_key())?;
+
+        let package = sign_at(&base_package, false, 1, &mut key);
+        let tombstone = sign_at(&base_package, true, 2, &mut key);
+
+        for mutations in [
+            vec![package.clone(), tombstone.clone(), package.clone()],
+            vec![tombstone.clone(), package.clone()],
+        ] {
+            let TestEnv {
+                blockchains_repository,
+                packages_service,
+                ..
+            } = TestEnv::new();
+
+            let blockchains_service =
+                create_service_reading(mutations, &packages_service, &blockchains_repository).await;
+
+            let (tx_packages, mut rx_packages): (Sender<Package>, Receiver<Package>) =
+                mpsc::channel(3);
+
+            let tally = blockchains_service.update(&tx_packages).await.unwrap();
+
+            while rx_packages.try_recv().is_ok() {}
+
+            assert_eq!(tally.revoked, 1);
+            assert_eq!(tally.rejected, 1);
+            assert_eq!(packages_service.get_all().await, vec![tombstone.clone()]);
+        }
+
+        Ok(())
+    }
+
+    /**
+     * It should reject tombstone which does not prohibit release
+     */
+    #[tokio::test]
+    async fn test_update_rejects_permissive_revocation() -> Result<(), Box<dyn std::error::Error>> {
+        let TestEnv {
+            blockchains_repository,
+            packages_service,
+            ..
+        } = TestEnv::new();
+
+        let mut key = SigningKey::generate(&mut OsRng);
+
+        let base_package = create_package_without_sig(&key.verifying_key())?;
+
+        let package = sign_at(&base_package, false, 1, &mut key);
+
+        // Revoked yet still installable, which a maintainer could only mean by mistake
+        let unsigned_tombstone = PackageBuilder::from_package(&base_package)
+            .set_revoked(&true)
+            .set_signed_at(&2)
+            .build();
+
+        let permissive_tombstone = PackageBuilder::from_package(&unsigned_tombstone)
+            .set_signature(&sign_package(&unsigned_tombstone, &mut key))
+            .build();
+
+        let blockchains_service = create_service_reading(
+            vec![package.clone(), permissive_tombstone],
+            &packages_service,
+            &blockchains_repository,
+        )
+        .await;
+
+        let (tx_packages, mut rx_packages): (Sender<Package>, Receiver<Package>) = mpsc::channel(2);
+
+        let tally = blockchains_service.update(&tx_packages).await.unwrap();
+
+        while rx_packages.try_recv().is_ok() {}
+
+        assert_eq!(tally.rejected, 1);
+        assert_eq!(packages_service.get_all().await, vec![package]);
+
+        Ok(())
+    }
+
+    /**
+     * It should keep package when tombstone is signed by another maintainer
+     */
+    #[tokio::test]
+    async fn test_update_ignores_forged_revocation() -> Result<(), Box<dyn std::error::Error>> {
+        let TestEnv {
+            blockchains_repository,
+            packages_service,
+            ..
+        } = TestEnv::new();
+
+        let mut key = SigningKey::generate(&mut OsRng);
+        let mut other_key = SigningKey::generate(&mut OsRng);
+
+        let base_package = create_package_without_sig(&key.verifying_key())?;
+
+        let package = sign_at(&base_package, false, 1, &mut key);
+
+        // Same release published by someone else, so it cannot reach original maintainer's package
+        let foreign_tombstone = sign_at(
+            &PackageBuilder::from_package(&base_package)
+                .set_maintainer(&other_key.verifying_key())
+                .build(),
+            true,
+            2,
+            &mut other_key,
+        );
+
+        let blockchains_service = create_service_reading(
+            vec![package.clone(), foreign_tombstone.clone()],
+            &packages_service,
+            &blockchains_repository,
+        )
+        .await;
+
+        let (tx_packages, mut rx_packages): (Sender<Package>, Receiver<Package>) = mpsc::channel(2);
+
+        let tally = blockchains_service.update(&tx_packages).await.unwrap();
+
+        while rx_packages.try_recv().is_ok() {}
+
+        assert_eq!(
+            tally,
+            UpdateTally {
+                added: 1,
+                updated: 0,
+                unchanged: 0,
+                rejected: 0,
+                revoked: 1
+            }
+        );
+
+        let blockchain_client = blockchains_service.get_selected_client().await;
+
+        // Tombstone only covers its own maintainer release
+        assert_eq!(
+            packages_service.get(&package, &blockchain_client).await,
+            Some(package)
+        );
+        assert_eq!(
+            packages_service
+                .get(&foreign_tombstone, &blockchain_client)
+                .await,
+            Some(foreign_tombstone)
+        );
+
+        Ok(())
+    }
+
+    /**
+     * It should notify registered callbacks when processing packages
+     */
+    #[tokio::test]
+    async fn test_package_update_callbacks() -> Result<(), Box<dyn std::error::Error>> {
+        let mut blockchain_mock = MockBlockchainClient::default();
+
+        blockchain_mock
+            .expect_get_last_sync()
+            .returning(|| Box::pin(async { 0 }));
+
+        let expected_package = create_package_with_sig().unwrap();
+        let shared_package = expected_package.clone();
+
+        // Return one package mutation
+        blockchain_mock
+            .expect_read_packages()
+            .returning(move |tx_packages| {
+                let tx_packages = tx_packages.clone();
+
+                let package = shared_package.clone();
+
+                Box::pin(async move {
+                    tx_packages.send(Ok(package.clone())).await.unwrap();
+                    Ok(())
+                })
+            });
+
+        let TestEnv {
+            blockchains_repository,
+            packages_service,
+            blockchain_client,
+            ..
+        } = TestEnv::with_client(blockchain_mock);
+
+        let blockchains_clients_mock = vec![Arc::new(blockchain_client)];
+
+        let blockchains_service = BlockchainsService::new(
+            &blockchains_clients_mock,
+            &blockchains_repository,
+            &packages_service,
+        )
+        .await;
+
+        blockchains_service.set_client(0).await;
+
+        // Register two callbacks
+        let first_events = Arc::new(std::sync::Mutex::new(vec![]));
+        let second_events = Arc::new(std::sync::Mutex::new(vec![]));
+
+        let shared_first_events = Arc::clone(&first_events);
+        blockchains_service
+            .on_package_update(Box::new(move |package, update_kind| {
+                shared_first_events
+                    .lock()
+                    .unwrap()
+                    .push((package.clone(), update_kind.clone()));
+            }))
+            .await;
+
+        let shared_second_events = Arc::clone(&second_events);
+        blockchains_service
+            .on_package_update(Box::new(move |package, update_kind| {
+                shared_second_events
+                    .lock()
+                    .unwrap()
+                    .push((package.clone(), update_kind.clone()));
+            }))
+            .await;
+
+        let (tx_packages, mut _rx_packages): (Sender<Package>, Receiver<Package>) =
+            mpsc::channel(1);
+
+        // First update adds package, second one leaves it unchanged
+        blockchains_service.update(&tx_packages).await.unwrap();
+        _rx_packages.recv().await;
+
+        blockchains_service.update(&tx_packages).await.unwrap();
+        _rx_packages.recv().await;
+
+        let expected_events = vec![
+            (expected_package.clone(), PackageUpdateKind::Added),
+            (expected_package.clone(), PackageUpdateKind::Unchanged),
+        ];
+
+        assert_eq!(*first_events.lock().unwrap(), expected_events);
+        assert_eq!(*second_events.lock().unwrap(), expected_events);
+
+        Ok(())
+    }
+
+    /**
+     * It should keep updating until receiver is dropped
+     */
+    #[tokio::test]
+    async fn test_watch() -> Result<(), Box<dyn std::error::Error>> {
+        let mut blockchain_mock = MockBlockchainClient::default();
+
+        blockchain_mock
+            .expect_get_last_sync()
+            .returning(|| Box::pin(async { 0 }));
+
+        let expected_package = create_package_with_sig()?;
+
+        let shared_package = expected_package.clone();
+
+        blockchain_mock
+            .expect_read_packages()
+            .returning(move |tx_packages| {
+                let tx_packages = tx_packages.clone();
+
+                let package = shared_package.clone();
+
+                Box::pin(async move {
+                    tx_packages.send(Ok(package)).await.unwrap();
+                    Ok(())
+                })
+            });
+
+        let TestEnv {
+            blockchains_repository,
+            packages_service,
+            blockchain_client,
+            ..
+        } = TestEnv::with_client(blockchain_mock);
+
+        let blockchains_clients_mock = vec![Arc::new(blockchain_client)];
+
+        let blockchains_service = BlockchainsService::new(
+            &blockchains_clients_mock,
+            &blockchains_repository,
+            &packages_service,
+        )
+        .await;
+
+        blockchains_service.set_client(0).await;
+
+        let (tx_packages, mut rx_packages): (Sender<Package>, Receiver<Package>) = mpsc::channel(1);
+
+        let watch = blockchains_service.watch(Duration::from_millis(10), &tx_packages);
+
+        // Stop watching once mutation was seen on two rounds
+        let receive = async move {
+            let mut received_packages = vec![];
+
+            while received_packages.len() < 2 {
+                received_packages.push(rx_packages.recv().await.unwrap());
+            }
+
+            received_packages
+        };
+
+        let (tally, received_packages) = tokio::join!(watch, receive);
+
+        let tally = tally?;
+
+        assert_eq!(received_packages[0], expected_package);
+        assert_eq!(tally.added, 1);
+        assert_eq!(tally.unchanged >= 1, true);
+
+        Ok(())
+    }
+
+    /**
+     * It should raise BlockchainError
+     */
+    #[tokio::test]
+    async fn test_update_blockchain_error() -> Result<(), Box<dyn std::error::Error>> {
+        let mut blockchain_mock = MockBlockchainClient::default();
+
+        blockchain_mock
+            .expect_get_last_sync()
+            .returning(|| Box::pin(async { 0 }));
+
+        let expected_error = BlockchainError::SubscriptionTimeout;
+
+        blockchain_mock
+            .expect_read_packages()
+            .returning(|tx_packages| {
+                Box::pin(async move {
+                    return Err(BlockchainError::SubscriptionTimeout);
+                })
+            });
+
+        let TestEnv {
+            blockchains_repository,
+            packages_service,
+            blockchain_client,
+            ..
+        } = TestEnv::with_client(blockchain_mock);
+
+        let blockchains_clients_mock = vec![Arc::new(blockchain_client)];
+
+        let blockchains_service = BlockchainsService::new(
+            &blockchains_clients_mock,
+            &blockchains_repository,
+            &packages_service,
+        )
+        .await;
+
+        blockchains_service.set_client(0).await;
+
+        // Get packages mutations
+        let (tx_packages, mut _rx_packages): (Sender<Package>, Receiver<Package>) =
+            mpsc::channel(1);
+        let res = blockchains_service.update(&tx_packages).await;
+
+        assert_eq!(res.unwrap_err(), expected_error);
+
+        // Failure should be persisted on blockchain document
+
+        let blockchains_status = blockchains_service.get_blockchains_status().await;
+
+        assert_eq!(
+            blockchains_status[0].last_error,
+            Some(expected_error.to_string())
+        );
+        assert_eq!(blockchains_status[0].last_attempt > 0, true);
+
+        Ok(())
+    }
+
+    /**
+     * It should treat empty topic as successful update
+     */
+    #[tokio::test]
+    async fn test_update_empty_topic() -> Result<(), Box<dyn std::error::Error>> {
+        let mut blockchain_mock = MockBlockchainClient::default();
+
+        blockchain_mock
+            .expect_get_last_sync()
+            .returning(|| Box::pin(async { 0 }));
+
+        blockchain_mock
+            .expect_read_packages()
+            .returning(|_| Box::pin(async move { Err(BlockchainError::NoPackagesData) }));
+
+        let TestEnv {
+            blockchains_repository,
+            packages_service,
+            blockchain_client,
+            ..
+        } = TestEnv::with_client(blockchain_mock);
+
+        let blockchains_clients_mock = vec![Arc::new(blockchain_client)];
+
+        let blockchains_service = BlockchainsService::new(
+            &blockchains_clients_mock,
+            &blockchains_repository,
+            &packages_service,
+        )
+        .await;
+
+        blockchains_service.set_client(0).await;
+
+        let (tx_packages, mut _rx_packages): (Sender<Package>, Receiver<Package>) =
+            mpsc::channel(1);
+        let res = blockchains_service.update(&tx_packages).await;
+
+        assert_eq!(res.unwrap(), UpdateTally::default());
+
+        Ok(())
+    }
+
+    /**
+     * It should reconstruct packages as of given timestamp into a view, leaving cache and last sync untouched
+     */
+    #[tokio::test]
+    async fn test_view_as_of() -> Result<(), Box<dyn std::error::Error>> {
+        let as_of = 1700000000;
+
+        let mut blockchain_mock = MockBlockchainClient::default();
+
+        blockchain_mock
+            .expect_get_last_sync()
+            .returning(|| Box::pin(async { 0 }));
+
+        blockchain_mock.expect_read_packages().never();
+        blockchain_mock.expect_set_last_sync().never();
+
+        let expected_package = create_package_with_sig()?;
+        let view_package = expected_package.clone();
+
+        blockchain_mock
+            .expect_read_packages_as_of()
+            .withf(move |_, read_as_of| *read_as_of == as_of)
+            .times(1)
+            .returning(move |tx_packages, _| {
+                let tx_packages = tx_packages.clone();
+
+                let package = view_package.clone();
+
+                Box::pin(async move {
+                    tx_packages.send(Ok(package)).await.unwrap();
+                    Ok(())
+                })
+            });
+
+        let TestEnv {
+            blockchains_repository,
+            packages_service,
+            blockchain_client,
+            ..
+        } = TestEnv::with_client(blockchain_mock);
+
+        let blockchains_clients_mock = vec![Arc::new(blockchain_client)];
+
+        let blockchains_service = BlockchainsService::new(
+            &blockchains_clients_mock,
+            &blockchains_repository,
+            &packages_service,
+        )
+        .await;
+
+        blockchains_service.set_client(0).await;
+
+        let stored_blockchain_doc = blockchains_repository
+            .read_by_key(&TEST_BLOCKCHAIN_LABEL.to_string())
+            .await;
+
+        let (tx_packages, mut rx_packages): (Sender<Package>, Receiver<Package>) = mpsc::channel(1);
+
+        let (view_packages_service, tally) =
+            blockchains_service.view_as_of(&tx_packages, as_of).await?;
+
+        rx_packages.recv().await;
+
+        assert_eq!(tally.added, 1);
+        assert_eq!(
+            view_packages_service.get_all().await,
+            vec![expected_package]
+        );
+        assert_eq!(packages_service.get_all().await.is_empty(), true);
+        assert_eq!(
+            blockchains_repository
+                .read_by_key(&TEST_BLOCKCHAIN_LABEL.to_string())
+                .await,
+            stored_blockchain_doc
+        );
+
+        Ok(())
+    }
+
+    /**
+     * It should find package by release
+     */
+    #[tokio::test]
+    async fn test_should_find_package_by_release() -> Result<(), Box<dyn std::error::Error>> {
+        let mut blockchain_mock = MockBlockchainClient::default();
+
+        blockchain_mock
+            .expect_get_last_sync()
+            .returning(|| Box::pin(async { 0 }));
+
+        let package = create_package_with_sig().unwrap();
+        let shared_package = package.clone();
+
+        // Return one package mutation
+        blockchain_mock
+            .expect_read_packages()
+            .returning(move |tx_packages| {
+                let tx_packages = tx_packages.clone();
+
+                let package = shared_package.clone();
+
+                Box::pin(async move {
+                    tx_packages.send(Ok(package.clone())).await.unwrap();
+                    Ok(())
+                })
+            });
+
+        let TestEnv {
+            blockchains_repository,
+            packages_service,
+            blockchain_client,
+            ..
+        } = TestEnv::with_client(blockchain_mock);
+
+        let blockchains_clients_mock = vec![Arc::new(blockchain_client)];
+
+        let blockchains_service = BlockchainsService::new(
+            &blockchains_clients_mock,
+            &blockchains_repository,
+            &packages_service,
+        )
+        .await;
+
+        blockchains_service.set_client(0).await;
+
+        // Get packages mutations
+        let (tx_packages, mut _rx_packages): (Sender<Package>, Receiver<Package>) =
+            mpsc::channel(1);
+
+        blockchains_service.update(&tx_packages).await.unwrap();
+
+        let found_packages = blockchains_service
+            .find_package(&package.name, &package.version, &None)
+            .await;
+
+        assert_eq!(package, found_packages[0]);
+
+        Ok(())
+    }
+
+    /**
+     * Build blockchains service having given packages in local cache
+     */
+    async fn create_service_caching(packages: &[Package]) -> BlockchainsService {
+        let TestEnv {
+            blockchains_repository,
+            packages_service,
+            ..
+        } = TestEnv::new();
+
+        let blockchains_service =
+            create_service_reading(vec![], &packages_service, &blockchains_repository).await;
+
+        let blockchain_client = blockchains_service.get_selected_client().await;
+
+        for package in packages {
+            packages_service.add(package, &blockchain_client).await;
+        }
+
+        blockchains_service
+    }
+
+    /**
+     * Create package release requiring given releases
+     */
+    fn create_dependent_package(name: &str, dependencies: &[&str]) -> Package {
+        let dependencies: Vec<(String, String)> = dependencies
+            .iter()
+            .map(|dependency| (dependency.to_string(), String::from("1.0.0")))
+            .collect();
+
+        PackageBuilder::from_package(&create_package_with_sig().unwrap())
+            .set_name(&name.to_string())
+            .set_version(&String::from("1.0.0"))
+            .set_dependencies(&dependencies)
+            .build()
+    }
+
+    /**
+     * Create package release requiring given releases, published by given maintainer with given status
+     */
+    fn create_maintained_package(
+        name: &str,
+        dependencies: &[&str],
+        maintainer_key: &SigningKey,
+        status: PackageStatus,
+    ) -> Package {
+        let package = PackageBuilder::from_package(&create_dependent_package(name, dependencies))
+            .set_maintainer(&maintainer_key.verifying_key())
+            .set_status(&status)
+            .build();
+
+        sign_package_now(&package, maintainer_key)
+    }
+
+    /**
+     * Create given package release requiring given version constraints, published by given maintainer with given status
+     */
+    fn create_release(
+        name: &str,
+        version: &str,
+        dependencies: &[(&str, &str)],
+        maintainer_key: &SigningKey,
+        status: PackageStatus,
+    ) -> Package {
+        let dependencies: Vec<(String, String)> = dependencies
+            .iter()
+            .map(|(name, constraint)| (name.to_string(), constraint.to_string()))
+            .collect();
+
+        let package = PackageBuilder::from_package(&create_maintained_package(
+            name,
+            &[],
+            maintainer_key,
+            status,
+        ))
+        .set_version(&version.to_string())
+        .set_dependencies(&dependencies)
+        .build();
+
+        sign_package_now(&package, maintainer_key)
+    }
+
+    /**
+     * Get package names, in order
+     */
+    fn get_names(packages: &[Package]) -> Vec<String> {
+        packages
+            .iter()
+            .map(|package| package.name.clone())
+            .collect()
+    }
+
+    /**
+     * It should install each dependency of a chain before package requiring it
+     */
+    #[tokio::test]
+    async fn test_resolve_dependencies_chain() -> Result<(), Box<dyn std::error::Error>> {
+        let foo = create_dependent_package("foo", &["bar"]);
+        let bar = create_dependent_package("bar", &["baz"]);
+        let baz = create_dependent_package("baz", &[]);
+
+        let blockchains_service = create_service_caching(&[bar, baz]).await;
+
+        let install_order = blockchains_service
+            .resolve_dependencies(&foo, false)
+            .await?;
+
+        assert_eq!(get_names(&install_order), vec!["baz", "bar", "foo"]);
+
+        Ok(())
+    }
+
+    /**
+     * It should install shared dependency once, before every package requiring it
+     */
+    #[tokio::test]
+    async fn test_resolve_dependencies_diamond() -> Result<(), Box<dyn std::error::Error>> {
+        let foo = create_dependent_package("foo", &["bar", "baz"]);
+        let bar = create_dependent_package("bar", &["qux"]);
+        let baz = create_dependent_package("baz", &["qux"]);
+        let qux = create_dependent_package("qux", &[]);
+
+        let blockchains_service = create_service_caching(&[bar, baz, qux]).await;
+
+        let install_order = blockchains_service
+            .resolve_dependencies(&foo, false)
+            .await?;
+
+        assert_eq!(get_names(&install_order), vec!["qux", "bar", "baz", "foo"]);
+
+        Ok(())
+    }
+
+    /**
+     * It should install shared dependency once, in newest installable release satisfying minimums of every package
+     * requiring it
+     */
+    #[tokio::test]
+    async fn test_resolve_dependencies_diamond_min_versions(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let maintainer_key = SigningKey::generate(&mut OsRng);
+        let other_key = SigningKey::generate(&mut OsRng);
+
+        let foo = create_release(
+            "foo",
+            "1.0.0",
+            &[("bar", "1.0.0"), ("baz", "1.0.0")],
+            &maintainer_key,
+            PackageStatus::Fine,
+        );
+        let bar = create_release(
+            "bar",
+            "1.0.0",
+            &[("qux", ">=1.0")],
+            &maintainer_key,
+            PackageStatus::Fine,
+        );
+        let baz = create_release(
+            "baz",
+            "1.0.0",
+            &[("qux", ">=2.0")],
+            &other_key,
+            PackageStatus::Fine,
+        );
+
+        // Release of maintainer of bar only satisfies bar, newer one is prohibited
+        let old_qux = create_release("qux", "1.5", &[], &maintainer_key, PackageStatus::Fine);
+        let qux = create_release("qux", "2.0", &[], &other_key, PackageStatus::Outdated);
+        let prohibited_qux =
+            create_release("qux", "3.0", &[], &other_key, PackageStatus::Prohibited);
+
+        let blockchains_service =
+            create_service_caching(&[bar, baz, old_qux, qux, prohibited_qux]).await;
+
+        let install_order = blockchains_service.resolve_dependencies(&foo, true).await?;
+
+        assert_eq!(get_names(&install_order), vec!["qux", "bar", "baz", "foo"]);
+        assert_eq!(install_order[0].version, "2.0");
+
+        Ok(())
+    }
+
+    /**
+     * It should refuse dependencies requiring each other, naming packages involved
+     */
+    #[tokio::test]
+    async fn test_resolve_dependencies_cycle() -> Result<(), Box<dyn std::error::Error>> {
+        let foo = create_dependent_package("foo", &["bar"]);
+        let bar = create_dependent_package("bar", &["baz"]);
+        let baz = create_dependent_package("baz", &["bar"]);
+
+        let blockchains_service = create_service_caching(&[bar, baz]).await;
+
+        let resolve_result = blockchains_service.resolve_dependencies(&foo, false).await;
+
+        assert_eq!(
+            resolve_result,
+            Err(InstallError::DependencyCycle(String::from(
+                "foo:1.0.0 -> bar:1.0.0 -> baz:1.0.0 -> bar:1.0.0"
+            )))
+        );
+
+        Ok(())
+    }
+
+    /**
+     * It should prefer dependency published by maintainer of package requiring it, even when less recommended
+     */
+    #[tokio::test]
+    async fn test_resolve_dependencies_same_maintainer() -> Result<(), Box<dyn std::error::Error>> {
+        let maintainer_key = SigningKey::generate(&mut OsRng);
+        let other_key = SigningKey::generate(&mut OsRng);
+
+        let foo = create_maintained_package("foo", &["bar"], &maintainer_key, PackageStatus::Fine);
+        let bar = create_maintained_package("bar", &[], &maintainer_key, PackageStatus::Outdated);
+        let other_bar =
+            create_maintained_package("bar", &[], &other_key, PackageStatus::HighlyRecommended);
+
+        let blockchains_service = create_service_caching(&[bar, other_bar]).await;
+
+        let install_order = blockchains_service.resolve_dependencies(&foo, true).await?;
+
+        assert_eq!(get_names(&install_order), vec!["bar", "foo"]);
+        assert_eq!(install_order[0].maintainer, maintainer_key.verifying_key());
+
+        Ok(())
+    }
+
+    /**
+     * It should pick most recommended dependency among other maintainers, unless strict mode is enabled
+     */
+    #[tokio::test]
+    async fn test_resolve_dependencies_maintainers_conflict(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let maintainer_key = SigningKey::generate(&mut OsRng);
+        let first_key = SigningKey::generate(&mut OsRng);
+        let second_key = SigningKey::generate(&mut OsRng);
+
+        let foo = create_maintained_package("foo", &["bar"], &maintainer_key, PackageStatus::Fine);
+        let first_bar = create_maintained_package("bar", &[], &first_key, PackageStatus::Outdated);
+        let second_bar =
+            create_maintained_package("bar", &[], &second_key, PackageStatus::Recommended);
+
+        let blockchains_service = create_service_caching(&[first_bar, second_bar]).await;
+
+        let install_order = blockchains_service
+            .resolve_dependencies(&foo, false)
+            .await?;
+
+        assert_eq!(install_order[0].maintainer, second_key.verifying_key());
+
+        assert_eq!(
+            blockchains_service.resolve_dependencies(&foo, true).await,
+            Err(InstallError::MaintainersConflict(String::from("bar:1.0.0")))
+        );
+
+        Ok(())
+    }
+
+    /**
+     * It should refuse missing or prohibited dependencies, naming them
+     */
+    #[tokio::test]
+    async fn test_resolve_dependencies_unavailable() -> Result<(), Box<dyn std::error::Error>> {
+        let foo = create_dependent_package("foo", &["bar"]);
+        let bar = PackageBuilder::from_package(&create_dependent_package("bar", &[]))
+            .set_status(&PackageStatus::Prohibited)
+            .build();
+
+        let empty_service = create_service_caching(&[]).await;
+
+        assert_eq!(
+            empty_service.resolve_dependencies(&foo, false).await,
+            Err(InstallError::MissingDependency(String::from("bar:1.0.0")))
+        );
+
+        let prohibited_service = create_service_caching(&[bar]).await;
+
+        assert_eq!(
+            prohibited_service.resolve_dependencies(&foo, false).await,
+            Err(InstallError::ForbiddenDependency(String::from(
+                "bar:1.0.0 ( Prohibited )"
+            )))
+        );
+
+        Ok(())
+    }
+
+    /**
+     * It should only submit packages correctly signed
+     */
+    #[tokio::test]
+    async fn test_submit_signed() -> Result<(), Box<dyn std::error::Error>> {
+        let mut blockchain_mock = MockBlockchainClient::default();
+
+        // Only valid package should reach blockchain
+        blockchain_mock
+            .expect_write_package()
+            .times(1)
+            .returning(|_| Box::pin(async { Ok(()) }));
+
+        let TestEnv {
+            blockchains_repository,
+            packages_service,
+            blockchain_client,
+            ..
+        } = TestEnv::with_client(blockchain_mock);
+
+        let blockchains_clients_mock = vec![Arc::new(blockchain_client)];
+
+        let blockchains_service = BlockchainsService::new(
+            &blockchains_clients_mock,
             &blockchains_repository,
             &packages_service,
         )
         .await;
 
-        let mut blockhains_docs_count = blockchains_repository.read_all().await.len();
+        blockchains_service.set_client(0).await;
 
-        assert_eq!(blockhains_docs_count, 1);
+        let key = SigningKey::generate(&mut OsRng);
 
-        // If blockchain doc already exists it should not add it twice
-        blockchains_service.init_blockchains().await;
-        blockhains_docs_count = blockchains_repository.read_all().await.len();
+        let unsigned_package = create_package_without_sig(&key.verifying_key())?;
 
-        assert_eq!(blockhains_docs_count, 1);
+        let signed_package = create_package_with_sig()?;
+
+        let forged_package = PackageBuilder::from_package(&signed_package)
+            .set_status(&PackageStatus::Prohibited)
+            .build();
+
+        assert_eq!(
+            blockchains_service.submit_signed(&unsigned_package).await,
+            Err(BlockchainError::UnsignedPackage)
+        );
+        assert_eq!(
+            blockchains_service.submit_signed(&forged_package).await,
+            Err(BlockchainError::InvalidSignature)
+        );
+
+        let unsupported_package = sign_package_now(
+            &PackageBuilder::from_package(&unsigned_package)
+                .set_integrity(
+                    &String::from("MD5"),
+                    &unsigned_package.integrity.archive_hash,
+                )
+                .build(),
+            &key,
+        );
+
+        assert_eq!(
+            blockchains_service
+                .submit_signed(&unsupported_package)
+                .await,
+            Err(BlockchainError::UnsupportedIntegrityAlgorithm(
+                String::from("MD5")
+            ))
+        );
+        assert_eq!(
+            blockchains_service.submit_signed(&signed_package).await,
+            Ok(())
+        );
 
         Ok(())
     }
 
     /**
-     * It should update package
+     * It should submit valid packages of batch at once, reporting invalid and unwritten ones
      */
     #[tokio::test]
-    async fn test_update_blockchain() -> Result<(), Box<dyn std::error::Error>> {
-        let db_client = create_test_db();
+    async fn test_submit_packages() -> Result<(), Box<dyn std::error::Error>> {
+        let key = SigningKey::generate(&mut OsRng);
 
-        // Instantiate required resources
-
-        let blockchains_repository = Arc::new(BlockchainsRepository::from(&db_client));
-        let packages_repository = Arc::new(PackagesRepository::from(&db_client));
+        let first_package = create_package_with_sig()?;
+        let unsigned_package = create_package_without_sig(&key.verifying_key())?;
+        let second_package = create_package_with_sig()?;
 
-        let packages_service = Arc::new(PackagesService::from(&packages_repository));
+        let expected_batch = vec![first_package.clone(), second_package.clone()];
 
         let mut blockchain_mock = MockBlockchainClient::default();
 
+        // Single batch only made of valid packages, last one failing to be written
         blockchain_mock
-            .expect_get_label()
-            .returning(|| "MockBlockchain".to_string());
-
-        blockchain_mock
-            .expect_get_last_sync()
-            .returning(|| Box::pin(async { 0 }));
-
-        let expected_package = create_package_with_sig().unwrap();
-        // Return one package mutation
-        blockchain_mock
-            .expect_read_packages()
-            .returning(move |tx_packages| {
-                let tx_packages = tx_packages.clone();
-
-                let package = expected_package.clone();
-
-                Box::pin(async move {
-                    tx_packages.send(Ok(package.clone())).await.unwrap();
-                    Ok(())
+            .expect_write_packages()
+            .withf(move |packages| packages == expected_batch.as_slice())
+            .times(1)
+            .returning(|_| {
+                Box::pin(async {
+                    vec![
+                        Ok(()),
+                        Err(BlockchainError::WriteFailure(
+                            "Mocked write failure".to_string(),
+                        )),
+                    ]
                 })
             });
 
-        let blockchain_client: Box<dyn BlockchainClient> = Box::new(blockchain_mock);
+        let TestEnv {
+            blockchains_repository,
+            packages_service,
+            blockchain_client,
+            ..
+        } = TestEnv::with_client(blockchain_mock);
 
         let blockchains_clients_mock = vec![Arc::new(blockchain_client)];
 
@@ -399,72 +2679,174 @@ mod tests {
 
         blockchains_service.set_client(0).await;
 
-        // Get packages mutations
-        let (tx_packages, mut _rx_packages): (Sender<Package>, Receiver<Package>) =
-            mpsc::channel(1);
-        blockchains_service.update(&tx_packages).await.unwrap();
+        let results = blockchains_service
+            .submit_packages(&[first_package, unsigned_package, second_package])
+            .await;
 
-        _rx_packages.recv().await;
+        assert_eq!(
+            results,
+            vec![
+                Ok(()),
+                Err(BlockchainError::UnsignedPackage),
+                Err(BlockchainError::WriteFailure(
+                    "Mocked write failure".to_string()
+                ))
+            ]
+        );
 
-        // Only one mutation should be found now
+        Ok(())
+    }
 
-        let mut packages_docs_count = packages_service.get_all().await.len();
+    /**
+     * It should tell package is published only when same content is stored
+     */
+    #[tokio::test]
+    async fn test_is_published() -> Result<(), Box<dyn std::error::Error>> {
+        let mut blockchain_mock = MockBlockchainClient::default();
 
-        let expected_packages_count = 1;
+        let TestEnv {
+            blockchains_repository,
+            packages_service,
+            blockchain_client,
+            ..
+        } = TestEnv::with_client(blockchain_mock);
 
-        assert_eq!(packages_docs_count, expected_packages_count);
+        let blockchains_clients_mock = vec![Arc::new(blockchain_client)];
 
-        blockchains_service.update(&tx_packages).await.unwrap();
+        let blockchains_service = BlockchainsService::new(
+            &blockchains_clients_mock,
+            &blockchains_repository,
+            &packages_service,
+        )
+        .await;
 
-        _rx_packages.recv().await;
+        blockchains_service.set_client(0).await;
 
-        packages_docs_count = packages_service.get_all().await.len();
+        let package = create_package_with_sig()?;
 
-        assert_eq!(packages_docs_count, expected_packages_count);
+        assert_eq!(blockchains_service.is_published(&package).await, false);
+
+        packages_service
+            .add(&package, &blockchains_clients_mock[0])
+            .await;
+
+        let resigned_package = PackageBuilder::from_package(&package)
+            .set_signed_at(&1)
+            .build();
+
+        let mutated_package = PackageBuilder::from_package(&package)
+            .set_status(&PackageStatus::Prohibited)
+            .build();
+
+        assert_eq!(
+            blockchains_service.is_published(&resigned_package).await,
+            true
+        );
+        assert_eq!(
+            blockchains_service.is_published(&mutated_package).await,
+            false
+        );
+        assert_eq!(
+            blockchains_service.get_published_chains(&package).await,
+            vec![TEST_BLOCKCHAIN_LABEL.to_string()]
+        );
 
         Ok(())
     }
 
     /**
-     * It should raise BlockchainError
+     * It should select client by label, leaving selection untouched for unknown labels
      */
     #[tokio::test]
-    async fn test_update_blockchain_error() -> Result<(), Box<dyn std::error::Error>> {
-        let db_client = create_test_db();
-
-        // Instantiate required resources
+    async fn test_set_client_by_label() {
+        let TestEnv {
+            blockchains_repository,
+            packages_service,
+            ..
+        } = TestEnv::new();
+
+        let clients: Vec<Arc<Box<dyn BlockchainClient>>> = ["FirstBlockchain", "SecondBlockchain"]
+            .into_iter()
+            .map(|label| {
+                let mut blockchain_mock = MockBlockchainClient::default();
+
+                blockchain_mock
+                    .expect_get_label()
+                    .returning(move || label.to_string());
+
+                let blockchain_client: Box<dyn BlockchainClient> = Box::new(blockchain_mock);
+
+                Arc::new(blockchain_client)
+            })
+            .collect();
+
+        let blockchains_service =
+            BlockchainsService::new(&clients, &blockchains_repository, &packages_service).await;
+
+        assert_eq!(
+            blockchains_service
+                .set_client_by_label(&"SecondBlockchain".to_string())
+                .await,
+            true
+        );
+        assert_eq!(
+            blockchains_service.get_selected_client().await.get_label(),
+            "SecondBlockchain"
+        );
+
+        assert_eq!(
+            blockchains_service
+                .set_client_by_label(&"UnknownBlockchain".to_string())
+                .await,
+            false
+        );
+        assert_eq!(
+            blockchains_service.get_selected_client().await.get_label(),
+            "SecondBlockchain"
+        );
+    }
 
-        let blockchains_repository = Arc::new(BlockchainsRepository::from(&db_client));
-        let packages_repository = Arc::new(PackagesRepository::from(&db_client));
+    /**
+     * It should only consider cache fresh when last synchronization is recent enough
+     */
+    #[tokio::test]
+    async fn test_is_cache_fresh() -> Result<(), Box<dyn std::error::Error>> {
+        let TestEnv {
+            blockchains_repository,
+            packages_service,
+            ..
+        } = TestEnv::new();
 
-        let packages_service = Arc::new(PackagesService::from(&packages_repository));
+        let last_sync = u64::try_from(current_timestamp())? - 120;
 
-        let mut blockchain_mock = MockBlockchainClient::default();
+        let mut synced_blockchain_mock = MockBlockchainClient::default();
 
-        blockchain_mock
+        synced_blockchain_mock
             .expect_get_label()
-            .returning(|| "MockBlockchain".to_string());
+            .returning(|| "SyncedBlockchain".to_string());
 
-        blockchain_mock
+        synced_blockchain_mock
             .expect_get_last_sync()
-            .returning(|| Box::pin(async { 0 }));
+            .returning(move || Box::pin(async move { last_sync }));
 
-        let expected_error = BlockchainError::NoPackagesData;
+        let mut new_blockchain_mock = MockBlockchainClient::default();
 
-        blockchain_mock
-            .expect_read_packages()
-            .returning(|tx_packages| {
-                Box::pin(async move {
-                    return Err(BlockchainError::NoPackagesData);
-                })
-            });
+        new_blockchain_mock
+            .expect_get_label()
+            .returning(|| "NewBlockchain".to_string());
 
-        let blockchain_client: Box<dyn BlockchainClient> = Box::new(blockchain_mock);
+        new_blockchain_mock
+            .expect_get_last_sync()
+            .returning(|| Box::pin(async { 0 }));
 
-        let blockchains_clients_mock = vec![Arc::new(blockchain_client)];
+        let synced_blockchain_client: Box<dyn BlockchainClient> = Box::new(synced_blockchain_mock);
+        let new_blockchain_client: Box<dyn BlockchainClient> = Box::new(new_blockchain_mock);
 
         let blockchains_service = BlockchainsService::new(
-            &blockchains_clients_mock,
+            &vec![
+                Arc::new(synced_blockchain_client),
+                Arc::new(new_blockchain_client),
+            ],
             &blockchains_repository,
             &packages_service,
         )
@@ -472,63 +2854,129 @@ mod tests {
 
         blockchains_service.set_client(0).await;
 
-        // Get packages mutations
-        let (tx_packages, mut _rx_packages): (Sender<Package>, Receiver<Package>) =
-            mpsc::channel(1);
-        let res = blockchains_service.update(&tx_packages).await;
-
-        assert_eq!(res.unwrap_err(), expected_error);
+        assert_eq!(
+            blockchains_service
+                .is_cache_fresh(&Duration::from_secs(3600))
+                .await,
+            true
+        );
+        assert_eq!(
+            blockchains_service
+                .is_cache_fresh(&Duration::from_secs(60))
+                .await,
+            false
+        );
+
+        blockchains_service.set_client(1).await;
+
+        assert_eq!(
+            blockchains_service
+                .is_cache_fresh(&Duration::from_secs(3600))
+                .await,
+            false
+        );
 
         Ok(())
     }
 
     /**
-     * It should find package by release
+     * It should persist every package when updating concurrently
      */
     #[tokio::test]
-    async fn test_should_find_package_by_release() -> Result<(), Box<dyn std::error::Error>> {
-        let db_client = create_test_db();
+    async fn test_update_concurrently() -> Result<(), Box<dyn std::error::Error>> {
+        let TestEnv {
+            blockchains_repository,
+            packages_service,
+            ..
+        } = TestEnv::new();
 
-        // Instantiate required resources
+        let packages_count = 50;
 
-        let blockchains_repository = Arc::new(BlockchainsRepository::from(&db_client));
-        let packages_repository = Arc::new(PackagesRepository::from(&db_client));
+        let packages = (0..packages_count)
+            .map(|_| create_package_with_sig())
+            .collect::<Result<Vec<Package>, Box<dyn std::error::Error>>>()?;
 
-        let packages_service = Arc::new(PackagesService::from(&packages_repository));
+        let blockchains_service =
+            create_service_reading(packages, &packages_service, &blockchains_repository).await;
+
+        blockchains_service.set_update_concurrency(8).await;
+
+        let (tx_packages, mut rx_packages): (Sender<Package>, Receiver<Package>) =
+            mpsc::channel(packages_count);
+
+        let tally = blockchains_service.update(&tx_packages).await.unwrap();
+
+        let mut forwarded_count = 0;
+
+        while rx_packages.try_recv().is_ok() {
+            forwarded_count += 1;
+        }
+
+        assert_eq!(tally.added, packages_count);
+        assert_eq!(forwarded_count, packages_count);
+        assert_eq!(packages_service.get_all().await.len(), packages_count);
+
+        Ok(())
+    }
+
+    /**
+     * It should re-read whole blockchain once last sync is reset
+     */
+    #[tokio::test]
+    async fn test_reset_last_sync() -> Result<(), Box<dyn std::error::Error>> {
+        let package = create_package_with_sig()?;
+
+        // Package is only part of read window when reading from the very beginning
+        let last_sync = Arc::new(std::sync::Mutex::new(0u64));
 
         let mut blockchain_mock = MockBlockchainClient::default();
 
-        blockchain_mock
-            .expect_get_label()
-            .returning(|| "MockBlockchain".to_string());
+        let get_last_sync = Arc::clone(&last_sync);
+        blockchain_mock.expect_get_last_sync().returning(move || {
+            let last_sync = *get_last_sync.lock().unwrap();
+            Box::pin(async move { last_sync })
+        });
 
+        let set_last_sync = Arc::clone(&last_sync);
         blockchain_mock
-            .expect_get_last_sync()
-            .returning(|| Box::pin(async { 0 }));
-
-        let package = create_package_with_sig().unwrap();
-        let shared_package = package.clone();
+            .expect_set_last_sync()
+            .returning(move |last_sync| {
+                *set_last_sync.lock().unwrap() = last_sync;
+                Box::pin(async {})
+            });
 
-        // Return one package mutation
+        let read_last_sync = Arc::clone(&last_sync);
         blockchain_mock
             .expect_read_packages()
             .returning(move |tx_packages| {
                 let tx_packages = tx_packages.clone();
 
-                let package = shared_package.clone();
+                let mut last_sync = read_last_sync.lock().unwrap();
+
+                let packages = match *last_sync {
+                    0 => vec![package.clone()],
+                    _ => vec![],
+                };
+
+                *last_sync = 42;
 
                 Box::pin(async move {
-                    tx_packages.send(Ok(package.clone())).await.unwrap();
+                    for package in packages {
+                        tx_packages.send(Ok(package)).await.unwrap();
+                    }
                     Ok(())
                 })
             });
 
-        let blockchain_client: Box<dyn BlockchainClient> = Box::new(blockchain_mock);
-
-        let blockchains_clients_mock = vec![Arc::new(blockchain_client)];
+        let TestEnv {
+            blockchains_repository,
+            packages_service,
+            blockchain_client,
+            ..
+        } = TestEnv::with_client(blockchain_mock);
 
         let blockchains_service = BlockchainsService::new(
-            &blockchains_clients_mock,
+            &vec![Arc::new(blockchain_client)],
             &blockchains_repository,
             &packages_service,
         )
@@ -536,54 +2984,79 @@ mod tests {
 
         blockchains_service.set_client(0).await;
 
-        // Get packages mutations
-        let (tx_packages, mut _rx_packages): (Sender<Package>, Receiver<Package>) =
-            mpsc::channel(1);
+        let (tx_packages, mut rx_packages): (Sender<Package>, Receiver<Package>) = mpsc::channel(1);
 
-        blockchains_service.update(&tx_packages).await.unwrap();
+        let first_tally = blockchains_service.update(&tx_packages).await.unwrap();
+        rx_packages.try_recv()?;
 
-        let found_packages = blockchains_service
-            .find_package(&package.name, &package.version)
-            .await;
+        let second_tally = blockchains_service.update(&tx_packages).await.unwrap();
 
-        assert_eq!(package, found_packages[0]);
+        assert_eq!(first_tally.added, 1);
+        assert_eq!(second_tally, UpdateTally::default());
+        assert_eq!(blockchains_service.get_last_sync().await, 42);
+
+        blockchains_service.reset_last_sync().await;
+
+        assert_eq!(blockchains_service.get_last_sync().await, 0);
+        assert_eq!(
+            blockchains_repository
+                .read_by_key(&TEST_BLOCKCHAIN_LABEL.to_string())
+                .await
+                .map(|doc| doc.last_synchronization),
+            Some(0)
+        );
+
+        let resync_tally = blockchains_service.update(&tx_packages).await.unwrap();
+        rx_packages.try_recv()?;
+
+        assert_eq!(resync_tally.unchanged, 1);
 
         Ok(())
     }
 
     /**
-     * It should submit package
+     * It should submit package to every client but skipped ones, reporting failing ones
      */
     #[tokio::test]
-    async fn test_submit_package() -> Result<(), Box<dyn std::error::Error>> {
-        let db_client = create_test_db();
+    async fn test_submit_package_to_all() -> Result<(), Box<dyn std::error::Error>> {
+        let TestEnv {
+            blockchains_repository,
+            packages_service,
+            ..
+        } = TestEnv::new();
 
-        // Instantiate required resources
+        let mut working_blockchain_mock = MockBlockchainClient::default();
 
-        let blockchains_repository = Arc::new(BlockchainsRepository::from(&db_client));
-        let packages_repository = Arc::new(PackagesRepository::from(&db_client));
+        working_blockchain_mock
+            .expect_write_package()
+            .times(2)
+            .returning(|_| Box::pin(async { Ok(()) }));
 
-        let packages_service = Arc::new(PackagesService::from(&packages_repository));
+        working_blockchain_mock
+            .expect_get_label()
+            .returning(|| "WorkingBlockchain".to_string());
 
-        let mut blockchain_mock = MockBlockchainClient::default();
+        let mut failing_blockchain_mock = MockBlockchainClient::default();
 
-        let expected_submission_calls_count = 1;
-        blockchain_mock
+        failing_blockchain_mock
             .expect_write_package()
-            .times(expected_submission_calls_count)
-            .returning(|package| {
+            .times(1)
+            .returning(|_| {
                 Box::pin(async {
-                    println!("Mocked package write...");
+                    Err(BlockchainError::WriteFailure(
+                        "Mocked write failure".to_string(),
+                    ))
                 })
             });
 
-        blockchain_mock
+        failing_blockchain_mock
             .expect_get_label()
-            .returning(|| "MockBlockchain".to_string());
+            .returning(|| "FailingBlockchain".to_string());
 
-        let blockchain_client: Box<dyn BlockchainClient> = Box::new(blockchain_mock);
+        let working_client: Box<dyn BlockchainClient> = Box::new(working_blockchain_mock);
+        let failing_client: Box<dyn BlockchainClient> = Box::new(failing_blockchain_mock);
 
-        let blockchains_clients_mock = vec![Arc::new(blockchain_client)];
+        let blockchains_clients_mock = vec![Arc::new(failing_client), Arc::new(working_client)];
 
         let blockchains_service = BlockchainsService::new(
             &blockchains_clients_mock,
@@ -592,10 +3065,168 @@ mod tests {
         )
         .await;
 
+        let package = create_package_with_sig()?;
+
+        let submissions = blockchains_service
+            .submit_package_to_all(&package, &[])
+            .await;
+
+        assert_eq!(
+            submissions,
+            vec![
+                ChainSubmission {
+                    blockchain_label: "FailingBlockchain".to_string(),
+                    submitted: false
+                },
+                ChainSubmission {
+                    blockchain_label: "WorkingBlockchain".to_string(),
+                    submitted: true
+                }
+            ]
+        );
+
+        let skipping_submissions = blockchains_service
+            .submit_package_to_all(&package, &[String::from("FailingBlockchain")])
+            .await;
+
+        assert_eq!(
+            skipping_submissions,
+            vec![ChainSubmission {
+                blockchain_label: "WorkingBlockchain".to_string(),
+                submitted: true
+            }]
+        );
+
+        Ok(())
+    }
+
+    /**
+     * It should confirm package once it can be read back
+     */
+    #[tokio::test]
+    async fn test_confirm_package() -> Result<(), Box<dyn std::error::Error>> {
+        let TestEnv {
+            blockchains_repository,
+            packages_service,
+            ..
+        } = TestEnv::new();
+
+        let expected_package = create_package_with_sig()?;
+        let other_package = create_package_with_sig()?;
+
+        let mut blockchain_io_mock = MockBlockchainIO::default();
+
+        let shared_package = expected_package.clone();
+
+        blockchain_io_mock
+            .expect_read()
+            .returning(move |tx_data, _, _| {
+                let tx = tx_data.clone();
+                let encoded_package = rlp::encode(&shared_package).to_vec();
+                let encoded_other_package = rlp::encode(&other_package).to_vec();
+
+                Box::pin(async move {
+                    tx.send(Ok(encoded_other_package.into())).await.unwrap();
+                    tx.send(Ok(encoded_package.into())).await.unwrap();
+                })
+            });
+
+        let blockchain_io: Arc<Box<dyn BlockchainIO>> = Arc::new(Box::new(blockchain_io_mock));
+
+        let blockchain_client: Box<dyn BlockchainClient> =
+            Box::new(TestBlockchainClient::new(blockchain_io, 0));
+
+        let blockchains_service = BlockchainsService::new(
+            &vec![Arc::new(blockchain_client)],
+            &blockchains_repository,
+            &packages_service,
+        )
+        .await;
+
         blockchains_service.set_client(0).await;
 
-        let package = create_package_with_sig()?;
-        blockchains_service.submit_package(&package).await;
+        let confirmed = blockchains_service
+            .confirm_package(&expected_package, 0, Duration::from_secs(5))
+            .await;
+
+        assert_eq!(confirmed, true);
+
+        Ok(())
+    }
+
+    /**
+     * It should not confirm package that never comes back
+     */
+    #[tokio::test]
+    async fn test_confirm_package_timeout() -> Result<(), Box<dyn std::error::Error>> {
+        let TestEnv {
+            blockchains_repository,
+            packages_service,
+            ..
+        } = TestEnv::new();
+
+        let expected_package = create_package_with_sig()?;
+
+        let mut blockchain_io_mock = MockBlockchainIO::default();
+
+        blockchain_io_mock
+            .expect_read()
+            .returning(move |_, _, _| Box::pin(async move {}));
+
+        let blockchain_io: Arc<Box<dyn BlockchainIO>> = Arc::new(Box::new(blockchain_io_mock));
+
+        let blockchain_client: Box<dyn BlockchainClient> =
+            Box::new(TestBlockchainClient::new(blockchain_io, 0));
+
+        let blockchains_service = BlockchainsService::new(
+            &vec![Arc::new(blockchain_client)],
+            &blockchains_repository,
+            &packages_service,
+        )
+        .await;
+
+        blockchains_service.set_client(0).await;
+
+        let confirmed = blockchains_service
+            .confirm_package(&expected_package, 0, Duration::from_millis(100))
+            .await;
+
+        assert_eq!(confirmed, false);
+
+        Ok(())
+    }
+
+    /**
+     * It should add client
+     */
+    #[tokio::test]
+    async fn test_add_client() -> Result<(), Box<dyn std::error::Error>> {
+        let TestEnv {
+            blockchains_repository,
+            packages_service,
+            ..
+        } = TestEnv::new();
+
+        let blockchains_service =
+            BlockchainsService::new(&vec![], &blockchains_repository, &packages_service).await;
+
+        let expected_label = String::from("hedera-0.0.1234");
+
+        let blockchain_client: Box<dyn BlockchainClient> = create_labelled_client(&expected_label);
+
+        let client_idx = blockchains_service
+            .add_client(&Arc::new(blockchain_client))
+            .await;
+
+        blockchains_service.set_client(client_idx).await;
+
+        let selected_client = blockchains_service.get_selected_client().await;
+
+        assert_eq!(selected_client.get_label(), expected_label);
+        assert_eq!(
+            blockchains_repository.exists_by_key(&expected_label).await,
+            true
+        );
 
         Ok(())
     }
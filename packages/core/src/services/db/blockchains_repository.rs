@@ -3,7 +3,8 @@ use polodb_core::{bson::doc, CollectionT};
 use std::sync::Arc;
 
 use crate::db::{
-    client::DbClient, documents::blockchain_document::BlockchainDocument,
+    client::DbClient, cursor::skip_errored_documents,
+    documents::blockchain_document::BlockchainDocument, errors::repository_error::RepositoryError,
     traits::repository::Repository,
 };
 
@@ -19,7 +20,7 @@ impl Repository<BlockchainDocument, String> for BlockchainsRepository {
 
         let cursor = collection.find(doc! {}).run().unwrap();
 
-        let docs = cursor.map(|doc| doc.unwrap()).collect();
+        let docs = skip_errored_documents(cursor).collect();
 
         debug!("Done reading all blockchains from repo !");
 
@@ -41,13 +42,22 @@ impl Repository<BlockchainDocument, String> for BlockchainsRepository {
         db_response
     }
 
-    async fn create(&self, document: &BlockchainDocument) {
+    async fn create(&self, document: &BlockchainDocument) -> Result<(), RepositoryError> {
         debug!("Adding new blockchain to repo...");
+
+        if self.exists_by_key(&document.label).await {
+            return Err(RepositoryError::DuplicateKey(document.label.clone()));
+        }
+
         let blockchains_collection = self.db_client.get_blockchains_collection().await;
 
-        blockchains_collection.insert_one(document).unwrap();
+        blockchains_collection
+            .insert_one(document)
+            .map_err(|e| RepositoryError::WriteFailure(e.to_string()))?;
 
         debug!("Done adding new blockchain to repo !");
+
+        Ok(())
     }
 
     async fn update(&self, doc_key: &String, document: &BlockchainDocument) {
@@ -69,7 +79,19 @@ impl Repository<BlockchainDocument, String> for BlockchainsRepository {
         debug!("Done updating blockchain in repo !");
     }
 
-    //async fn delete(&self, key: String) -> BlockchainDocument;
+    async fn delete(&self, doc_key: &String) {
+        debug!("Deleting blockchain from repo...");
+
+        let blockchains_collection = self.db_client.get_blockchains_collection().await;
+
+        blockchains_collection
+            .delete_one(doc! {
+                "label": &doc_key
+            })
+            .unwrap();
+
+        debug!("Done deleting blockchain from repo !");
+    }
 
     async fn exists_by_key(&self, key: &String) -> bool {
         debug!("Checking if blockchain already exists...");
@@ -134,10 +156,10 @@ mod tests {
 
         let test_dir_path = test_dir.path().join(db_dir);
 
-        let db_client = Arc::new(DbClient::from(&test_dir_path));
+        let db_client = Arc::new(DbClient::try_from(&test_dir_path).unwrap());
 
         let expected_blockchain_label = "hedera".to_string();
-        let expected_sync_time = "0".to_string();
+        let expected_sync_time = 0;
 
         let expected_blockchain_doc = BlockchainDocumentBuilder::default()
             .set_label(&expected_blockchain_label)
@@ -146,7 +168,10 @@ mod tests {
 
         let blockchain_repo = BlockchainsRepository::from(&db_client);
 
-        blockchain_repo.create(&expected_blockchain_doc).await;
+        blockchain_repo
+            .create(&expected_blockchain_doc)
+            .await
+            .unwrap();
 
         let actual_blockchain_doc = blockchain_repo
             .read_by_key(&expected_blockchain_doc.label)
@@ -167,20 +192,23 @@ mod tests {
 
         let test_dir_path = test_dir.path().join(db_dir);
 
-        let db_client = Arc::new(DbClient::from(&test_dir_path));
+        let db_client = Arc::new(DbClient::try_from(&test_dir_path).unwrap());
 
         let blockchain_repo = BlockchainsRepository::from(&db_client);
 
         // Blockchain one
         let blockchain_label_one_mock = "hedera".to_string();
-        let sync_time_one_mock = "0".to_string();
+        let sync_time_one_mock = 0;
 
         let expected_blockchain_doc_one = BlockchainDocumentBuilder::default()
             .set_label(&blockchain_label_one_mock)
             .set_last_synchronization(&sync_time_one_mock)
             .build();
 
-        blockchain_repo.create(&expected_blockchain_doc_one).await;
+        blockchain_repo
+            .create(&expected_blockchain_doc_one)
+            .await
+            .unwrap();
 
         // Blockchain two
         let blockchain_label_two_mock = "iota".to_string();
@@ -189,7 +217,10 @@ mod tests {
                 .set_label(&blockchain_label_two_mock)
                 .build();
 
-        blockchain_repo.create(&expected_blockchain_doc_two).await;
+        blockchain_repo
+            .create(&expected_blockchain_doc_two)
+            .await
+            .unwrap();
 
         let expected_blockchains = vec![expected_blockchain_doc_one, expected_blockchain_doc_two];
 
@@ -211,7 +242,7 @@ mod tests {
 
         let blockchain_label_mock = "foo".to_string();
 
-        let db_client = Arc::new(DbClient::from(&test_dir_path));
+        let db_client = Arc::new(DbClient::try_from(&test_dir_path).unwrap());
 
         let blockchain_repo = BlockchainsRepository::from(&db_client);
 
@@ -231,13 +262,13 @@ mod tests {
 
         let test_dir_path = test_dir.path().join(db_dir);
 
-        let db_client = Arc::new(DbClient::from(&test_dir_path));
+        let db_client = Arc::new(DbClient::try_from(&test_dir_path).unwrap());
 
-        let expected_sync_time = "123".to_string();
+        let expected_sync_time = 123;
 
         let mock_blockchain_label = "foo".to_string();
 
-        let mock_sync_time = "0".to_string();
+        let mock_sync_time = 0;
 
         let mock_blockchain_doc = BlockchainDocumentBuilder::default()
             .set_label(&mock_blockchain_label)
@@ -247,7 +278,7 @@ mod tests {
         // Create blockchain doc
         let blockchain_repo = BlockchainsRepository::from(&db_client);
 
-        blockchain_repo.create(&mock_blockchain_doc).await;
+        blockchain_repo.create(&mock_blockchain_doc).await.unwrap();
 
         // Update blockchain doc
         let updated_blockchain_doc = BlockchainDocumentBuilder::from_document(&mock_blockchain_doc)
@@ -277,11 +308,11 @@ mod tests {
 
         let test_dir_path = test_dir.path().join(db_dir);
 
-        let db_client = Arc::new(DbClient::from(&test_dir_path));
+        let db_client = Arc::new(DbClient::try_from(&test_dir_path).unwrap());
 
         let expected_exists = true;
         let blockchain_label_mock = "hedera".to_string();
-        let sync_time_mock = "0".to_string();
+        let sync_time_mock = 0;
 
         let expected_blockchain_doc = BlockchainDocumentBuilder::default()
             .set_label(&blockchain_label_mock)
@@ -290,7 +321,10 @@ mod tests {
 
         let blockchain_repo = BlockchainsRepository::from(&db_client);
 
-        blockchain_repo.create(&expected_blockchain_doc).await;
+        blockchain_repo
+            .create(&expected_blockchain_doc)
+            .await
+            .unwrap();
 
         let blockchain_doc_exists = blockchain_repo
             .exists_by_key(&expected_blockchain_doc.label)
@@ -299,6 +333,48 @@ mod tests {
         assert_eq!(blockchain_doc_exists, expected_exists);
     }
 
+    /**
+     * It should not exist anymore once deleted
+     */
+    #[tokio::test]
+    async fn test_delete_entry() {
+        let db_dir = "db";
+
+        let test_dir = TempDir::new().unwrap();
+
+        let test_dir_path = test_dir.path().join(db_dir);
+
+        let db_client = Arc::new(DbClient::try_from(&test_dir_path).unwrap());
+
+        let blockchain_doc = BlockchainDocumentBuilder::default()
+            .set_label(&"hedera".to_string())
+            .set_last_synchronization(&0)
+            .build();
+
+        let other_blockchain_doc = BlockchainDocumentBuilder::default()
+            .set_label(&"foobar".to_string())
+            .set_last_synchronization(&0)
+            .build();
+
+        let blockchain_repo = BlockchainsRepository::from(&db_client);
+
+        blockchain_repo.create(&blockchain_doc).await.unwrap();
+        blockchain_repo.create(&other_blockchain_doc).await.unwrap();
+
+        blockchain_repo.delete(&blockchain_doc.label).await;
+
+        assert_eq!(
+            blockchain_repo.exists_by_key(&blockchain_doc.label).await,
+            false
+        );
+        assert_eq!(
+            blockchain_repo
+                .exists_by_key(&other_blockchain_doc.label)
+                .await,
+            true
+        );
+    }
+
     /**
      * It should not exist using key
      */
@@ -310,7 +386,7 @@ mod tests {
 
         let test_dir_path = test_dir.path().join(db_dir);
 
-        let db_client = Arc::new(DbClient::from(&test_dir_path));
+        let db_client = Arc::new(DbClient::try_from(&test_dir_path).unwrap());
 
         let expected_exists = false;
 
@@ -1,48 +1,44 @@
-use log::debug;
+use futures_util::{stream, Stream};
+use log::{debug, error};
 use polodb_core::{bson::doc, CollectionT};
 use std::sync::Arc;
 
-use crate::db::{
-    client::DbClient, documents::package_document::PackageDocument, traits::repository::Repository,
+use crate::{
+    db::{
+        client::DbClient,
+        cursor::skip_errored_documents,
+        documents::{composite_key::CompositeKey, package_document::PackageDocument},
+        errors::repository_error::RepositoryError,
+        traits::repository::Repository,
+    },
+    packages::package_status::PackageStatus,
 };
 
 pub struct PackagesRepository {
     db_client: Arc<DbClient>,
 }
 
-const COMPOSED_KEY_SEPARATOR: &str = ":";
-
 impl PackagesRepository {
     /**
-     * Get composed key parts
+     * Get composed key parts, None when key is malformed
      * Composed key is currently -> blockchain_label:package_name:package_version:maintainer_key
      */
-    fn get_composite_key_parts(&self, key: &String) -> (String, String, String, String) {
-        let splitted_key: Vec<&str> = key.split(COMPOSED_KEY_SEPARATOR).collect();
-
-        let blockchain_label = String::from(splitted_key[0]);
-        let package_name = String::from(splitted_key[1]);
-        let package_version = String::from(splitted_key[2]);
-        let maintainer_key = String::from(splitted_key[3]);
-
-        (
-            blockchain_label,
-            package_name,
-            package_version,
-            maintainer_key,
-        )
+    fn get_composite_key_parts(&self, key: &String) -> Option<(String, String, String, String)> {
+        let composite_key = CompositeKey::parse(key)?;
+
+        Some((
+            composite_key.blockchain_label,
+            composite_key.name,
+            composite_key.version,
+            composite_key.maintainer,
+        ))
     }
 
     /**
      * Create unique composed key
      */
     pub fn get_composite_key(&self, document: &PackageDocument) -> String {
-        let key = format!(
-            "{}:{}:{}:{}",
-            document.blockchain_label, document.name, document.version, document.maintainer
-        );
-
-        key
+        CompositeKey::from(document).to_string()
     }
 
     /**
@@ -53,6 +49,53 @@ impl PackagesRepository {
         package_name: &String,
         package_version: &String,
         blockchain_label: &String,
+    ) -> Vec<PackageDocument> {
+        self.read_by_release_with_min_status(package_name, package_version, blockchain_label, &None)
+            .await
+    }
+
+    /**
+     * Find packages by release, leaving out ones below given status if any
+     */
+    pub async fn read_by_release_with_min_status(
+        &self,
+        package_name: &String,
+        package_version: &String,
+        blockchain_label: &String,
+        min_status: &Option<PackageStatus>,
+    ) -> Vec<PackageDocument> {
+        debug!("Searching packages in repo using name {}...", package_name);
+        let collection = self.db_client.get_packages_collection().await;
+
+        let mut query = doc! {
+            "name": package_name,
+            "version": package_version,
+            "blockchain_label": blockchain_label,
+        };
+
+        if let Some(min_status) = min_status {
+            query.insert(
+                "status",
+                doc! { "$gte": i32::from(min_status.clone() as u8) },
+            );
+        }
+
+        let cursor = collection.find(query).run().unwrap();
+
+        let docs = skip_errored_documents(cursor).collect();
+
+        debug!("Done searching packages with name {} !", package_name);
+
+        docs
+    }
+
+    /**
+     * Find packages in given blockchain by name, whatever their version
+     */
+    pub async fn read_by_name(
+        &self,
+        package_name: &String,
+        blockchain_label: &String,
     ) -> Vec<PackageDocument> {
         debug!("Searching packages in repo using name {}...", package_name);
         let collection = self.db_client.get_packages_collection().await;
@@ -60,20 +103,130 @@ impl PackagesRepository {
         let cursor = collection
             .find(doc! {
                 "name": package_name,
-                "version": package_version,
                 "blockchain_label": blockchain_label,
-
             })
             .run()
             .unwrap();
 
-        let docs = cursor.map(|doc| doc.unwrap()).collect();
+        let docs = skip_errored_documents(cursor).collect();
 
         debug!("Done searching packages with name {} !", package_name);
 
         docs
     }
 
+    /**
+     * Read all documents lazily, one document at a time
+     */
+    pub async fn read_all_stream(&self) -> impl Stream<Item = PackageDocument> {
+        debug!("Streaming all packages from repo...");
+
+        let collection = self.db_client.get_packages_collection().await;
+
+        let cursor = collection.find(doc! {}).run().unwrap();
+
+        stream::iter(skip_errored_documents(cursor))
+    }
+
+    /**
+     * Read one page of documents, in insertion order
+     */
+    pub async fn read_paginated(&self, skip: u64, limit: u64) -> Vec<PackageDocument> {
+        debug!("Reading packages page from repo...");
+
+        let collection = self.db_client.get_packages_collection().await;
+
+        let cursor = collection
+            .find(doc! {})
+            .skip(skip)
+            .limit(limit)
+            .run()
+            .unwrap();
+
+        let docs = skip_errored_documents(cursor).collect();
+
+        debug!("Done reading packages page from repo !");
+
+        docs
+    }
+
+    /**
+     * Read one page of packages whose name contains query, leaving out ones below given status if any
+     *
+     * Returns page along with total count of matching packages
+     */
+    pub async fn read_filtered_page(
+        &self,
+        query: &Option<String>,
+        min_status: &Option<PackageStatus>,
+        skip: u64,
+        limit: u64,
+    ) -> (Vec<PackageDocument>, u64) {
+        debug!("Reading filtered packages page from repo...");
+        let collection = self.db_client.get_packages_collection().await;
+
+        let mut filter = doc! {};
+
+        if let Some(min_status) = min_status {
+            filter.insert(
+                "status",
+                doc! { "$gte": i32::from(min_status.clone() as u8) },
+            );
+        }
+
+        let cursor = collection.find(filter).run().unwrap();
+
+        // Name matching is case insensitive, total has to be known anyway so every match gets walked through
+        let query = query.as_ref().map(|query| query.to_lowercase());
+
+        let matching_docs = skip_errored_documents(cursor).filter(|doc: &PackageDocument| {
+            query
+                .as_ref()
+                .map_or(true, |query| doc.name.to_lowercase().contains(query))
+        });
+
+        let mut docs = vec![];
+        let mut total: u64 = 0;
+
+        for doc in matching_docs {
+            if total >= skip && (docs.len() as u64) < limit {
+                docs.push(doc);
+            }
+
+            total += 1;
+        }
+
+        debug!("Done reading filtered packages page from repo !");
+
+        (docs, total)
+    }
+
+    /**
+     * Read packages having exactly given status in given blockchain
+     */
+    pub async fn read_by_status(
+        &self,
+        status: &PackageStatus,
+        blockchain_label: &String,
+    ) -> Vec<PackageDocument> {
+        debug!("Searching packages in repo using status {}...", status);
+        let collection = self.db_client.get_packages_collection().await;
+
+        let cursor = collection
+            .find(doc! {
+                "status": i32::from(status.clone() as u8),
+                "blockchain_label": blockchain_label,
+            })
+            .run()
+            .unwrap();
+
+        let docs = skip_errored_documents(cursor).collect();
+
+        debug!("Done searching packages with status {} !", status);
+
+        docs
+    }
+
     /**
      * Read by maintainer
      */
@@ -98,7 +251,7 @@ impl PackagesRepository {
             .run()
             .unwrap();
 
-        let docs = cursor.map(|doc| doc.unwrap()).collect();
+        let docs = skip_errored_documents(cursor).collect();
 
         debug!("Done searching packages with maintainer {} !", maintainer);
 
@@ -115,7 +268,7 @@ impl Repository<PackageDocument, String> for PackagesRepository {
 
         let cursor = collection.find(doc! {}).run().unwrap();
 
-        let docs = cursor.map(|doc| doc.unwrap()).collect();
+        let docs = skip_errored_documents(cursor).collect();
 
         debug!("Done reading all packages from repo !");
 
@@ -130,7 +283,7 @@ impl Repository<PackageDocument, String> for PackagesRepository {
         let collection = self.db_client.get_packages_collection().await;
 
         let (blockchain_label, package_name, package_version, maintainer_key) =
-            self.get_composite_key_parts(key);
+            self.get_composite_key_parts(key)?;
 
         let db_response = collection
             .find_one(doc! {
@@ -150,13 +303,29 @@ impl Repository<PackageDocument, String> for PackagesRepository {
     /**
      * Create package document
      */
-    async fn create(&self, document: &PackageDocument) {
+    async fn create(&self, document: &PackageDocument) -> Result<(), RepositoryError> {
         debug!("Adding new package to repo...");
+
+        // Composite key is not unique on DB side, inserting it twice would silently duplicate package
+        let key = self.get_composite_key(document);
+
+        if self.get_composite_key_parts(&key).is_none() {
+            return Err(RepositoryError::MalformedKey(key));
+        }
+
+        if self.exists_by_key(&key).await {
+            return Err(RepositoryError::DuplicateKey(key));
+        }
+
         let collection = self.db_client.get_packages_collection().await;
 
-        collection.insert_one(document).unwrap();
+        collection
+            .insert_one(document)
+            .map_err(|e| RepositoryError::WriteFailure(e.to_string()))?;
 
         debug!("Done adding new package to repo !");
+
+        Ok(())
     }
 
     /**
@@ -168,7 +337,16 @@ impl Repository<PackageDocument, String> for PackagesRepository {
         let collection = self.db_client.get_packages_collection().await;
 
         let (blockchain_label, package_name, package_version, maintainer_key) =
-            self.get_composite_key_parts(&doc_composite_key);
+            match self.get_composite_key_parts(&doc_composite_key) {
+                Some(parts) => parts,
+                None => {
+                    error!(
+                        "Malformed package key {}, skipping update",
+                        doc_composite_key
+                    );
+                    return;
+                }
+            };
 
         collection
             .update_one(
@@ -187,7 +365,39 @@ impl Repository<PackageDocument, String> for PackagesRepository {
 
         debug!("Done updating package in repo !");
     }
-    //async fn delete(&self, key: String) -> BlockchainDocument;
+
+    /**
+     * Delete package document
+     */
+    async fn delete(&self, doc_composite_key: &String) {
+        debug!("Deleting package from repo...");
+
+        let collection = self.db_client.get_packages_collection().await;
+
+        let (blockchain_label, package_name, package_version, maintainer_key) =
+            match self.get_composite_key_parts(&doc_composite_key) {
+                Some(parts) => parts,
+                None => {
+                    error!(
+                        "Malformed package key {}, skipping deletion",
+                        doc_composite_key
+                    );
+                    return;
+                }
+            };
+
+        collection
+            .delete_one(doc! {
+                "name": package_name,
+                "version": package_version,
+                "maintainer": maintainer_key,
+                "blockchain_label": blockchain_label,
+            })
+            .unwrap();
+
+        debug!("Done deleting package from repo !");
+    }
+
     /**
      * Check if exists by key
      */
@@ -219,13 +429,15 @@ mod tests {
     use std::sync::Arc;
 
     use crate::{
-        blockchains::{blockchain::BlockchainClient, hedera::blockchain_client::HederaBlockchain},
+        blockchains::blockchain::BlockchainClient,
         db::{
             client::DbClient, documents::package_document_builder::PackageDocumentBuilder,
             traits::repository::Repository,
         },
-        packages::package_status::PackageStatus,
-        test_utils::package::tests::create_package_with_sig,
+        packages::{package_builder::PackageBuilder, package_status::PackageStatus},
+        test_utils::{
+            blockchain::tests::create_labelled_client, package::tests::create_package_with_sig,
+        },
     };
     use tempfile::TempDir;
 
@@ -244,17 +456,16 @@ mod tests {
 
         let test_dir_path = test_dir.path().join(db_dir);
 
-        let db_client = Arc::new(DbClient::from(&test_dir_path));
+        let db_client = Arc::new(DbClient::try_from(&test_dir_path).unwrap());
 
         let packages_repo = PackagesRepository::from(&db_client);
 
-        let blockchain_client: Box<dyn BlockchainClient> =
-            Box::new(HederaBlockchain::from("4991716"));
+        let blockchain_client: Box<dyn BlockchainClient> = create_labelled_client("MockBlockchain");
 
         let expected_package_doc =
             PackageDocumentBuilder::from_package(&package, &blockchain_client).build();
 
-        packages_repo.create(&expected_package_doc).await;
+        packages_repo.create(&expected_package_doc).await.unwrap();
 
         let expected_package_doc_key = &packages_repo.get_composite_key(&expected_package_doc);
 
@@ -266,6 +477,118 @@ mod tests {
         assert_eq!(actual_package_doc, expected_package_doc);
     }
 
+    /**
+     * It should read right package by key among many once indexes are created
+     */
+    #[tokio::test]
+    async fn test_read_by_key_with_indexes() {
+        let db_dir = "db";
+
+        let test_dir = TempDir::new().unwrap();
+
+        let test_dir_path = test_dir.path().join(db_dir);
+
+        let db_client = Arc::new(DbClient::try_from(&test_dir_path).unwrap());
+
+        db_client.ensure_indexes().await.unwrap();
+
+        let packages_repo = PackagesRepository::from(&db_client);
+
+        let blockchain_client: Box<dyn BlockchainClient> = create_labelled_client("MockBlockchain");
+
+        // Same name for every package, so index alone cannot tell them apart
+        let package_docs: Vec<PackageDocument> = (0..50)
+            .map(|_| {
+                let package = create_package_with_sig().unwrap();
+
+                PackageDocumentBuilder::from_package(&package, &blockchain_client).build()
+            })
+            .collect();
+
+        for package_doc in &package_docs {
+            packages_repo.create(package_doc).await.unwrap();
+        }
+
+        // Indexes created over existing documents too
+        db_client.ensure_indexes().await.unwrap();
+
+        let expected_package_doc = &package_docs[27];
+
+        let actual_package_doc = packages_repo
+            .read_by_key(&packages_repo.get_composite_key(expected_package_doc))
+            .await;
+
+        assert_eq!(actual_package_doc.as_ref(), Some(expected_package_doc));
+        assert_eq!(packages_repo.read_all().await.len(), package_docs.len());
+    }
+
+    /**
+     * It should refuse to create same package entry twice
+     */
+    #[tokio::test]
+    async fn test_create_duplicate_package_entry() {
+        let package = create_package_with_sig().unwrap();
+
+        let db_dir = "db";
+
+        let test_dir = TempDir::new().unwrap();
+
+        let test_dir_path = test_dir.path().join(db_dir);
+
+        let db_client = Arc::new(DbClient::try_from(&test_dir_path).unwrap());
+
+        let packages_repo = PackagesRepository::from(&db_client);
+
+        let blockchain_client: Box<dyn BlockchainClient> = create_labelled_client("MockBlockchain");
+
+        let package_doc =
+            PackageDocumentBuilder::from_package(&package, &blockchain_client).build();
+
+        let package_doc_key = packages_repo.get_composite_key(&package_doc);
+
+        assert_eq!(packages_repo.create(&package_doc).await, Ok(()));
+        assert_eq!(
+            packages_repo.create(&package_doc).await,
+            Err(RepositoryError::DuplicateKey(package_doc_key))
+        );
+        assert_eq!(packages_repo.read_all().await.len(), 1);
+    }
+
+    /**
+     * It should refuse package whose key cannot be parsed back, without panicking on any operation
+     */
+    #[tokio::test]
+    async fn test_malformed_package_key() {
+        let package = PackageBuilder::from_package(&create_package_with_sig().unwrap())
+            .set_version(&String::from("1:2.3-1"))
+            .build();
+
+        let test_dir = TempDir::new().unwrap();
+
+        let db_client = Arc::new(DbClient::try_from(&test_dir.path().join("db")).unwrap());
+
+        let packages_repo = PackagesRepository::from(&db_client);
+
+        let blockchain_client: Box<dyn BlockchainClient> = create_labelled_client("MockBlockchain");
+
+        let package_doc =
+            PackageDocumentBuilder::from_package(&package, &blockchain_client).build();
+
+        let package_doc_key = packages_repo.get_composite_key(&package_doc);
+
+        assert_eq!(
+            packages_repo.create(&package_doc).await,
+            Err(RepositoryError::MalformedKey(package_doc_key.clone()))
+        );
+        assert_eq!(packages_repo.read_by_key(&package_doc_key).await, None);
+        assert_eq!(packages_repo.exists_by_key(&package_doc_key).await, false);
+
+        packages_repo.update(&package_doc_key, &package_doc).await;
+        packages_repo.delete(&package_doc_key).await;
+
+        assert_eq!(packages_repo.read_all().await.is_empty(), true);
+    }
+
     /**
      * It should return None if package not found
      */
@@ -278,12 +601,11 @@ mod tests {
 
         let test_dir_path = test_dir.path().join(db_dir);
 
-        let db_client = Arc::new(DbClient::from(&test_dir_path));
+        let db_client = Arc::new(DbClient::try_from(&test_dir_path).unwrap());
 
         let packages_repo = PackagesRepository::from(&db_client);
 
-        let blockchain_client: Box<dyn BlockchainClient> =
-            Box::new(HederaBlockchain::from("4991716"));
+        let blockchain_client: Box<dyn BlockchainClient> = create_labelled_client("MockBlockchain");
 
         // do not insert it in db
         let package_doc =
@@ -309,17 +631,16 @@ mod tests {
 
         let test_dir_path = test_dir.path().join(db_dir);
 
-        let db_client = Arc::new(DbClient::from(&test_dir_path));
+        let db_client = Arc::new(DbClient::try_from(&test_dir_path).unwrap());
 
         let packages_repo = PackagesRepository::from(&db_client);
 
-        let blockchain_client: Box<dyn BlockchainClient> =
-            Box::new(HederaBlockchain::from("4991716"));
+        let blockchain_client: Box<dyn BlockchainClient> = create_labelled_client("MockBlockchain");
 
         let expected_package_doc =
             PackageDocumentBuilder::from_package(&package, &blockchain_client).build();
 
-        packages_repo.create(&expected_package_doc).await;
+        packages_repo.create(&expected_package_doc).await.unwrap();
 
         let packages_docs = packages_repo
             .read_by_release(
@@ -345,17 +666,16 @@ mod tests {
 
         let test_dir_path = test_dir.path().join(db_dir);
 
-        let db_client = Arc::new(DbClient::from(&test_dir_path));
+        let db_client = Arc::new(DbClient::try_from(&test_dir_path).unwrap());
 
         let packages_repo = PackagesRepository::from(&db_client);
 
-        let blockchain_client: Box<dyn BlockchainClient> =
-            Box::new(HederaBlockchain::from("4991716"));
+        let blockchain_client: Box<dyn BlockchainClient> = create_labelled_client("MockBlockchain");
 
         let expected_package_doc =
             PackageDocumentBuilder::from_package(&package, &blockchain_client).build();
 
-        packages_repo.create(&expected_package_doc).await;
+        packages_repo.create(&expected_package_doc).await.unwrap();
 
         let packages_docs = packages_repo
             .read_by_maintainer(
@@ -380,12 +700,11 @@ mod tests {
 
         let test_dir_path = test_dir.path().join(db_dir);
 
-        let db_client = Arc::new(DbClient::from(&test_dir_path));
+        let db_client = Arc::new(DbClient::try_from(&test_dir_path).unwrap());
 
         let packages_repo = PackagesRepository::from(&db_client);
 
-        let blockchain_client: Box<dyn BlockchainClient> =
-            Box::new(HederaBlockchain::from("4991716"));
+        let blockchain_client: Box<dyn BlockchainClient> = create_labelled_client("MockBlockchain");
 
         let expected_package_doc_one =
             PackageDocumentBuilder::from_package(&package, &blockchain_client).build();
@@ -396,8 +715,14 @@ mod tests {
                 .set_name(&expected_package_doc_two_mock)
                 .build();
 
-        packages_repo.create(&expected_package_doc_one).await;
-        packages_repo.create(&expected_package_doc_two).await;
+        packages_repo
+            .create(&expected_package_doc_one)
+            .await
+            .unwrap();
+        packages_repo
+            .create(&expected_package_doc_two)
+            .await
+            .unwrap();
 
         let expected_packages_docs = vec![expected_package_doc_one, expected_package_doc_two];
 
@@ -419,18 +744,17 @@ mod tests {
 
         let test_dir_path = test_dir.path().join(db_dir);
 
-        let db_client = Arc::new(DbClient::from(&test_dir_path));
+        let db_client = Arc::new(DbClient::try_from(&test_dir_path).unwrap());
 
         let packages_repo = PackagesRepository::from(&db_client);
 
-        let blockchain_client: Box<dyn BlockchainClient> =
-            Box::new(HederaBlockchain::from("4991716"));
+        let blockchain_client: Box<dyn BlockchainClient> = create_labelled_client("MockBlockchain");
 
         // Create package entry
         let package_doc_mock =
             PackageDocumentBuilder::from_package(&package, &blockchain_client).build();
 
-        packages_repo.create(&package_doc_mock).await;
+        packages_repo.create(&package_doc_mock).await.unwrap();
 
         let package_doc_mock_key = &packages_repo.get_composite_key(&package_doc_mock);
 
@@ -468,19 +792,18 @@ mod tests {
 
         let test_dir_path = test_dir.path().join(db_dir);
 
-        let db_client = Arc::new(DbClient::from(&test_dir_path));
+        let db_client = Arc::new(DbClient::try_from(&test_dir_path).unwrap());
 
         let packages_repo = PackagesRepository::from(&db_client);
 
         let expected_exists = true;
 
-        let blockchain_client: Box<dyn BlockchainClient> =
-            Box::new(HederaBlockchain::from("4991716"));
+        let blockchain_client: Box<dyn BlockchainClient> = create_labelled_client("MockBlockchain");
 
         let package_doc =
             PackageDocumentBuilder::from_package(&package, &blockchain_client).build();
 
-        packages_repo.create(&package_doc).await;
+        packages_repo.create(&package_doc).await.unwrap();
 
         let expected_package_doc_key = packages_repo.get_composite_key(&package_doc);
 
@@ -502,14 +825,13 @@ mod tests {
 
         let test_dir_path = test_dir.path().join(db_dir);
 
-        let db_client = Arc::new(DbClient::from(&test_dir_path));
+        let db_client = Arc::new(DbClient::try_from(&test_dir_path).unwrap());
 
         let packages_repo = PackagesRepository::from(&db_client);
 
         let expected_exists = false;
 
-        let blockchain_client: Box<dyn BlockchainClient> =
-            Box::new(HederaBlockchain::from("4991716"));
+        let blockchain_client: Box<dyn BlockchainClient> = create_labelled_client("MockBlockchain");
 
         // do not insert package_doc
         let package_doc =
@@ -521,4 +843,160 @@ mod tests {
 
         assert_eq!(exists, expected_exists);
     }
+
+    /**
+     * It should not exist by composite key once deleted, leaving other releases untouched
+     */
+    #[tokio::test]
+    async fn test_delete_package_entry() {
+        let package = create_package_with_sig().unwrap();
+
+        let db_dir = "db";
+
+        let test_dir = TempDir::new().unwrap();
+
+        let test_dir_path = test_dir.path().join(db_dir);
+
+        let db_client = Arc::new(DbClient::try_from(&test_dir_path).unwrap());
+
+        let packages_repo = PackagesRepository::from(&db_client);
+
+        let blockchain_client: Box<dyn BlockchainClient> = create_labelled_client("MockBlockchain");
+
+        let package_doc =
+            PackageDocumentBuilder::from_package(&package, &blockchain_client).build();
+
+        let mut other_package_doc = package_doc.clone();
+        other_package_doc.version = "4.5.6".to_string();
+
+        packages_repo.create(&package_doc).await.unwrap();
+        packages_repo.create(&other_package_doc).await.unwrap();
+
+        let package_doc_key = packages_repo.get_composite_key(&package_doc);
+        let other_package_doc_key = packages_repo.get_composite_key(&other_package_doc);
+
+        packages_repo.delete(&package_doc_key).await;
+
+        assert_eq!(packages_repo.exists_by_key(&package_doc_key).await, false);
+        assert_eq!(
+            packages_repo.exists_by_key(&other_package_doc_key).await,
+            true
+        );
+    }
+
+    /**
+     * It should read every package page by page, without overlap
+     */
+    #[tokio::test]
+    async fn test_read_paginated_entries() {
+        let package = create_package_with_sig().unwrap();
+
+        let db_dir = "db";
+
+        let test_dir = TempDir::new().unwrap();
+
+        let test_dir_path = test_dir.path().join(db_dir);
+
+        let db_client = Arc::new(DbClient::try_from(&test_dir_path).unwrap());
+
+        let packages_repo = PackagesRepository::from(&db_client);
+
+        let blockchain_client: Box<dyn BlockchainClient> = create_labelled_client("MockBlockchain");
+
+        let package_doc =
+            PackageDocumentBuilder::from_package(&package, &blockchain_client).build();
+
+        let expected_versions: Vec<String> = (0..25).map(|i| format!("1.0.{}", i)).collect();
+
+        for version in &expected_versions {
+            let mut versioned_package_doc = package_doc.clone();
+            versioned_package_doc.version = version.clone();
+
+            packages_repo.create(&versioned_package_doc).await.unwrap();
+        }
+
+        let mut page_sizes = vec![];
+        let mut versions = vec![];
+
+        for page in 0..3 {
+            let docs = packages_repo.read_paginated(page * 10, 10).await;
+
+            page_sizes.push(docs.len());
+            versions.extend(docs.into_iter().map(|doc| doc.version));
+        }
+
+        assert_eq!(page_sizes, vec![10, 10, 5]);
+        assert_eq!(versions, expected_versions);
+
+        assert_eq!(packages_repo.read_paginated(30, 10).await.is_empty(), true);
+    }
+
+    /**
+     * It should only read packages having exactly given status
+     */
+    #[tokio::test]
+    async fn test_read_by_status_entries() {
+        let package = create_package_with_sig().unwrap();
+
+        let db_dir = "db";
+
+        let test_dir = TempDir::new().unwrap();
+
+        let test_dir_path = test_dir.path().join(db_dir);
+
+        let db_client = Arc::new(DbClient::try_from(&test_dir_path).unwrap());
+
+        let packages_repo = PackagesRepository::from(&db_client);
+
+        let blockchain_client: Box<dyn BlockchainClient> = create_labelled_client("MockBlockchain");
+
+        let package_doc =
+            PackageDocumentBuilder::from_package(&package, &blockchain_client).build();
+
+        let statuses = [
+            ("1.0.0", PackageStatus::Prohibited),
+            ("1.0.1", PackageStatus::Outdated),
+            ("1.0.2", PackageStatus::Prohibited),
+            ("1.0.3", PackageStatus::Fine),
+        ];
+
+        for (version, status) in &statuses {
+            let mut status_package_doc = package_doc.clone();
+            status_package_doc.version = version.to_string();
+            status_package_doc.status = i32::from(status.clone() as u8);
+
+            packages_repo.create(&status_package_doc).await.unwrap();
+        }
+
+        // Same status on another blockchain must be left out
+        let mut other_blockchain_doc = package_doc.clone();
+        other_blockchain_doc.status = i32::from(PackageStatus::Prohibited as u8);
+        other_blockchain_doc.blockchain_label = "foobar".to_string();
+
+        packages_repo.create(&other_blockchain_doc).await.unwrap();
+
+        let prohibited_versions: Vec<String> = packages_repo
+            .read_by_status(&PackageStatus::Prohibited, &blockchain_client.get_label())
+            .await
+            .into_iter()
+            .map(|doc| doc.version)
+            .collect();
+
+        assert_eq!(prohibited_versions, vec!["1.0.0", "1.0.2"]);
+
+        assert_eq!(
+            packages_repo
+                .read_by_status(&PackageStatus::Outdated, &blockchain_client.get_label())
+                .await
+                .len(),
+            1
+        );
+        assert_eq!(
+            packages_repo
+                .read_by_status(&PackageStatus::Recommended, &blockchain_client.get_label())
+                .await
+                .is_empty(),
+            true
+        );
+    }
 }
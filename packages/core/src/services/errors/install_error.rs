@@ -0,0 +1,22 @@
+use thiserror::Error;
+
+/**
+ * Errors preventing package installation from being planned
+ */
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum InstallError {
+    #[error("Dependency {0} could not be found, synchronize then try again")]
+    MissingDependency(String),
+
+    #[error("Dependency {0} cannot be installed given its status")]
+    ForbiddenDependency(String),
+
+    #[error("Dependency {0} has an invalid version constraint")]
+    InvalidDependency(String),
+
+    #[error("Dependency cycle detected: {0}")]
+    DependencyCycle(String),
+
+    #[error("Several maintainers published dependency {0}, refusing to pick one in strict mode")]
+    MaintainersConflict(String),
+}
@@ -0,0 +1 @@
+pub mod install_error;
@@ -1,5 +1,6 @@
 pub mod blockchains;
 pub mod db;
+pub mod errors;
 pub mod packages;
 
 pub mod package_managers;
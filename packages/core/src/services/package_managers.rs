@@ -1,17 +1,39 @@
-use std::sync::Arc;
+use std::{
+    collections::{HashMap, HashSet},
+    env,
+    path::PathBuf,
+    sync::Arc,
+};
 
-use log::debug;
+use log::{debug, error, warn};
+use url::Url;
 
 use crate::{
-    package_managers::traits::package_manager::PackageManager, types::asynchronous::AsyncMutex,
+    package_managers::{
+        errors::package_manager_error::PackageManagerError, installed_package::InstalledPackage,
+        traits::package_manager::PackageManager, url_template::expand_archive_url,
+    },
+    packages::package::Package,
+    types::asynchronous::AsyncMutex,
 };
 
+/**
+ * Packages handled by an install run, in installation order
+ */
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct InstallProgress {
+    pub installed: Vec<String>, // Every package handed to package manager
+    pub added: Vec<String>,     // Only packages missing from system before run, safe to roll back
+}
+
 /**
  * Package managers service
  */
 pub struct PackageManagersService {
     available_package_managers: Arc<AsyncMutex<Vec<Arc<Box<dyn PackageManager>>>>>,
     selected_package_manager: Arc<AsyncMutex<Option<usize>>>,
+    url_variables: HashMap<String, String>,
+    http_client: reqwest::Client,
 }
 
 impl PackageManagersService {
@@ -23,14 +45,54 @@ impl PackageManagersService {
             available_package_managers: Arc::new(AsyncMutex::new(
                 available_package_managers.clone(),
             )),
-            selected_package_manager: Arc::new(AsyncMutex::new(Some(0))),
+            selected_package_manager: Arc::new(AsyncMutex::new(
+                (!available_package_managers.is_empty()).then_some(0),
+            )),
+            url_variables: HashMap::new(),
+            http_client: reqwest::Client::new(),
         }
     }
 
     /**
-     * Return selected package manager
+     * Set HTTP client used for downloads made outside of package managers
+     */
+    pub fn set_http_client(&mut self, http_client: &reqwest::Client) -> &mut Self {
+        self.http_client = http_client.clone();
+        self
+    }
+
+    /**
+     * Get HTTP client used for downloads made outside of package managers
+     */
+    pub fn get_http_client(&self) -> &reqwest::Client {
+        &self.http_client
+    }
+
+    /**
+     * Set configured values of archive url placeholders
+     */
+    pub fn set_url_variables(&mut self, url_variables: &HashMap<String, String>) -> &mut Self {
+        self.url_variables = url_variables.clone();
+        self
+    }
+
+    /**
+     * Expand placeholders of archive url from environment, then configuration
+     */
+    pub fn resolve_archive_url(&self, archive_url: &Url) -> Result<Url, PackageManagerError> {
+        expand_archive_url(archive_url, &|name| {
+            env::var(name)
+                .ok()
+                .or_else(|| self.url_variables.get(name).cloned())
+        })
+    }
+
+    /**
+     * Return selected package manager, failing when none was detected on system
      */
-    pub async fn get_selected_package_manager(&self) -> Arc<Box<dyn PackageManager>> {
+    pub async fn get_selected_package_manager(
+        &self,
+    ) -> Result<Arc<Box<dyn PackageManager>>, PackageManagerError> {
         debug!("Getting selected package manager...");
 
         let package_managers = self.available_package_managers.lock().await;
@@ -39,24 +101,193 @@ impl PackageManagersService {
             .selected_package_manager
             .lock()
             .await
-            .expect("Package manager selected id must be set in order to get package manager");
+            .ok_or(PackageManagerError::NoPackageManager)?;
 
         let package_manager = package_managers
             .get(selected_id)
-            .expect("Could not find package manager");
+            .ok_or(PackageManagerError::NoPackageManager)?;
 
         debug!("Done getting selected package manager !");
 
-        Arc::clone(package_manager)
+        Ok(Arc::clone(package_manager))
+    }
+
+    /**
+     * Abort download in progress on selected package manager, if any
+     */
+    pub async fn cancel_download(&self) -> Result<(), PackageManagerError> {
+        let package_manager = self.get_selected_package_manager().await?;
+
+        package_manager.cancel_download();
+
+        Ok(())
+    }
+
+    /**
+     * Get packages installed using selected package manager
+     */
+    pub async fn list_installed(&self) -> Result<Vec<InstalledPackage>, PackageManagerError> {
+        let package_manager = self.get_selected_package_manager().await?;
+
+        package_manager.list_installed().await
+    }
+
+    /**
+     * Install package trying each mirror in turn, moving to next one when integrity check fails
+     */
+    pub async fn install_from_mirrors(
+        &self,
+        package: &Package,
+        archive_urls: &[Url],
+    ) -> Result<PathBuf, PackageManagerError> {
+        // No mirror can help when archive cannot be verified
+        package.integrity.ensure_supported_algorithm()?;
+
+        let package_manager = self.get_selected_package_manager().await?;
+
+        package_manager.reset_download();
+
+        self.install_with(&package_manager, package, archive_urls)
+            .await
+    }
+
+    /**
+     * Install package using given package manager, trying each mirror in turn
+     *
+     * Cancellation is not reset here, so it holds for every package of an install operation
+     */
+    async fn install_with(
+        &self,
+        package_manager: &Arc<Box<dyn PackageManager>>,
+        package: &Package,
+        archive_urls: &[Url],
+    ) -> Result<PathBuf, PackageManagerError> {
+        package.integrity.ensure_supported_algorithm()?;
+
+        for archive_url in archive_urls {
+            let archive_url = self.resolve_archive_url(archive_url)?;
+
+            match package_manager
+                .install_verified(&archive_url, &package.integrity)
+                .await
+            {
+                Err(PackageManagerError::IntegrityMismatch(_)) => {
+                    warn!(
+                        "Archive from {} does not match package integrity, trying next mirror...",
+                        archive_url
+                    );
+                }
+                install_result => return install_result,
+            }
+        }
+
+        Err(PackageManagerError::IntegrityMismatch(format!(
+            "every mirror of {} failed verification",
+            package.name
+        )))
+    }
+
+    /**
+     * Install packages in given order, stopping at first failure
+     *
+     * Names of successfully installed packages are recorded in progress, in installation order.
+     * Packages already on system before run ( eg: dependencies turned into no-ops by --needed, or
+     * present at another version ) are not recorded as added, so rolling back never removes them
+     */
+    pub async fn install_all(
+        &self,
+        packages: &[Package],
+        progress: &mut InstallProgress,
+    ) -> Result<(), PackageManagerError> {
+        debug!("Installing {} packages...", packages.len());
+
+        let package_manager = self.get_selected_package_manager().await?;
+
+        // Without knowing what was already there, nothing could be safely rolled back
+        let present_before: HashSet<String> = package_manager
+            .list_installed()
+            .await?
+            .into_iter()
+            .map(|installed_package| installed_package.name)
+            .collect();
+
+        // Cancelling must stop every remaining package, not only current download
+        package_manager.reset_download();
+
+        for package in packages {
+            // Packages only carry a single archive url for now
+            let archive_urls = vec![package.archive_url.clone()];
+
+            self.install_with(&package_manager, package, &archive_urls)
+                .await?;
+
+            progress.installed.push(package.name.clone());
+
+            if !present_before.contains(&package.name) {
+                progress.added.push(package.name.clone());
+            }
+        }
+
+        debug!("Done installing {} packages !", packages.len());
+
+        Ok(())
+    }
+
+    /**
+     * Remove given installed packages in reverse installation order
+     *
+     * Every package is attempted even if a removal fails, failures are reported together
+     */
+    pub async fn rollback(&self, installed: &[String]) -> Result<(), PackageManagerError> {
+        debug!("Rolling back {} packages...", installed.len());
+
+        let package_manager = self.get_selected_package_manager().await?;
+
+        let mut failed_removals: Vec<String> = vec![];
+
+        for package_name in installed.iter().rev() {
+            if let Err(e) = package_manager.remove(package_name).await {
+                error!("Could not roll back package {} : {}", package_name, e);
+
+                failed_removals.push(package_name.clone());
+            }
+        }
+
+        if !failed_removals.is_empty() {
+            return Err(PackageManagerError::RemovalError(
+                failed_removals.join(", "),
+            ));
+        }
+
+        debug!("Done rolling back {} packages !", installed.len());
+
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::package_managers::traits::package_manager::{MockPackageManager, PackageManager};
+    use mockall::{predicate::eq, Sequence};
+
+    use crate::{
+        package_managers::traits::package_manager::{MockPackageManager, PackageManager},
+        packages::package_builder::PackageBuilder,
+        test_utils::package::tests::create_package_with_sig,
+    };
 
     use super::*;
 
+    /**
+     * Create package with given name
+     */
+    fn create_named_package(name: &str) -> Package {
+        let package = create_package_with_sig().unwrap();
+
+        PackageBuilder::from_package(&package)
+            .set_name(&name.to_string())
+            .build()
+    }
+
     #[tokio::test]
     async fn test_should_get_package_manager() {
         let mut package_manager_mock = MockPackageManager::default();
@@ -75,11 +306,391 @@ mod tests {
 
         let current_package_manager = package_managers_service
             .get_selected_package_manager()
-            .await;
+            .await
+            .unwrap();
 
         assert_eq!(
             current_package_manager.get_name(),
             expected_package_manager_name
         );
     }
+
+    /**
+     * It should report missing package manager instead of panicking
+     */
+    #[tokio::test]
+    async fn test_should_fail_without_package_manager() {
+        let package_managers_service = PackageManagersService::new(&vec![]);
+
+        let current_package_manager = package_managers_service
+            .get_selected_package_manager()
+            .await;
+
+        assert_eq!(
+            matches!(
+                current_package_manager,
+                Err(PackageManagerError::NoPackageManager)
+            ),
+            true
+        );
+    }
+
+    /**
+     * It should list packages installed using selected package manager
+     */
+    #[tokio::test]
+    async fn test_list_installed() {
+        let installed_package = InstalledPackage {
+            name: String::from("foo"),
+            version: String::from("1.0.0"),
+        };
+
+        let mut package_manager_mock = MockPackageManager::default();
+
+        let shared_installed_package = installed_package.clone();
+
+        package_manager_mock
+            .expect_list_installed()
+            .times(1)
+            .returning(move || Ok(vec![shared_installed_package.clone()]));
+
+        let package_manager: Arc<Box<dyn PackageManager>> =
+            Arc::new(Box::new(package_manager_mock));
+
+        let package_managers_service = PackageManagersService::new(&vec![package_manager]);
+
+        let installed_packages = package_managers_service.list_installed().await.unwrap();
+
+        assert_eq!(installed_packages, vec![installed_package]);
+    }
+
+    /**
+     * It should stop at first failure and report packages installed before it
+     */
+    #[tokio::test]
+    async fn test_install_all_partial_failure() {
+        let packages = vec![
+            create_named_package("foo"),
+            create_named_package("bar"),
+            create_named_package("baz"),
+        ];
+
+        let failing_url = packages[1].archive_url.clone();
+
+        let mut package_manager_mock = MockPackageManager::default();
+
+        let mut install_calls = 0;
+
+        package_manager_mock
+            .expect_list_installed()
+            .times(1)
+            .returning(|| Ok(vec![]));
+
+        package_manager_mock
+            .expect_reset_download()
+            .times(1)
+            .return_const(());
+
+        package_manager_mock
+            .expect_install_verified()
+            .times(2)
+            .returning(move |_, _| {
+                install_calls += 1;
+
+                if install_calls == 2 {
+                    Err(PackageManagerError::InstallationError(
+                        failing_url.to_string(),
+                    ))
+                } else {
+                    Ok(PathBuf::from("/tmp/foo"))
+                }
+            });
+
+        let package_manager: Arc<Box<dyn PackageManager>> =
+            Arc::new(Box::new(package_manager_mock));
+
+        let package_managers_service = PackageManagersService::new(&vec![package_manager]);
+
+        let mut progress = InstallProgress::default();
+
+        let install_result = package_managers_service
+            .install_all(&packages, &mut progress)
+            .await;
+
+        assert_eq!(install_result.is_err(), true);
+        assert_eq!(progress.installed, vec![String::from("foo")]);
+        assert_eq!(progress.added, vec![String::from("foo")]);
+    }
+
+    /**
+     * It should reset cancellation once per install operation, so cancelling stops remaining packages
+     */
+    #[tokio::test]
+    async fn test_install_all_cancelled() {
+        let packages = vec![
+            create_named_package("foo"),
+            create_named_package("bar"),
+            create_named_package("baz"),
+        ];
+
+        let mut package_manager_mock = MockPackageManager::default();
+
+        let mut install_calls = 0;
+
+        package_manager_mock
+            .expect_list_installed()
+            .times(1)
+            .returning(|| Ok(vec![]));
+
+        package_manager_mock
+            .expect_reset_download()
+            .times(1)
+            .return_const(());
+
+        // Cancelled while downloading second package
+        package_manager_mock
+            .expect_install_verified()
+            .times(2)
+            .returning(move |_, _| {
+                install_calls += 1;
+
+                if install_calls == 2 {
+                    Err(PackageManagerError::DownloadCancelled)
+                } else {
+                    Ok(PathBuf::from("/tmp/foo"))
+                }
+            });
+
+        let package_manager: Arc<Box<dyn PackageManager>> =
+            Arc::new(Box::new(package_manager_mock));
+
+        let package_managers_service = PackageManagersService::new(&vec![package_manager]);
+
+        let mut progress = InstallProgress::default();
+
+        let install_result = package_managers_service
+            .install_all(&packages, &mut progress)
+            .await;
+
+        assert_eq!(
+            matches!(install_result, Err(PackageManagerError::DownloadCancelled)),
+            true
+        );
+        assert_eq!(progress.installed, vec![String::from("foo")]);
+        assert_eq!(progress.added, vec![String::from("foo")]);
+    }
+
+    /**
+     * It should only roll back packages added by run, keeping dependency installed beforehand
+     */
+    #[tokio::test]
+    async fn test_install_all_keeps_preinstalled_dependency() {
+        let packages = vec![
+            create_named_package("glibc"),
+            create_named_package("foo"),
+            create_named_package("bar"),
+        ];
+
+        let mut package_manager_mock = MockPackageManager::default();
+
+        let mut install_calls = 0;
+
+        // Dependency already on system, at another version than the one being installed
+        package_manager_mock
+            .expect_list_installed()
+            .times(1)
+            .returning(|| {
+                Ok(vec![InstalledPackage {
+                    name: String::from("glibc"),
+                    version: String::from("0.1.0"),
+                }])
+            });
+
+        package_manager_mock
+            .expect_reset_download()
+            .times(1)
+            .return_const(());
+
+        package_manager_mock
+            .expect_install_verified()
+            .times(3)
+            .returning(move |archive_url, _| {
+                install_calls += 1;
+
+                if install_calls == 3 {
+                    Err(PackageManagerError::InstallationError(
+                        archive_url.to_string(),
+                    ))
+                } else {
+                    Ok(PathBuf::from("/tmp/foo"))
+                }
+            });
+
+        package_manager_mock
+            .expect_remove()
+            .with(eq(String::from("foo")))
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let package_manager: Arc<Box<dyn PackageManager>> =
+            Arc::new(Box::new(package_manager_mock));
+
+        let package_managers_service = PackageManagersService::new(&vec![package_manager]);
+
+        let mut progress = InstallProgress::default();
+
+        let install_result = package_managers_service
+            .install_all(&packages, &mut progress)
+            .await;
+
+        assert_eq!(install_result.is_err(), true);
+        assert_eq!(
+            progress.installed,
+            vec![String::from("glibc"), String::from("foo")]
+        );
+        assert_eq!(progress.added, vec![String::from("foo")]);
+
+        let rollback_result = package_managers_service.rollback(&progress.added).await;
+
+        assert_eq!(rollback_result.is_ok(), true);
+    }
+
+    /**
+     * It should try next mirror when integrity check fails, failing once every mirror failed
+     */
+    #[tokio::test]
+    async fn test_install_from_mirrors() {
+        let package = create_named_package("foo");
+
+        let tampered_mirror = Url::parse("https://tampered.example.com/foo.pkg.tar.zst").unwrap();
+        let sane_mirror = Url::parse("https://sane.example.com/foo.pkg.tar.zst").unwrap();
+
+        let mut package_manager_mock = MockPackageManager::default();
+
+        let shared_tampered_mirror = tampered_mirror.clone();
+
+        package_manager_mock
+            .expect_reset_download()
+            .times(2)
+            .return_const(());
+
+        package_manager_mock
+            .expect_install_verified()
+            .returning(move |archive_url, _| {
+                if *archive_url == shared_tampered_mirror {
+                    Err(PackageManagerError::IntegrityMismatch(
+                        archive_url.to_string(),
+                    ))
+                } else {
+                    Ok(PathBuf::from("/tmp/foo"))
+                }
+            });
+
+        let package_manager: Arc<Box<dyn PackageManager>> =
+            Arc::new(Box::new(package_manager_mock));
+
+        let package_managers_service = PackageManagersService::new(&vec![package_manager]);
+
+        let install_result = package_managers_service
+            .install_from_mirrors(&package, &[tampered_mirror.clone(), sane_mirror])
+            .await;
+
+        assert_eq!(install_result.unwrap(), PathBuf::from("/tmp/foo"));
+
+        let all_tampered_result = package_managers_service
+            .install_from_mirrors(&package, &[tampered_mirror.clone(), tampered_mirror])
+            .await;
+
+        assert_eq!(
+            matches!(
+                all_tampered_result,
+                Err(PackageManagerError::IntegrityMismatch(_))
+            ),
+            true
+        );
+    }
+
+    /**
+     * It should refuse package whose integrity algorithm is not supported, without fetching anything
+     */
+    #[tokio::test]
+    async fn test_install_from_mirrors_unsupported_algorithm() {
+        let package = create_named_package("foo");
+
+        let mut unknown_algorithm_package = package.clone();
+        unknown_algorithm_package.integrity.algorithm = String::from("MD5");
+
+        let mut package_manager_mock = MockPackageManager::default();
+
+        package_manager_mock.expect_install_verified().never();
+
+        let package_manager: Arc<Box<dyn PackageManager>> =
+            Arc::new(Box::new(package_manager_mock));
+
+        let package_managers_service = PackageManagersService::new(&vec![package_manager]);
+
+        let install_result = package_managers_service
+            .install_from_mirrors(
+                &unknown_algorithm_package,
+                &[unknown_algorithm_package.archive_url.clone()],
+            )
+            .await;
+
+        assert_eq!(
+            matches!(
+                install_result,
+                Err(PackageManagerError::UnsupportedIntegrityAlgorithm(ref algorithm)) if algorithm == "MD5"
+            ),
+            true
+        );
+    }
+
+    /**
+     * It should remove installed packages in reverse order, even when a removal fails
+     */
+    #[tokio::test]
+    async fn test_rollback() {
+        let installed = vec![
+            String::from("foo"),
+            String::from("bar"),
+            String::from("baz"),
+        ];
+
+        let mut package_manager_mock = MockPackageManager::default();
+
+        let mut sequence = Sequence::new();
+
+        package_manager_mock
+            .expect_remove()
+            .with(eq(String::from("baz")))
+            .times(1)
+            .in_sequence(&mut sequence)
+            .returning(|_| Ok(()));
+
+        package_manager_mock
+            .expect_remove()
+            .with(eq(String::from("bar")))
+            .times(1)
+            .in_sequence(&mut sequence)
+            .returning(|package_name| Err(PackageManagerError::RemovalError(package_name.clone())));
+
+        package_manager_mock
+            .expect_remove()
+            .with(eq(String::from("foo")))
+            .times(1)
+            .in_sequence(&mut sequence)
+            .returning(|_| Ok(()));
+
+        let package_manager: Arc<Box<dyn PackageManager>> =
+            Arc::new(Box::new(package_manager_mock));
+
+        let package_managers_service = PackageManagersService::new(&vec![package_manager]);
+
+        let rollback_result = package_managers_service.rollback(&installed).await;
+
+        assert_eq!(
+            rollback_result.unwrap_err().to_string(),
+            PackageManagerError::RemovalError(String::from("bar")).to_string()
+        );
+    }
 }
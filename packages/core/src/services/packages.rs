@@ -1,18 +1,44 @@
-use std::sync::Arc;
+use std::{cmp::Ordering, collections::HashMap, path::PathBuf, sync::Arc};
 
 use ed25519_dalek::VerifyingKey;
-use log::debug;
+use futures_util::{Stream, StreamExt};
+use log::{debug, error};
 
 use crate::{
     blockchains::blockchain::BlockchainClient,
     db::{
-        documents::package_document_builder::PackageDocumentBuilder, traits::repository::Repository,
+        client::DbClient, documents::package_document_builder::PackageDocumentBuilder,
+        errors::repository_error::RepositoryError, traits::repository::Repository,
+    },
+    packages::{
+        package::Package,
+        package_builder::PackageBuilder,
+        package_status::PackageStatus,
+        utils::{maintainers::matches_maintainer, versions::compare_versions},
     },
-    packages::{package::Package, package_builder::PackageBuilder},
 };
 
 use super::db::packages_repository::PackagesRepository;
 
+/**
+ * One page of packages, along with total count of packages matching same filters
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackagesPage {
+    pub packages: Vec<Package>,
+    pub total: u64,
+}
+
+/**
+ * Differences between two packages sets, packages being identified by release and maintainer
+ */
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PackageDiff {
+    pub added: Vec<Package>,
+    pub removed: Vec<Package>,
+    pub status_changed: Vec<(Package, Package)>, // Before and after status change
+}
+
 /**
  * Packages service
  */
@@ -22,7 +48,65 @@ pub struct PackagesService {
 
 impl PackagesService {
     /**
-     * Add new package to DB
+     * Open DB at given path and wire packages service on top of it
+     */
+    pub fn open(db_path: &PathBuf) -> Result<Arc<PackagesService>, RepositoryError> {
+        let db_client = Arc::new(DbClient::try_from(db_path)?);
+
+        let packages_repository = Arc::new(PackagesRepository::from(&db_client));
+
+        Ok(Arc::new(PackagesService::from(&packages_repository)))
+    }
+
+    /**
+     * Compare packages sets, such as cache before and after synchronization
+     */
+    pub fn diff(before: &[Package], after: &[Package]) -> PackageDiff {
+        let package_key = |package: &Package| {
+            (
+                package.name.clone(),
+                package.version.clone(),
+                package.maintainer.to_bytes(),
+            )
+        };
+
+        let before_by_key: HashMap<_, &Package> = before
+            .iter()
+            .map(|package| (package_key(package), package))
+            .collect();
+
+        let after_by_key: HashMap<_, &Package> = after
+            .iter()
+            .map(|package| (package_key(package), package))
+            .collect();
+
+        let mut diff = PackageDiff::default();
+
+        for package in after {
+            match before_by_key.get(&package_key(package)) {
+                None => diff.added.push(package.clone()),
+                Some(previous_package)
+                    if !previous_package.content_eq(package)
+                        && previous_package.status != package.status =>
+                {
+                    diff.status_changed
+                        .push(((*previous_package).clone(), package.clone()))
+                }
+                Some(_) => {}
+            }
+        }
+
+        diff.removed = before
+            .iter()
+            .filter(|package| !after_by_key.contains_key(&package_key(package)))
+            .cloned()
+            .collect();
+
+        diff
+    }
+
+    /**
+     * Add new package to DB, updating it instead if it is already stored
      */
     pub async fn add(&self, package: &Package, blockchain_client: &Box<dyn BlockchainClient>) {
         debug!("Adding new package...");
@@ -31,7 +115,15 @@ impl PackagesService {
 
         let package_doc = builder.build();
 
-        self.packages_repository.create(&package_doc).await;
+        match self.packages_repository.create(&package_doc).await {
+            Ok(_) => (),
+            Err(RepositoryError::DuplicateKey(key)) => {
+                debug!("Package {} is already stored, updating it instead", key);
+
+                self.packages_repository.update(&key, &package_doc).await;
+            }
+            Err(e) => error!("Could not add package {} : {}", package.name, e),
+        }
 
         debug!("Done adding new package !");
     }
@@ -53,6 +145,24 @@ impl PackagesService {
         package_exists
     }
 
+    /**
+     * Get stored version of package, if any
+     */
+    pub async fn get(
+        &self,
+        package: &Package,
+        blockchain_client: &Box<dyn BlockchainClient>,
+    ) -> Option<Package> {
+        let doc = PackageDocumentBuilder::from_package(&package, &blockchain_client).build();
+
+        let key = self.packages_repository.get_composite_key(&doc);
+
+        self.packages_repository
+            .read_by_key(&key)
+            .await
+            .map(|doc| PackageBuilder::from_document(&doc).build())
+    }
+
     /**
      * Get all packages
      */
@@ -77,20 +187,78 @@ impl PackagesService {
     }
 
     /**
-     * Get by release name
+     * Get all packages lazily, rebuilding one package at a time
+     */
+    pub async fn get_all_stream(&self) -> impl Stream<Item = Package> {
+        self.packages_repository
+            .read_all_stream()
+            .await
+            .map(|doc| PackageBuilder::from_document(&doc).build())
+    }
+
+    /**
+     * Get one page of packages, in the order they were stored
+     */
+    pub async fn get_paginated(&self, skip: u64, limit: u64) -> Vec<Package> {
+        debug!("Getting paginated packages...");
+
+        let packages = self
+            .packages_repository
+            .read_paginated(skip, limit)
+            .await
+            .iter()
+            .map(|doc| PackageBuilder::from_document(doc).build())
+            .collect();
+
+        debug!("Done getting paginated packages !");
+
+        packages
+    }
+
+    /**
+     * Get one page of packages whose name contains query, leaving out ones below given status if any
+     */
+    pub async fn get_page(
+        &self,
+        query: &Option<String>,
+        min_status: &Option<PackageStatus>,
+        offset: u64,
+        limit: u64,
+    ) -> PackagesPage {
+        debug!("Getting packages page...");
+
+        let (docs, total) = self
+            .packages_repository
+            .read_filtered_page(query, min_status, offset, limit)
+            .await;
+
+        let packages = docs
+            .iter()
+            .map(|doc| PackageBuilder::from_document(doc).build())
+            .collect();
+
+        debug!("Done getting packages page !");
+
+        PackagesPage { packages, total }
+    }
+
+    /**
+     * Get by release name, leaving out packages below given status if any
      */
     pub async fn get_by_release(
         &self,
         package_name: &String,
         package_version: &String,
+        min_status: &Option<PackageStatus>,
         blockchain_client: &Box<dyn BlockchainClient>,
     ) -> Vec<Package> {
         let packages = self
             .packages_repository
-            .read_by_release(
+            .read_by_release_with_min_status(
                 &package_name,
                 &package_version,
                 &blockchain_client.get_label(),
+                min_status,
             )
             .await
             .iter()
@@ -104,6 +272,69 @@ impl PackagesService {
         packages
     }
 
+    /**
+     * Get known versions of package, oldest first and deduplicated
+     */
+    pub async fn get_versions(
+        &self,
+        package_name: &String,
+        blockchain_client: &Box<dyn BlockchainClient>,
+    ) -> Vec<String> {
+        let mut versions: Vec<String> = self
+            .packages_repository
+            .read_by_name(&package_name, &blockchain_client.get_label())
+            .await
+            .into_iter()
+            .map(|doc| doc.version)
+            .collect();
+
+        // Lexical tie-break keeps same versions next to each other so they can be deduplicated
+        versions.sort_by(|version_a, version_b| {
+            compare_versions(version_a, version_b).then_with(|| version_a.cmp(version_b))
+        });
+        versions.dedup();
+
+        versions
+    }
+
+    /**
+     * Find best release to upgrade installed package to, if any
+     *
+     * Only releases of given maintainer ( hex key or fingerprint ) are considered, so an upgrade never switches maintainer.
+     * Only newer releases qualify, even when installed one got flagged ( eg: Outdated ), an upgrade never downgrades.
+     * Best status then newest version is preferred
+     */
+    pub async fn find_upgrade(
+        &self,
+        package_name: &String,
+        installed_version: &String,
+        maintainer: &str,
+        blockchain_client: &Box<dyn BlockchainClient>,
+    ) -> Option<Package> {
+        let packages: Vec<Package> = self
+            .packages_repository
+            .read_by_name(&package_name, &blockchain_client.get_label())
+            .await
+            .iter()
+            .map(|doc| PackageBuilder::from_document(doc).build())
+            .filter(|package| matches_maintainer(&package.maintainer, maintainer))
+            .collect();
+
+        packages
+            .into_iter()
+            .filter(|package| {
+                // Same threshold as install, below it release cannot be installed
+                package.status >= PackageStatus::Outdated
+                    && compare_versions(&package.version, installed_version) == Ordering::Greater
+            })
+            .max_by(|package_a, package_b| {
+                package_a
+                    .status
+                    .cmp(&package_b.status)
+                    .then_with(|| compare_versions(&package_a.version, &package_b.version))
+            })
+    }
+
     /**
      * Get packages by maintainer
      */
@@ -128,6 +359,46 @@ impl PackagesService {
         packages
     }
 
+    /**
+     * Get packages having exactly given status, eg: to audit every prohibited release
+     */
+    pub async fn get_by_status(
+        &self,
+        status: &PackageStatus,
+        blockchain_client: &Box<dyn BlockchainClient>,
+    ) -> Vec<Package> {
+        self.packages_repository
+            .read_by_status(status, &blockchain_client.get_label())
+            .await
+            .iter()
+            .map(|doc| PackageBuilder::from_document(doc).build())
+            .collect()
+    }
+
+    /**
+     * Get last time given package release was mutated
+     */
+    pub async fn last_mutation_time(
+        &self,
+        package_name: &String,
+        package_version: &String,
+        maintainer: &VerifyingKey,
+        blockchain_client: &Box<dyn BlockchainClient>,
+    ) -> Option<u64> {
+        let encoded_maintainer = hex::encode(maintainer.to_bytes());
+
+        self.packages_repository
+            .read_by_release(
+                &package_name,
+                &package_version,
+                &blockchain_client.get_label(),
+            )
+            .await
+            .iter()
+            .find(|doc| doc.maintainer == encoded_maintainer)
+            .and_then(|doc| doc.mutation_time)
+    }
+
     /**
      * Update package
      */
@@ -165,20 +436,18 @@ impl From<&Arc<PackagesRepository>> for PackagesService {
 #[cfg(test)]
 
 mod tests {
-    use std::sync::Arc;
-
     use ed25519::signature::rand_core::OsRng;
     use ed25519_dalek::SigningKey;
+    use futures_util::StreamExt;
+    use tempfile::TempDir;
 
     use crate::{
-        blockchains::blockchain::{BlockchainClient, MockBlockchainClient},
         packages::{
-            package_builder::PackageBuilder, package_status::PackageStatus,
+            package::Package, package_builder::PackageBuilder, package_status::PackageStatus,
             utils::signatures::sign_package,
         },
-        services::{db::packages_repository::PackagesRepository, packages::PackagesService},
         test_utils::{
-            db::tests::create_test_db,
+            fixtures::tests::TestEnv,
             package::tests::{create_package_with_sig, create_package_without_sig},
         },
     };
@@ -188,21 +457,11 @@ mod tests {
      */
     #[tokio::test]
     async fn test_should_add_package() -> Result<(), Box<dyn std::error::Error>> {
-        let db_client = create_test_db();
-
-        // Instantiate required resources
-
-        let packages_repository = Arc::new(PackagesRepository::from(&db_client));
-
-        let packages_service = Arc::new(PackagesService::from(&packages_repository));
-
-        let mut blockchain_mock = MockBlockchainClient::default();
-
-        blockchain_mock
-            .expect_get_label()
-            .returning(|| "MockBlockchain".to_string());
-
-        let blockchain_client: Box<dyn BlockchainClient> = Box::new(blockchain_mock);
+        let TestEnv {
+            packages_service,
+            blockchain_client,
+            ..
+        } = TestEnv::new();
         let expected_package = create_package_with_sig()?;
 
         packages_service
@@ -213,6 +472,7 @@ mod tests {
             .get_by_release(
                 &expected_package.name,
                 &expected_package.version,
+                &None,
                 &blockchain_client,
             )
             .await;
@@ -222,25 +482,96 @@ mod tests {
     }
 
     /**
-     * It should get all packages
+     * It should leave out packages below minimum status
      */
     #[tokio::test]
-    async fn test_should_get_all_packages() -> Result<(), Box<dyn std::error::Error>> {
-        let db_client = create_test_db();
+    async fn test_get_by_release_min_status() -> Result<(), Box<dyn std::error::Error>> {
+        let TestEnv {
+            packages_service,
+            blockchain_client,
+            ..
+        } = TestEnv::new();
+
+        let fine_package = create_package_with_sig()?;
+        let prohibited_package = PackageBuilder::from_package(&create_package_with_sig()?)
+            .set_status(&PackageStatus::Prohibited)
+            .build();
+
+        packages_service
+            .add(&fine_package, &blockchain_client)
+            .await;
+        packages_service
+            .add(&prohibited_package, &blockchain_client)
+            .await;
 
-        // Instantiate required resources
+        let all_packages = packages_service
+            .get_by_release(
+                &fine_package.name,
+                &fine_package.version,
+                &None,
+                &blockchain_client,
+            )
+            .await;
 
-        let packages_repository = Arc::new(PackagesRepository::from(&db_client));
+        let acceptable_packages = packages_service
+            .get_by_release(
+                &fine_package.name,
+                &fine_package.version,
+                &Some(PackageStatus::Outdated),
+                &blockchain_client,
+            )
+            .await;
+
+        assert_eq!(all_packages.len(), 2);
+        assert_eq!(acceptable_packages, vec![fine_package]);
+
+        Ok(())
+    }
 
-        let packages_service = Arc::new(PackagesService::from(&packages_repository));
+    /**
+     * It should get stored package
+     */
+    #[tokio::test]
+    async fn test_should_get_package() -> Result<(), Box<dyn std::error::Error>> {
+        let TestEnv {
+            packages_service,
+            blockchain_client,
+            ..
+        } = TestEnv::new();
 
-        let mut blockchain_mock = MockBlockchainClient::default();
+        let expected_mutation_time = 1704067200;
+
+        let package = PackageBuilder::from_package(&create_package_with_sig()?)
+            .set_consensus_time(&expected_mutation_time)
+            .build();
+        let unknown_package = create_package_with_sig()?;
 
-        blockchain_mock
-            .expect_get_label()
-            .returning(|| "MockBlockchain".to_string());
+        packages_service.add(&package, &blockchain_client).await;
 
-        let blockchain_client: Box<dyn BlockchainClient> = Box::new(blockchain_mock);
+        assert_eq!(
+            packages_service.get(&package, &blockchain_client).await,
+            Some(package)
+        );
+        assert_eq!(
+            packages_service
+                .get(&unknown_package, &blockchain_client)
+                .await,
+            None
+        );
+
+        Ok(())
+    }
+
+    /**
+     * It should get all packages
+     */
+    #[tokio::test]
+    async fn test_should_get_all_packages() -> Result<(), Box<dyn std::error::Error>> {
+        let TestEnv {
+            packages_service,
+            blockchain_client,
+            ..
+        } = TestEnv::new();
         let package_one = create_package_with_sig()?;
 
         packages_service.add(&package_one, &blockchain_client).await;
@@ -258,25 +589,164 @@ mod tests {
     }
 
     /**
-     * It should get by maintainer
+     * It should stream all packages
      */
     #[tokio::test]
-    async fn test_should_get_package_by_maintainer() -> Result<(), Box<dyn std::error::Error>> {
-        let db_client = create_test_db();
+    async fn test_should_stream_all_packages() -> Result<(), Box<dyn std::error::Error>> {
+        let TestEnv {
+            packages_service,
+            blockchain_client,
+            ..
+        } = TestEnv::new();
+        let package_one = create_package_with_sig()?;
 
-        // Instantiate required resources
+        packages_service.add(&package_one, &blockchain_client).await;
 
-        let packages_repository = Arc::new(PackagesRepository::from(&db_client));
+        let package_two = create_package_with_sig()?;
 
-        let packages_service = Arc::new(PackagesService::from(&packages_repository));
+        packages_service.add(&package_two, &blockchain_client).await;
 
-        let mut blockchain_mock = MockBlockchainClient::default();
+        let streamed_packages: Vec<_> = packages_service.get_all_stream().await.collect().await;
 
-        blockchain_mock
-            .expect_get_label()
-            .returning(|| "MockBlockchain".to_string());
+        assert_eq!(streamed_packages, packages_service.get_all().await);
 
-        let blockchain_client: Box<dyn BlockchainClient> = Box::new(blockchain_mock);
+        Ok(())
+    }
+
+    /**
+     * It should get package versions
+     */
+    #[tokio::test]
+    async fn test_should_get_package_versions() -> Result<(), Box<dyn std::error::Error>> {
+        let TestEnv {
+            packages_service,
+            blockchain_client,
+            ..
+        } = TestEnv::new();
+
+        // Same release published twice should only be listed once
+
+        let package_one = create_package_with_sig()?;
+        let package_two = create_package_with_sig()?;
+        let package_three = PackageBuilder::from_package(&create_package_with_sig()?)
+            .set_version(&String::from("0.10.0"))
+            .build();
+
+        // Lexically greater but older than 0.10.0
+        let package_four = PackageBuilder::from_package(&create_package_with_sig()?)
+            .set_version(&String::from("0.9.0"))
+            .build();
+
+        packages_service.add(&package_one, &blockchain_client).await;
+        packages_service.add(&package_two, &blockchain_client).await;
+        packages_service
+            .add(&package_three, &blockchain_client)
+            .await;
+        packages_service
+            .add(&package_four, &blockchain_client)
+            .await;
+
+        let versions = packages_service
+            .get_versions(&package_one.name, &blockchain_client)
+            .await;
+
+        assert_eq!(
+            versions,
+            vec![
+                package_four.version.clone(),
+                package_three.version.clone(),
+                package_one.version.clone()
+            ]
+        );
+
+        Ok(())
+    }
+
+    /**
+     * It should prefer best status then newest version of same maintainer when looking for upgrade
+     */
+    #[tokio::test]
+    async fn test_should_find_upgrade() -> Result<(), Box<dyn std::error::Error>> {
+        let TestEnv {
+            packages_service,
+            blockchain_client,
+            ..
+        } = TestEnv::new();
+
+        let maintainer_key = SigningKey::generate(&mut OsRng);
+        let other_key = SigningKey::generate(&mut OsRng);
+
+        let create_release = |version: &str, status: &PackageStatus, key: &SigningKey| {
+            let package =
+                PackageBuilder::from_package(&create_package_without_sig(&key.verifying_key())?)
+                    .set_version(&version.to_string())
+                    .set_status(status)
+                    .build();
+
+            let sig = sign_package(&package, key);
+
+            Ok::<Package, Box<dyn std::error::Error>>(
+                PackageBuilder::from_package(&package)
+                    .set_signature(&sig)
+                    .build(),
+            )
+        };
+
+        let installed_package = create_release("0.1.0", &PackageStatus::Outdated, &maintainer_key)?;
+        let recommended_package =
+            create_release("0.2.0", &PackageStatus::Recommended, &maintainer_key)?;
+
+        let releases = vec![
+            installed_package.clone(),
+            recommended_package.clone(),
+            create_release("0.3.0", &PackageStatus::Outdated, &maintainer_key)?,
+            create_release("0.4.0", &PackageStatus::Prohibited, &maintainer_key)?,
+            // Better rated release of another maintainer must never be picked
+            create_release("0.5.0", &PackageStatus::HighlyRecommended, &other_key)?,
+        ];
+
+        for release in &releases {
+            packages_service.add(release, &blockchain_client).await;
+        }
+
+        let maintainer = hex::encode(maintainer_key.verifying_key().to_bytes());
+
+        let upgrade = packages_service
+            .find_upgrade(
+                &installed_package.name,
+                &installed_package.version,
+                &maintainer,
+                &blockchain_client,
+            )
+            .await;
+
+        assert_eq!(upgrade, Some(recommended_package.clone()));
+
+        // Flagged release is never downgraded, even to a better rated one
+        let no_upgrade = packages_service
+            .find_upgrade(
+                &recommended_package.name,
+                &String::from("0.3.0"),
+                &maintainer,
+                &blockchain_client,
+            )
+            .await;
+
+        assert_eq!(no_upgrade, None);
+
+        Ok(())
+    }
+
+    /**
+     * It should get by maintainer
+     */
+    #[tokio::test]
+    async fn test_should_get_package_by_maintainer() -> Result<(), Box<dyn std::error::Error>> {
+        let TestEnv {
+            packages_service,
+            blockchain_client,
+            ..
+        } = TestEnv::new();
 
         let mut csprng = OsRng;
         let mut key = SigningKey::generate(&mut csprng);
@@ -311,21 +781,11 @@ mod tests {
     async fn test_should_update_package() -> Result<(), Box<dyn std::error::Error>> {
         let expected_status = PackageStatus::Prohibited;
 
-        let db_client = create_test_db();
-
-        // Instantiate required resources
-
-        let packages_repository = Arc::new(PackagesRepository::from(&db_client));
-
-        let packages_service = Arc::new(PackagesService::from(&packages_repository));
-
-        let mut blockchain_mock = MockBlockchainClient::default();
-
-        blockchain_mock
-            .expect_get_label()
-            .returning(|| "MockBlockchain".to_string());
-
-        let blockchain_client: Box<dyn BlockchainClient> = Box::new(blockchain_mock);
+        let TestEnv {
+            packages_service,
+            blockchain_client,
+            ..
+        } = TestEnv::new();
 
         let mut csprng = OsRng;
         let mut key = SigningKey::generate(&mut csprng);
@@ -360,6 +820,7 @@ mod tests {
             .get_by_release(
                 &updated_package.name,
                 &updated_package.version,
+                &None,
                 &blockchain_client,
             )
             .await;
@@ -368,4 +829,257 @@ mod tests {
 
         Ok(())
     }
+
+    /**
+     * It should get last mutation time
+     */
+    #[tokio::test]
+    async fn test_should_get_last_mutation_time() -> Result<(), Box<dyn std::error::Error>> {
+        let TestEnv {
+            packages_service,
+            blockchain_client,
+            ..
+        } = TestEnv::new();
+
+        let package = create_package_with_sig()?;
+        let unknown_package = create_package_with_sig()?;
+
+        packages_service.add(&package, &blockchain_client).await;
+
+        let mutation_time = packages_service
+            .last_mutation_time(
+                &package.name,
+                &package.version,
+                &package.maintainer,
+                &blockchain_client,
+            )
+            .await;
+
+        let unknown_mutation_time = packages_service
+            .last_mutation_time(
+                &unknown_package.name,
+                &unknown_package.version,
+                &unknown_package.maintainer,
+                &blockchain_client,
+            )
+            .await;
+
+        assert_eq!(mutation_time, Some(expected_mutation_time));
+        assert_eq!(unknown_mutation_time, None);
+
+        Ok(())
+    }
+
+    /**
+     * It should get filtered packages page along with total matches count
+     */
+    #[tokio::test]
+    async fn test_should_get_page() -> Result<(), Box<dyn std::error::Error>> {
+        let TestEnv {
+            packages_service,
+            blockchain_client,
+            ..
+        } = TestEnv::new();
+
+        let package = create_package_with_sig()?;
+
+        for name in ["foo-one", "foo-two", "FOO-three", "bar"] {
+            let named_package = PackageBuilder::from_package(&package)
+                .set_name(&name.to_string())
+                .build();
+
+            packages_service
+                .add(&named_package, &blockchain_client)
+                .await;
+        }
+
+        let prohibited_package = PackageBuilder::from_package(&package)
+            .set_name(&String::from("foo-prohibited"))
+            .set_status(&PackageStatus::Prohibited)
+            .build();
+
+        packages_service
+            .add(&prohibited_package, &blockchain_client)
+            .await;
+
+        let page = packages_service
+            .get_page(
+                &Some(String::from("foo")),
+                &Some(PackageStatus::Outdated),
+                1,
+                10,
+            )
+            .await;
+
+        let names: Vec<String> = page.packages.iter().map(|p| p.name.clone()).collect();
+
+        assert_eq!(page.total, 3);
+        assert_eq!(names, vec!["foo-two", "FOO-three"]);
+
+        let page = packages_service.get_page(&None, &None, 0, 2).await;
+
+        assert_eq!(page.total, 5);
+        assert_eq!(page.packages.len(), 2);
+
+        Ok(())
+    }
+
+    /**
+     * It should get stored packages page by page
+     */
+    #[tokio::test]
+    async fn test_should_get_paginated() -> Result<(), Box<dyn std::error::Error>> {
+        let TestEnv {
+            packages_service,
+            blockchain_client,
+            ..
+        } = TestEnv::new();
+
+        let package = create_package_with_sig()?;
+
+        for i in 0..25 {
+            let versioned_package = PackageBuilder::from_package(&package)
+                .set_version(&format!("1.0.{}", i))
+                .build();
+
+            packages_service
+                .add(&versioned_package, &blockchain_client)
+                .await;
+        }
+
+        assert_eq!(packages_service.get_paginated(0, 10).await.len(), 10);
+        assert_eq!(packages_service.get_paginated(10, 10).await.len(), 10);
+
+        let last_page = packages_service.get_paginated(20, 10).await;
+
+        assert_eq!(last_page.len(), 5);
+        assert_eq!(last_page[4].version, "1.0.24");
+
+        Ok(())
+    }
+
+    /**
+     * It should get packages having exactly given status
+     */
+    #[tokio::test]
+    async fn test_should_get_by_status() -> Result<(), Box<dyn std::error::Error>> {
+        let TestEnv {
+            packages_service,
+            blockchain_client,
+            ..
+        } = TestEnv::new();
+
+        let package = create_package_with_sig()?;
+
+        let statuses = [
+            PackageStatus::Prohibited,
+            PackageStatus::Outdated,
+            PackageStatus::Fine,
+            PackageStatus::Prohibited,
+        ];
+
+        for (i, status) in statuses.iter().enumerate() {
+            let status_package = PackageBuilder::from_package(&package)
+                .set_version(&format!("1.0.{}", i))
+                .set_status(status)
+                .build();
+
+            packages_service
+                .add(&status_package, &blockchain_client)
+                .await;
+        }
+
+        let prohibited_packages = packages_service
+            .get_by_status(&PackageStatus::Prohibited, &blockchain_client)
+            .await;
+
+        assert_eq!(prohibited_packages.len(), 2);
+        assert_eq!(
+            prohibited_packages
+                .iter()
+                .all(|package| package.status == PackageStatus::Prohibited),
+            true
+        );
+
+        assert_eq!(
+            packages_service
+                .get_by_status(&PackageStatus::HighlyRecommended, &blockchain_client)
+                .await
+                .is_empty(),
+            true
+        );
+
+        Ok(())
+    }
+
+    /**
+     * It should list added, removed and status changed packages
+     */
+    #[test]
+    fn test_should_diff_packages() -> Result<(), Box<dyn std::error::Error>> {
+        let unchanged_package = create_package_with_sig()?;
+
+        let removed_package = create_package_with_sig()?;
+
+        let added_package = create_package_with_sig()?;
+
+        let outdated_package = PackageBuilder::from_package(&create_package_with_sig()?)
+            .set_status(&PackageStatus::Fine)
+            .build();
+
+        let prohibited_package = PackageBuilder::from_package(&outdated_package)
+            .set_status(&PackageStatus::Prohibited)
+            .build();
+
+        // Re-signing same content is not a change
+        let resigned_package = PackageBuilder::from_package(&unchanged_package)
+            .set_signed_at(&1)
+            .build();
+
+        let before = vec![
+            unchanged_package.clone(),
+            removed_package.clone(),
+            outdated_package.clone(),
+        ];
+        let after = vec![
+            resigned_package,
+            prohibited_package.clone(),
+            added_package.clone(),
+        ];
+
+        let diff = PackagesService::diff(&before, &after);
+
+        assert_eq!(diff.added, vec![added_package]);
+        assert_eq!(diff.removed, vec![removed_package]);
+        assert_eq!(
+            diff.status_changed,
+            vec![(outdated_package, prohibited_package)]
+        );
+
+        Ok(())
+    }
+
+    /**
+     * It should open service on top of DB stored at given path
+     */
+    #[tokio::test]
+    async fn test_should_open_service() -> Result<(), Box<dyn std::error::Error>> {
+        let TestEnv {
+            blockchain_client, ..
+        } = TestEnv::new();
+
+        let test_dir = TempDir::new()?;
+
+        let packages_service = PackagesService::open(&test_dir.path().join("bpm.db"))?;
+
+        let expected_package = create_package_with_sig()?;
+
+        packages_service
+            .add(&expected_package, &blockchain_client)
+            .await;
+
+        assert_eq!(packages_service.get_all().await, vec![expected_package]);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,66 @@
+#[cfg(test)]
+pub mod tests {
+    use std::sync::Arc;
+
+    use tokio::sync::Mutex;
+
+    use crate::blockchains::blockchain::{BlockchainClient, BlockchainIO, MockBlockchainClient};
+
+    /**
+     * Create blockchain client only telling its label, enough for documents bound to a blockchain
+     */
+    pub fn create_labelled_client(label: &str) -> Box<dyn BlockchainClient> {
+        let label = label.to_string();
+
+        let mut blockchain_mock = MockBlockchainClient::default();
+
+        blockchain_mock
+            .expect_get_label()
+            .returning(move || label.clone());
+
+        Box::new(blockchain_mock)
+    }
+
+    /**
+     * Blockchain client using given IO and frozen clock
+     */
+    #[derive(Debug)]
+    pub struct TestBlockchainClient {
+        io: Arc<Box<dyn BlockchainIO>>,
+        now: u64,
+        last_sync: Mutex<u64>,
+    }
+
+    impl TestBlockchainClient {
+        pub fn new(io: Arc<Box<dyn BlockchainIO>>, now: u64) -> Self {
+            Self {
+                io,
+                now,
+                last_sync: Mutex::new(0),
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl BlockchainClient for TestBlockchainClient {
+        fn get_label(&self) -> String {
+            String::from("test")
+        }
+
+        async fn create_io(&self) -> Arc<Box<dyn BlockchainIO>> {
+            Arc::clone(&self.io)
+        }
+
+        async fn set_last_sync(&self, last_sync: u64) {
+            *self.last_sync.lock().await = last_sync;
+        }
+
+        async fn get_last_sync(&self) -> u64 {
+            *self.last_sync.lock().await
+        }
+
+        fn now(&self) -> u64 {
+            self.now
+        }
+    }
+}
@@ -0,0 +1,63 @@
+#[cfg(test)]
+pub mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        blockchains::blockchain::{BlockchainClient, MockBlockchainClient},
+        db::client::DbClient,
+        services::{
+            db::{
+                blockchains_repository::BlockchainsRepository,
+                packages_repository::PackagesRepository,
+            },
+            packages::PackagesService,
+        },
+        test_utils::db::tests::create_test_db,
+    };
+
+    pub const TEST_BLOCKCHAIN_LABEL: &str = "MockBlockchain";
+
+    /**
+     * Services wired on top of a fresh in memory DB, along with a labeled mock client
+     */
+    pub struct TestEnv {
+        pub db_client: Arc<DbClient>,
+        pub packages_repository: Arc<PackagesRepository>,
+        pub blockchains_repository: Arc<BlockchainsRepository>,
+        pub packages_service: Arc<PackagesService>,
+        pub blockchain_client: Box<dyn BlockchainClient>,
+    }
+
+    impl TestEnv {
+        /**
+         * Create env using a mock client only answering its label
+         */
+        pub fn new() -> Self {
+            Self::with_client(MockBlockchainClient::default())
+        }
+
+        /**
+         * Create env using given mock client, label expectation gets added to it
+         */
+        pub fn with_client(mut blockchain_mock: MockBlockchainClient) -> Self {
+            blockchain_mock
+                .expect_get_label()
+                .returning(|| TEST_BLOCKCHAIN_LABEL.to_string());
+
+            let db_client = create_test_db();
+
+            let packages_repository = Arc::new(PackagesRepository::from(&db_client));
+            let blockchains_repository = Arc::new(BlockchainsRepository::from(&db_client));
+
+            let packages_service = Arc::new(PackagesService::from(&packages_repository));
+
+            Self {
+                db_client,
+                packages_repository,
+                blockchains_repository,
+                packages_service,
+                blockchain_client: Box::new(blockchain_mock),
+            }
+        }
+    }
+}
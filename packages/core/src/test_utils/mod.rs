@@ -1,2 +1,4 @@
+pub mod blockchain;
 pub mod db;
+pub mod fixtures;
 pub mod package;
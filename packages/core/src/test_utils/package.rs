@@ -3,6 +3,7 @@ pub mod tests {
 
     use ed25519::signature::{rand_core::OsRng, SignerMut};
     use ed25519_dalek::{SigningKey, VerifyingKey};
+    use rlp::{Rlp, RlpStream};
     use sha2::{Digest, Sha256};
     use url::Url;
 
@@ -62,4 +63,27 @@ pub mod tests {
 
         Ok(signed_package)
     }
+
+    /**
+     * Replace item at given index of encoded package with raw bytes, keeping every other item untouched
+     */
+    pub fn replace_encoded_package_item(
+        encoded_package: &[u8],
+        index: usize,
+        value: &[u8],
+    ) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let rlp = Rlp::new(encoded_package);
+
+        let mut stream = RlpStream::new_list(rlp.item_count()?);
+
+        for (item_index, item) in rlp.iter().enumerate() {
+            if item_index == index {
+                stream.append(&value);
+            } else {
+                stream.append_raw(item.as_raw(), 1);
+            }
+        }
+
+        Ok(stream.out().to_vec())
+    }
 }
@@ -0,0 +1,50 @@
+use std::time::Duration;
+
+/**
+ * Delay before given retry attempt ( starting at 0 ), doubling base delay each attempt up to max delay
+ *
+ * Never overflows, however many attempts were made
+ */
+pub fn backoff_delay(base_delay: Duration, attempt: u32, max_delay: Duration) -> Duration {
+    2u32.checked_pow(attempt)
+        .and_then(|factor| base_delay.checked_mul(factor))
+        .unwrap_or(max_delay)
+        .min(max_delay)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /**
+     * It should double delay each attempt
+     */
+    #[test]
+    fn test_backoff_delay() {
+        let base_delay = Duration::from_millis(500);
+        let max_delay = Duration::from_secs(30);
+
+        assert_eq!(
+            backoff_delay(base_delay, 0, max_delay),
+            Duration::from_millis(500)
+        );
+        assert_eq!(
+            backoff_delay(base_delay, 3, max_delay),
+            Duration::from_millis(4000)
+        );
+    }
+
+    /**
+     * It should cap delay instead of overflowing on many attempts
+     */
+    #[test]
+    fn test_backoff_delay_capped() {
+        let base_delay = Duration::from_millis(500);
+        let max_delay = Duration::from_secs(30);
+
+        assert_eq!(backoff_delay(base_delay, 10, max_delay), max_delay);
+        assert_eq!(backoff_delay(base_delay, 64, max_delay), max_delay);
+        assert_eq!(backoff_delay(base_delay, u32::MAX, max_delay), max_delay);
+        assert_eq!(backoff_delay(Duration::MAX, 1, max_delay), max_delay);
+    }
+}
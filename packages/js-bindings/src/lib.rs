@@ -1,21 +1,18 @@
-use std::sync::Arc;
+use std::{str::FromStr, sync::Arc};
 
 use bpm_core::{
-    blockchains::{errors::blockchain_error::BlockchainError, get_available_clients},
-    config::init_config,
-    db::client::DbClient,
-    services::{
-        blockchains::BlockchainsService,
-        db::{
-            blockchains_repository::BlockchainsRepository, packages_repository::PackagesRepository,
-        },
-        packages::PackagesService,
-    },
+    blockchains::errors::blockchain_error::BlockchainError,
+    bpm::{errors::bpm_error::BpmError, Bpm},
+    packages::{package::Package, package_status::PackageStatus},
 };
+use futures_util::StreamExt;
 use home::home_dir;
 use neon::prelude::*;
 use once_cell::sync::OnceCell;
-use tokio::{runtime::Runtime, sync::mpsc};
+use tokio::{
+    runtime::Runtime,
+    sync::{mpsc, OnceCell as AsyncOnceCell},
+};
 
 // Return a global tokio runtime or create one if it doesn't exist.
 // Throws a JavaScript exception if the `Runtime` fails to create.
@@ -25,20 +22,87 @@ fn runtime<'a, C: Context<'a>>(cx: &mut C) -> NeonResult<&'static Runtime> {
     RUNTIME.get_or_try_init(|| Runtime::new().or_else(|err| cx.throw_error(err.to_string())))
 }
 
+// Services are built once then shared by every call
+static BPM: AsyncOnceCell<Arc<Bpm>> = AsyncOnceCell::const_new();
+
+// Same services caching packages in a throwaway DB, for callers which must not touch disk cache
+static IN_MEMORY_BPM: AsyncOnceCell<Arc<Bpm>> = AsyncOnceCell::const_new();
+
+// Page size used when listing packages without limit
+const DEFAULT_PAGE_LIMIT: u64 = 50;
+
+/**
+ * Convert package to JS object
+ */
+fn package_to_object<'a, C: Context<'a>>(cx: &mut C, package: &Package) -> JsResult<'a, JsObject> {
+    let package_obj = JsObject::new(cx);
+
+    let package_obj_name_key = cx.string("package_name");
+    let package_obj_name_value = cx.string(&package.name);
+
+    package_obj.set(cx, package_obj_name_key, package_obj_name_value)?;
+
+    let package_obj_version_key = cx.string("package_version");
+    let package_obj_version_value = cx.string(&package.version);
+
+    package_obj.set(cx, package_obj_version_key, package_obj_version_value)?;
+
+    let package_obj_maintainer_key = cx.string("maintainer");
+    let package_obj_maintainer_value = cx.string(hex::encode(&package.maintainer));
+
+    package_obj.set(cx, package_obj_maintainer_key, package_obj_maintainer_value)?;
+
+    let package_obj_fingerprint_key = cx.string("maintainer_fingerprint");
+    let package_obj_fingerprint_value = cx.string(package.get_maintainer_fingerprint());
+
+    package_obj.set(
+        cx,
+        package_obj_fingerprint_key,
+        package_obj_fingerprint_value,
+    )?;
+
+    Ok(package_obj)
+}
+
+/**
+ * Find package release, resolves with an array of packages, one per maintainer
+ *
+ * Options are all optional : { inMemory }, inMemory synchronizing a throwaway DB instead of using disk cache
+ */
 fn fetch_package(mut cx: FunctionContext) -> JsResult<JsPromise> {
     let rt = runtime(&mut cx)?;
     let package_name = cx.argument::<JsString>(0)?.value(&mut cx);
     let package_version = cx.argument::<JsString>(1)?.value(&mut cx);
 
+    let in_memory = match cx.argument_opt(2) {
+        Some(options) if options.is_a::<JsObject, _>(&mut cx) => {
+            let options = options.downcast_or_throw::<JsObject, _>(&mut cx)?;
+
+            options
+                .get_opt::<JsBoolean, _, _>(&mut cx, "inMemory")?
+                .map(|in_memory| in_memory.value(&mut cx))
+                .unwrap_or(false)
+        }
+        _ => false,
+    };
+
     let channel = cx.channel();
 
     let (deferred, promise) = cx.promise();
 
     rt.spawn(async move {
-        let blockchains_service = init().await;
-
-        let packages = blockchains_service
-            .find_package(&package_name, &package_version)
+        let init_result = match in_memory {
+            true => init_in_memory().await,
+            false => init().await,
+        };
+
+        let bpm = match init_result {
+            Ok(bpm) => bpm,
+            Err(e) => return reject_init(&channel, deferred, e),
+        };
+
+        let packages = bpm
+            .find_package(&package_name, &package_version, &None)
             .await;
 
         deferred.settle_with(&channel, move |mut cx| {
@@ -46,45 +110,221 @@ fn fetch_package(mut cx: FunctionContext) -> JsResult<JsPromise> {
 
             let packages_objects = JsArray::new(&mut cx, packages_objects.len());
             for (i, package) in packages.iter().enumerate() {
-                let package_obj = JsObject::new(&mut cx);
+                let package_obj = package_to_object(&mut cx, package)?;
 
-                let package_obj_name_key = cx.string("package_name");
-                let package_obj_name_value = cx.string(&package.name);
+                let index = u32::try_from(i).or_else(|err| cx.throw_error(err.to_string()))?;
 
-                package_obj.set(&mut cx, package_obj_name_key, package_obj_name_value)?;
+                packages_objects.set(&mut cx, index, package_obj)?;
+            }
 
-                let package_obj_version_key = cx.string("package_version");
-                let package_obj_version_value = cx.string(&package.version);
+            Ok(packages_objects)
+        });
+    });
 
-                package_obj.set(&mut cx, package_obj_version_key, package_obj_version_value)?;
+    Ok(promise)
+}
 
-                let package_obj_maintainer_key = cx.string("maintainer");
-                let package_obj_maintainer_value = cx.string(hex::encode(&package.maintainer));
+/**
+ * Fetch known versions of package, resolves with an array of version strings
+ */
+fn fetch_versions(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let rt = runtime(&mut cx)?;
+    let package_name = cx.argument::<JsString>(0)?.value(&mut cx);
 
-                package_obj.set(
-                    &mut cx,
-                    package_obj_maintainer_key,
-                    package_obj_maintainer_value,
-                )?;
+    let channel = cx.channel();
+
+    let (deferred, promise) = cx.promise();
+
+    rt.spawn(async move {
+        let bpm = match init().await {
+            Ok(bpm) => bpm,
+            Err(e) => return reject_init(&channel, deferred, e),
+        };
+
+        let versions = bpm.get_versions(&package_name).await;
+
+        deferred.settle_with(&channel, move |mut cx| {
+            let versions_array = JsArray::new(&mut cx, versions.len());
+
+            for (i, version) in versions.iter().enumerate() {
+                let version_value = cx.string(version);
+
+                let index = u32::try_from(i).or_else(|err| cx.throw_error(err.to_string()))?;
+
+                versions_array.set(&mut cx, index, version_value)?;
+            }
+
+            Ok(versions_array)
+        });
+    });
+
+    Ok(promise)
+}
+
+/**
+ * List one page of cached packages, resolves with { packages, total }
+ *
+ * Options are all optional : { offset, limit, query, minStatus }, minStatus being a status name such as "Fine"
+ */
+fn list_packages(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let rt = runtime(&mut cx)?;
+
+    let options = cx.argument_opt(0);
+
+    let (offset, limit, query, min_status) = match options {
+        Some(options) if options.is_a::<JsObject, _>(&mut cx) => {
+            let options = options.downcast_or_throw::<JsObject, _>(&mut cx)?;
+
+            let offset = options
+                .get_opt::<JsNumber, _, _>(&mut cx, "offset")?
+                .map_or(0, |offset| offset.value(&mut cx) as u64);
+
+            let limit = options
+                .get_opt::<JsNumber, _, _>(&mut cx, "limit")?
+                .map_or(DEFAULT_PAGE_LIMIT, |limit| limit.value(&mut cx) as u64);
+
+            let query = options
+                .get_opt::<JsString, _, _>(&mut cx, "query")?
+                .map(|query| query.value(&mut cx));
+
+            let min_status = match options.get_opt::<JsString, _, _>(&mut cx, "minStatus")? {
+                Some(min_status) => {
+                    let min_status = min_status.value(&mut cx);
+
+                    match PackageStatus::from_str(&min_status) {
+                        Ok(status) => Some(status),
+                        Err(_) => {
+                            return cx.throw_error(format!("Unknown package status {}", min_status))
+                        }
+                    }
+                }
+                None => None,
+            };
+
+            (offset, limit, query, min_status)
+        }
+        _ => (0, DEFAULT_PAGE_LIMIT, None, None),
+    };
+
+    let channel = cx.channel();
+
+    let (deferred, promise) = cx.promise();
+
+    rt.spawn(async move {
+        let bpm = match init().await {
+            Ok(bpm) => bpm,
+            Err(e) => return reject_init(&channel, deferred, e),
+        };
+
+        let page = bpm.list_packages(&query, &min_status, offset, limit).await;
+
+        deferred.settle_with(&channel, move |mut cx| {
+            let packages_objects = JsArray::new(&mut cx, page.packages.len());
+
+            for (i, package) in page.packages.iter().enumerate() {
+                let package_obj = package_to_object(&mut cx, package)?;
 
                 let index = u32::try_from(i).or_else(|err| cx.throw_error(err.to_string()))?;
 
                 packages_objects.set(&mut cx, index, package_obj)?;
             }
 
-            Ok(packages_objects)
+            let page_obj = cx.empty_object();
+
+            page_obj.set(&mut cx, "packages", packages_objects)?;
+
+            let total_value = cx.number(page.total as f64);
+
+            page_obj.set(&mut cx, "total", total_value)?;
+
+            Ok(page_obj)
         });
     });
 
     Ok(promise)
 }
 
-async fn update(blockchains_service: &Arc<BlockchainsService>) {
+/**
+ * Synchronize then call given callback for each known package, resolves with packages count
+ */
+fn sync_packages(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let rt = runtime(&mut cx)?;
+    let callback = Arc::new(cx.argument::<JsFunction>(0)?.root(&mut cx));
+
+    let channel = cx.channel();
+
+    let (deferred, promise) = cx.promise();
+
+    rt.spawn(async move {
+        let bpm = match init().await {
+            Ok(bpm) => bpm,
+            Err(e) => return reject_init(&channel, deferred, e),
+        };
+
+        update(&bpm).await;
+
+        // Packages are sent to JS one at a time so UI can render incrementally
+        let mut packages_stream = Box::pin(bpm.get_packages_service().get_all_stream().await);
+
+        let mut packages_count: u32 = 0;
+
+        while let Some(package) = packages_stream.next().await {
+            let task_callback = Arc::clone(&callback);
+
+            channel.send(move |mut cx| {
+                let package_obj = package_to_object(&mut cx, &package)?;
+
+                task_callback
+                    .to_inner(&mut cx)
+                    .call_with(&cx)
+                    .arg(package_obj)
+                    .exec(&mut cx)?;
+
+                Ok(())
+            });
+
+            packages_count += 1;
+        }
+
+        deferred.settle_with(&channel, move |mut cx| Ok(cx.number(packages_count)));
+    });
+
+    Ok(promise)
+}
+
+/**
+ * Synchronize local DB with blockchain, resolves with found packages mutations count
+ */
+fn sync(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let rt = runtime(&mut cx)?;
+
+    let channel = cx.channel();
+
+    let (deferred, promise) = cx.promise();
+
+    rt.spawn(async move {
+        let bpm = match init().await {
+            Ok(bpm) => bpm,
+            Err(e) => return reject_init(&channel, deferred, e),
+        };
+
+        let packages_count = update(&bpm).await;
+
+        deferred.settle_with(&channel, move |mut cx| Ok(cx.number(packages_count)));
+    });
+
+    Ok(promise)
+}
+
+/**
+ * Update local DB from blockchain, returns found packages mutations count
+ */
+async fn update(bpm: &Arc<Bpm>) -> u32 {
     let (tx_packages_update, mut rx_packages_update) = mpsc::channel(1);
 
-    let task_blockchains_service_ref = Arc::clone(&blockchains_service);
-    tokio::spawn(async move {
-        let task_res = task_blockchains_service_ref.update(&tx_packages_update);
+    let task_bpm_ref = Arc::clone(&bpm);
+    let update_task = tokio::spawn(async move {
+        let task_res = task_bpm_ref.sync(&tx_packages_update);
 
         match task_res.await {
             Ok(_) => return,
@@ -99,50 +339,75 @@ async fn update(blockchains_service: &Arc<BlockchainsService>) {
         }
     });
 
-    let mut packages_count: u128 = 0;
+    let mut packages_count: u32 = 0;
 
-    while let Some(package) = rx_packages_update.recv().await {
-        // TODO : console log
+    while let Some(_package) = rx_packages_update.recv().await {
+        packages_count += 1;
     }
+
+    // Make sure nothing keeps running once update is done
+    let _ = update_task.await;
+
+    packages_count
 }
 
-async fn init() -> Arc<BlockchainsService> {
-    let config_path = home_dir().unwrap();
+/**
+ * Reject promise when BPM instance could not be built
+ */
+fn reject_init(channel: &Channel, deferred: Deferred, e: BpmError) {
+    deferred.settle_with(channel, move |mut cx| -> JsResult<JsUndefined> {
+        cx.throw_error(e.to_string())
+    });
+}
+
+/**
+ * Get shared BPM instance, building services on first call
+ */
+async fn init() -> Result<Arc<Bpm>, BpmError> {
+    let bpm = BPM
+        .get_or_try_init(|| async {
+            let config_path = home_dir().unwrap();
 
-    let config_manager = init_config(&config_path);
+            let bpm = Arc::new(Bpm::new(&config_path).await?);
 
-    let db_client = Arc::new(DbClient::from(&config_manager.get_db_path()));
+            // TODO : test, change it later
+            bpm.get_blockchains_service().set_client(0).await;
 
-    // Blockchains clients
-    let available_blockchains = get_available_clients();
+            Ok::<Arc<Bpm>, BpmError>(bpm)
+        })
+        .await?;
 
-    // Repositories
-    let blockchains_repository = Arc::new(BlockchainsRepository::from(&db_client));
-    let packages_repository = Arc::new(PackagesRepository::from(&db_client));
+    Ok(Arc::clone(bpm))
+}
 
-    let packages_service = Arc::new(PackagesService::from(&packages_repository));
+/**
+ * Get shared BPM instance using a throwaway DB, synchronized from blockchain on first call
+ */
+async fn init_in_memory() -> Result<Arc<Bpm>, BpmError> {
+    let bpm = IN_MEMORY_BPM
+        .get_or_try_init(|| async {
+            let config_path = home_dir().unwrap();
 
-    let blockchains_service = Arc::new(
-        BlockchainsService::new(
-            &available_blockchains,
-            &blockchains_repository,
-            &packages_service,
-        )
-        .await,
-    );
+            let bpm = Arc::new(Bpm::in_memory(&config_path).await?);
 
-    blockchains_service.init_blockchains().await;
+            bpm.get_blockchains_service().set_client(0).await;
 
-    // TODO : test, change it later
-    blockchains_service.set_client(0).await;
+            // Throwaway DB starts empty, nothing could be found otherwise
+            update(&bpm).await;
 
-    update(&blockchains_service).await;
+            Ok::<Arc<Bpm>, BpmError>(bpm)
+        })
+        .await?;
 
-    Arc::clone(&blockchains_service)
+    Ok(Arc::clone(bpm))
 }
 
 #[neon::main]
 fn main(mut cx: ModuleContext) -> NeonResult<()> {
     cx.export_function("fetchPackage", fetch_package)?;
+    cx.export_function("fetchVersions", fetch_versions)?;
+    cx.export_function("listPackages", list_packages)?;
+    cx.export_function("syncPackages", sync_packages)?;
+    cx.export_function("sync", sync)?;
     Ok(())
 }